@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use crate::flylang::errors::{ErrorType, RaisableErr, codes::ErrorId};
+
+#[derive(Debug, Clone)]
+pub struct InvalidAddon {
+    pub folder: PathBuf,
+    pub reason: String,
+}
+impl RaisableErr for InvalidAddon {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidAddon
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Warn
+    }
+    fn _code(&self) -> i32 {
+        1
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The add-on at <{:?}> is not valid: {}",
+            self.folder, self.reason
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddonNotFound(pub String);
+impl RaisableErr for AddonNotFound {
+    fn _id(&self) -> ErrorId {
+        ErrorId::AddonNotFound
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _message(&self) -> String {
+        format!(
+            "No add-on named \"{}\" was found.\nSee `flylang parser search <query>` to find the available ones.",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddonFailed {
+    pub name: String,
+    pub reason: String,
+}
+impl RaisableErr for AddonFailed {
+    fn _id(&self) -> ErrorId {
+        ErrorId::AddonFailed
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _message(&self) -> String {
+        format!("The add-on \"{}\" failed: {}", self.name, self.reason)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddonAlreadyInstalled {
+    pub name: String,
+    pub folder: PathBuf,
+}
+impl RaisableErr for AddonAlreadyInstalled {
+    fn _id(&self) -> ErrorId {
+        ErrorId::AddonAlreadyInstalled
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Warn
+    }
+    fn _code(&self) -> i32 {
+        4
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The add-on \"{}\" is already installed at <{:?}>.",
+            self.name, self.folder
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NoAddonsFolder;
+impl RaisableErr for NoAddonsFolder {
+    fn _id(&self) -> ErrorId {
+        ErrorId::NoAddonsFolder
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _code(&self) -> i32 {
+        5
+    }
+    fn _message(&self) -> String {
+        String::from(
+            "No folder to install the add-ons in.\nSet `language.compilers_folders` in the behavior file, or use the `--directory` option.",
+        )
+    }
+}
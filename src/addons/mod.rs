@@ -0,0 +1,312 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+};
+
+use serde_json::json;
+use toml::{Table, Value};
+
+use crate::{
+    addons::errors::{AddonFailed, InvalidAddon},
+    behavior::LangBehavior,
+    flylang::{
+        errors::{LangResult, lang_err},
+        module::LangModule,
+        parser::ast::{Branches, json},
+    },
+};
+
+pub mod errors;
+
+/// A compiler add-on : an external program converting flylang code to another language.
+///
+/// An add-on is a folder containing an `addon.toml` manifest. Its command is executed in the add-on's
+/// folder, receives the parsed module as JSON on its standard input, and writes the generated code on its
+/// standard output (see `docs/cli/addons.md`).
+#[derive(Debug, Clone)]
+pub struct Addon {
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// The command generating the code
+    pub command: Vec<String>,
+    /// The command executing the generated code (its file is given as last argument)
+    pub runner: Option<Vec<String>>,
+    /// The extension of the generated files
+    pub extension: Option<String>,
+    pub folder: PathBuf,
+}
+
+impl Addon {
+    pub const MANIFEST: &str = "addon.toml";
+
+    /// Load the add-on contained in a folder
+    pub fn load(folder: &Path) -> LangResult<Self> {
+        let invalid = |reason: &str| -> LangResult<Self> {
+            lang_err!(InvalidAddon {
+                folder: folder.to_path_buf(),
+                reason: reason.to_string(),
+            })
+        };
+
+        let Ok(content) = fs::read_to_string(folder.join(Self::MANIFEST)) else {
+            return invalid(&format!("the \"{}\" file cannot be read", Self::MANIFEST));
+        };
+        let Ok(manifest) = content.parse::<Table>() else {
+            return invalid(&format!("the \"{}\" file is not valid", Self::MANIFEST));
+        };
+        let Some(Value::Table(addon)) = manifest.get("addon") else {
+            return invalid("the [addon] table is missing");
+        };
+
+        let text = |key: &str| match addon.get(key) {
+            Some(Value::String(value)) => Some(value.clone()),
+            _ => None,
+        };
+        let command = |key: &str| match addon.get(key) {
+            Some(Value::Array(parts)) if !parts.is_empty() => parts
+                .iter()
+                .map(|part| part.as_str().map(String::from))
+                .collect::<Option<Vec<String>>>(),
+            _ => None,
+        };
+
+        let Some(command_line) = command("command") else {
+            return invalid("`command` must be a non-empty array of strings");
+        };
+        let name = text("name").or_else(|| {
+            folder
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        });
+        let Some(name) = name else {
+            return invalid("the add-on has no name");
+        };
+        // The add-on is installed in a folder of its name, which must stay in the add-ons folder
+        let mut components = Path::new(&name).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(part)), None) if part == name.as_str()
+        ) {
+            return invalid(&format!(
+                "its name (\"{}\") must be a folder name, without any path separator",
+                name
+            ));
+        }
+
+        Ok(Self {
+            name,
+            version: text("version"),
+            description: text("description"),
+            command: command_line,
+            runner: command("runner"),
+            extension: text("extension"),
+            folder: folder.to_path_buf(),
+        })
+    }
+
+    /// Create the process of a command defined by the add-on
+    fn process(&self, command: &[String]) -> Command {
+        let (program, arguments) = command.split_first().unwrap();
+
+        // Programs given as a path are relative to the add-on's folder
+        let program = match program.contains('/') {
+            true => self.folder.join(program).into_os_string(),
+            false => program.into(),
+        };
+        let mut process = Command::new(program);
+        process.args(arguments).current_dir(&self.folder);
+        process
+    }
+
+    fn failed<R>(&self, reason: impl ToString) -> LangResult<R> {
+        lang_err!(AddonFailed {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Generate the code of a module
+    pub fn compile(&self, module: &LangModule, branches: &Branches) -> LangResult<String> {
+        let input = json!({
+            "version": json::FORMAT_VERSION,
+            "module": {"path": module.path(), "code": module.code()},
+            "branches": json::branches(branches),
+        })
+        .to_string();
+
+        let spawned = self
+            .process(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(error) => return self.failed(format!("cannot start its command ({})", error)),
+        };
+
+        // The input is written while the output is read, so large modules do not block the add-on
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let output = match child.wait_with_output() {
+            Ok(output) => output,
+            Err(error) => return self.failed(error),
+        };
+        // The add-on may not read its whole input : it is its own choice
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return self.failed(format!(
+                "it exited with {}.\n{}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            ));
+        }
+        match String::from_utf8(output.stdout) {
+            Ok(code) => Ok(code),
+            Err(_) => self.failed("the generated code is not valid UTF-8"),
+        }
+    }
+
+    /// Generate the code of a module in the given folder.
+    /// Returns the path of the generated file.
+    pub fn write(
+        &self,
+        module: &LangModule,
+        branches: &Branches,
+        folder: &Path,
+    ) -> LangResult<PathBuf> {
+        let code = self.compile(module, branches)?;
+
+        let mut file = folder.join(module.path().file_stem().unwrap_or("module".as_ref()));
+        file.set_extension(self.extension.as_deref().unwrap_or("out"));
+        if let Err(error) = fs::create_dir_all(folder).and_then(|_| fs::write(&file, code)) {
+            return self.failed(format!("the generated code cannot be written ({})", error));
+        }
+
+        Ok(file)
+    }
+
+    /// Generate the code of a module, and execute it if the add-on has a runner (else the code is printed).
+    /// Returns the exit code of the runner.
+    pub fn execute(&self, module: &LangModule, branches: &Branches) -> LangResult<i32> {
+        if self.runner.is_none() {
+            print!("{}", self.compile(module, branches)?);
+            return Ok(0);
+        }
+
+        let folder = std::env::temp_dir().join("flylang").join(&self.name);
+        let file = self.write(module, branches, &folder)?;
+        Ok(self.run(&file)?.code().unwrap_or(1))
+    }
+
+    /// Execute a file generated by the add-on with its runner
+    pub fn run(&self, file: &Path) -> LangResult<ExitStatus> {
+        let Some(runner) = &self.runner else {
+            return self.failed("it cannot run the code it generates");
+        };
+
+        match self.process(runner).arg(file).current_dir(".").status() {
+            Ok(status) => Ok(status),
+            Err(error) => self.failed(format!("cannot start its runner ({})", error)),
+        }
+    }
+}
+
+/// The add-ons found in a list of folders
+#[derive(Debug, Clone)]
+pub struct Addons {
+    folders: Vec<PathBuf>,
+}
+
+impl Addons {
+    pub fn new(folders: Vec<PathBuf>) -> Self {
+        Self { folders }
+    }
+
+    /// Get the add-ons of the folders listed at `key` in the behavior (like `language.compilers_folders`)
+    pub fn from_behavior(behavior: &LangBehavior, key: &str) -> Self {
+        let folders = match behavior.get(key) {
+            Some(Value::Array(folders)) => folders
+                .iter()
+                .filter_map(|folder| folder.as_str().map(PathBuf::from))
+                .collect(),
+            Some(Value::String(folder)) => vec![PathBuf::from(folder)],
+            _ => vec![],
+        };
+
+        Self::new(folders)
+    }
+
+    pub fn folders(&self) -> &[PathBuf] {
+        &self.folders
+    }
+
+    /// Get every valid add-on. The invalid ones are printed as warnings.
+    pub fn list(&self) -> Vec<Addon> {
+        let mut addons = vec![];
+
+        for folder in &self.folders {
+            let Ok(entries) = fs::read_dir(folder) else {
+                continue;
+            };
+            let mut entries: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .collect();
+            entries.sort();
+
+            for entry in entries {
+                match Addon::load(&entry) {
+                    Ok(addon) => addons.push(addon),
+                    Err(error) => error.controlled_raise(),
+                }
+            }
+        }
+
+        addons
+    }
+
+    /// Get an add-on by its name (the first folders have the priority)
+    pub fn find(&self, name: &str) -> Option<Addon> {
+        self.list().into_iter().find(|addon| addon.name == name)
+    }
+
+    /// Get the add-ons whose name or description contains the query
+    pub fn search(&self, query: &str) -> Vec<Addon> {
+        let query = query.to_lowercase();
+
+        self.list()
+            .into_iter()
+            .filter(|addon| {
+                addon.name.to_lowercase().contains(&query)
+                    || addon
+                        .description
+                        .as_ref()
+                        .is_some_and(|description| description.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
+/// Copy a folder and its content
+pub(crate) fn copy_folder(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let destination = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_folder(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), destination)?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,7 +2,10 @@ use std::path::PathBuf;
 use toml::Value;
 
 use crate::{
-    flylang::errors::{ErrorType, RaisableErr},
+    flylang::{
+        errors::{ErrorType, RaisableErr, codes::ErrorId},
+        module::slice::LangModuleSlice,
+    },
     utils::macros::abs_path::absolute_path,
 };
 
@@ -13,6 +16,9 @@ pub struct InvalidPath {
 }
 
 impl RaisableErr for InvalidPath {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidPath
+    }
     fn _kind(&self) -> ErrorType {
         ErrorType::Stop
     }
@@ -38,6 +44,9 @@ pub struct PathNotFound {
 }
 
 impl RaisableErr for PathNotFound {
+    fn _id(&self) -> ErrorId {
+        ErrorId::PathNotFound
+    }
     fn _kind(&self) -> ErrorType {
         ErrorType::Warn
     }
@@ -45,6 +54,37 @@ impl RaisableErr for PathNotFound {
     fn _message(&self) -> String {
         format!("No file found at <{:?}>", self.path)
     }
+    fn _help(&self) -> Option<String> {
+        Some(String::from("create the file or fix the path to it"))
+    }
+}
+
+/// The content of a behavior file is not valid TOML
+#[derive(Debug, Clone)]
+pub struct InvalidSyntax {
+    pub location: LangModuleSlice,
+    pub reason: String,
+}
+
+impl RaisableErr for InvalidSyntax {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidBehaviorSyntax
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Behavior file at <{:?}> is not a valid TOML file.",
+            self.location.module().path()
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+    fn _notes(&self) -> Vec<String> {
+        vec![self.reason.trim().to_string()]
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,14 +98,34 @@ pub struct InvalidKeyValue {
 }
 
 impl RaisableErr for InvalidKeyValue {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidKeyValue
+    }
     fn _kind(&self) -> ErrorType {
         self.kind.clone()
     }
 
     fn _message(&self) -> String {
         format!(
-            "Value for key '{:?}' in <{:?}> is not valid.\nExpected: {:?}. Found: {:?}",
-            self.key, self.from_file, self.expected, self.value_found
+            "Value for key '{:?}' in <{:?}> is not valid.",
+            self.key, self.from_file
         )
     }
+    fn _notes(&self) -> Vec<String> {
+        match &self.value_found {
+            Some(value) => vec![format!("found: {}", value)],
+            None => vec![String::from("no value is found")],
+        }
+    }
+    fn _help(&self) -> Option<String> {
+        let expected = self.expected.as_ref().filter(|values| !values.is_empty())?;
+        Some(format!(
+            "expected {}",
+            expected
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(" or ")
+        ))
+    }
 }
@@ -6,8 +6,11 @@ use std::{
 use toml::{Table, Value};
 
 use crate::{
-    behavior::errors::{InvalidKeyValue, InvalidPath, PathNotFound},
-    flylang::errors::{ErrorType, LangResult, RaisableErr, lang_err},
+    behavior::errors::{InvalidKeyValue, InvalidPath, InvalidSyntax, PathNotFound},
+    flylang::{
+        errors::{ErrorType, LangResult, RaisableErr, lang_err},
+        module::{LangModule, slice::LangModuleSlice},
+    },
     utils::{env::get_env_hashmap, str::ReplaceByKey},
 };
 pub mod errors;
@@ -21,6 +24,8 @@ pub struct LangBehavior {
 
 impl LangBehavior {
     const EXTEND_KEY: &str = "extends";
+    /// The keys of the paths (or arrays of paths), relative to the directory of the file defining them
+    const PATH_KEYS: [&str; 2] = ["language.compilers_folders", "language.compilers_sources"];
 
     pub fn new_parsed(base_file: &Path) -> Self {
         let mut behavior = Self::default();
@@ -77,6 +82,36 @@ impl LangBehavior {
         }
     }
 
+    /// Resolve the relative paths of the `PATH_KEYS` defined in a file against the directory of this file
+    fn resolve_paths(data: &mut Table, file: &Path) {
+        let directory = file.parent().unwrap_or(Path::new(""));
+        let resolve = |value: &Value| match Self::get_parsed_value(value, false) {
+            Value::String(path) => {
+                Value::String(directory.join(path).to_string_lossy().to_string())
+            }
+            value => value,
+        };
+
+        for key in Self::PATH_KEYS {
+            let (tables, last) = key.rsplit_once('.').unwrap_or(("", key));
+            let mut table = Some(&mut *data);
+            for key in tables.split('.').filter(|key| !key.is_empty()) {
+                table = match table.and_then(|table| table.get_mut(key)) {
+                    Some(Value::Table(table)) => Some(table),
+                    _ => None,
+                };
+            }
+
+            match table.and_then(|table| table.get_mut(last)) {
+                Some(Value::Array(paths)) => {
+                    paths.iter_mut().for_each(|path| *path = resolve(path))
+                }
+                Some(path) => *path = resolve(path),
+                None => {}
+            }
+        }
+    }
+
     fn merge(table: &Table, into: &mut Table, same_does_replace: bool) {
         for (key, raw_value) in table {
             let value = Self::get_parsed_value(raw_value, false);
@@ -107,7 +142,8 @@ impl LangBehavior {
 
         let path_exist = exists(&base_file);
         if path_exist.is_err() || path_exist.is_ok_and(|v| !v) {
-            PathNotFound { path: base_file }.raise()
+            PathNotFound { path: base_file }.controlled_raise();
+            return self;
         }
 
         let file_err = InvalidPath {
@@ -119,9 +155,20 @@ impl LangBehavior {
             file_err.raise()
         };
 
-        let data = content
-            .parse::<Table>()
-            .unwrap_or_else(|_| file_err.raise());
+        let mut data = match content.parse::<Table>() {
+            Ok(data) => data,
+            Err(error) => match error.span() {
+                Some(span) => InvalidSyntax {
+                    location: LangModuleSlice::new_with(
+                        &LangModule::new_from_code(base_file, content).into_rc(),
+                        span,
+                    ),
+                    reason: error.message().to_string(),
+                }
+                .raise(),
+                None => file_err.raise(),
+            },
+        };
 
         if let Some(extend) = data.get(Self::EXTEND_KEY)
             && let Err(e) = self.handle_extend(extend, &base_file)
@@ -129,6 +176,7 @@ impl LangBehavior {
             e.controlled_raise();
         }
 
+        Self::resolve_paths(&mut data, &base_file);
         Self::merge(&data, &mut self.map, true);
         self.processed.insert(base_file);
 
@@ -0,0 +1,36 @@
+use crate::flylang::errors::{ErrorType, RaisableErr, codes::ErrorId};
+
+pub struct UnknownParser(pub String);
+impl RaisableErr for UnknownParser {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnknownParser
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Unknown parser \"{}\".\nSee `flylang parser list` to view the available parsers.",
+            self.0
+        )
+    }
+}
+
+pub struct NotACompiler(pub String);
+impl RaisableErr for NotACompiler {
+    fn _id(&self) -> ErrorId {
+        ErrorId::NotACompiler
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The parser \"{}\" does not generate code : it cannot be used to compile.",
+            self.0
+        )
+    }
+}
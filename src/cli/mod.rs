@@ -1,7 +1,10 @@
-use clap::{Parser, command};
+use std::io::{IsTerminal, stdout};
+
+use clap::{Parser, ValueEnum};
 
 use crate::cli::{parser::LangParserCLI, pkg::LangPkgCLI};
-mod parser;
+pub mod errors;
+pub mod parser;
 mod pkg;
 
 #[derive(Parser, Clone, Debug)]
@@ -12,11 +15,42 @@ pub enum LangCommands {
         /// The .fly file to parse (default to 'entry.fly')
         entrypoint: Option<String>,
 
-        /// The parser's name to use.
+        /// The parser's name to use (default to the `language.compiler` behavior, or "flylang-interpreter").
         /// Depending on the selected parser, you may have to pass additionnal arguments.
         /// See `flylang parser list` to view the available parsers
-        #[arg(short, long, default_value = "flylang-interpreter")]
+        #[arg(short, long)]
+        parser: Option<String>,
+
+        /// Print the bytecode before executing it (with the "flylang-vm" parser)
+        #[arg(long)]
+        disassemble: bool,
+    },
+
+    /// Compile a flylang code file to another language
+    #[command()]
+    Compile {
+        /// The .fly file to compile (default to 'entry.fly')
+        entrypoint: Option<String>,
+
+        /// The parser's name to use (default to the `language.compiler` behavior).
+        /// It must be a compiler : see `flylang parser list` to view the available parsers
+        #[arg(short, long)]
         parser: Option<String>,
+
+        /// The folder the generated files are written in
+        #[arg(short, long, default_value = "build")]
+        output: String,
+    },
+
+    /// Format flylang code files
+    #[command()]
+    Fmt {
+        /// The .fly files to format, or folders containing them (default to 'entry.fly')
+        files: Vec<String>,
+
+        /// Do not write the files : fail if one of them is not formatted
+        #[arg(long)]
+        check: bool,
     },
 
     /// Package manager system
@@ -42,6 +76,37 @@ pub enum LangCommands {
     Behavior {},
 }
 
+/// When the errors are rendered with colors
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ColorChoice {
+    /// If the output is a terminal and the `NO_COLOR` variable is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// If the errors are rendered with colors
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Auto => stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// How the errors are printed
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum MessageFormat {
+    /// Rendered with the code they concern
+    #[default]
+    Human,
+    /// As JSON objects, one per line
+    Json,
+}
+
 /// Flylang parser.
 /// Use this CLI to execute or compile flylang code.
 #[derive(Debug, Clone, Parser)]
@@ -53,6 +118,18 @@ pub struct LangCLI {
     #[arg(long, short, required = false)]
     pub behavior_file: Option<String>,
 
+    /// When the errors are rendered with colors
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// How the errors are printed
+    #[arg(long, global = true, value_enum, default_value_t = MessageFormat::Human)]
+    pub message_format: MessageFormat,
+
+    /// Report the warnings as errors, stopping the process
+    #[arg(long, global = true)]
+    pub deny_warnings: bool,
+
     #[command(subcommand)]
     pub command: LangCommands,
 }
@@ -62,14 +139,17 @@ impl LangCLI {
         let mut parsed = <Self as Parser>::parse();
 
         // Append default options
-        if let LangCommands::Exec {
-            entrypoint,
-            parser: _,
-        } = &mut parsed.command
+        if let LangCommands::Exec { entrypoint, .. } | LangCommands::Compile { entrypoint, .. } =
+            &mut parsed.command
             && entrypoint.is_none()
         {
             *entrypoint = Some(String::from("entry.fly"));
         }
+        if let LangCommands::Fmt { files, .. } = &mut parsed.command
+            && files.is_empty()
+        {
+            files.push(String::from("entry.fly"));
+        }
 
         parsed
     }
@@ -1,4 +1,19 @@
-use clap::{Subcommand, command};
+use std::{fs, path::Path};
+
+use clap::Subcommand;
+
+use crate::{
+    addons::{
+        Addon, Addons, copy_folder,
+        errors::{AddonAlreadyInstalled, AddonFailed, AddonNotFound, NoAddonsFolder},
+    },
+    flylang::{compilers, errors::RaisableErr},
+};
+
+/// The name of the built-in parser, executing the code
+pub const INTERPRETER: &str = "flylang-interpreter";
+/// The name of the built-in parser, compiling the code to bytecode executed by a virtual machine
+pub const VIRTUAL_MACHINE: &str = "flylang-vm";
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum LangParserCLI {
@@ -27,3 +42,99 @@ pub enum LangParserCLI {
         names: Vec<String>,
     },
 }
+
+impl LangParserCLI {
+    /// Execute the command with the `installed` add-ons, installing new ones from the `sources`
+    pub fn run(&self, installed: &Addons, sources: &Addons) {
+        match self {
+            Self::List {} => {
+                println!("{} (built-in)\n  Execute the code directly", INTERPRETER);
+                println!(
+                    "{} (built-in)\n  Execute the code with a bytecode virtual machine",
+                    VIRTUAL_MACHINE
+                );
+                for compiler in compilers::compilers() {
+                    println!(
+                        "{} (built-in)\n  {}",
+                        compiler.name(),
+                        compiler.description()
+                    );
+                }
+                for addon in installed.list() {
+                    print_addon(&addon);
+                }
+            }
+            Self::Search { query } => {
+                let found = sources.search(query);
+                if found.is_empty() {
+                    println!("No add-on found for \"{}\".", query);
+                }
+                for addon in found {
+                    print_addon(&addon);
+                }
+            }
+            Self::Install { names } => {
+                let Some(destination) = installed.folders().first() else {
+                    NoAddonsFolder.raise();
+                };
+
+                for name in names {
+                    // Add-ons are installed from a folder, or by their name from the sources
+                    let path = Path::new(name);
+                    let addon = match path.join(Addon::MANIFEST).exists() {
+                        true => Addon::load(path).unwrap_or_else(|e| e.raise()),
+                        false => sources
+                            .find(name)
+                            .unwrap_or_else(|| AddonNotFound(name.clone()).raise()),
+                    };
+
+                    if let Some(existing) = installed.find(&addon.name) {
+                        AddonAlreadyInstalled {
+                            name: existing.name,
+                            folder: existing.folder,
+                        }
+                        .controlled_raise();
+                        continue;
+                    }
+
+                    let folder = destination.join(&addon.name);
+                    if let Err(error) = copy_folder(&addon.folder, &folder) {
+                        AddonFailed {
+                            name: addon.name.clone(),
+                            reason: format!("it cannot be copied to <{:?}> ({})", folder, error),
+                        }
+                        .raise();
+                    }
+                    println!("Installed \"{}\" at <{:?}>.", addon.name, folder);
+                }
+            }
+            Self::Remove { names } => {
+                for name in names {
+                    let addon = installed
+                        .find(name)
+                        .unwrap_or_else(|| AddonNotFound(name.clone()).raise());
+
+                    if let Err(error) = fs::remove_dir_all(&addon.folder) {
+                        AddonFailed {
+                            name: addon.name.clone(),
+                            reason: format!("it cannot be removed ({})", error),
+                        }
+                        .raise();
+                    }
+                    println!("Removed \"{}\".", addon.name);
+                }
+            }
+        }
+    }
+}
+
+fn print_addon(addon: &Addon) {
+    match &addon.version {
+        Some(version) => println!("{} {}", addon.name, version),
+        None => println!("{}", addon.name),
+    }
+    if let Some(description) = &addon.description {
+        println!("  {}", description);
+    }
+    println!("  at <{:?}>", addon.folder);
+}
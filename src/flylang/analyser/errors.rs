@@ -0,0 +1,98 @@
+use crate::flylang::{
+    errors::{ErrorType, RaisableErr, codes::ErrorId},
+    module::slice::LangModuleSlice,
+};
+
+pub struct UndefinedVariable(pub LangModuleSlice);
+impl RaisableErr for UndefinedVariable {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UndefinedVariable
+    }
+    fn _code(&self) -> i32 {
+        1
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("\"{}\" is not defined.", self.0.code())
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct UnknownScope {
+    pub location: LangModuleSlice,
+    pub target: String,
+}
+impl RaisableErr for UnknownScope {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnknownScope
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("The scope {} does not exist here.", self.target)
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct InvalidScopeTarget {
+    pub location: LangModuleSlice,
+    pub target: String,
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+impl RaisableErr for InvalidScopeTarget {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidScopeTarget
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The scope {} is a {} scope, but this breaker expects a {} scope.",
+            self.target, self.found, self.expected
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct MisplacedBreaker {
+    pub location: LangModuleSlice,
+    pub expected: &'static str,
+}
+impl RaisableErr for MisplacedBreaker {
+    fn _id(&self) -> ErrorId {
+        ErrorId::MisplacedBreaker
+    }
+    fn _code(&self) -> i32 {
+        4
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" can only be used inside a {}.",
+            self.location.code(),
+            self.expected
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
@@ -0,0 +1,240 @@
+//! Verification of the accesses to the private and protected members of the classes.
+//!
+//! The class of a value is only known in a few cases : a class or an instance (`new C()`) assigned once to a
+//! variable, an instance read where it is created, and `self` and `super` in the methods. As a child class can
+//! declare a member again, an access is only reported if it fails for every class the receiver can have.
+
+use std::collections::HashMap;
+
+use crate::flylang::{
+    analyser::symbols::{NamespaceId, SymbolId},
+    interpreter::errors::InaccessibleMember,
+    module::slice::LangModuleSlice,
+    parser::ast::definables::class::{ClassItemVisibility, DefineClass},
+    utils::linearization,
+};
+
+/// What is known about the value of a variable
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Known {
+    /// A class, identified by the namespace of its members
+    Class(NamespaceId),
+    /// An instance of the class held by a variable
+    Instance(SymbolId),
+}
+
+/// What a member is read from
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Target {
+    /// The value of a variable
+    Variable(SymbolId),
+    /// An instance of the class held by a variable
+    Instance(SymbolId),
+    /// `self`, in a method of the class
+    Receiver(NamespaceId),
+    /// `super`, in a method of the class
+    Super(NamespaceId),
+}
+
+/// A member read (or written) by the code
+#[derive(Debug)]
+pub(super) struct Access {
+    pub target: Target,
+    pub member: LangModuleSlice,
+    /// The class whose method contains the code (if any)
+    pub from: Option<NamespaceId>,
+}
+
+/// A class defined in the module
+struct Definition<'a> {
+    class: &'a DefineClass,
+    /// The variables its parents are read from (None if a parent is not defined)
+    parents: Vec<Option<SymbolId>>,
+}
+
+/// The classes, the values and the accesses to the members found by the analyser
+#[derive(Default)]
+pub(super) struct Members<'a> {
+    classes: HashMap<NamespaceId, Definition<'a>>,
+    /// The number of times each variable is assigned
+    assignments: HashMap<SymbolId, usize>,
+    values: HashMap<SymbolId, Known>,
+    accesses: Vec<Access>,
+}
+
+impl<'a> Members<'a> {
+    pub fn class(
+        &mut self,
+        members: NamespaceId,
+        class: &'a DefineClass,
+        parents: Vec<Option<SymbolId>>,
+    ) {
+        self.classes.insert(members, Definition { class, parents });
+    }
+
+    pub fn assign(&mut self, symbol: SymbolId) {
+        *self.assignments.entry(symbol).or_default() += 1;
+    }
+    /// Remember the value a variable is assigned with (it is used if the variable is assigned once)
+    pub fn know(&mut self, symbol: SymbolId, value: Known) {
+        self.values.insert(symbol, value);
+    }
+
+    pub fn access(&mut self, access: Access) {
+        self.accesses.push(access);
+    }
+
+    /// Verify the accesses, once every variable of the module is known
+    pub fn verify(&self) -> Vec<InaccessibleMember> {
+        self.accesses
+            .iter()
+            .filter_map(|access| {
+                let (is_static, candidates) = self.candidates(access.target)?;
+                let mut denials = candidates
+                    .iter()
+                    .map(|classes| self.denied(classes, access, is_static));
+
+                let first = denials.next()??;
+                denials.all(|denial| denial.is_some()).then(|| {
+                    let definition = &self.classes[&first.0];
+                    InaccessibleMember {
+                        location: access.member.clone(),
+                        member: access.member.code().to_string(),
+                        class: definition.class.name.location().code().to_string(),
+                        visibility: first.1.name(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The value of a variable, if it is assigned once
+    fn value(&self, symbol: SymbolId) -> Option<Known> {
+        match self.assignments.get(&symbol) {
+            Some(1) => self.values.get(&symbol).copied(),
+            _ => None,
+        }
+    }
+
+    fn class_of(&self, symbol: SymbolId) -> Option<NamespaceId> {
+        match self.value(symbol)? {
+            Known::Class(class) => Some(class),
+            Known::Instance(_) => None,
+        }
+    }
+
+    /// The method resolution order of a class, like the interpreter computes it (C3 linearization)
+    fn mro(&self, class: NamespaceId) -> Option<Vec<NamespaceId>> {
+        self.linearize(class, &mut vec![])
+    }
+
+    fn linearize(
+        &self,
+        class: NamespaceId,
+        visiting: &mut Vec<NamespaceId>,
+    ) -> Option<Vec<NamespaceId>> {
+        if visiting.contains(&class) {
+            return None;
+        }
+        visiting.push(class);
+
+        let parents = self.classes.get(&class)?.parents.iter();
+        let parents: Vec<NamespaceId> = parents
+            .map(|parent| self.class_of((*parent)?))
+            .collect::<Option<_>>()?;
+        let ancestors = linearization::linearize(
+            &parents,
+            |parent| self.linearize(*parent, visiting),
+            |a, b| a == b,
+        )?;
+        visiting.pop();
+
+        Some([vec![class], ancestors].concat())
+    }
+
+    /// The classes whose instances can be the receiver of a method of the given class : the class and its
+    /// children (None if the parents of a class are not known)
+    fn receivers(&self, class: NamespaceId) -> Option<Vec<Vec<NamespaceId>>> {
+        let mut receivers = vec![self.mro(class)?];
+        for other in self.classes.keys().filter(|other| **other != class) {
+            let mro = self.mro(*other)?;
+            if mro.contains(&class) {
+                receivers.push(mro);
+            }
+        }
+        Some(receivers)
+    }
+
+    /// The classes searched for the member for each possible class of the target, and if the member is static
+    fn candidates(&self, target: Target) -> Option<(bool, Vec<Vec<NamespaceId>>)> {
+        match target {
+            Target::Variable(symbol) => match self.value(symbol)? {
+                Known::Class(class) => Some((true, vec![self.mro(class)?])),
+                Known::Instance(class) => self.candidates(Target::Instance(class)),
+            },
+            Target::Instance(class) => Some((false, vec![self.mro(self.class_of(class)?)?])),
+            Target::Receiver(class) => Some((false, self.receivers(class)?)),
+            // `super` searches the classes following the class in the method resolution order of the receiver
+            Target::Super(class) => {
+                let mut receivers = self.receivers(class)?;
+                for mro in receivers.iter_mut() {
+                    let position = mro.iter().position(|other| *other == class)?;
+                    mro.drain(..=position);
+                }
+                Some((false, receivers))
+            }
+        }
+    }
+
+    /// Get the class declaring the member and its visibility, if the access is surely denied
+    /// (see `Interpreter::check_access`)
+    fn denied(
+        &self,
+        classes: &[NamespaceId],
+        access: &Access,
+        is_static: bool,
+    ) -> Option<(NamespaceId, ClassItemVisibility)> {
+        let (owner, visibility) = classes.iter().find_map(|class| {
+            let definition = self.classes.get(class)?.class;
+            Some((
+                *class,
+                declaration(definition, access.member.code(), is_static)?,
+            ))
+        })?;
+
+        let allowed = match visibility {
+            ClassItemVisibility::Public => true,
+            ClassItemVisibility::Private => access.from == Some(owner),
+            // If the ancestors of the current class are not known, the access may be allowed
+            ClassItemVisibility::Protected => access
+                .from
+                .is_some_and(|from| self.mro(from).is_none_or(|mro| mro.contains(&owner))),
+        };
+        (!allowed).then_some((owner, visibility))
+    }
+}
+
+/// Get the visibility of an attribute or a method declared by a class (see `Class::declaration`)
+fn declaration(class: &DefineClass, name: &str, is_static: bool) -> Option<ClassItemVisibility> {
+    let attribute = class.attributes.iter().find(|attribute| {
+        let attribute = attribute.kind();
+        attribute.is_static == is_static && attribute.item.emplacement.location().code() == name
+    });
+    if let Some(attribute) = attribute {
+        return Some(attribute.kind().visibility);
+    }
+
+    class
+        .methods
+        .iter()
+        .map(|method| method.kind())
+        .find(|method| {
+            method.is_static == is_static
+                && method
+                    .item
+                    .name
+                    .as_ref()
+                    .is_some_and(|method| method.location().code() == name)
+        })
+        .map(|method| method.visibility)
+}
@@ -0,0 +1,665 @@
+use std::collections::HashMap;
+
+use crate::flylang::{
+    analyser::{
+        errors::{InvalidScopeTarget, MisplacedBreaker, UndefinedVariable, UnknownScope},
+        members::{Access, Known, Members, Target},
+        symbols::{NamespaceId, NamespaceKind, SymbolId, SymbolKind, SymbolTable},
+    },
+    checker::types::Type,
+    errors::RaisableErr,
+    interpreter::Interpreter,
+    lexer::tokens::ScopeTarget,
+    module::slice::LangModuleSlice,
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables, class::DefineClass, functions::DefineFunction,
+            variables::VariableEmplacements,
+        },
+        expressions::{
+            Expressions,
+            literals::{ParsedLiterals, ParsedStringItem, Word},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::LoopParameter,
+            r#use::{PackageContentEmplacement, PackageIncludedContent},
+        },
+        specials::typing::Typing,
+    },
+    runtime::builtins,
+    utils::breakers::{self, BreakableScope, ScopeKind, Unresolved},
+};
+
+pub mod errors;
+mod members;
+pub mod symbols;
+
+/// The result of the analysis of a module
+#[derive(Debug)]
+pub struct Analysis {
+    pub symbols: SymbolTable,
+    pub errors: Vec<Box<dyn RaisableErr>>,
+}
+
+/// A scope (function, loop or condition) that breakers can target
+#[derive(Debug, Clone)]
+struct Block {
+    kind: ScopeKind,
+    name: Option<String>,
+}
+impl BreakableScope for Block {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// The class a method is declared in
+#[derive(Clone, Copy)]
+struct Method {
+    /// The namespace of the class' members
+    class: NamespaceId,
+    is_static: bool,
+}
+
+/// Code whose analysis waits for every name of its namespace to be declared
+enum Deferred<'a> {
+    /// The instructions of a function. For methods, the class is given to declare `self` and `super`
+    /// (unless the method is static).
+    Function(&'a DefineFunction, Option<Method>),
+    /// The value of a class' property, evaluated when the class is instanciated
+    Expression(&'a Node<Expressions>),
+}
+
+/// A namespace being analysed
+struct Frame<'a> {
+    namespace: NamespaceId,
+    names: HashMap<String, SymbolId>,
+    /// The number of loops being analysed in this namespace
+    loops: usize,
+    /// The names read in a loop before being declared (they may be declared later in the loop)
+    pending: Vec<LangModuleSlice>,
+    /// The class whose method is analysed in this namespace
+    class: Option<NamespaceId>,
+    /// The code to analyse once the whole namespace has been analysed,
+    /// with the scopes it is defined in
+    deferred: Vec<(Deferred<'a>, Vec<Block>)>,
+}
+
+/// Resolve the names of a module to their declaration, and verify the breakers' targets and the accesses to
+/// the members of the classes.
+///
+/// The functions are analysed after the code they are defined in, because they can use the variables
+/// declared after them (as long as they are called after).
+pub struct Analyser<'a> {
+    symbols: SymbolTable,
+    frames: Vec<Frame<'a>>,
+    /// The scopes containing the code being analysed, from the outer to the inner one
+    blocks: Vec<Block>,
+    /// If the module imports the whole content of another one, its names cannot be known
+    wildcard: bool,
+    members: Members<'a>,
+    errors: Vec<Box<dyn RaisableErr>>,
+}
+
+impl Default for Analyser<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Analyser<'a> {
+    pub fn new() -> Self {
+        let mut analyser = Self {
+            symbols: SymbolTable::default(),
+            frames: vec![],
+            blocks: vec![],
+            wildcard: false,
+            members: Members::default(),
+            errors: vec![],
+        };
+
+        analyser.enter(NamespaceKind::Builtins);
+        for (name, _) in builtins::globals::<Interpreter>() {
+            analyser.declare(name, SymbolKind::Builtin, None);
+        }
+        analyser
+    }
+
+    /// Analyse the instructions of a module
+    pub fn analyse(mut self, branches: &'a Branches) -> Analysis {
+        self.enter(NamespaceKind::Module);
+        self.instructions(branches);
+        self.leave();
+
+        // The imported names can replace the classes and the variables
+        if !self.wildcard {
+            for error in self.members.verify() {
+                self.report(error);
+            }
+        }
+
+        Analysis {
+            symbols: self.symbols,
+            errors: self.errors,
+        }
+    }
+
+    fn report(&mut self, error: impl RaisableErr + 'static) {
+        self.errors.push(Box::new(error));
+    }
+
+    fn frame(&mut self) -> &mut Frame<'a> {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn enter(&mut self, kind: NamespaceKind) -> NamespaceId {
+        let parent = self.frames.last().map(|frame| frame.namespace);
+        let namespace = self.symbols.add_namespace(kind, parent);
+
+        self.frames.push(Frame {
+            namespace,
+            names: HashMap::new(),
+            loops: 0,
+            pending: vec![],
+            class: None,
+            deferred: vec![],
+        });
+        namespace
+    }
+
+    /// Analyse the deferred code of the current namespace, then leave it
+    fn leave(&mut self) {
+        while !self.frame().deferred.is_empty() {
+            let (deferred, blocks) = self.frame().deferred.remove(0);
+            let outer = std::mem::replace(&mut self.blocks, blocks);
+
+            match deferred {
+                Deferred::Function(function, method) => self.function(function, method),
+                Deferred::Expression(expression) => {
+                    self.expression(expression.kind(), expression.location())
+                }
+            }
+            self.blocks = outer;
+        }
+
+        self.frames.pop();
+    }
+
+    fn defer(&mut self, deferred: Deferred<'a>) {
+        let blocks = self.blocks.clone();
+        self.frame().deferred.push((deferred, blocks));
+    }
+
+    fn declare(
+        &mut self,
+        name: &str,
+        kind: SymbolKind,
+        location: Option<&LangModuleSlice>,
+    ) -> SymbolId {
+        let namespace = self.frame().namespace;
+        let symbol = self.symbols.declare(name, kind, namespace, location);
+        self.frame().names.insert(name.to_string(), symbol);
+
+        symbol
+    }
+
+    fn lookup(&self, name: &str) -> Option<SymbolId> {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.names.get(name).copied())
+    }
+
+    /// Resolve a name read by the code
+    fn read(&mut self, location: &LangModuleSlice) {
+        match self.lookup(location.code()) {
+            Some(symbol) => self.symbols.refer(location, symbol),
+            // Inside a loop, the variable can be defined later in the loop
+            None if self.frame().loops > 0 => self.frame().pending.push(location.clone()),
+            None if self.wildcard => {}
+            None => self.report(UndefinedVariable(location.clone())),
+        }
+    }
+
+    /// Resolve a name assigned by the code, the same way the interpreter does : the nearest variable with
+    /// this name is modified, or the variable is created in the current namespace.
+    fn assign(&mut self, location: &LangModuleSlice, kind: SymbolKind) -> SymbolId {
+        let symbol = match self.lookup(location.code()) {
+            Some(symbol) => {
+                self.symbols.refer(location, symbol);
+                symbol
+            }
+            None => self.declare(location.code(), kind, Some(location)),
+        };
+        self.members.assign(symbol);
+        symbol
+    }
+
+    /// The class whose method contains the code being analysed
+    fn current_class(&self) -> Option<NamespaceId> {
+        self.frames.iter().rev().find_map(|frame| frame.class)
+    }
+
+    fn instructions(&mut self, branches: &'a Branches) {
+        for instruction in branches {
+            self.instruction(instruction);
+        }
+    }
+
+    /// Analyse instructions executed in a scope
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &'a Branches,
+    ) {
+        let name = target.as_ref().and_then(|target| match target.kind() {
+            ScopeTarget::Named(name) => Some(name.clone()),
+            ScopeTarget::Numbered(_) => None,
+        });
+
+        self.blocks.push(Block { kind, name });
+        self.instructions(branches);
+        self.blocks.pop();
+    }
+
+    fn instruction(&mut self, instruction: &'a Node) {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => {
+                self.expression(expression, instruction.location())
+            }
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => {
+                match &repetition.parameter {
+                    LoopParameter::Through(each) => {
+                        self.expression(each.iterable.kind(), each.iterable.location());
+                        for word in [&each.item, &each.index].into_iter().flatten() {
+                            self.assign(word.location(), SymbolKind::Binding);
+                        }
+                    }
+                    LoopParameter::Conditionnaly(condition) => {
+                        let expression = &condition.condition;
+                        self.frame().loops += 1;
+                        self.expression(expression.kind(), expression.location());
+                        self.frame().loops -= 1;
+
+                        if let Some(word) = &condition.iteration_number {
+                            self.assign(word.location(), SymbolKind::Binding);
+                        }
+                    }
+                }
+
+                self.frame().loops += 1;
+                self.scoped(
+                    ScopeKind::Loop,
+                    &repetition.scope_target,
+                    &repetition.process,
+                );
+                self.frame().loops -= 1;
+
+                if self.frame().loops == 0 {
+                    let pending = std::mem::take(&mut self.frame().pending);
+                    for location in pending {
+                        self.read(&location);
+                    }
+                }
+            }
+            Instructions::Break(breaker) => {
+                self.breaker(breaker);
+                if let BreakKind::Return(_, Some(value)) = &breaker.kind {
+                    self.expression(value.kind(), value.location());
+                }
+            }
+            Instructions::Use(package) => {
+                let names = match &package.included {
+                    PackageIncludedContent::Only(names) => names.iter().collect(),
+                    PackageIncludedContent::All => vec![],
+                };
+                match &package.emplacement {
+                    PackageContentEmplacement::Variable(variable) => {
+                        self.assign(variable.location(), SymbolKind::Import);
+                    }
+                    PackageContentEmplacement::Global if names.is_empty() => self.wildcard = true,
+                    PackageContentEmplacement::Global => {
+                        for name in names {
+                            self.assign(name.location(), SymbolKind::Import);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn condition(&mut self, condition: &'a If) {
+        self.expression(condition.condition.kind(), condition.condition.location());
+        self.scoped(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            &condition.process,
+        );
+
+        if let Some(fallback) = &condition.fallback {
+            match fallback.kind() {
+                IfFallBack::Process(target, branches) => {
+                    self.scoped(ScopeKind::Condition, target, branches)
+                }
+                IfFallBack::If(condition) => self.condition(condition.kind()),
+            }
+        }
+    }
+
+    /// Verify that the scope targeted by a breaker exists, like the interpreter resolves it
+    fn breaker(&mut self, breaker: &Break) {
+        let location = breaker.keyword_location.clone();
+        let error: Box<dyn RaisableErr> = match breakers::resolve(&self.blocks, breaker) {
+            Ok(_) => return,
+            Err(Unresolved::Uncaught { expected }) => Box::new(MisplacedBreaker {
+                location,
+                expected: expected.map_or("scope", |kind| kind.name()),
+            }),
+            Err(Unresolved::Invalid {
+                target,
+                expected,
+                found,
+            }) => Box::new(InvalidScopeTarget {
+                location,
+                target,
+                expected: expected.map_or("", |kind| kind.name()),
+                found: found.name(),
+            }),
+            Err(Unresolved::Unknown { target }) => Box::new(UnknownScope { location, target }),
+        };
+        self.errors.push(error);
+    }
+
+    fn expression(&mut self, expression: &'a Expressions, location: &LangModuleSlice) {
+        match expression {
+            Expressions::Literal(ParsedLiterals::Word) => self.read(location),
+            Expressions::Literal(ParsedLiterals::String(items)) => {
+                for item in items {
+                    if let ParsedStringItem::Expression(expression) = item.kind() {
+                        self.expression(expression.kind(), expression.location());
+                    }
+                }
+            }
+            Expressions::Literal(_) => {}
+            Expressions::Defined(definable) => self.define(definable, false),
+            Expressions::Modifed(modified) => {
+                self.modifiers(&modified.modified_by);
+                self.define(modified.definable.kind(), true);
+            }
+            Expressions::Read(property) => self.property(property),
+            Expressions::ReturnOf(call) => {
+                self.expression(call.callable.kind(), call.callable.location());
+                for argument in call.arguments.iter() {
+                    self.expression(argument.kind(), argument.location());
+                }
+            }
+            Expressions::Reverse(reverse) => {
+                self.expression(reverse.expression.kind(), reverse.expression.location())
+            }
+            Expressions::Operation(operation) => {
+                let (left, right) = match operation {
+                    Operations::Numeric(operation) => &operation.operands,
+                    Operations::Binary(operation) => &operation.operands,
+                    Operations::Comparative(operation) => &operation.operands,
+                };
+                self.expression(left.kind(), left.location());
+                self.expression(right.kind(), right.location());
+            }
+            Expressions::Prioritized(inner) => self.expression(inner.kind(), inner.location()),
+            Expressions::Ternary(ternary) => {
+                for expression in [&ternary.condition, &ternary.yes, &ternary.no] {
+                    self.expression(expression.kind(), expression.location());
+                }
+            }
+            Expressions::Structure(structure) => {
+                for entry in structure.entries.iter() {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    if !matches!(entry.key.kind(), Expressions::Literal(ParsedLiterals::Word)) {
+                        self.expression(entry.key.kind(), entry.key.location());
+                    }
+                    self.expression(entry.value.kind(), entry.value.location());
+                }
+            }
+            Expressions::Array(array) => {
+                for item in array.entries.iter() {
+                    self.expression(item.kind(), item.location());
+                }
+            }
+            Expressions::Instanciate(instanciation) => {
+                let class = &instanciation.class;
+                self.expression(class.kind(), class.location());
+                for argument in instanciation.arguments.iter() {
+                    self.expression(argument.kind(), argument.location());
+                }
+            }
+            Expressions::Typed(typed) => {
+                self.expression(typed.expression.kind(), typed.expression.location());
+                self.typing(Some(&typed.typing));
+            }
+        }
+    }
+
+    fn modifiers(&mut self, modifiers: &'a [Node<Modifier>]) {
+        for modifier in modifiers {
+            match modifier.kind() {
+                Modifier::DefinedElement => self.read(modifier.location()),
+                Modifier::CallReturn(call) => {
+                    self.expression(call.callable.kind(), call.callable.location());
+                    for argument in call.arguments.iter() {
+                        self.expression(argument.kind(), argument.location());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolve the classes used as types (the built-in types are not symbols)
+    fn typing(&mut self, typing: Option<&Node<Typing>>) {
+        let Some(typing) = typing else {
+            return;
+        };
+
+        for name in &typing.kind().types {
+            let location = name.location();
+            if Type::builtin(location.code()).is_none()
+                && let Some(symbol) = self.lookup(location.code())
+            {
+                self.symbols.refer(location, symbol);
+            }
+        }
+    }
+
+    /// Analyse a definition. The value of a modified definition is the one returned by its modifiers.
+    fn define(&mut self, definable: &'a Definables, modified: bool) {
+        match definable {
+            Definables::Variable(variable) => {
+                self.expression(variable.value.kind(), variable.value.location());
+                self.typing(variable.typing.as_ref());
+
+                let emplacement = &variable.emplacement;
+                match emplacement.kind() {
+                    VariableEmplacements::Scope => {
+                        let kind = match variable.readonly {
+                            true => SymbolKind::Constant,
+                            false => SymbolKind::Variable,
+                        };
+                        let symbol = self.assign(emplacement.location(), kind);
+
+                        let value = self.target(variable.value.kind(), variable.value.location());
+                        if let (false, Some(Target::Instance(class))) = (modified, value) {
+                            self.members.know(symbol, Known::Instance(class));
+                        }
+                    }
+                    VariableEmplacements::Property(property) => self.property(property),
+                    VariableEmplacements::Any(expression) => {
+                        self.expression(expression, emplacement.location())
+                    }
+                }
+            }
+            Definables::Function(function) => {
+                if let Some(name) = &function.name {
+                    self.assign(name.location(), SymbolKind::Function);
+                }
+                self.defer(Deferred::Function(function, None));
+            }
+            Definables::Class(class) => self.class(class, modified),
+        }
+    }
+
+    /// Analyse a property read (or written), and remember the accesses to the members of the classes
+    fn property(&mut self, property: &'a ReadProperty) {
+        self.expression(property.from.kind(), property.from.location());
+        match property.read.kind() {
+            Property::Expression(key) => self.expression(key.kind(), key.location()),
+            Property::Key => {
+                if let Some(target) = self.target(property.from.kind(), property.from.location()) {
+                    let from = self.current_class();
+                    self.members.access(Access {
+                        target,
+                        member: property.read.location().clone(),
+                        from,
+                    });
+                }
+            }
+            Property::Index => {}
+        }
+    }
+
+    /// Get what an expression is, if it can be a class or an instance
+    fn target(&self, expression: &Expressions, location: &LangModuleSlice) -> Option<Target> {
+        match expression {
+            Expressions::Literal(ParsedLiterals::Word) => {
+                let name = location.code();
+                let frame = self
+                    .frames
+                    .iter()
+                    .rev()
+                    .find(|frame| frame.names.contains_key(name))?;
+                let symbol = frame.names[name];
+
+                match (self.symbols.symbol(symbol).kind, frame.class) {
+                    (SymbolKind::Receiver, Some(class)) if name == "super" => {
+                        Some(Target::Super(class))
+                    }
+                    (SymbolKind::Receiver, Some(class)) => Some(Target::Receiver(class)),
+                    _ => Some(Target::Variable(symbol)),
+                }
+            }
+            Expressions::Instanciate(instanciation) => {
+                let class = &instanciation.class;
+                match self.target(class.kind(), class.location())? {
+                    Target::Variable(symbol) => Some(Target::Instance(symbol)),
+                    _ => None,
+                }
+            }
+            Expressions::Prioritized(inner) => self.target(inner.kind(), inner.location()),
+            _ => None,
+        }
+    }
+
+    /// Analyse the instructions of a function, in its own namespace
+    fn function(&mut self, function: &'a DefineFunction, method: Option<Method>) {
+        for argument in function.arguments.iter() {
+            self.typing(argument.kind().typing.as_ref());
+        }
+        self.typing(function.returns.as_ref());
+
+        self.enter(NamespaceKind::Function);
+        if let Some(method) = method {
+            self.frame().class = Some(method.class);
+            if !method.is_static {
+                for receiver in ["self", "super"] {
+                    self.declare(receiver, SymbolKind::Receiver, None);
+                }
+            }
+        }
+        for argument in function.arguments.iter() {
+            let name: &Node<Word> = &argument.kind().name;
+            self.declare(
+                name.location().code(),
+                SymbolKind::Argument,
+                Some(name.location()),
+            );
+        }
+
+        self.scoped(
+            ScopeKind::Function,
+            &function.scope_target,
+            &function.execution,
+        );
+        self.leave();
+    }
+
+    fn class(&mut self, class: &'a DefineClass, modified: bool) {
+        for parent in &class.parents {
+            self.read(parent.location());
+        }
+        let parents = class
+            .parents
+            .iter()
+            .map(|parent| self.lookup(parent.location().code()))
+            .collect();
+        let symbol = self.assign(class.name.location(), SymbolKind::Class);
+
+        // The members are not variables : they are declared in the namespace of the class
+        let parent = self.frame().namespace;
+        let members = self
+            .symbols
+            .add_namespace(NamespaceKind::Class, Some(parent));
+        self.members.class(members, class, parents);
+        if !modified {
+            self.members.know(symbol, Known::Class(members));
+        }
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            let name = item.item.emplacement.location();
+            self.symbols
+                .declare(name.code(), SymbolKind::Member, members, Some(name));
+
+            self.modifiers(&item.modifie_by);
+            self.typing(item.item.typing.as_ref());
+            match item.is_static {
+                true => self.expression(item.item.value.kind(), item.item.value.location()),
+                false => self.defer(Deferred::Expression(&item.item.value)),
+            }
+        }
+        for method in &class.methods {
+            let item = method.kind();
+            if let Some(name) = &item.item.name {
+                self.symbols.declare(
+                    name.location().code(),
+                    SymbolKind::Member,
+                    members,
+                    Some(name.location()),
+                );
+            }
+
+            self.modifiers(&item.modifie_by);
+            let method = Method {
+                class: members,
+                is_static: item.is_static,
+            };
+            self.defer(Deferred::Function(&item.item, Some(method)));
+        }
+        if let Some(constructor) = &class.constructor {
+            let method = Method {
+                class: members,
+                is_static: false,
+            };
+            self.defer(Deferred::Function(constructor.kind(), Some(method)));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::HashMap, ops::Range};
+
+use crate::flylang::module::slice::LangModuleSlice;
+
+pub type SymbolId = usize;
+pub type NamespaceId = usize;
+
+/// What a symbol has been declared as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// An element defined by the language (like `std`)
+    Builtin,
+    Variable,
+    Constant,
+    Function,
+    Class,
+    /// An argument of a function
+    Argument,
+    /// A variable bound by a loop (item, index or iteration number)
+    Binding,
+    /// `self` and `super`, inside a method
+    Receiver,
+    /// An element imported with `use`
+    Import,
+    /// A property or a method declared in a class' body
+    Member,
+}
+
+/// A declared name
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The namespace the symbol is declared in
+    pub namespace: NamespaceId,
+    /// Where the symbol is declared (`None` for the built-in elements)
+    pub declaration: Option<LangModuleSlice>,
+}
+
+/// What creates a namespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamespaceKind {
+    /// The built-in elements, parent of the module
+    Builtins,
+    Module,
+    Function,
+    /// The members of a class (they are not visible as variables)
+    Class,
+}
+
+#[derive(Debug, Clone)]
+pub struct Namespace {
+    pub kind: NamespaceKind,
+    pub parent: Option<NamespaceId>,
+}
+
+/// The symbols declared in a module, and the place each name refers to them
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    namespaces: Vec<Namespace>,
+    /// The symbol referenced by each resolved name (keyed by its range in the module)
+    references: HashMap<Range<usize>, SymbolId>,
+}
+
+impl SymbolTable {
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id]
+    }
+    pub fn namespaces(&self) -> &[Namespace] {
+        &self.namespaces
+    }
+    pub fn namespace(&self, id: NamespaceId) -> &Namespace {
+        &self.namespaces[id]
+    }
+
+    /// Get the symbol a name (or a declaration) refers to
+    pub fn resolve(&self, location: &LangModuleSlice) -> Option<&Symbol> {
+        self.references
+            .get(&location.range())
+            .map(|id| &self.symbols[*id])
+    }
+
+    /// Get the ranges of the module referring to a symbol, including its declaration
+    pub fn references(&self, symbol: SymbolId) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = self
+            .references
+            .iter()
+            .filter(|(_, id)| **id == symbol)
+            .map(|(range, _)| range.clone())
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+
+    /// Get the symbols declared in a namespace
+    pub fn declared_in(&self, namespace: NamespaceId) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.namespace == namespace)
+    }
+
+    pub(super) fn add_namespace(
+        &mut self,
+        kind: NamespaceKind,
+        parent: Option<NamespaceId>,
+    ) -> NamespaceId {
+        self.namespaces.push(Namespace { kind, parent });
+        self.namespaces.len() - 1
+    }
+
+    pub(super) fn declare(
+        &mut self,
+        name: &str,
+        kind: SymbolKind,
+        namespace: NamespaceId,
+        declaration: Option<&LangModuleSlice>,
+    ) -> SymbolId {
+        self.symbols.push(Symbol {
+            name: name.to_string(),
+            kind,
+            namespace,
+            declaration: declaration.cloned(),
+        });
+
+        let id = self.symbols.len() - 1;
+        if let Some(location) = declaration {
+            self.refer(location, id);
+        }
+        id
+    }
+
+    pub(super) fn refer(&mut self, location: &LangModuleSlice, symbol: SymbolId) {
+        self.references.insert(location.range(), symbol);
+    }
+}
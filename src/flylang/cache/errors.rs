@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::flylang::errors::{ErrorType, RaisableErr, codes::ErrorId};
+
+pub struct CacheNotWritten {
+    pub path: PathBuf,
+    pub reason: String,
+}
+impl RaisableErr for CacheNotWritten {
+    fn _id(&self) -> ErrorId {
+        ErrorId::CacheNotWritten
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Warn
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The parsed module cannot be cached in ({}): {}.\nThe module will be parsed again on the next run.",
+            self.path.display(),
+            self.reason
+        )
+    }
+}
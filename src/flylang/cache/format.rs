@@ -0,0 +1,1005 @@
+//! The binary format of the cache files.
+//!
+//! Each node is written as its location in the module (start and length), followed by its content. Enumerations are
+//! written as the index of their variant, followed by its fields. Numbers are written with a variable length (LEB128).
+
+use std::{num::NonZero, rc::Rc};
+
+use crate::flylang::{
+    lexer::tokens::{BinaryOperator, Comparison, Operator, ScopeTarget, Token},
+    module::{LangModule, slice::LangModuleSlice},
+    parser::ast::{
+        Node,
+        definables::{
+            Definables,
+            class::{ClassItem, ClassItemVisibility, DefineClass},
+            functions::{Argument, DefineFunction},
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            instanciate::ClassInstanciation,
+            literals::{ParsedLiterals, ParsedStringItem, Word},
+            modified::{ModifiedDefinable, Modifier},
+            objects::{Array, Structure, StructureEntry},
+            operations::{Operation, Operations},
+            property::{Property, ReadProperty},
+            reverse::{Reverse, ReverseKind},
+            ternary::Ternary,
+            typed::Typed,
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::{Each, Loop, LoopParameter, While},
+            r#use::{Package, PackageContentEmplacement, PackageIncludedContent, PackageSource},
+        },
+        specials::typing::Typing,
+    },
+};
+
+/// Write values in the cache format
+#[derive(Default)]
+pub struct Encoder {
+    output: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Get the written bytes
+    pub fn finish(self) -> Vec<u8> {
+        self.output
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+    }
+    pub fn tag(&mut self, tag: u8) {
+        self.output.push(tag);
+    }
+    pub fn number(&mut self, mut number: usize) {
+        while number >= 0x80 {
+            self.output.push((number as u8 & 0x7f) | 0x80);
+            number >>= 7;
+        }
+        self.output.push(number as u8);
+    }
+    pub fn float(&mut self, float: f64) {
+        self.bytes(&float.to_le_bytes());
+    }
+    pub fn text(&mut self, text: &str) {
+        self.number(text.len());
+        self.bytes(text.as_bytes());
+    }
+    pub fn slice(&mut self, slice: &LangModuleSlice) {
+        let range = slice.range();
+        self.number(range.start);
+        self.number(range.len());
+    }
+}
+
+/// Read values written in the cache format.
+/// Each method returns None if the bytes are not valid.
+pub struct Decoder<'a> {
+    module: Rc<LangModule>,
+    input: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a decoder of the given bytes, whose locations are slices of the given module
+    pub fn new(module: &Rc<LangModule>, input: &'a [u8]) -> Self {
+        Self {
+            module: Rc::clone(module),
+            input,
+            cursor: 0,
+        }
+    }
+    pub fn module(&self) -> &Rc<LangModule> {
+        &self.module
+    }
+    /// If every byte has been read
+    pub fn finished(&self) -> bool {
+        self.cursor == self.input.len()
+    }
+
+    pub fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .input
+            .get(self.cursor..self.cursor.checked_add(length)?)?;
+        self.cursor += length;
+        Some(bytes)
+    }
+    pub fn tag(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+    pub fn number(&mut self) -> Option<usize> {
+        let mut number = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.tag()?;
+            number |= usize::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(number);
+            }
+        }
+        None
+    }
+    pub fn float(&mut self) -> Option<f64> {
+        Some(f64::from_le_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+    pub fn text(&mut self) -> Option<String> {
+        let length = self.number()?;
+        String::from_utf8(self.bytes(length)?.to_vec()).ok()
+    }
+    pub fn slice(&mut self) -> Option<LangModuleSlice> {
+        let start = self.number()?;
+        let end = start.checked_add(self.number()?)?;
+
+        let code = self.module.code();
+        let valid = end <= code.len() && code.is_char_boundary(start) && code.is_char_boundary(end);
+        valid.then(|| LangModuleSlice::new_with(&self.module, start..end))
+    }
+}
+
+/// A value that can be stored in the cache
+pub trait Cachable: Sized {
+    fn encode(&self, encoder: &mut Encoder);
+    fn decode(decoder: &mut Decoder) -> Option<Self>;
+}
+
+// ----- Generic values
+
+impl Cachable for bool {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.tag(u8::from(*self));
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for String {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.text(self);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        decoder.text()
+    }
+}
+
+impl<T: Cachable> Cachable for Box<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.as_ref().encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        T::decode(decoder).map(Box::new)
+    }
+}
+
+impl<T: Cachable> Cachable for Option<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            None => encoder.tag(0),
+            Some(value) => {
+                encoder.tag(1);
+                value.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(None),
+            1 => Some(Some(T::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Cachable> Cachable for Vec<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.number(self.len());
+        for item in self {
+            item.encode(encoder);
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let length = decoder.number()?;
+        (0..length).map(|_| T::decode(decoder)).collect()
+    }
+}
+
+impl<K: Cachable> Cachable for Node<K> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.slice(self.location());
+        self.kind().encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let location = decoder.slice()?;
+        Some(Node::new(K::decode(decoder)?, &location))
+    }
+}
+
+impl<K: Cachable> Cachable for Token<K> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.slice(self.location());
+        self.kind().encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let location = decoder.slice()?;
+        Some(Token::new(K::decode(decoder)?, &location))
+    }
+}
+
+// ----- Tokens
+
+impl Cachable for ScopeTarget {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Named(name) => {
+                encoder.tag(0);
+                encoder.text(name);
+            }
+            Self::Numbered(skip) => {
+                encoder.tag(1);
+                encoder.number(skip.get());
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Named(decoder.text()?)),
+            1 => Some(Self::Numbered(NonZero::new(decoder.number()?)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Operator {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.tag(match self {
+            Self::Add => 0,
+            Self::Substract => 1,
+            Self::Multiply => 2,
+            Self::Divide => 3,
+            Self::Power => 4,
+            Self::Modulo => 5,
+            Self::EuclidianDivision => 6,
+        });
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Add),
+            1 => Some(Self::Substract),
+            2 => Some(Self::Multiply),
+            3 => Some(Self::Divide),
+            4 => Some(Self::Power),
+            5 => Some(Self::Modulo),
+            6 => Some(Self::EuclidianDivision),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for BinaryOperator {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.tag(match self {
+            Self::And => 0,
+            Self::Xor => 1,
+            Self::Or => 2,
+        });
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::And),
+            1 => Some(Self::Xor),
+            2 => Some(Self::Or),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Comparison {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Equal => encoder.tag(0),
+            Self::Less(strict) => {
+                encoder.tag(1);
+                strict.encode(encoder);
+            }
+            Self::Greater(strict) => {
+                encoder.tag(2);
+                strict.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Equal),
+            1 => Some(Self::Less(bool::decode(decoder)?)),
+            2 => Some(Self::Greater(bool::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+// ----- Instructions
+
+impl Cachable for Instructions {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::ValueOf(expression) => {
+                encoder.tag(0);
+                expression.encode(encoder);
+            }
+            Self::If(condition) => {
+                encoder.tag(1);
+                condition.encode(encoder);
+            }
+            Self::Loop(repetition) => {
+                encoder.tag(2);
+                repetition.encode(encoder);
+            }
+            Self::Break(breaker) => {
+                encoder.tag(3);
+                breaker.encode(encoder);
+            }
+            Self::Use(package) => {
+                encoder.tag(4);
+                package.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::ValueOf(Expressions::decode(decoder)?)),
+            1 => Some(Self::If(If::decode(decoder)?)),
+            2 => Some(Self::Loop(Loop::decode(decoder)?)),
+            3 => Some(Self::Break(Break::decode(decoder)?)),
+            4 => Some(Self::Use(Package::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for If {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.condition.encode(encoder);
+        self.process.encode(encoder);
+        self.fallback.encode(encoder);
+        self.scope_target.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            condition: Cachable::decode(decoder)?,
+            process: Cachable::decode(decoder)?,
+            fallback: Cachable::decode(decoder)?,
+            scope_target: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for IfFallBack {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Process(target, process) => {
+                encoder.tag(0);
+                target.encode(encoder);
+                process.encode(encoder);
+            }
+            Self::If(other) => {
+                encoder.tag(1);
+                other.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Process(
+                Cachable::decode(decoder)?,
+                Cachable::decode(decoder)?,
+            )),
+            1 => Some(Self::If(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Loop {
+    fn encode(&self, encoder: &mut Encoder) {
+        match &self.parameter {
+            LoopParameter::Through(each) => {
+                encoder.tag(0);
+                each.iterable.encode(encoder);
+                each.item.encode(encoder);
+                each.index.encode(encoder);
+            }
+            LoopParameter::Conditionnaly(condition) => {
+                encoder.tag(1);
+                condition.condition.encode(encoder);
+                condition.iteration_number.encode(encoder);
+            }
+        }
+        self.process.encode(encoder);
+        self.scope_target.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let parameter = match decoder.tag()? {
+            0 => LoopParameter::Through(Each {
+                iterable: Cachable::decode(decoder)?,
+                item: Cachable::decode(decoder)?,
+                index: Cachable::decode(decoder)?,
+            }),
+            1 => LoopParameter::Conditionnaly(While {
+                condition: Cachable::decode(decoder)?,
+                iteration_number: Cachable::decode(decoder)?,
+            }),
+            _ => return None,
+        };
+
+        Some(Self {
+            parameter,
+            process: Cachable::decode(decoder)?,
+            scope_target: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Break {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.slice(&self.keyword_location);
+        match &self.kind {
+            BreakKind::Stop(target) => {
+                encoder.tag(0);
+                target.encode(encoder);
+            }
+            BreakKind::Pass(target) => {
+                encoder.tag(1);
+                target.encode(encoder);
+            }
+            BreakKind::Return(target, value) => {
+                encoder.tag(2);
+                target.encode(encoder);
+                value.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let keyword_location = decoder.slice()?;
+        let kind = match decoder.tag()? {
+            0 => BreakKind::Stop(Cachable::decode(decoder)?),
+            1 => BreakKind::Pass(Cachable::decode(decoder)?),
+            2 => BreakKind::Return(Cachable::decode(decoder)?, Cachable::decode(decoder)?),
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            keyword_location,
+        })
+    }
+}
+
+impl Cachable for Package {
+    fn encode(&self, encoder: &mut Encoder) {
+        match &self.source {
+            PackageSource::Package(name) => {
+                encoder.tag(0);
+                encoder.text(name);
+            }
+            PackageSource::File(path) => {
+                encoder.tag(1);
+                encoder.text(path);
+            }
+        }
+        match &self.included {
+            PackageIncludedContent::All => encoder.tag(0),
+            PackageIncludedContent::Only(names) => {
+                encoder.tag(1);
+                names.encode(encoder);
+            }
+        }
+        match &self.emplacement {
+            PackageContentEmplacement::Global => encoder.tag(0),
+            PackageContentEmplacement::Variable(variable) => {
+                encoder.tag(1);
+                variable.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let source = match decoder.tag()? {
+            0 => PackageSource::Package(decoder.text()?),
+            1 => PackageSource::File(decoder.text()?),
+            _ => return None,
+        };
+        let included = match decoder.tag()? {
+            0 => PackageIncludedContent::All,
+            1 => PackageIncludedContent::Only(Cachable::decode(decoder)?),
+            _ => return None,
+        };
+        let emplacement = match decoder.tag()? {
+            0 => PackageContentEmplacement::Global,
+            1 => PackageContentEmplacement::Variable(Cachable::decode(decoder)?),
+            _ => return None,
+        };
+
+        Some(Package::new(
+            source,
+            included,
+            emplacement,
+            decoder.module(),
+        ))
+    }
+}
+
+// ----- Expressions
+
+impl Cachable for Expressions {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Literal(literal) => {
+                encoder.tag(0);
+                literal.encode(encoder);
+            }
+            Self::Defined(definable) => {
+                encoder.tag(1);
+                definable.encode(encoder);
+            }
+            Self::Read(property) => {
+                encoder.tag(2);
+                property.encode(encoder);
+            }
+            Self::ReturnOf(call) => {
+                encoder.tag(3);
+                call.encode(encoder);
+            }
+            Self::Reverse(reverse) => {
+                encoder.tag(4);
+                reverse.encode(encoder);
+            }
+            Self::Operation(operation) => {
+                encoder.tag(5);
+                operation.encode(encoder);
+            }
+            Self::Prioritized(inner) => {
+                encoder.tag(6);
+                inner.encode(encoder);
+            }
+            Self::Ternary(ternary) => {
+                encoder.tag(7);
+                ternary.condition.encode(encoder);
+                ternary.yes.encode(encoder);
+                ternary.no.encode(encoder);
+            }
+            Self::Structure(structure) => {
+                encoder.tag(8);
+                structure.entries.encode(encoder);
+            }
+            Self::Array(array) => {
+                encoder.tag(9);
+                array.entries.encode(encoder);
+            }
+            Self::Modifed(modified) => {
+                encoder.tag(10);
+                modified.definable.encode(encoder);
+                modified.modified_by.encode(encoder);
+            }
+            Self::Instanciate(instanciation) => {
+                encoder.tag(11);
+                instanciation.class.encode(encoder);
+                instanciation.arguments.encode(encoder);
+            }
+            Self::Typed(typed) => {
+                encoder.tag(12);
+                typed.expression.encode(encoder);
+                typed.typing.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Literal(Cachable::decode(decoder)?)),
+            1 => Some(Self::Defined(Cachable::decode(decoder)?)),
+            2 => Some(Self::Read(Cachable::decode(decoder)?)),
+            3 => Some(Self::ReturnOf(Cachable::decode(decoder)?)),
+            4 => Some(Self::Reverse(Cachable::decode(decoder)?)),
+            5 => Some(Self::Operation(Cachable::decode(decoder)?)),
+            6 => Some(Self::Prioritized(Cachable::decode(decoder)?)),
+            7 => Some(Self::Ternary(Ternary {
+                condition: Cachable::decode(decoder)?,
+                yes: Cachable::decode(decoder)?,
+                no: Cachable::decode(decoder)?,
+            })),
+            8 => Some(Self::Structure(Structure {
+                entries: Cachable::decode(decoder)?,
+            })),
+            9 => Some(Self::Array(Array {
+                entries: Cachable::decode(decoder)?,
+            })),
+            10 => Some(Self::Modifed(ModifiedDefinable {
+                definable: Cachable::decode(decoder)?,
+                modified_by: Cachable::decode(decoder)?,
+            })),
+            11 => Some(Self::Instanciate(ClassInstanciation {
+                class: Cachable::decode(decoder)?,
+                arguments: Cachable::decode(decoder)?,
+            })),
+            12 => Some(Self::Typed(Typed {
+                expression: Cachable::decode(decoder)?,
+                typing: Cachable::decode(decoder)?,
+            })),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Word {
+    fn encode(&self, _: &mut Encoder) {}
+    fn decode(_: &mut Decoder) -> Option<Self> {
+        Some(Word)
+    }
+}
+
+impl Cachable for ParsedLiterals {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Word => encoder.tag(0),
+            Self::True => encoder.tag(1),
+            Self::False => encoder.tag(2),
+            Self::Number => encoder.tag(3),
+            Self::Computed(number) => {
+                encoder.tag(4);
+                encoder.float(*number);
+            }
+            Self::Empty => encoder.tag(5),
+            Self::String(items) => {
+                encoder.tag(6);
+                items.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Word),
+            1 => Some(Self::True),
+            2 => Some(Self::False),
+            3 => Some(Self::Number),
+            4 => Some(Self::Computed(decoder.float()?)),
+            5 => Some(Self::Empty),
+            6 => Some(Self::String(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for ParsedStringItem {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Literal(content) => {
+                encoder.tag(0);
+                encoder.text(content);
+            }
+            Self::Expression(expression) => {
+                encoder.tag(1);
+                expression.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Literal(decoder.text()?)),
+            1 => Some(Self::Expression(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for ReadProperty {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.from.encode(encoder);
+        self.read.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            from: Cachable::decode(decoder)?,
+            read: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Property {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Key => encoder.tag(0),
+            Self::Index => encoder.tag(1),
+            Self::Expression(expression) => {
+                encoder.tag(2);
+                expression.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Key),
+            1 => Some(Self::Index),
+            2 => Some(Self::Expression(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Call {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.callable.encode(encoder);
+        self.arguments.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            callable: Cachable::decode(decoder)?,
+            arguments: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Reverse {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.tag(match self.kind {
+            ReverseKind::Sign => 0,
+            ReverseKind::Boolean => 1,
+        });
+        self.expression.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let kind = match decoder.tag()? {
+            0 => ReverseKind::Sign,
+            1 => ReverseKind::Boolean,
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            expression: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Operations {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Numeric(operation) => {
+                encoder.tag(0);
+                operation.encode(encoder);
+            }
+            Self::Binary(operation) => {
+                encoder.tag(1);
+                operation.encode(encoder);
+            }
+            Self::Comparative(operation) => {
+                encoder.tag(2);
+                operation.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Numeric(Cachable::decode(decoder)?)),
+            1 => Some(Self::Binary(Cachable::decode(decoder)?)),
+            2 => Some(Self::Comparative(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl<O: Cachable> Cachable for Operation<O> {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.operator.encode(encoder);
+        self.operands.0.encode(encoder);
+        self.operands.1.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            operator: Cachable::decode(decoder)?,
+            operands: (Cachable::decode(decoder)?, Cachable::decode(decoder)?),
+        })
+    }
+}
+
+impl Cachable for StructureEntry {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.key.encode(encoder);
+        self.value.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            key: Cachable::decode(decoder)?,
+            value: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Modifier {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::DefinedElement => encoder.tag(0),
+            Self::CallReturn(call) => {
+                encoder.tag(1);
+                call.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::DefinedElement),
+            1 => Some(Self::CallReturn(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for Typing {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.types.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            types: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+// ----- Definables
+
+impl Cachable for Definables {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Function(function) => {
+                encoder.tag(0);
+                function.encode(encoder);
+            }
+            Self::Variable(variable) => {
+                encoder.tag(1);
+                variable.encode(encoder);
+            }
+            Self::Class(class) => {
+                encoder.tag(2);
+                class.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Function(Cachable::decode(decoder)?)),
+            1 => Some(Self::Variable(Cachable::decode(decoder)?)),
+            2 => Some(Self::Class(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for DefineFunction {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.name.encode(encoder);
+        self.arguments.encode(encoder);
+        self.returns.encode(encoder);
+        self.scope_target.encode(encoder);
+        self.execution.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            name: Cachable::decode(decoder)?,
+            arguments: Cachable::decode(decoder)?,
+            returns: Cachable::decode(decoder)?,
+            scope_target: Cachable::decode(decoder)?,
+            execution: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for Argument {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.name.encode(encoder);
+        self.typing.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            name: Cachable::decode(decoder)?,
+            typing: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for DefineVariable {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.emplacement.encode(encoder);
+        self.value.encode(encoder);
+        self.readonly.encode(encoder);
+        self.typing.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            emplacement: Cachable::decode(decoder)?,
+            value: Cachable::decode(decoder)?,
+            readonly: Cachable::decode(decoder)?,
+            typing: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl Cachable for VariableEmplacements {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Self::Scope => encoder.tag(0),
+            Self::Property(property) => {
+                encoder.tag(1);
+                property.encode(encoder);
+            }
+            Self::Any(expression) => {
+                encoder.tag(2);
+                expression.encode(encoder);
+            }
+        }
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        match decoder.tag()? {
+            0 => Some(Self::Scope),
+            1 => Some(Self::Property(Cachable::decode(decoder)?)),
+            2 => Some(Self::Any(Cachable::decode(decoder)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Cachable for DefineClass {
+    fn encode(&self, encoder: &mut Encoder) {
+        self.name.encode(encoder);
+        self.constructor.encode(encoder);
+        self.parents.encode(encoder);
+        self.attributes.encode(encoder);
+        self.methods.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        Some(Self {
+            name: Cachable::decode(decoder)?,
+            constructor: Cachable::decode(decoder)?,
+            parents: Cachable::decode(decoder)?,
+            attributes: Cachable::decode(decoder)?,
+            methods: Cachable::decode(decoder)?,
+        })
+    }
+}
+
+impl<K: Cachable> Cachable for ClassItem<K> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.tag(match self.visibility {
+            ClassItemVisibility::Public => 0,
+            ClassItemVisibility::Private => 1,
+            ClassItemVisibility::Protected => 2,
+        });
+        self.is_static.encode(encoder);
+        self.item.encode(encoder);
+        self.modifie_by.encode(encoder);
+    }
+    fn decode(decoder: &mut Decoder) -> Option<Self> {
+        let visibility = match decoder.tag()? {
+            0 => ClassItemVisibility::Public,
+            1 => ClassItemVisibility::Private,
+            2 => ClassItemVisibility::Protected,
+            _ => return None,
+        };
+
+        Some(Self {
+            visibility,
+            is_static: Cachable::decode(decoder)?,
+            item: Cachable::decode(decoder)?,
+            modifie_by: Cachable::decode(decoder)?,
+        })
+    }
+}
@@ -0,0 +1,127 @@
+//! Cache of the parsed modules.
+//!
+//! The instructions parsed from a module are stored in a `.flyc` file, next to the module. The cache is valid while
+//! the code of the module and the version of flylang do not change : the next runs read it instead of lexing and
+//! parsing the module again.
+
+use std::{fs, path::PathBuf, rc::Rc};
+
+use toml::Value;
+
+use crate::{
+    behavior::LangBehavior,
+    flylang::{
+        cache::{
+            errors::CacheNotWritten,
+            format::{Cachable, Decoder, Encoder},
+        },
+        errors::RaisableErr,
+        lexer::Lexer,
+        module::LangModule,
+        parser::{Parser, ast::Branches},
+    },
+};
+
+pub mod errors;
+pub mod format;
+
+/// The extension of the cache files
+pub const EXTENSION: &str = "flyc";
+/// The version of the format. It changes each time the format is modified in a non-compatible way.
+pub const FORMAT_VERSION: usize = 1;
+/// The version of flylang, the parsed instructions of a module can change between versions
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The bytes starting each cache file
+const SIGNATURE: &[u8] = b"FLYC";
+
+/// If the parsed modules are cached (the `language.cache` behavior)
+pub fn enabled(behaviors: &LangBehavior) -> bool {
+    matches!(behaviors.get("language.cache"), Some(Value::Boolean(true)))
+}
+
+/// The hash of the code of a module (64 bits FNV-1a), identifying the code a cache has been created from
+pub fn key(code: &str) -> u64 {
+    code.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The location of the cache of a module, or None if the module is not a file
+pub fn location(module: &LangModule) -> Option<PathBuf> {
+    let path = module.path();
+    path.is_file().then(|| path.with_extension(EXTENSION))
+}
+
+/// Serialize the parsed instructions of a module
+pub fn encode(module: &LangModule, branches: &Branches) -> Vec<u8> {
+    let mut encoder = Encoder::new();
+
+    encoder.bytes(SIGNATURE);
+    encoder.number(FORMAT_VERSION);
+    encoder.text(VERSION);
+    encoder.bytes(&key(module.code()).to_le_bytes());
+    branches.encode(&mut encoder);
+
+    encoder.finish()
+}
+
+/// Deserialize the parsed instructions of a module.
+///
+/// Returns None if the bytes are not a valid cache of the current code of the module, with the current version of flylang.
+pub fn decode(module: &Rc<LangModule>, bytes: &[u8]) -> Option<Branches> {
+    let mut decoder = Decoder::new(module, bytes);
+
+    let valid = decoder.bytes(SIGNATURE.len())? == SIGNATURE
+        && decoder.number()? == FORMAT_VERSION
+        && decoder.text()? == VERSION
+        && decoder.bytes(8)? == key(module.code()).to_le_bytes();
+    if !valid {
+        return None;
+    }
+
+    let branches = Branches::decode(&mut decoder)?;
+    decoder.finished().then_some(branches)
+}
+
+/// Read the cached instructions of a module, or None if its cache does not exist or is stale
+pub fn read(module: &Rc<LangModule>) -> Option<Branches> {
+    let bytes = fs::read(location(module)?).ok()?;
+    decode(module, &bytes)
+}
+
+/// Write the cache of a module. Returns the location of the written file.
+pub fn write(module: &LangModule, branches: &Branches) -> Result<PathBuf, CacheNotWritten> {
+    let Some(path) = location(module) else {
+        return Err(CacheNotWritten {
+            path: module.path(),
+            reason: String::from("the module is not a file"),
+        });
+    };
+
+    match fs::write(&path, encode(module, branches)) {
+        Ok(()) => Ok(path),
+        Err(error) => Err(CacheNotWritten {
+            path,
+            reason: error.to_string(),
+        }),
+    }
+}
+
+/// Parse a module.
+///
+/// If the cache is enabled, the instructions are read from the cache of the module when it is valid,
+/// otherwise the module is parsed and its cache is rebuilt.
+pub fn parse(module: &Rc<LangModule>, behaviors: &LangBehavior) -> Branches {
+    let cached = enabled(behaviors) && location(module).is_some();
+    if cached && let Some(branches) = read(module) {
+        return branches;
+    }
+
+    let branches = Parser::from(&mut Lexer::new(module)).parse().clone();
+    if cached && let Err(error) = write(module, &branches) {
+        error.controlled_raise();
+    }
+
+    branches
+}
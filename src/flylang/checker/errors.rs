@@ -0,0 +1,79 @@
+use crate::flylang::{
+    errors::{ErrorType, RaisableErr, codes::ErrorId},
+    module::slice::LangModuleSlice,
+};
+
+pub struct TypeMismatch {
+    pub location: LangModuleSlice,
+    pub expected: String,
+    pub found: String,
+}
+impl RaisableErr for TypeMismatch {
+    fn _id(&self) -> ErrorId {
+        ErrorId::TypeMismatch
+    }
+    fn _code(&self) -> i32 {
+        1
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Expected a value of type {}, but found {}.",
+            self.expected, self.found
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct UnknownType(pub LangModuleSlice);
+impl RaisableErr for UnknownType {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnknownType
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The type \"{}\" does not exist. Use a built-in type or the name of a class.",
+            self.0.code()
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct InvalidOperands {
+    pub location: LangModuleSlice,
+    pub operator: String,
+    pub operands: Vec<String>,
+}
+impl RaisableErr for InvalidOperands {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidOperands
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Cannot apply \"{}\" on {}.",
+            self.operator,
+            self.operands.join(" and ")
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
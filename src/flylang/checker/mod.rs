@@ -0,0 +1,703 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
+use crate::flylang::{
+    checker::{
+        errors::{InvalidOperands, TypeMismatch, UnknownType},
+        types::{Signature, Type},
+    },
+    errors::RaisableErr,
+    lexer::tokens::Operator,
+    module::slice::LangModuleSlice,
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::{ClassItem, DefineClass},
+            functions::DefineFunction,
+            variables::VariableEmplacements,
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::BreakKind,
+            conditionnal::{If, IfFallBack},
+            loops::LoopParameter,
+        },
+        specials::typing::Typing,
+    },
+};
+
+pub mod errors;
+pub mod types;
+
+/// What the checker knows about a variable
+#[derive(Debug, Clone)]
+struct Symbol {
+    /// The type given to the variable : every value assigned to it must match it
+    declared: Option<Type>,
+    /// The type of its value
+    value: Type,
+}
+
+/// The types of the members of a class
+#[derive(Debug, Default)]
+struct ClassTypes {
+    parents: Vec<String>,
+    attributes: HashMap<String, Type>,
+    methods: HashMap<String, Rc<Signature>>,
+    constructor: Option<Rc<Signature>>,
+}
+
+/// Verify the type annotations of a script, before running it.
+///
+/// The type of an untyped value is not known, so it is accepted everywhere : only the annotated parts of a
+/// script are checked.
+#[derive(Debug)]
+pub struct Checker {
+    /// The variables of the functions being checked, from the outer to the inner one
+    scopes: Vec<HashMap<String, Symbol>>,
+    classes: HashMap<String, ClassTypes>,
+    /// The type returned by each function being checked (if it is declared)
+    returns: Vec<Option<Type>>,
+    errors: Vec<Box<dyn RaisableErr>>,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            classes: HashMap::new(),
+            returns: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// Check the given instructions and return the errors found
+    pub fn check(mut self, branches: &Branches) -> Vec<Box<dyn RaisableErr>> {
+        self.declare_classes(branches);
+        self.instructions(branches);
+
+        self.errors
+    }
+
+    fn report(&mut self, error: impl RaisableErr + 'static) {
+        self.errors.push(Box::new(error));
+    }
+
+    /// Register the classes defined by the instructions, so their names can be used as types before
+    /// their definition
+    fn declare_classes(&mut self, branches: &Branches) {
+        for instruction in branches {
+            let Instructions::ValueOf(expression) = instruction.kind() else {
+                continue;
+            };
+            let definable = match expression {
+                Expressions::Defined(definable) => definable,
+                Expressions::Modifed(modified) => modified.definable.kind(),
+                _ => continue,
+            };
+
+            if let Definables::Class(class) = definable {
+                let name = class.name.location().code().to_string();
+                self.classes.entry(name).or_default();
+            }
+        }
+    }
+
+    /// Get the type described by an annotation
+    fn typing(&mut self, typing: &Node<Typing>) -> Type {
+        let mut types = vec![];
+        for name in &typing.kind().types {
+            let code = name.location().code();
+            types.push(match Type::builtin(code) {
+                Some(builtin) => builtin,
+                None if self.classes.contains_key(code) => Type::Instance(code.to_string()),
+                None => {
+                    self.report(UnknownType(name.location().clone()));
+                    Type::Any
+                }
+            });
+        }
+
+        Type::union(types)
+    }
+
+    fn optional_typing(&mut self, typing: &Option<Node<Typing>>) -> Option<Type> {
+        typing.as_ref().map(|typing| self.typing(typing))
+    }
+
+    /// The class and its ancestors, from the nearest to the farthest one
+    fn lineage<'a>(&'a self, class: &'a str) -> Vec<&'a str> {
+        let mut lineage = vec![];
+        let mut pending = VecDeque::from([class]);
+
+        while let Some(name) = pending.pop_front() {
+            if lineage.contains(&name) {
+                continue;
+            }
+            lineage.push(name);
+
+            if let Some(types) = self.classes.get(name) {
+                pending.extend(types.parents.iter().map(String::as_str));
+            }
+        }
+
+        lineage
+    }
+
+    /// Returns if a value of type `found` can be used where a value of type `expected` is required
+    fn accepts(&self, expected: &Type, found: &Type) -> bool {
+        match (expected, found) {
+            (Type::Any, _) | (_, Type::Any) => true,
+            (_, Type::Union(types)) => types.iter().all(|typing| self.accepts(expected, typing)),
+            (Type::Union(types), _) => types.iter().any(|typing| self.accepts(typing, found)),
+            (Type::Instance(expected), Type::Instance(found)) => {
+                self.lineage(found).contains(&expected.as_str())
+            }
+            (Type::Function(_), Type::Function(_)) | (Type::Class(_), Type::Class(_)) => true,
+            (expected, found) => expected == found,
+        }
+    }
+
+    /// Report an error if a value of type `found` cannot be used where `expected` is required
+    fn expect(&mut self, expected: &Type, found: &Type, location: &LangModuleSlice) {
+        if !self.accepts(expected, found) {
+            self.report(TypeMismatch {
+                location: location.clone(),
+                expected: expected.to_string(),
+                found: found.to_string(),
+            });
+        }
+    }
+
+    /// The type of an attribute or a method of the instances of a class
+    fn member(&self, class: &str, name: &str, attributes_only: bool) -> Option<Type> {
+        self.lineage(class)
+            .into_iter()
+            .filter_map(|class| self.classes.get(class))
+            .find_map(|types| match types.attributes.get(name) {
+                Some(attribute) => Some(attribute.clone()),
+                None if attributes_only => None,
+                None => types
+                    .methods
+                    .get(name)
+                    .map(|method| Type::Function(Some(Rc::clone(method)))),
+            })
+    }
+
+    /// The signature of the constructor used to create an instance of a class
+    fn constructor(&self, class: &str) -> Option<Rc<Signature>> {
+        self.lineage(class)
+            .into_iter()
+            .filter_map(|class| self.classes.get(class))
+            .find_map(|types| types.constructor.clone())
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Assign a value to a variable, the same way the interpreter does : the nearest variable with this
+    /// name is modified, or the variable is created in the current function.
+    fn assign(
+        &mut self,
+        name: &str,
+        declared: Option<Type>,
+        value: Type,
+        location: &LangModuleSlice,
+    ) {
+        let position = self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+            .unwrap_or(self.scopes.len() - 1);
+        let previous = self.scopes[position].get(name).cloned();
+
+        let declared = declared.or_else(|| previous.as_ref()?.declared.clone());
+        if let Some(expected) = &declared {
+            self.expect(expected, &value, location);
+        }
+
+        // An untyped variable can receive any value, so only the functions and classes it is defined
+        // with once are remembered (to check their calls)
+        let value = match value {
+            Type::Function(Some(_)) | Type::Class(Some(_)) if previous.is_none() => value,
+            _ => Type::Any,
+        };
+        self.scopes[position].insert(name.to_string(), Symbol { declared, value });
+    }
+
+    fn instructions(&mut self, branches: &Branches) {
+        for instruction in branches {
+            self.instruction(instruction);
+        }
+    }
+
+    fn instruction(&mut self, instruction: &Node) {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => {
+                self.expression(expression, instruction.location());
+            }
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => {
+                match &repetition.parameter {
+                    LoopParameter::Through(each) => {
+                        self.expression(each.iterable.kind(), each.iterable.location());
+                        for word in [&each.item, &each.index].into_iter().flatten() {
+                            self.assign(word.location().code(), None, Type::Any, word.location());
+                        }
+                    }
+                    LoopParameter::Conditionnaly(condition) => {
+                        let expression = &condition.condition;
+                        self.expression(expression.kind(), expression.location());
+                        if let Some(word) = &condition.iteration_number {
+                            self.assign(
+                                word.location().code(),
+                                None,
+                                Type::Number,
+                                word.location(),
+                            );
+                        }
+                    }
+                }
+
+                self.instructions(&repetition.process);
+            }
+            Instructions::Break(breaker) => {
+                let BreakKind::Return(target, value) = &breaker.kind else {
+                    return;
+                };
+                let (found, location) = match value {
+                    Some(value) => (
+                        self.expression(value.kind(), value.location()),
+                        value.location(),
+                    ),
+                    None => (Type::Empty, &breaker.keyword_location),
+                };
+
+                // A targeted return may leave another function than the current one
+                if target.is_none()
+                    && let Some(Some(expected)) = self.returns.last().cloned()
+                {
+                    self.expect(&expected, &found, location);
+                }
+            }
+            Instructions::Use(_) => {}
+        }
+    }
+
+    fn condition(&mut self, condition: &If) {
+        self.expression(condition.condition.kind(), condition.condition.location());
+        self.instructions(&condition.process);
+
+        if let Some(fallback) = &condition.fallback {
+            match fallback.kind() {
+                IfFallBack::Process(_, branches) => self.instructions(branches),
+                IfFallBack::If(condition) => self.condition(condition.kind()),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expressions, location: &LangModuleSlice) -> Type {
+        match expression {
+            Expressions::Literal(literal) => match literal {
+                ParsedLiterals::Word => self
+                    .lookup(location.code())
+                    .map(|symbol| symbol.declared.clone().unwrap_or(symbol.value.clone()))
+                    .unwrap_or(Type::Any),
+                ParsedLiterals::True | ParsedLiterals::False => Type::Boolean,
+                ParsedLiterals::Number | ParsedLiterals::Computed(_) => Type::Number,
+                ParsedLiterals::Empty => Type::Empty,
+                ParsedLiterals::String(items) => {
+                    for item in items {
+                        if let ParsedStringItem::Expression(expression) = item.kind() {
+                            self.expression(expression.kind(), expression.location());
+                        }
+                    }
+                    Type::String
+                }
+            },
+            Expressions::Defined(definable) => self.define(definable, false, location),
+            Expressions::Modifed(modified) => {
+                for modifier in &modified.modified_by {
+                    if let Modifier::CallReturn(call) = modifier.kind() {
+                        self.call(call);
+                    }
+                }
+                // The value is replaced by the one returned by the modifiers
+                self.define(
+                    modified.definable.kind(),
+                    true,
+                    modified.definable.location(),
+                );
+                Type::Any
+            }
+            Expressions::Read(property) => self.read(property),
+            Expressions::ReturnOf(call) => self.call(call),
+            Expressions::Reverse(reverse) => {
+                let found =
+                    self.expression(reverse.expression.kind(), reverse.expression.location());
+                match reverse.kind {
+                    ReverseKind::Boolean => Type::Boolean,
+                    ReverseKind::Sign => {
+                        if found.is_known() && found != Type::Number {
+                            self.report(InvalidOperands {
+                                location: location.clone(),
+                                operator: String::from("-"),
+                                operands: vec![found.to_string()],
+                            });
+                        }
+                        Type::Number
+                    }
+                }
+            }
+            Expressions::Operation(operation) => self.operation(operation, location),
+            Expressions::Prioritized(inner) => self.expression(inner.kind(), inner.location()),
+            Expressions::Ternary(ternary) => {
+                self.expression(ternary.condition.kind(), ternary.condition.location());
+                let yes = self.expression(ternary.yes.kind(), ternary.yes.location());
+                let no = self.expression(ternary.no.kind(), ternary.no.location());
+
+                Type::union(vec![yes, no])
+            }
+            Expressions::Structure(structure) => {
+                for entry in structure.entries.iter() {
+                    let entry = entry.kind();
+                    self.expression(entry.key.kind(), entry.key.location());
+                    self.expression(entry.value.kind(), entry.value.location());
+                }
+                Type::Structure
+            }
+            Expressions::Array(array) => {
+                for item in array.entries.iter() {
+                    self.expression(item.kind(), item.location());
+                }
+                Type::Array
+            }
+            Expressions::Instanciate(instanciation) => {
+                let class =
+                    self.expression(instanciation.class.kind(), instanciation.class.location());
+                let arguments = self.arguments(&instanciation.arguments);
+
+                let Type::Class(Some(name)) = class else {
+                    return Type::Any;
+                };
+                if let Some(constructor) = self.constructor(&name) {
+                    self.signature(&constructor, &arguments);
+                }
+                Type::Instance(name)
+            }
+            Expressions::Typed(typed) => {
+                let found = self.expression(typed.expression.kind(), typed.expression.location());
+                let expected = self.typing(&typed.typing);
+                self.expect(&expected, &found, typed.expression.location());
+
+                expected
+            }
+        }
+    }
+
+    /// Get the type of each argument given to a function
+    fn arguments(&mut self, arguments: &[Box<Node<Expressions>>]) -> Vec<(Type, LangModuleSlice)> {
+        arguments
+            .iter()
+            .map(|argument| {
+                let typing = self.expression(argument.kind(), argument.location());
+                (typing, argument.location().clone())
+            })
+            .collect()
+    }
+
+    /// Verify the arguments given to a function matching the signature
+    fn signature(&mut self, signature: &Signature, arguments: &[(Type, LangModuleSlice)]) {
+        for (expected, (found, location)) in signature.arguments.iter().zip(arguments) {
+            if let Some(expected) = expected {
+                self.expect(expected, found, location);
+            }
+        }
+    }
+
+    fn call(&mut self, call: &Call) -> Type {
+        let callable = self.expression(call.callable.kind(), call.callable.location());
+        let arguments = self.arguments(&call.arguments);
+
+        match callable {
+            Type::Function(Some(signature)) => {
+                self.signature(&signature, &arguments);
+                signature.returns.clone().unwrap_or(Type::Any)
+            }
+            _ => Type::Any,
+        }
+    }
+
+    fn read(&mut self, property: &ReadProperty) -> Type {
+        let from = self.expression(property.from.kind(), property.from.location());
+
+        match (from, property.read.kind()) {
+            (Type::Instance(class), Property::Key) => self
+                .member(&class, property.read.location().code(), false)
+                .unwrap_or(Type::Any),
+            (_, Property::Expression(key)) => {
+                self.expression(key.kind(), key.location());
+                Type::Any
+            }
+            _ => Type::Any,
+        }
+    }
+
+    fn operation(&mut self, operation: &Operations, location: &LangModuleSlice) -> Type {
+        match operation {
+            Operations::Numeric(numeric) => {
+                let (left, right) = &numeric.operands;
+                let left = self.expression(left.kind(), left.location());
+                let right = self.expression(right.kind(), right.location());
+                let addition = matches!(numeric.operator.kind(), Operator::Add);
+
+                match (&left, &right) {
+                    (Type::Number, Type::Number) => Type::Number,
+                    (Type::String, _) | (_, Type::String) if addition => Type::String,
+                    _ if left.is_known() && right.is_known() => {
+                        self.report(InvalidOperands {
+                            location: location.clone(),
+                            operator: numeric.operator.location().code().to_string(),
+                            operands: vec![left.to_string(), right.to_string()],
+                        });
+                        Type::Any
+                    }
+                    // Only an addition can be applied on something else than numbers
+                    _ if addition => Type::Any,
+                    _ => Type::Number,
+                }
+            }
+            Operations::Binary(binary) => {
+                let (left, right) = &binary.operands;
+                self.expression(left.kind(), left.location());
+                self.expression(right.kind(), right.location());
+                Type::Boolean
+            }
+            Operations::Comparative(comparison) => {
+                let (left, right) = &comparison.operands;
+                self.expression(left.kind(), left.location());
+                self.expression(right.kind(), right.location());
+                Type::Boolean
+            }
+        }
+    }
+
+    /// Check a definition and get the type of the defined value.
+    /// If it is modified, the value is replaced by the modifiers, so its type is not known.
+    fn define(
+        &mut self,
+        definable: &Definables,
+        modified: bool,
+        location: &LangModuleSlice,
+    ) -> Type {
+        match definable {
+            Definables::Variable(variable) => {
+                let mut value = self.expression(variable.value.kind(), variable.value.location());
+                if modified {
+                    value = Type::Any;
+                }
+                let declared = self.optional_typing(&variable.typing);
+
+                let emplacement = &variable.emplacement;
+                match emplacement.kind() {
+                    VariableEmplacements::Scope => self.assign(
+                        emplacement.location().code(),
+                        declared.clone(),
+                        value.clone(),
+                        variable.value.location(),
+                    ),
+                    VariableEmplacements::Property(property) => {
+                        if let Some(expected) = &declared {
+                            self.expect(expected, &value, variable.value.location());
+                        }
+
+                        let target =
+                            self.expression(property.from.kind(), property.from.location());
+                        match (target, property.read.kind()) {
+                            (Type::Instance(class), Property::Key) => {
+                                let name = property.read.location().code();
+                                if let Some(expected) = self.member(&class, name, true) {
+                                    self.expect(&expected, &value, variable.value.location());
+                                }
+                            }
+                            (_, Property::Expression(key)) => {
+                                self.expression(key.kind(), key.location());
+                            }
+                            _ => {}
+                        }
+                    }
+                    VariableEmplacements::Any(expression) => {
+                        self.expression(expression, emplacement.location());
+                    }
+                }
+
+                declared.unwrap_or(value)
+            }
+            Definables::Function(function) => {
+                let signature = self.function_signature(function);
+                let value = match modified {
+                    true => Type::Any,
+                    false => Type::Function(Some(Rc::clone(&signature))),
+                };
+
+                // The name is defined before checking the function, so it can call itself
+                if let Some(name) = &function.name {
+                    self.assign(name.location().code(), None, value.clone(), location);
+                }
+                self.function(function, &signature, None);
+
+                value
+            }
+            Definables::Class(class) => {
+                let value = match modified {
+                    true => Type::Any,
+                    false => Type::Class(Some(class.name.location().code().to_string())),
+                };
+                self.assign(class.name.location().code(), None, value.clone(), location);
+                self.class(class);
+
+                value
+            }
+        }
+    }
+
+    fn function_signature(&mut self, function: &DefineFunction) -> Rc<Signature> {
+        let arguments = function
+            .arguments
+            .iter()
+            .map(|argument| self.optional_typing(&argument.kind().typing))
+            .collect();
+        let returns = self.optional_typing(&function.returns);
+
+        Rc::new(Signature { arguments, returns })
+    }
+
+    /// Check the instructions of a function.
+    /// For methods, `receiver` is the name of the class of `self`.
+    fn function(
+        &mut self,
+        function: &DefineFunction,
+        signature: &Signature,
+        receiver: Option<&str>,
+    ) {
+        let mut scope = HashMap::new();
+        for (argument, declared) in function.arguments.iter().zip(&signature.arguments) {
+            let symbol = Symbol {
+                declared: declared.clone(),
+                value: Type::Any,
+            };
+            scope.insert(argument.kind().name.location().code().to_string(), symbol);
+        }
+        if let Some(class) = receiver {
+            let receiver = Symbol {
+                declared: Some(Type::Instance(class.to_string())),
+                value: Type::Any,
+            };
+            scope.insert(String::from("self"), receiver);
+        }
+
+        self.scopes.push(scope);
+        self.returns.push(signature.returns.clone());
+        self.instructions(&function.execution);
+        self.returns.pop();
+        self.scopes.pop();
+    }
+
+    fn class(&mut self, class: &DefineClass) {
+        let name = class.name.location().code().to_string();
+
+        // The members' types are registered before checking them, so they can use each other
+        let mut types = ClassTypes {
+            parents: class
+                .parents
+                .iter()
+                .map(|parent| parent.location().code().to_string())
+                .collect(),
+            ..Default::default()
+        };
+        self.classes.entry(name.clone()).or_default();
+
+        let mut declarations = vec![];
+        for attribute in class.attributes.iter().map(Node::kind) {
+            let declared = self.optional_typing(&attribute.item.typing);
+            if !attribute.is_static {
+                types.attributes.insert(
+                    attribute.item.emplacement.location().code().to_string(),
+                    declared.clone().unwrap_or(Type::Any),
+                );
+            }
+            declarations.push(declared);
+        }
+        let mut signatures = vec![];
+        for method in class.methods.iter().map(Node::kind) {
+            let signature = self.function_signature(&method.item);
+            if let (false, Some(method_name)) = (method.is_static, &method.item.name) {
+                types.methods.insert(
+                    method_name.location().code().to_string(),
+                    Rc::clone(&signature),
+                );
+            }
+            signatures.push(signature);
+        }
+        let constructor = class.constructor.as_ref().map(|constructor| {
+            let signature = self.function_signature(constructor.kind());
+            (constructor, signature)
+        });
+        types.constructor = constructor
+            .as_ref()
+            .map(|(_, signature)| Rc::clone(signature));
+        self.classes.insert(name.clone(), types);
+
+        for (attribute, declared) in class.attributes.iter().zip(declarations) {
+            let ClassItem {
+                item: variable,
+                modifie_by,
+                ..
+            } = attribute.kind();
+            for modifier in modifie_by {
+                if let Modifier::CallReturn(call) = modifier.kind() {
+                    self.call(call);
+                }
+            }
+
+            let value = self.expression(variable.value.kind(), variable.value.location());
+            if let (Some(expected), true) = (declared, modifie_by.is_empty()) {
+                self.expect(&expected, &value, variable.value.location());
+            }
+        }
+
+        if let Some((constructor, signature)) = &constructor {
+            self.function(constructor.kind(), signature, Some(&name));
+        }
+        for (method, signature) in class.methods.iter().zip(&signatures) {
+            let method = method.kind();
+            for modifier in &method.modifie_by {
+                if let Modifier::CallReturn(call) = modifier.kind() {
+                    self.call(call);
+                }
+            }
+
+            let receiver = (!method.is_static).then_some(name.as_str());
+            self.function(&method.item, signature, receiver);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::{fmt::Display, rc::Rc};
+
+/// The types of the arguments and of the returned value of a function
+#[derive(Debug, PartialEq)]
+pub struct Signature {
+    pub arguments: Vec<Option<Type>>,
+    pub returns: Option<Type>,
+}
+
+/// The type of a value, as known by the checker
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    /// The type is not known : every value is accepted
+    Any,
+    Empty,
+    Boolean,
+    Number,
+    String,
+    Array,
+    Structure,
+    /// A function, with its signature if it is known
+    Function(Option<Rc<Signature>>),
+    /// A class, with its name if it is known
+    Class(Option<String>),
+    /// An instance of the class with the given name
+    Instance(String),
+    /// One of the given types
+    Union(Vec<Type>),
+}
+
+impl Type {
+    /// Get a built-in type from its name
+    pub fn builtin(name: &str) -> Option<Self> {
+        Some(match name {
+            "any" => Self::Any,
+            "empty" => Self::Empty,
+            "boolean" => Self::Boolean,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "structure" => Self::Structure,
+            "function" => Self::Function(None),
+            "class" => Self::Class(None),
+            _ => return None,
+        })
+    }
+
+    /// Create a type accepting any of the given ones
+    pub fn union(types: Vec<Type>) -> Self {
+        let mut members: Vec<Type> = vec![];
+        for typing in types {
+            match typing {
+                Self::Any => return Self::Any,
+                Self::Union(nested) => members.extend(nested),
+                typing => members.push(typing),
+            }
+        }
+        members.dedup();
+
+        if members.len() == 1 {
+            members.pop().unwrap()
+        } else {
+            Self::Union(members)
+        }
+    }
+
+    /// Returns if the type is precisely known (not `any` and not a union)
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Self::Any | Self::Union(_))
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "any"),
+            Self::Empty => write!(f, "empty"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::Number => write!(f, "number"),
+            Self::String => write!(f, "string"),
+            Self::Array => write!(f, "array"),
+            Self::Structure => write!(f, "structure"),
+            Self::Function(_) => write!(f, "function"),
+            Self::Class(_) => write!(f, "class"),
+            Self::Instance(class) => write!(f, "{}", class),
+            Self::Union(types) => {
+                let names: Vec<String> = types.iter().map(|typing| typing.to_string()).collect();
+                write!(f, "{}", names.join(" ? "))
+            }
+        }
+    }
+}
@@ -0,0 +1,1568 @@
+/*
+ * The runtime library of the C code generated from flylang (C99).
+ * It implements the flylang semantics : dynamic values, display, equality, classes, modifiers, breakers, ...
+ */
+
+#include "flylang.h"
+
+#include <math.h>
+#include <stdarg.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+typedef struct FlyString {
+    FlyObject object;
+    size_t length;
+    char data[];
+} FlyString;
+
+typedef struct FlyArray {
+    FlyObject object;
+    size_t length;
+    size_t capacity;
+    Fly *items;
+} FlyArray;
+
+/* The entries of a structure keep their insertion order */
+typedef struct FlyStructure {
+    FlyObject object;
+    size_t length;
+    size_t capacity;
+    Fly *keys;
+    Fly *values;
+} FlyStructure;
+
+typedef struct FlyClass {
+    FlyObject object;
+    const char *name;
+    size_t parents_count;
+    Fly *parents;
+    /* The method resolution order (C3 linearization), starting with the class itself */
+    size_t mro_count;
+    struct FlyClass **mro;
+    FlyStructure *statics;
+    FlyStructure *methods;
+    FlyStructure *modifiers;
+    Fly constructor;
+    Fly attributes;
+} FlyClass;
+
+typedef struct FlyInstance {
+    FlyObject object;
+    FlyClass *class;
+    FlyStructure *fields;
+} FlyInstance;
+
+/* A function or a class wrapped with a modifier : calling it calls the modifier with the element and the
+ * arguments */
+typedef struct FlyModified {
+    FlyObject object;
+    Fly target;
+    Fly modifier;
+} FlyModified;
+
+#define AS(Type, value) ((Type *) (value).as.object)
+
+static Fly object_value(FlyType type, void *object) {
+    Fly value;
+    value.type = type;
+    value.as.object = object;
+    return value;
+}
+
+/* Errors */
+
+static void fly_error(const char *format, ...) FLY_NORETURN;
+static void fly_error(const char *format, ...) {
+    va_list arguments;
+    fflush(stdout);
+    fputs("Error: ", stderr);
+    va_start(arguments, format);
+    vfprintf(stderr, format, arguments);
+    va_end(arguments);
+    fputc('\n', stderr);
+    exit(1);
+}
+
+static void *allocate(size_t size) {
+    void *memory = malloc(size ? size : 1);
+    if (!memory) {
+        fly_error("Out of memory.");
+    }
+    return memory;
+}
+
+static void *reallocate(void *memory, size_t size) {
+    memory = realloc(memory, size ? size : 1);
+    if (!memory) {
+        fly_error("Out of memory.");
+    }
+    return memory;
+}
+
+/*
+ * Garbage collector
+ *
+ * The objects are marked from the roots : the words of the C stack (between `stack_bottom` and the collector)
+ * pointing inside an object, and the builtins. Then the objects which are not marked are freed.
+ */
+
+static FlyObject *objects = NULL;
+static size_t allocated = 0;
+static size_t threshold = 1 << 20;
+static char *stack_bottom = NULL;
+
+static Fly roots[2];
+static size_t roots_count = 0;
+
+/* The objects sorted by address while collecting, to find the objects the stack points to */
+static FlyObject **sorted = NULL;
+static size_t sorted_count = 0;
+
+/* The objects marked and not traced yet */
+static FlyObject **pending = NULL;
+static size_t pending_count = 0;
+static size_t pending_capacity = 0;
+
+static void mark_object(FlyObject *object) {
+    if (!object || object->marked) {
+        return;
+    }
+    object->marked = true;
+    if (pending_count == pending_capacity) {
+        pending_capacity = pending_capacity ? 2 * pending_capacity : 256;
+        pending = reallocate(pending, pending_capacity * sizeof *pending);
+    }
+    pending[pending_count++] = object;
+}
+
+static void mark_value(Fly value) {
+    if (value.type >= FLY_STRING) {
+        mark_object(value.as.object);
+    }
+}
+
+static void mark_values(const Fly *values, size_t count) {
+    for (size_t i = 0; i < count; i++) {
+        mark_value(values[i]);
+    }
+}
+
+static void trace(FlyObject *object) {
+    switch (object->type) {
+        case FLY_ARRAY: {
+            FlyArray *array = (FlyArray *) object;
+            mark_values(array->items, array->length);
+            break;
+        }
+        case FLY_STRUCTURE: {
+            FlyStructure *structure = (FlyStructure *) object;
+            mark_values(structure->keys, structure->length);
+            mark_values(structure->values, structure->length);
+            break;
+        }
+        case FLY_FUNCTION: {
+            FlyFunction *function = (FlyFunction *) object;
+            mark_object((FlyObject *) function->env);
+            mark_value(function->self);
+            mark_value(function->owner);
+            break;
+        }
+        case FLY_CLASS: {
+            FlyClass *class = (FlyClass *) object;
+            mark_values(class->parents, class->parents_count);
+            mark_object((FlyObject *) class->statics);
+            mark_object((FlyObject *) class->methods);
+            mark_object((FlyObject *) class->modifiers);
+            mark_value(class->constructor);
+            mark_value(class->attributes);
+            break;
+        }
+        case FLY_INSTANCE: {
+            FlyInstance *instance = (FlyInstance *) object;
+            mark_object((FlyObject *) instance->class);
+            mark_object((FlyObject *) instance->fields);
+            break;
+        }
+        case FLY_MODIFIED: {
+            FlyModified *modified = (FlyModified *) object;
+            mark_value(modified->target);
+            mark_value(modified->modifier);
+            break;
+        }
+        case FLY_ENVIRONMENT: {
+            /* The variables follow the header of the environment */
+            FlyEnv *env = (FlyEnv *) object;
+            mark_object((FlyObject *) env->outer);
+            mark_values((Fly *) (env + 1), (object->size - sizeof(FlyEnv)) / sizeof(Fly));
+            break;
+        }
+        default:
+            break;
+    }
+}
+
+static int compare_addresses(const void *a, const void *b) {
+    uintptr_t x = (uintptr_t) *(FlyObject *const *) a;
+    uintptr_t y = (uintptr_t) *(FlyObject *const *) b;
+    return (x > y) - (x < y);
+}
+
+/* Mark the object containing the address, if there is one */
+static void mark_address(uintptr_t address) {
+    size_t low = 0;
+    size_t high = sorted_count;
+    while (low < high) {
+        size_t middle = low + (high - low) / 2;
+        if ((uintptr_t) sorted[middle] <= address) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    if (low > 0) {
+        FlyObject *object = sorted[low - 1];
+        if (address < (uintptr_t) object + object->size) {
+            mark_object(object);
+        }
+    }
+}
+
+static void mark_stack(void) {
+    char top;
+    char *low = &top;
+    char *high = stack_bottom;
+    if (low > high) {
+        char *swap = low;
+        low = high;
+        high = swap;
+    }
+
+    low = (char *) ((uintptr_t) low & ~(uintptr_t) (sizeof(void *) - 1));
+    for (char *word = low; word + sizeof(void *) <= high; word += sizeof(void *)) {
+        uintptr_t address;
+        memcpy(&address, word, sizeof address);
+        mark_address(address);
+    }
+}
+
+/* Called through a volatile pointer, so it is not inlined in `collect` */
+static void (*volatile mark_stack_pointer)(void) = mark_stack;
+
+static void free_object(FlyObject *object) {
+    switch (object->type) {
+        case FLY_ARRAY:
+            free(((FlyArray *) object)->items);
+            break;
+        case FLY_STRUCTURE:
+            free(((FlyStructure *) object)->keys);
+            free(((FlyStructure *) object)->values);
+            break;
+        case FLY_CLASS:
+            free(((FlyClass *) object)->parents);
+            free(((FlyClass *) object)->mro);
+            break;
+        default:
+            break;
+    }
+    free(object);
+}
+
+static void collect(void) {
+    jmp_buf registers;
+
+    /* The registers are written on the stack, to find the objects they point to */
+#ifdef __GNUC__
+    __builtin_unwind_init();
+#endif
+    setjmp(registers);
+
+    sorted_count = 0;
+    for (FlyObject *object = objects; object; object = object->next) {
+        sorted_count++;
+    }
+    sorted = reallocate(sorted, sorted_count * sizeof *sorted);
+    size_t position = 0;
+    for (FlyObject *object = objects; object; object = object->next) {
+        sorted[position++] = object;
+    }
+    qsort(sorted, sorted_count, sizeof *sorted, compare_addresses);
+
+    mark_stack_pointer();
+    mark_values(roots, roots_count);
+    while (pending_count > 0) {
+        trace(pending[--pending_count]);
+    }
+
+    size_t alive = 0;
+    FlyObject **link = &objects;
+    while (*link) {
+        FlyObject *object = *link;
+        if (object->marked) {
+            object->marked = false;
+            alive += object->size;
+            link = &object->next;
+        } else {
+            *link = object->next;
+            free_object(object);
+        }
+    }
+
+    allocated = alive;
+    threshold = alive * 2 > ((size_t) 1 << 20) ? alive * 2 : (size_t) 1 << 20;
+}
+
+static void *fly_alloc(FlyType type, size_t size) {
+    if (stack_bottom && allocated + size > threshold) {
+        collect();
+    }
+
+    FlyObject *object = allocate(size);
+    memset(object, 0, size);
+    object->type = type;
+    object->size = size;
+    object->next = objects;
+    objects = object;
+    allocated += size;
+    return object;
+}
+
+/* Text buffers */
+
+typedef struct Buffer {
+    char *data;
+    size_t length;
+    size_t capacity;
+} Buffer;
+
+static void buffer_append(Buffer *buffer, const char *text, size_t length) {
+    if (buffer->length + length + 1 > buffer->capacity) {
+        buffer->capacity = 2 * (buffer->length + length + 1);
+        buffer->data = reallocate(buffer->data, buffer->capacity);
+    }
+    memcpy(buffer->data + buffer->length, text, length);
+    buffer->length += length;
+    buffer->data[buffer->length] = '\0';
+}
+
+static void buffer_text(Buffer *buffer, const char *text) {
+    buffer_append(buffer, text, strlen(text));
+}
+
+/* Values */
+
+static Fly string_of(const char *text, size_t length) {
+    FlyString *string = fly_alloc(FLY_STRING, sizeof(FlyString) + length + 1);
+    string->length = length;
+    memcpy(string->data, text, length);
+    string->data[length] = '\0';
+    return object_value(FLY_STRING, string);
+}
+
+Fly fly_string(const char *text) {
+    return string_of(text, strlen(text));
+}
+
+/* Take the content of a buffer as a string */
+static Fly buffer_string(Buffer *buffer) {
+    Fly string = string_of(buffer->data ? buffer->data : "", buffer->length);
+    free(buffer->data);
+    return string;
+}
+
+static void array_push(FlyArray *array, Fly item) {
+    if (array->length == array->capacity) {
+        array->capacity = array->capacity ? 2 * array->capacity : 4;
+        array->items = reallocate(array->items, array->capacity * sizeof(Fly));
+    }
+    array->items[array->length++] = item;
+}
+
+Fly fly_array(size_t count, const Fly *items) {
+    FlyArray *array = fly_alloc(FLY_ARRAY, sizeof(FlyArray));
+    array->capacity = count;
+    array->items = allocate(count * sizeof(Fly));
+    for (size_t i = 0; i < count; i++) {
+        array->items[i] = items[i];
+    }
+    array->length = count;
+    return object_value(FLY_ARRAY, array);
+}
+
+static FlyStructure *structure_new(void) {
+    return fly_alloc(FLY_STRUCTURE, sizeof(FlyStructure));
+}
+
+static bool equal(Fly a, Fly b);
+
+/* The position of a key in a structure, or -1 */
+static long structure_find(FlyStructure *structure, Fly key) {
+    for (size_t i = 0; i < structure->length; i++) {
+        if (equal(structure->keys[i], key)) {
+            return (long) i;
+        }
+    }
+    return -1;
+}
+
+static long structure_find_name(FlyStructure *structure, const char *name) {
+    for (size_t i = 0; i < structure->length; i++) {
+        Fly key = structure->keys[i];
+        if (key.type == FLY_STRING && strcmp(AS(FlyString, key)->data, name) == 0) {
+            return (long) i;
+        }
+    }
+    return -1;
+}
+
+static void structure_append(FlyStructure *structure, Fly key, Fly value) {
+    if (structure->length == structure->capacity) {
+        structure->capacity = structure->capacity ? 2 * structure->capacity : 4;
+        structure->keys = reallocate(structure->keys, structure->capacity * sizeof(Fly));
+        structure->values = reallocate(structure->values, structure->capacity * sizeof(Fly));
+    }
+    structure->keys[structure->length] = key;
+    structure->values[structure->length] = value;
+    structure->length++;
+}
+
+static void structure_set(FlyStructure *structure, Fly key, Fly value) {
+    long position = structure_find(structure, key);
+    if (position >= 0) {
+        structure->values[position] = value;
+    } else {
+        structure_append(structure, key, value);
+    }
+}
+
+static void structure_set_name(FlyStructure *structure, const char *name, Fly value) {
+    long position = structure_find_name(structure, name);
+    if (position >= 0) {
+        structure->values[position] = value;
+    } else {
+        structure_append(structure, fly_string(name), value);
+    }
+}
+
+Fly fly_structure(size_t count, const Fly *entries) {
+    FlyStructure *structure = structure_new();
+    for (size_t i = 0; i < count; i++) {
+        structure_set(structure, entries[2 * i], entries[2 * i + 1]);
+    }
+    return object_value(FLY_STRUCTURE, structure);
+}
+
+Fly fly_function(const char *name, FlyCode code, void *env) {
+    FlyFunction *function = fly_alloc(FLY_FUNCTION, sizeof(FlyFunction));
+    function->name = name;
+    function->code = code;
+    function->env = env;
+    return object_value(FLY_FUNCTION, function);
+}
+
+/* Bind a method to an instance */
+static Fly bind(Fly method, Fly self, Fly owner) {
+    FlyFunction *bound = fly_alloc(FLY_FUNCTION, sizeof(FlyFunction));
+    FlyObject header = bound->object;
+    *bound = *AS(FlyFunction, method);
+    bound->object = header;
+    bound->self = self;
+    bound->owner = owner;
+    return object_value(FLY_FUNCTION, bound);
+}
+
+static Fly unmodified(Fly value) {
+    while (value.type == FLY_MODIFIED) {
+        value = AS(FlyModified, value)->target;
+    }
+    return value;
+}
+
+Fly fly_modify(Fly target, size_t count, const Fly *modifiers) {
+    for (size_t i = 0; i < count; i++) {
+        FlyModified *modified = fly_alloc(FLY_MODIFIED, sizeof(FlyModified));
+        modified->target = target;
+        modified->modifier = modifiers[i];
+        target = object_value(FLY_MODIFIED, modified);
+    }
+    return target;
+}
+
+Fly fly_modify_variable(const char *name, bool constant, Fly value, size_t count, const Fly *modifiers) {
+    for (size_t i = 0; i < count; i++) {
+        Fly couple[2] = {fly_boolean(constant), value};
+        Fly arguments[2] = {fly_string(name), fly_array(2, couple)};
+        value = fly_call(modifiers[i], 2, arguments);
+    }
+    return value;
+}
+
+static const char *type_of(Fly value) {
+    value = unmodified(value);
+    switch (value.type) {
+        case FLY_EMPTY:
+            return "empty";
+        case FLY_BOOLEAN:
+            return "boolean";
+        case FLY_NUMBER:
+            return "number";
+        case FLY_STRING:
+            return "string";
+        case FLY_ARRAY:
+            return "array";
+        case FLY_STRUCTURE:
+            return "structure";
+        case FLY_CLASS:
+            return "class";
+        case FLY_INSTANCE:
+            return "instance";
+        default:
+            return "function";
+    }
+}
+
+/* Strings are UTF-8 encoded : their length and indexes count the characters */
+
+static size_t characters(const FlyString *string) {
+    size_t count = 0;
+    for (size_t i = 0; i < string->length; i++) {
+        count += ((unsigned char) string->data[i] & 0xC0) != 0x80;
+    }
+    return count;
+}
+
+/* The byte offset of a character, and its length in bytes */
+static size_t character(const FlyString *string, size_t index, size_t *length) {
+    size_t offset = 0;
+    for (size_t count = 0; count < index; count++) {
+        offset++;
+        while (offset < string->length && ((unsigned char) string->data[offset] & 0xC0) == 0x80) {
+            offset++;
+        }
+    }
+    size_t end = offset + 1;
+    while (end < string->length && ((unsigned char) string->data[end] & 0xC0) == 0x80) {
+        end++;
+    }
+    *length = end - offset;
+    return offset;
+}
+
+/* Display */
+
+/* Write a number the way Rust displays it : integers without decimals, the others with the shortest decimal
+ * representation (never in scientific notation) */
+static void format_number(double number, Buffer *buffer) {
+    char text[64];
+    if (isnan(number)) {
+        buffer_text(buffer, "NaN");
+        return;
+    }
+    if (isinf(number)) {
+        buffer_text(buffer, number > 0 ? "inf" : "-inf");
+        return;
+    }
+    if (number == floor(number) && fabs(number) < 1e15) {
+        snprintf(text, sizeof text, "%lld", (long long) number);
+        buffer_text(buffer, text);
+        return;
+    }
+
+    int precision = 1;
+    for (; precision < 17; precision++) {
+        snprintf(text, sizeof text, "%.*e", precision - 1, number);
+        if (strtod(text, NULL) == number) {
+            break;
+        }
+    }
+    snprintf(text, sizeof text, "%.*e", precision - 1, number);
+
+    /* `text` is [-]d[.ddd]e[+-]x */
+    char digits[32];
+    size_t count = 0;
+    char *cursor = text;
+    if (*cursor == '-') {
+        buffer_text(buffer, "-");
+        cursor++;
+    }
+    for (; *cursor != 'e'; cursor++) {
+        if (*cursor != '.') {
+            digits[count++] = *cursor;
+        }
+    }
+    long point = strtol(cursor + 1, NULL, 10) + 1;
+
+    if (point <= 0) {
+        buffer_text(buffer, "0.");
+        for (long i = 0; i < -point; i++) {
+            buffer_text(buffer, "0");
+        }
+        buffer_append(buffer, digits, count);
+    } else if ((size_t) point >= count) {
+        buffer_append(buffer, digits, count);
+        for (size_t i = count; i < (size_t) point; i++) {
+            buffer_text(buffer, "0");
+        }
+    } else {
+        buffer_append(buffer, digits, (size_t) point);
+        buffer_text(buffer, ".");
+        buffer_append(buffer, digits + point, count - (size_t) point);
+    }
+}
+
+static void quote(const FlyString *string, Buffer *buffer) {
+    buffer_text(buffer, "\"");
+    for (size_t i = 0; i < string->length; i++) {
+        unsigned char c = (unsigned char) string->data[i];
+        char escaped[8];
+        switch (c) {
+            case '"':
+                buffer_text(buffer, "\\\"");
+                break;
+            case '\\':
+                buffer_text(buffer, "\\\\");
+                break;
+            case '\n':
+                buffer_text(buffer, "\\n");
+                break;
+            case '\r':
+                buffer_text(buffer, "\\r");
+                break;
+            case '\t':
+                buffer_text(buffer, "\\t");
+                break;
+            default:
+                if (c < 0x20) {
+                    snprintf(escaped, sizeof escaped, "\\u%04x", c);
+                    buffer_text(buffer, escaped);
+                } else {
+                    buffer_append(buffer, (const char *) &c, 1);
+                }
+        }
+    }
+    buffer_text(buffer, "\"");
+}
+
+static void display(Fly value, Buffer *buffer);
+
+/* Same as `display`, but strings are quoted (used inside arrays and structures) */
+static void represent(Fly value, Buffer *buffer) {
+    if (value.type == FLY_STRING) {
+        quote(AS(FlyString, value), buffer);
+    } else {
+        display(value, buffer);
+    }
+}
+
+static void represent_key(Fly key, Buffer *buffer) {
+    if (key.type == FLY_STRING) {
+        FlyString *string = AS(FlyString, key);
+        bool word = string->length > 0 && !(string->data[0] >= '0' && string->data[0] <= '9');
+        for (size_t i = 0; word && i < string->length; i++) {
+            unsigned char c = (unsigned char) string->data[i];
+            word = c >= 0x80 || c == '_' || (c >= '0' && c <= '9') || (c >= 'a' && c <= 'z') ||
+                   (c >= 'A' && c <= 'Z');
+        }
+        if (word) {
+            buffer_append(buffer, string->data, string->length);
+            return;
+        }
+    }
+    represent(key, buffer);
+}
+
+static void display(Fly value, Buffer *buffer) {
+    value = unmodified(value);
+    switch (value.type) {
+        case FLY_EMPTY:
+            buffer_text(buffer, "()");
+            break;
+        case FLY_BOOLEAN:
+            buffer_text(buffer, value.as.boolean ? "true" : "false");
+            break;
+        case FLY_NUMBER:
+            format_number(value.as.number, buffer);
+            break;
+        case FLY_STRING:
+            buffer_append(buffer, AS(FlyString, value)->data, AS(FlyString, value)->length);
+            break;
+        case FLY_ARRAY: {
+            FlyArray *array = AS(FlyArray, value);
+            buffer_text(buffer, "{");
+            for (size_t i = 0; i < array->length; i++) {
+                if (i > 0) {
+                    buffer_text(buffer, ", ");
+                }
+                represent(array->items[i], buffer);
+            }
+            buffer_text(buffer, "}");
+            break;
+        }
+        case FLY_STRUCTURE: {
+            FlyStructure *structure = AS(FlyStructure, value);
+            if (structure->length == 0) {
+                buffer_text(buffer, "{:}");
+                break;
+            }
+            buffer_text(buffer, "{");
+            for (size_t i = 0; i < structure->length; i++) {
+                if (i > 0) {
+                    buffer_text(buffer, ", ");
+                }
+                represent_key(structure->keys[i], buffer);
+                buffer_text(buffer, ": ");
+                represent(structure->values[i], buffer);
+            }
+            buffer_text(buffer, "}");
+            break;
+        }
+        case FLY_FUNCTION: {
+            FlyFunction *function = AS(FlyFunction, value);
+            buffer_text(buffer, "<fn ");
+            if (function->self.type == FLY_INSTANCE && function->owner.type == FLY_CLASS) {
+                buffer_text(buffer, AS(FlyClass, function->owner)->name);
+                buffer_text(buffer, ".");
+            }
+            buffer_text(buffer, function->name ? function->name : "anonymous");
+            buffer_text(buffer, ">");
+            break;
+        }
+        case FLY_CLASS:
+            buffer_text(buffer, "<cs ");
+            buffer_text(buffer, AS(FlyClass, value)->name);
+            buffer_text(buffer, ">");
+            break;
+        case FLY_INSTANCE:
+            buffer_text(buffer, "<");
+            buffer_text(buffer, AS(FlyInstance, value)->class->name);
+            buffer_text(buffer, " instance>");
+            break;
+        default:
+            break;
+    }
+}
+
+/* The display of a value, for error messages. The text must be freed. */
+static char *describe(Fly value) {
+    Buffer buffer = {NULL, 0, 0};
+    represent(value, &buffer);
+    return buffer.data;
+}
+
+Fly fly_concat(size_t count, const Fly *parts) {
+    Buffer buffer = {NULL, 0, 0};
+    for (size_t i = 0; i < count; i++) {
+        display(parts[i], &buffer);
+    }
+    return buffer_string(&buffer);
+}
+
+/* Operations */
+
+bool fly_truthy(Fly value) {
+    value = unmodified(value);
+    switch (value.type) {
+        case FLY_EMPTY:
+            return false;
+        case FLY_BOOLEAN:
+            return value.as.boolean;
+        case FLY_NUMBER:
+            return value.as.number != 0 && !isnan(value.as.number);
+        case FLY_STRING:
+            return AS(FlyString, value)->length > 0;
+        case FLY_ARRAY:
+            return AS(FlyArray, value)->length > 0;
+        case FLY_STRUCTURE:
+            return AS(FlyStructure, value)->length > 0;
+        default:
+            return true;
+    }
+}
+
+Fly fly_not(Fly value) {
+    return fly_boolean(!fly_truthy(value));
+}
+
+/* Compare two values : primitives, arrays and structures by their content, the others by reference */
+static bool equal(Fly a, Fly b) {
+    a = unmodified(a);
+    b = unmodified(b);
+    if (a.type != b.type) {
+        return false;
+    }
+
+    switch (a.type) {
+        case FLY_EMPTY:
+            return true;
+        case FLY_BOOLEAN:
+            return a.as.boolean == b.as.boolean;
+        case FLY_NUMBER:
+            return a.as.number == b.as.number;
+        case FLY_STRING:
+            return AS(FlyString, a)->length == AS(FlyString, b)->length &&
+                   memcmp(AS(FlyString, a)->data, AS(FlyString, b)->data, AS(FlyString, a)->length) == 0;
+        case FLY_ARRAY: {
+            FlyArray *x = AS(FlyArray, a);
+            FlyArray *y = AS(FlyArray, b);
+            if (x->length != y->length) {
+                return false;
+            }
+            for (size_t i = 0; i < x->length; i++) {
+                if (!equal(x->items[i], y->items[i])) {
+                    return false;
+                }
+            }
+            return true;
+        }
+        case FLY_STRUCTURE: {
+            FlyStructure *x = AS(FlyStructure, a);
+            FlyStructure *y = AS(FlyStructure, b);
+            if (x->length != y->length) {
+                return false;
+            }
+            for (size_t i = 0; i < x->length; i++) {
+                long position = structure_find(y, x->keys[i]);
+                if (position < 0 || !equal(x->values[i], y->values[position])) {
+                    return false;
+                }
+            }
+            return true;
+        }
+        case FLY_FUNCTION: {
+            /* Bound methods are created each time they are read */
+            FlyFunction *x = AS(FlyFunction, a);
+            FlyFunction *y = AS(FlyFunction, b);
+            return x == y || (x->self.type == FLY_INSTANCE && x->code == y->code && x->env == y->env &&
+                              equal(x->self, y->self));
+        }
+        default:
+            return a.as.object == b.as.object;
+    }
+}
+
+Fly fly_equal(Fly a, Fly b) {
+    return fly_boolean(equal(a, b));
+}
+
+static void invalid(const char *operator, Fly a, Fly b) FLY_NORETURN;
+static void invalid(const char *operator, Fly a, Fly b) {
+    fly_error("The operation %s cannot be applied to %s and %s.", operator, type_of(a), type_of(b));
+}
+
+static void numbers(const char *operator, Fly a, Fly b) {
+    if (a.type != FLY_NUMBER || b.type != FLY_NUMBER) {
+        invalid(operator, a, b);
+    }
+}
+
+static void divisor(const char *operator, Fly a, Fly b) {
+    numbers(operator, a, b);
+    if (b.as.number == 0) {
+        fly_error("Division by zero.");
+    }
+}
+
+Fly fly_negate(Fly value) {
+    if (value.type != FLY_NUMBER) {
+        fly_error("The operation - cannot be applied to %s.", type_of(value));
+    }
+    return fly_number(-value.as.number);
+}
+
+/* The `+` operator : adds numbers, or concatenates strings */
+Fly fly_add(Fly a, Fly b) {
+    if (a.type == FLY_NUMBER && b.type == FLY_NUMBER) {
+        return fly_number(a.as.number + b.as.number);
+    }
+    if (a.type == FLY_STRING || b.type == FLY_STRING) {
+        Fly parts[2] = {a, b};
+        return fly_concat(2, parts);
+    }
+    invalid("+", a, b);
+}
+
+Fly fly_subtract(Fly a, Fly b) {
+    numbers("-", a, b);
+    return fly_number(a.as.number - b.as.number);
+}
+
+Fly fly_multiply(Fly a, Fly b) {
+    numbers("*", a, b);
+    return fly_number(a.as.number * b.as.number);
+}
+
+Fly fly_divide(Fly a, Fly b) {
+    divisor("/", a, b);
+    return fly_number(a.as.number / b.as.number);
+}
+
+/* The `%` operator (the result has the sign of `a`) */
+Fly fly_modulo(Fly a, Fly b) {
+    divisor("%", a, b);
+    return fly_number(fmod(a.as.number, b.as.number));
+}
+
+/* The `//` operator (euclidian division) */
+Fly fly_quotient(Fly a, Fly b) {
+    divisor("//", a, b);
+    double quotient = trunc(a.as.number / b.as.number);
+    if (fmod(a.as.number, b.as.number) < 0) {
+        quotient = b.as.number > 0 ? quotient - 1 : quotient + 1;
+    }
+    return fly_number(quotient);
+}
+
+Fly fly_power(Fly a, Fly b) {
+    numbers("**", a, b);
+    return fly_number(pow(a.as.number, b.as.number));
+}
+
+/* Compare numbers, or strings (by their bytes) */
+static int order(const char *operator, Fly a, Fly b) {
+    if (a.type == FLY_NUMBER && b.type == FLY_NUMBER) {
+        return (a.as.number > b.as.number) - (a.as.number < b.as.number);
+    }
+    if (a.type == FLY_STRING && b.type == FLY_STRING) {
+        FlyString *x = AS(FlyString, a);
+        FlyString *y = AS(FlyString, b);
+        int compared = memcmp(x->data, y->data, x->length < y->length ? x->length : y->length);
+        if (compared == 0) {
+            return (x->length > y->length) - (x->length < y->length);
+        }
+        return compared;
+    }
+    invalid(operator, a, b);
+}
+
+/* NaN is neither lower nor greater than a number */
+static bool is_nan(Fly a, Fly b) {
+    return (a.type == FLY_NUMBER && isnan(a.as.number)) || (b.type == FLY_NUMBER && isnan(b.as.number));
+}
+
+Fly fly_less(Fly a, Fly b) {
+    int compared = order("<", a, b);
+    return fly_boolean(!is_nan(a, b) && compared < 0);
+}
+
+Fly fly_less_equal(Fly a, Fly b) {
+    int compared = order("<=", a, b);
+    return fly_boolean(!is_nan(a, b) && compared <= 0);
+}
+
+Fly fly_greater(Fly a, Fly b) {
+    int compared = order(">", a, b);
+    return fly_boolean(!is_nan(a, b) && compared > 0);
+}
+
+Fly fly_greater_equal(Fly a, Fly b) {
+    int compared = order(">=", a, b);
+    return fly_boolean(!is_nan(a, b) && compared >= 0);
+}
+
+Fly fly_undefined(const char *name) {
+    fly_error("The variable %s is not defined.", name);
+}
+
+Fly fly_reassign(const char *name, Fly value) {
+    (void) value;
+    fly_error("\"%s\" is a constant and cannot be reassigned.", name);
+}
+
+/* Functions */
+
+void *fly_environment(size_t size, void *outer) {
+    FlyEnv *env = fly_alloc(FLY_ENVIRONMENT, size);
+    env->outer = outer;
+    return env;
+}
+
+Fly fly_argument(int argc, const Fly *argv, int position) {
+    return position < argc ? argv[position] : fly_empty();
+}
+
+void fly_arguments(int argc, int expected) {
+    if (argc > expected) {
+        fly_error("Too many arguments given. Expected at most %d but found %d.", expected, argc);
+    }
+}
+
+Fly fly_call(Fly callee, int argc, Fly *argv) {
+    switch (callee.type) {
+        case FLY_FUNCTION: {
+            FlyFunction *function = AS(FlyFunction, callee);
+            return function->code(function, argc, argv);
+        }
+        case FLY_MODIFIED: {
+            Fly arguments[2] = {AS(FlyModified, callee)->target, fly_array((size_t) argc, argv)};
+            return fly_call(AS(FlyModified, callee)->modifier, 2, arguments);
+        }
+        default: {
+            char *described = describe(callee);
+            fly_error("%s is not callable.", described);
+        }
+    }
+}
+
+/* Compute the method resolution order of a class (C3 linearization) */
+static void linearize(FlyClass *class) {
+    size_t lists_count = class->parents_count + 1;
+    FlyClass ***lists = allocate(lists_count * sizeof *lists);
+    size_t *lengths = allocate(lists_count * sizeof *lengths);
+    size_t *heads = allocate(lists_count * sizeof *heads);
+    size_t total = 1;
+
+    for (size_t i = 0; i < class->parents_count; i++) {
+        FlyClass *parent = AS(FlyClass, class->parents[i]);
+        lists[i] = parent->mro;
+        lengths[i] = parent->mro_count;
+        total += parent->mro_count;
+    }
+    FlyClass **parents = allocate(class->parents_count * sizeof *parents);
+    for (size_t i = 0; i < class->parents_count; i++) {
+        parents[i] = AS(FlyClass, class->parents[i]);
+    }
+    lists[class->parents_count] = parents;
+    lengths[class->parents_count] = class->parents_count;
+    memset(heads, 0, lists_count * sizeof *heads);
+
+    class->mro = allocate(total * sizeof *class->mro);
+    class->mro[0] = class;
+    class->mro_count = 1;
+
+    for (;;) {
+        FlyClass *next = NULL;
+        bool remaining = false;
+        for (size_t i = 0; i < lists_count && !next; i++) {
+            if (heads[i] == lengths[i]) {
+                continue;
+            }
+            remaining = true;
+            FlyClass *candidate = lists[i][heads[i]];
+
+            /* A class is added when it is not in the tail of a list */
+            bool tail = false;
+            for (size_t j = 0; j < lists_count && !tail; j++) {
+                for (size_t k = heads[j] + 1; k < lengths[j] && !tail; k++) {
+                    tail = lists[j][k] == candidate;
+                }
+            }
+            if (!tail) {
+                next = candidate;
+            }
+        }
+
+        if (!remaining) {
+            break;
+        }
+        if (!next) {
+            fly_error("Cannot create a consistent method resolution order for the class %s.", class->name);
+        }
+        class->mro[class->mro_count++] = next;
+        for (size_t i = 0; i < lists_count; i++) {
+            if (heads[i] < lengths[i] && lists[i][heads[i]] == next) {
+                heads[i]++;
+            }
+        }
+    }
+
+    free(parents);
+    free(heads);
+    free(lengths);
+    free(lists);
+}
+
+Fly fly_class(const char *name, size_t parents_count, const Fly *parents, size_t members_count,
+              const FlyMember *members) {
+    FlyClass *class = fly_alloc(FLY_CLASS, sizeof(FlyClass));
+    Fly value = object_value(FLY_CLASS, class);
+    class->name = name;
+
+    class->parents = allocate(parents_count * sizeof(Fly));
+    for (size_t i = 0; i < parents_count; i++) {
+        Fly parent = unmodified(parents[i]);
+        if (parent.type != FLY_CLASS) {
+            char *described = describe(parent);
+            fly_error("The class %s cannot inherit %s, which is not a class.", name, described);
+        }
+        class->parents[i] = parent;
+        class->parents_count++;
+    }
+
+    class->statics = structure_new();
+    class->methods = structure_new();
+    class->modifiers = structure_new();
+    for (size_t i = 0; i < members_count; i++) {
+        FlyMember member = members[i];
+        if (member.kind != FLY_STATIC && member.kind != FLY_STATIC_METHOD && member.kind != FLY_MODIFIERS) {
+            AS(FlyFunction, member.value)->owner = value;
+        }
+
+        switch (member.kind) {
+            case FLY_STATIC:
+            case FLY_STATIC_METHOD:
+                structure_set_name(class->statics, member.name, member.value);
+                break;
+            case FLY_METHOD:
+                structure_set_name(class->methods, member.name, member.value);
+                break;
+            case FLY_CONSTRUCTOR:
+                class->constructor = member.value;
+                break;
+            case FLY_ATTRIBUTES:
+                class->attributes = member.value;
+                break;
+            case FLY_MODIFIERS:
+                structure_set_name(class->modifiers, member.name, member.value);
+                break;
+        }
+    }
+
+    linearize(class);
+    return value;
+}
+
+/* Read a method from an instance : it is bound to it, then modified */
+static bool method(Fly self, FlyClass **mro, size_t count, const char *name, Fly *found) {
+    for (size_t i = 0; i < count; i++) {
+        long position = structure_find_name(mro[i]->methods, name);
+        if (position < 0) {
+            continue;
+        }
+
+        Fly owner = object_value(FLY_CLASS, mro[i]);
+        *found = bind(mro[i]->methods->values[position], self, owner);
+        long modifiers = structure_find_name(mro[i]->modifiers, name);
+        if (modifiers >= 0) {
+            FlyArray *array = AS(FlyArray, mro[i]->modifiers->values[modifiers]);
+            *found = fly_modify(*found, array->length, array->items);
+        }
+        return true;
+    }
+    return false;
+}
+
+static Fly default_constructor(FlyFunction *function, int argc, Fly *argv) {
+    (void) function;
+    (void) argv;
+    fly_arguments(argc, 0);
+    return fly_empty();
+}
+
+/* The constructor of the first class of the list declaring one, bound to the instance */
+static Fly constructor(Fly self, FlyClass **mro, size_t count) {
+    for (size_t i = 0; i < count; i++) {
+        if (mro[i]->constructor.type == FLY_FUNCTION) {
+            return bind(mro[i]->constructor, self, object_value(FLY_CLASS, mro[i]));
+        }
+    }
+    return fly_function("constructor", default_constructor, NULL);
+}
+
+Fly fly_new(Fly class, int argc, Fly *argv) {
+    if (class.type == FLY_MODIFIED) {
+        return fly_call(class, argc, argv);
+    }
+    if (class.type != FLY_CLASS) {
+        char *described = describe(class);
+        fly_error("%s is not a class.", described);
+    }
+
+    FlyClass *created = AS(FlyClass, class);
+    FlyInstance *instance = fly_alloc(FLY_INSTANCE, sizeof(FlyInstance));
+    Fly self = object_value(FLY_INSTANCE, instance);
+    instance->class = created;
+    instance->fields = structure_new();
+
+    /* Attributes are initialised from the farthest ancestor to the class itself, then the constructor is
+     * called */
+    for (size_t i = created->mro_count; i > 0; i--) {
+        if (created->mro[i - 1]->attributes.type == FLY_FUNCTION) {
+            Fly owner = object_value(FLY_CLASS, created->mro[i - 1]);
+            fly_call(bind(created->mro[i - 1]->attributes, self, owner), 0, NULL);
+        }
+    }
+    fly_call(constructor(self, created->mro, created->mro_count), argc, argv);
+
+    return self;
+}
+
+Fly fly_super(Fly self, Fly owner, const char *name) {
+    FlyClass *class = AS(FlyInstance, self)->class;
+    size_t position = 0;
+    while (position < class->mro_count && class->mro[position] != AS(FlyClass, owner)) {
+        position++;
+    }
+    FlyClass **following = class->mro + position + 1;
+    size_t count = position < class->mro_count ? class->mro_count - position - 1 : 0;
+
+    if (!name) {
+        return constructor(self, following, count);
+    }
+    Fly found;
+    if (!method(self, following, count, name, &found)) {
+        fly_error("The property \"%s\" is not defined.", name);
+    }
+    return found;
+}
+
+/* Properties */
+
+/* The position of an index. Negative indexes start from the end. */
+static size_t position(Fly key, size_t length) {
+    if (key.type != FLY_NUMBER) {
+        fly_error("Expected a number as index, but found %s.", type_of(key));
+    }
+    double index = key.as.number < 0 ? (double) length + key.as.number : key.as.number;
+    if (index != floor(index) || index < 0 || index >= (double) length) {
+        Buffer buffer = {NULL, 0, 0};
+        display(key, &buffer);
+        fly_error("The index %s is out of range (length %zu).", buffer.data, length);
+    }
+    return (size_t) index;
+}
+
+static void undefined(Fly key) FLY_NORETURN;
+static void undefined(Fly key) {
+    char *described = describe(key);
+    fly_error("The property %s is not defined.", described);
+}
+
+Fly fly_get_name(Fly from, const char *name) {
+    from = unmodified(from);
+    long found;
+    switch (from.type) {
+        case FLY_STRUCTURE:
+            found = structure_find_name(AS(FlyStructure, from), name);
+            if (found >= 0) {
+                return AS(FlyStructure, from)->values[found];
+            }
+            break;
+        case FLY_INSTANCE: {
+            FlyInstance *instance = AS(FlyInstance, from);
+            Fly bound;
+            found = structure_find_name(instance->fields, name);
+            if (found >= 0) {
+                return instance->fields->values[found];
+            }
+            if (method(from, instance->class->mro, instance->class->mro_count, name, &bound)) {
+                return bound;
+            }
+            break;
+        }
+        case FLY_CLASS: {
+            FlyClass *class = AS(FlyClass, from);
+            for (size_t i = 0; i < class->mro_count; i++) {
+                found = structure_find_name(class->mro[i]->statics, name);
+                if (found >= 0) {
+                    return class->mro[i]->statics->values[found];
+                }
+            }
+            break;
+        }
+        case FLY_EMPTY:
+            fly_error("Cannot read the property \"%s\" of empty.", name);
+        default:
+            break;
+    }
+    fly_error("The property \"%s\" is not defined.", name);
+}
+
+Fly fly_get(Fly from, Fly key) {
+    from = unmodified(from);
+    switch (from.type) {
+        case FLY_ARRAY: {
+            FlyArray *array = AS(FlyArray, from);
+            return array->items[position(key, array->length)];
+        }
+        case FLY_STRING: {
+            FlyString *string = AS(FlyString, from);
+            size_t length;
+            size_t offset = character(string, position(key, characters(string)), &length);
+            return string_of(string->data + offset, length);
+        }
+        case FLY_STRUCTURE: {
+            long found = structure_find(AS(FlyStructure, from), key);
+            if (found < 0) {
+                undefined(key);
+            }
+            return AS(FlyStructure, from)->values[found];
+        }
+        case FLY_INSTANCE:
+        case FLY_CLASS:
+        case FLY_EMPTY:
+            if (key.type == FLY_STRING) {
+                return fly_get_name(from, AS(FlyString, key)->data);
+            }
+            break;
+        default:
+            break;
+    }
+    undefined(key);
+}
+
+static void unwritable(Fly target, Fly key) FLY_NORETURN;
+static void unwritable(Fly target, Fly key) {
+    char *described = describe(key);
+    fly_error("Cannot write the property %s of %s.", described, type_of(target));
+}
+
+Fly fly_put_name(Fly target, const char *name, Fly value) {
+    target = unmodified(target);
+    switch (target.type) {
+        case FLY_STRUCTURE:
+            structure_set_name(AS(FlyStructure, target), name, value);
+            break;
+        case FLY_INSTANCE:
+            structure_set_name(AS(FlyInstance, target)->fields, name, value);
+            break;
+        case FLY_CLASS:
+            structure_set_name(AS(FlyClass, target)->statics, name, value);
+            break;
+        default:
+            unwritable(target, fly_string(name));
+    }
+    return value;
+}
+
+/* Write a property. Writing right after the last item of an array appends it. */
+Fly fly_put(Fly target, Fly key, Fly value) {
+    target = unmodified(target);
+    switch (target.type) {
+        case FLY_ARRAY: {
+            FlyArray *array = AS(FlyArray, target);
+            if (key.type == FLY_NUMBER && key.as.number == (double) array->length) {
+                array_push(array, value);
+            } else {
+                array->items[position(key, array->length)] = value;
+            }
+            return value;
+        }
+        case FLY_STRUCTURE:
+            structure_set(AS(FlyStructure, target), key, value);
+            return value;
+        case FLY_INSTANCE:
+        case FLY_CLASS:
+            if (key.type == FLY_STRING) {
+                return fly_put_name(target, AS(FlyString, key)->data, value);
+            }
+            break;
+        default:
+            break;
+    }
+    unwritable(target, key);
+}
+
+/* Loops */
+
+/* The items of arrays and strings with their position, the values of structures with their key, and the
+ * numbers from 0 to `n` (excluded). The items are copied before the loop. */
+FlyIterator fly_iterate(Fly iterable) {
+    FlyIterator iterator;
+    iterable = unmodified(iterable);
+    iterator.position = 0;
+
+    switch (iterable.type) {
+        case FLY_NUMBER:
+            iterator.indexes = fly_number(ceil(iterable.as.number));
+            iterator.items = fly_empty();
+            break;
+        case FLY_ARRAY: {
+            FlyArray *array = AS(FlyArray, iterable);
+            iterator.indexes = fly_empty();
+            iterator.items = fly_array(array->length, array->items);
+            break;
+        }
+        case FLY_STRING: {
+            FlyString *string = AS(FlyString, iterable);
+            iterator.indexes = fly_empty();
+            iterator.items = fly_array(0, NULL);
+            for (size_t offset = 0; offset < string->length;) {
+                size_t length = 1;
+                while (offset + length < string->length &&
+                       ((unsigned char) string->data[offset + length] & 0xC0) == 0x80) {
+                    length++;
+                }
+                Fly item = string_of(string->data + offset, length);
+                array_push(AS(FlyArray, iterator.items), item);
+                offset += length;
+            }
+            break;
+        }
+        case FLY_STRUCTURE: {
+            FlyStructure *structure = AS(FlyStructure, iterable);
+            iterator.indexes = fly_array(structure->length, structure->keys);
+            iterator.items = fly_array(structure->length, structure->values);
+            break;
+        }
+        default:
+            fly_error("Cannot go through %s.", type_of(iterable));
+    }
+    return iterator;
+}
+
+bool fly_next(FlyIterator *iterator, Fly *index, Fly *item) {
+    size_t current = iterator->position;
+    if (iterator->indexes.type == FLY_NUMBER) {
+        if ((double) current >= iterator->indexes.as.number) {
+            return false;
+        }
+        if (index) {
+            *index = fly_number((double) current);
+        }
+        if (item) {
+            *item = fly_number((double) current);
+        }
+    } else {
+        FlyArray *items = AS(FlyArray, iterator->items);
+        if (current >= items->length) {
+            return false;
+        }
+        if (index) {
+            *index = iterator->indexes.type == FLY_ARRAY ? AS(FlyArray, iterator->indexes)->items[current]
+                                                         : fly_number((double) current);
+        }
+        if (item) {
+            *item = items->items[current];
+        }
+    }
+    iterator->position++;
+    return true;
+}
+
+/* Breakers */
+
+static FlyHandler *handlers = NULL;
+
+void fly_push(FlyHandler *handler, const char *label) {
+    handler->label = label;
+    handler->previous = handlers;
+    handler->kind = FLY_STOP;
+    handler->value = fly_empty();
+    handlers = handler;
+}
+
+void fly_pop(FlyHandler *handler) {
+    handlers = handler->previous;
+}
+
+Fly fly_leave(FlyHandler *handler, Fly value) {
+    fly_pop(handler);
+    return value;
+}
+
+void fly_throw(const char *label, FlyBreak kind, Fly value) {
+    while (handlers) {
+        FlyHandler *handler = handlers;
+        handlers = handler->previous;
+        if (strcmp(handler->label, label) == 0) {
+            handler->kind = kind;
+            handler->value = value;
+            longjmp(handler->jump, 1);
+        }
+    }
+    fly_error("The scope %s the breaker goes to is not running.", label);
+}
+
+/* Builtins */
+
+static void print(FILE *stream, int argc, Fly *argv) {
+    Buffer buffer = {NULL, 0, 0};
+    for (int i = 0; i < argc; i++) {
+        if (i > 0) {
+            buffer_text(&buffer, " ");
+        }
+        display(argv[i], &buffer);
+    }
+    buffer_text(&buffer, "\n");
+    fputs(buffer.data, stream);
+    free(buffer.data);
+}
+
+static Fly std_out(FlyFunction *function, int argc, Fly *argv) {
+    (void) function;
+    print(stdout, argc, argv);
+    return fly_empty();
+}
+
+static Fly std_err(FlyFunction *function, int argc, Fly *argv) {
+    (void) function;
+    fflush(stdout);
+    print(stderr, argc, argv);
+    return fly_empty();
+}
+
+/* Get the number of items in an array, a structure or a string */
+static Fly std_len(FlyFunction *function, int argc, Fly *argv) {
+    Fly value = unmodified(fly_argument(argc, argv, 0));
+    (void) function;
+    switch (value.type) {
+        case FLY_STRING:
+            return fly_number((double) characters(AS(FlyString, value)));
+        case FLY_ARRAY:
+            return fly_number((double) AS(FlyArray, value)->length);
+        case FLY_STRUCTURE:
+            return fly_number((double) AS(FlyStructure, value)->length);
+        default:
+            fly_error("Expected array, structure or string, but found %s.", type_of(value));
+    }
+}
+
+static Fly std_type(FlyFunction *function, int argc, Fly *argv) {
+    (void) function;
+    return fly_string(type_of(fly_argument(argc, argv, 0)));
+}
+
+/* Raise an error if one of the given values is not truthy */
+static Fly assert_values(FlyFunction *function, int argc, Fly *argv) {
+    (void) function;
+    for (int i = 0; i < argc; i++) {
+        if (!fly_truthy(argv[i])) {
+            fly_error("Assertion failed.");
+        }
+    }
+    return fly_empty();
+}
+
+static Fly std_value;
+static Fly assert_value;
+
+Fly fly_std(void) {
+    if (std_value.type == FLY_EMPTY) {
+        Fly entries[8];
+        entries[0] = fly_string("out");
+        entries[1] = fly_function("out", std_out, NULL);
+        entries[2] = fly_string("err");
+        entries[3] = fly_function("err", std_err, NULL);
+        entries[4] = fly_string("len");
+        entries[5] = fly_function("len", std_len, NULL);
+        entries[6] = fly_string("type");
+        entries[7] = fly_function("type", std_type, NULL);
+        std_value = fly_structure(4, entries);
+        roots[roots_count++] = std_value;
+    }
+    return std_value;
+}
+
+Fly fly_assert(void) {
+    if (assert_value.type == FLY_EMPTY) {
+        assert_value = fly_function("assert", assert_values, NULL);
+        roots[roots_count++] = assert_value;
+    }
+    return assert_value;
+}
+
+int fly_run(FlyCode module) {
+    volatile char bottom = 0;
+    stack_bottom = (char *) &bottom;
+
+    FlyFunction function;
+    memset(&function, 0, sizeof function);
+    function.name = "module";
+    function.code = module;
+    module(&function, 0, NULL);
+
+    fflush(stdout);
+    return bottom;
+}
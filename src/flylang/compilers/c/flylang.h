@@ -0,0 +1,202 @@
+/*
+ * The runtime library of the C code generated from flylang (C99).
+ *
+ * Values are dynamically typed (`Fly`). The objects they refer to are allocated by the runtime and freed by a
+ * mark-and-sweep garbage collector, which finds the objects used by the program on the C stack.
+ */
+
+#ifndef FLYLANG_H
+#define FLYLANG_H
+
+#include <setjmp.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __GNUC__
+#define FLY_NORETURN __attribute__((noreturn))
+#else
+#define FLY_NORETURN
+#endif
+
+typedef enum FlyType {
+    FLY_EMPTY,
+    FLY_BOOLEAN,
+    FLY_NUMBER,
+    FLY_STRING,
+    FLY_ARRAY,
+    FLY_STRUCTURE,
+    FLY_FUNCTION,
+    FLY_CLASS,
+    FLY_INSTANCE,
+    FLY_MODIFIED,
+    FLY_ENVIRONMENT,
+} FlyType;
+
+/* The header of the objects allocated by the runtime */
+typedef struct FlyObject {
+    FlyType type;
+    bool marked;
+    size_t size;
+    struct FlyObject *next;
+} FlyObject;
+
+/* A flylang value */
+typedef struct Fly {
+    FlyType type;
+    union {
+        bool boolean;
+        double number;
+        FlyObject *object;
+    } as;
+} Fly;
+
+/* The variables of a function call. `FLY_ENV(Outer)` starts the structure of the variables of a function. */
+typedef struct FlyEnv {
+    FlyObject object;
+    struct FlyEnv *outer;
+} FlyEnv;
+#define FLY_ENV(Outer) \
+    FlyObject object;  \
+    Outer *outer
+
+typedef struct FlyFunction FlyFunction;
+/* The code of a function. Methods receive their instance in `function->self`. */
+typedef Fly (*FlyCode)(FlyFunction *function, int argc, Fly *argv);
+
+struct FlyFunction {
+    FlyObject object;
+    const char *name;
+    FlyCode code;
+    FlyEnv *env;
+    /* The instance of a method, and the class declaring it */
+    Fly self;
+    Fly owner;
+};
+
+/* The members given to `fly_class` */
+typedef enum FlyMemberKind {
+    FLY_STATIC,
+    FLY_METHOD,
+    FLY_STATIC_METHOD,
+    FLY_CONSTRUCTOR,
+    /* The method initialising the attributes of an instance */
+    FLY_ATTRIBUTES,
+    /* The modifiers applied to a method (an array) each time it is read from an instance */
+    FLY_MODIFIERS,
+} FlyMemberKind;
+
+typedef struct FlyMember {
+    FlyMemberKind kind;
+    const char *name;
+    Fly value;
+} FlyMember;
+
+/* Values */
+static inline Fly fly_empty(void) {
+    Fly value;
+    value.type = FLY_EMPTY;
+    value.as.object = NULL;
+    return value;
+}
+static inline Fly fly_boolean(bool boolean) {
+    Fly value;
+    value.type = FLY_BOOLEAN;
+    value.as.boolean = boolean;
+    return value;
+}
+static inline Fly fly_number(double number) {
+    Fly value;
+    value.type = FLY_NUMBER;
+    value.as.number = number;
+    return value;
+}
+Fly fly_string(const char *text);
+Fly fly_array(size_t count, const Fly *items);
+/* `entries` contains `count` keys, each one followed by its value */
+Fly fly_structure(size_t count, const Fly *entries);
+Fly fly_function(const char *name, FlyCode code, void *env);
+Fly fly_class(const char *name, size_t parents_count, const Fly *parents, size_t members_count,
+              const FlyMember *members);
+/* Wrap a function or a class with modifiers. The first modifier is the most inner one. */
+Fly fly_modify(Fly target, size_t count, const Fly *modifiers);
+/* Apply the modifiers of a variable on its value */
+Fly fly_modify_variable(const char *name, bool constant, Fly value, size_t count, const Fly *modifiers);
+
+/* Reading a variable which is not defined */
+Fly fly_undefined(const char *name);
+/* Assigning a constant (or a receiver or a built-in element). The value is computed before, like the interpreter
+ * does. */
+Fly fly_reassign(const char *name, Fly value);
+
+/* Functions */
+void *fly_environment(size_t size, void *outer);
+/* Get an argument of a function (empty when it is not given) */
+Fly fly_argument(int argc, const Fly *argv, int position);
+/* Verify a function is not called with more than `expected` arguments */
+void fly_arguments(int argc, int expected);
+Fly fly_call(Fly callee, int argc, Fly *argv);
+Fly fly_new(Fly class, int argc, Fly *argv);
+/* A method of the classes following `owner` in the method resolution order of `self` (the constructor when
+ * `name` is NULL) */
+Fly fly_super(Fly self, Fly owner, const char *name);
+
+/* Operations */
+bool fly_truthy(Fly value);
+Fly fly_not(Fly value);
+Fly fly_negate(Fly value);
+Fly fly_add(Fly a, Fly b);
+Fly fly_subtract(Fly a, Fly b);
+Fly fly_multiply(Fly a, Fly b);
+Fly fly_divide(Fly a, Fly b);
+Fly fly_modulo(Fly a, Fly b);
+Fly fly_quotient(Fly a, Fly b);
+Fly fly_power(Fly a, Fly b);
+Fly fly_equal(Fly a, Fly b);
+Fly fly_less(Fly a, Fly b);
+Fly fly_less_equal(Fly a, Fly b);
+Fly fly_greater(Fly a, Fly b);
+Fly fly_greater_equal(Fly a, Fly b);
+/* Concatenate the display of values (string interpolation) */
+Fly fly_concat(size_t count, const Fly *parts);
+
+/* Properties */
+Fly fly_get(Fly from, Fly key);
+Fly fly_get_name(Fly from, const char *name);
+Fly fly_put(Fly target, Fly key, Fly value);
+Fly fly_put_name(Fly target, const char *name, Fly value);
+
+/* The [index, item] couples an `each` loop goes through */
+typedef struct FlyIterator {
+    Fly indexes;
+    Fly items;
+    size_t position;
+} FlyIterator;
+FlyIterator fly_iterate(Fly iterable);
+/* Go to the next couple. `index` and `item` can be NULL. */
+bool fly_next(FlyIterator *iterator, Fly *index, Fly *item);
+
+/* Breakers going out of their function jump to the handler of the scope they target */
+typedef enum FlyBreak { FLY_STOP, FLY_PASS, FLY_RETURN } FlyBreak;
+typedef struct FlyHandler {
+    jmp_buf jump;
+    const char *label;
+    struct FlyHandler *previous;
+    FlyBreak kind;
+    Fly value;
+} FlyHandler;
+/* Push a handler, before `if (!setjmp(handler.jump))` */
+void fly_push(FlyHandler *handler, const char *label);
+/* Remove a handler, and the handlers pushed after it */
+void fly_pop(FlyHandler *handler);
+/* Remove a handler, and returns the value (used to return from the function) */
+Fly fly_leave(FlyHandler *handler, Fly value);
+void fly_throw(const char *label, FlyBreak kind, Fly value) FLY_NORETURN;
+
+/* Builtins */
+Fly fly_std(void);
+Fly fly_assert(void);
+
+/* Execute the code of a module, and returns the exit code of the program */
+int fly_run(FlyCode module);
+
+#endif
@@ -0,0 +1,1502 @@
+//! Translation of flylang to C (C99).
+//!
+//! The generated file includes the runtime library (`flylang.h`, implemented by `flylang.c`) : the dynamic
+//! values, their garbage collector, and the flylang semantics (display, equality, classes, modifiers, ...).
+//!
+//! - Every function is a C function receiving its arguments as an array. Its variables are the fields of a
+//!   structure allocated at each call, that the functions defined inside keep : they reach the variables of
+//!   the enclosing functions through `outer`. The code of the module is the `fly_module` function.
+//! - C does not specify the order the arguments of a function are evaluated in : the operands followed by an
+//!   operand with effects (like a call) are evaluated before, in temporaries.
+//! - Breakers leave the scopes of their function with `goto`. Breakers going out of their function jump
+//!   (`longjmp`) to a handler pushed by the scope they target.
+//!
+//! The code is generated twice : the first generation finds the variables of each function and the scopes
+//! breakers jump to from another function, which the code of the second one needs before reaching them.
+
+use std::{
+    collections::HashSet,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+use crate::flylang::{
+    analyser::{
+        Analyser,
+        symbols::{SymbolKind, SymbolTable},
+    },
+    compilers::{
+        Compiler,
+        errors::{CompilationFailed, UnresolvedBreaker, Unsupported},
+    },
+    errors::{LangResult, lang_err},
+    lexer::tokens::{
+        BinaryOperator, Comparison, Operator, ScopeTarget,
+        representations::number::NumberRepresentation,
+    },
+    module::{LangModule, slice::LangModuleSlice},
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::DefineClass,
+            functions::DefineFunction,
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::{Loop, LoopParameter},
+        },
+    },
+    utils::breakers::{self, BreakableScope, ScopeKind},
+};
+
+/// The runtime library included by the generated files
+pub const HEADER: &str = include_str!("flylang.h");
+pub const RUNTIME: &str = include_str!("flylang.c");
+
+const NAME: &str = "c";
+
+/// The keywords of C99
+const KEYWORDS: &[&str] = &[
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+];
+
+/// The names used by the generated code or defined by the included headers, that variables cannot take
+const RESERVED: &[&str] = &["object", "outer", "self", "bool", "true", "false", "NULL"];
+
+/// The C compiler, generating a native program with the system C compiler
+pub struct C;
+
+impl Compiler for C {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+    fn description(&self) -> &'static str {
+        "Translate the code to C99, compiled to a native program with the system C compiler"
+    }
+    fn extension(&self) -> &'static str {
+        "c"
+    }
+
+    fn compile(&self, _: &LangModule, branches: &Branches) -> LangResult<String> {
+        let analysis = Analyser::new().analyse(branches);
+        let (_, layout) = Generator::new(&analysis.symbols, Layout::default()).module(branches)?;
+        let (code, _) = Generator::new(&analysis.symbols, layout).module(branches)?;
+        Ok(code)
+    }
+
+    fn runtime(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("flylang.h", HEADER), ("flylang.c", RUNTIME)]
+    }
+
+    /// Compile the generated file with its runtime library (with the compiler of the `CC` environment
+    /// variable, `cc` by default), then execute the program
+    fn run(&self, file: &Path) -> LangResult<ExitStatus> {
+        let compiler = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+        let program = file.with_extension(std::env::consts::EXE_EXTENSION);
+
+        let compiled = Command::new(&compiler)
+            .args(["-std=c99", "-O2", "-o"])
+            .arg(&program)
+            .arg(file)
+            .arg(file.with_file_name("flylang.c"))
+            .arg("-lm")
+            .status();
+        let reason = match compiled {
+            Ok(status) if status.success() => match Command::new(&program).status() {
+                Ok(status) => return Ok(status),
+                Err(error) => format!("the program cannot be started ({})", error),
+            },
+            Ok(_) => format!("{} cannot compile the generated code", compiler),
+            Err(error) => format!("{} cannot be started ({})", compiler, error),
+        };
+
+        lang_err!(CompilationFailed {
+            compiler: NAME,
+            reason,
+        })
+    }
+}
+
+/// A C expression
+struct Code {
+    text: String,
+    /// If evaluating the expression can change the values of the program (it calls a function, assigns a
+    /// variable, ...)
+    effects: bool,
+    /// If the expression always gives the same value, and can be evaluated at any moment
+    constant: bool,
+}
+impl Code {
+    fn value(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            effects: false,
+            constant: false,
+        }
+    }
+    fn constant(text: impl Into<String>) -> Self {
+        Self {
+            constant: true,
+            ..Self::value(text)
+        }
+    }
+    fn effect(text: impl Into<String>) -> Self {
+        Self {
+            effects: true,
+            ..Self::value(text)
+        }
+    }
+}
+
+/// What the first generation finds, and the second one uses
+#[derive(Default)]
+struct Layout {
+    /// The variables of each function, in the order they are generated
+    variables: Vec<Vec<String>>,
+    /// The scopes (numbered in the order they are entered) that breakers leave their function to reach
+    raised: HashSet<usize>,
+}
+
+/// A C function being generated
+struct Frame {
+    /// The name of the function. Its variables are the fields of `struct <function>_env`.
+    function: String,
+    variables: Vec<String>,
+    /// Number of temporaries used by the function
+    temporaries: usize,
+}
+
+/// A flylang scope containing the code being generated, that breakers can target
+struct Scope {
+    kind: ScopeKind,
+    name: Option<String>,
+    /// The label breakers going out of their function look for
+    label: String,
+    /// The number of the scope, naming its handler (`fly_handler_<id>`) and its C labels
+    id: usize,
+    /// The position of the function containing the scope, in the frames
+    frame: usize,
+    /// If a breaker reaches this scope from another function : its body pushes a handler
+    raised: bool,
+    /// If a breaker jumps after the scope (`fly_stop_<id>` or `fly_end_<id>`)
+    left: bool,
+    /// If a breaker jumps to the next iteration of the loop (`fly_pass_<id>`)
+    passed: bool,
+}
+impl BreakableScope for Scope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+struct Generator<'a> {
+    symbols: &'a SymbolTable,
+    /// The layout found by a previous generation
+    known: Layout,
+    layout: Layout,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    /// The structures of the variables of the functions, their prototypes and their definitions
+    structures: Vec<String>,
+    prototypes: Vec<String>,
+    definitions: Vec<String>,
+    /// Number of generated names, to make them unique
+    names: usize,
+    /// Number of scopes entered
+    entered: usize,
+}
+
+/// Convert a flylang name to a valid C identifier.
+/// The names C or the generated code reserve, and the names ending with `_`, are followed by `_`.
+fn identifier(name: &str) -> String {
+    let mut escaped: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c.to_string(),
+            false => format!("_u{:x}", c as u32),
+        })
+        .collect();
+
+    if KEYWORDS.contains(&escaped.as_str())
+        || RESERVED.contains(&escaped.as_str())
+        || escaped.starts_with("fly_")
+        || escaped.starts_with("__")
+        || escaped.ends_with('_')
+    {
+        escaped.push('_');
+    }
+    escaped
+}
+
+/// Write a C string literal. The bytes outside of ASCII are escaped, and `?` is escaped to avoid trigraphs.
+fn string(content: &str) -> String {
+    let mut literal = String::from("\"");
+    for byte in content.bytes() {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b'?' => literal.push_str("\\?"),
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            0x20..0x7f => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn number(value: f64) -> Code {
+    let text = if value.is_nan() {
+        String::from("0.0 / 0.0")
+    } else if value.is_infinite() {
+        String::from(if value > 0f64 {
+            "1.0 / 0.0"
+        } else {
+            "-1.0 / 0.0"
+        })
+    } else if value.fract() == 0f64 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:?}", value)
+    };
+    Code::constant(format!("fly_number({})", text))
+}
+
+/// Write the values given to the runtime as an array : `<count>, (Fly[]){<values>}`
+fn values(values: &[String]) -> String {
+    match values.is_empty() {
+        true => String::from("0, NULL"),
+        false => format!("{}, (Fly[]){{{}}}", values.len(), values.join(", ")),
+    }
+}
+
+/// Indent the lines of a block (the lines can contain several lines)
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .iter()
+        .flat_map(|line| line.lines())
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("    {}", line),
+        })
+        .collect()
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbols: &'a SymbolTable, known: Layout) -> Self {
+        Self {
+            symbols,
+            known,
+            layout: Layout::default(),
+            frames: vec![],
+            scopes: vec![],
+            structures: vec![],
+            prototypes: vec![],
+            definitions: vec![],
+            names: 0,
+            entered: 0,
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    /// Create a name for a generated element
+    fn generated(&mut self, kind: &str) -> String {
+        self.names += 1;
+        format!("{}_{}", kind, self.names)
+    }
+
+    /// Create a temporary of the current function
+    fn temporary(&mut self) -> String {
+        self.frame().temporaries += 1;
+        format!("fly_tmp_{}", self.frame().temporaries)
+    }
+
+    fn unsupported<R>(location: &LangModuleSlice, feature: &'static str) -> LangResult<R> {
+        lang_err!(Unsupported {
+            location: location.clone(),
+            compiler: NAME,
+            feature,
+        })
+    }
+
+    /// Generate a whole module, returning its code and its layout
+    fn module(mut self, branches: &Branches) -> LangResult<(String, Layout)> {
+        self.define("fly_module", None, false, |this| this.block(branches))?;
+
+        let mut lines = vec![String::from("#include \"flylang.h\""), String::new()];
+        for structure in &self.structures {
+            let name = structure.lines().next().unwrap().trim_end_matches(" {");
+            lines.push(format!("{};", name));
+        }
+        for section in [self.structures, self.prototypes] {
+            lines.push(String::new());
+            lines.extend(section);
+        }
+        for definition in self.definitions {
+            lines.push(String::new());
+            lines.push(definition);
+        }
+        lines.push(String::new());
+        lines.push(String::from("int main(void) {"));
+        lines.push(String::from("    return fly_run(fly_module);"));
+        lines.push(String::from("}"));
+
+        Ok((lines.join("\n") + "\n", self.layout))
+    }
+
+    /// Generate a C function, with its own frame.
+    /// `arguments` are the names of its arguments (`None` when it does not verify them), and `body` generates
+    /// its instructions. The variables of methods include `self` and the class declaring them.
+    fn define(
+        &mut self,
+        function: &str,
+        arguments: Option<&[String]>,
+        method: bool,
+        body: impl FnOnce(&mut Self) -> LangResult<Vec<String>>,
+    ) -> LangResult<()> {
+        let outer = match self.frames.last() {
+            Some(frame) => format!("struct {}_env", frame.function),
+            None => String::from("FlyEnv"),
+        };
+        let id = self.layout.variables.len();
+        self.layout.variables.push(vec![]);
+        let mut variables = self.known.variables.get(id).cloned().unwrap_or_default();
+
+        let mut start = vec![format!(
+            "struct {}_env *env = fly_environment(sizeof *env, function->env);",
+            function
+        )];
+        let mut bound = vec![];
+        if let Some(arguments) = arguments {
+            start.push(format!("fly_arguments(argc, {});", arguments.len()));
+            for (position, argument) in arguments.iter().enumerate() {
+                let value = format!("fly_argument(argc, argv, {})", position);
+                bound.push((argument.as_str(), value));
+            }
+        }
+        if method {
+            bound.push(("self", String::from("function->self")));
+            bound.push(("fly_owner", String::from("function->owner")));
+        }
+        for (variable, value) in bound {
+            if !variables.iter().any(|name| name == variable) {
+                variables.push(variable.to_string());
+            }
+            start.push(format!("env->{} = {};", variable, value));
+        }
+
+        self.frames.push(Frame {
+            function: function.to_string(),
+            variables,
+            temporaries: 0,
+        });
+        let body = body(self);
+        let frame = self.frames.pop().unwrap();
+        let body = body?;
+
+        if frame.temporaries > 0 {
+            let temporaries: Vec<String> = (1..=frame.temporaries)
+                .map(|n| format!("fly_tmp_{}", n))
+                .collect();
+            start.insert(1, format!("Fly {};", temporaries.join(", ")));
+        }
+        let mut fields = vec![format!("FLY_ENV({});", outer)];
+        fields.extend(frame.variables.iter().map(|name| format!("Fly {};", name)));
+        self.layout.variables[id] = frame.variables;
+
+        let mut structure = vec![format!("struct {}_env {{", function)];
+        structure.extend(indent(fields));
+        structure.push(String::from("};"));
+        self.structures.push(structure.join("\n"));
+
+        let signature = format!(
+            "static Fly {}(FlyFunction *function, int argc, Fly *argv)",
+            function
+        );
+        self.prototypes.push(format!("{};", signature));
+        let mut definition = vec![format!("{} {{", signature)];
+        definition.extend(indent(start));
+        definition.extend(indent(body));
+        definition.push(String::from("    return fly_empty();"));
+        definition.push(String::from("}"));
+        self.definitions.push(definition.join("\n"));
+
+        Ok(())
+    }
+
+    /// Get the C expression of a variable : a field of the variables of the function declaring it
+    fn variable(&self, field: &str) -> Option<String> {
+        let current = self.frames.len() - 1;
+        let owner = self
+            .frames
+            .iter()
+            .rposition(|frame| frame.variables.iter().any(|name| name == field))?;
+        Some(format!(
+            "env{}->{}",
+            "->outer".repeat(current - owner),
+            field
+        ))
+    }
+
+    /// Get the C expression of an assigned variable. The variable belongs to the current function if the
+    /// assignment declares it (according to the analyser), or if no enclosing function has it.
+    fn assign(&mut self, location: &LangModuleSlice) -> String {
+        let name = identifier(location.code());
+        if self.declares(location) || self.variable(&name).is_none() {
+            if !self.frame().variables.contains(&name) {
+                self.frame().variables.push(name.clone());
+            }
+            return format!("env->{}", name);
+        }
+        self.variable(&name).unwrap()
+    }
+
+    /// Returns if the assignment redefines a constant (or a receiver or a built-in element)
+    fn reassigns(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Constant | SymbolKind::Receiver | SymbolKind::Builtin
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_none_or(|declaration| declaration.range() != location.range())
+        })
+    }
+
+    /// Get the C expression of an assigned variable, or `None` if the assignment redefines a constant
+    fn target(&mut self, location: &LangModuleSlice) -> Option<String> {
+        (!self.reassigns(location)).then(|| self.assign(location))
+    }
+
+    /// Generate the error of the redefinition of a constant, raised once the value is computed
+    fn reassign(&mut self, location: &LangModuleSlice, value: Code) -> Code {
+        self.sequence(vec![value], |operands| {
+            format!("fly_reassign({}, {})", string(location.code()), operands[0])
+        })
+    }
+
+    /// Returns if the variable is declared at this location
+    fn declares(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Variable
+                    | SymbolKind::Constant
+                    | SymbolKind::Function
+                    | SymbolKind::Class
+                    | SymbolKind::Binding
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_some_and(|declaration| declaration.range() == location.range())
+        })
+    }
+
+    fn block(&mut self, branches: &Branches) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        for instruction in branches {
+            lines.extend(self.instruction(instruction)?);
+        }
+
+        Ok(lines)
+    }
+
+    fn instruction(&mut self, instruction: &Node) -> LangResult<Vec<String>> {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => Ok(vec![format!(
+                "{};",
+                self.statement(expression, instruction.location())?
+            )]),
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => self.repeat(repetition),
+            Instructions::Break(breaker) => self.breaker(breaker),
+            Instructions::Use(_) => Self::unsupported(instruction.location(), "`use`"),
+        }
+    }
+
+    /// Generate an expression used as a statement : the definitions are written as assignments
+    fn statement(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<String> {
+        let (definable, modifiers) = match expression {
+            Expressions::Defined(definable) => (definable, &[][..]),
+            Expressions::Modifed(modified) => {
+                (modified.definable.kind(), &modified.modified_by[..])
+            }
+            _ => return Ok(self.expression(expression, location)?.text),
+        };
+
+        Ok(match self.definition(definable, modifiers)? {
+            (Some(target), value) => format!("{} = {}", target, value.text),
+            (None, value) => value.text,
+        })
+    }
+
+    /// Enter a scope, that breakers can target
+    fn enter(&mut self, kind: ScopeKind, target: &Option<Node<ScopeTarget>>) {
+        let name = match target.as_ref().map(|target| target.kind()) {
+            Some(ScopeTarget::Named(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let id = self.entered;
+        self.entered += 1;
+        let label = match &name {
+            Some(name) => name.clone(),
+            None => match kind {
+                ScopeKind::Function => format!("fn{}", id),
+                ScopeKind::Loop => format!("loop{}", id),
+                ScopeKind::Condition => format!("if{}", id),
+            },
+        };
+
+        self.scopes.push(Scope {
+            kind,
+            name,
+            label,
+            id,
+            frame: self.frames.len() - 1,
+            raised: self.known.raised.contains(&id),
+            left: false,
+            passed: false,
+        });
+    }
+
+    /// Leave the current scope. When breakers reach it from another function, its body pushes a handler.
+    fn leave(&mut self, body: Vec<String>) -> (Vec<String>, Scope) {
+        let scope = self.scopes.pop().unwrap();
+        if !scope.raised {
+            return (body, scope);
+        }
+
+        let handler = format!("fly_handler_{}", scope.id);
+        let mut lines = vec![
+            format!("FlyHandler {};", handler),
+            format!("fly_push(&{}, {});", handler, string(&scope.label)),
+            format!("if (!setjmp({}.jump)) {{", handler),
+        ];
+        lines.extend(indent(body));
+        lines.push(format!("    fly_pop(&{});", handler));
+        match scope.kind {
+            ScopeKind::Function => {
+                lines.push(String::from("} else {"));
+                lines.push(format!("    return {}.value;", handler));
+            }
+            ScopeKind::Loop => {
+                lines.push(format!("}} else if ({}.kind == FLY_STOP) {{", handler));
+                lines.push(String::from("    break;"));
+            }
+            ScopeKind::Condition => {}
+        }
+        lines.push(String::from("}"));
+
+        (lines, scope)
+    }
+
+    /// Generate the instructions of a block (of a condition or a loop)
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &Branches,
+    ) -> LangResult<(Vec<String>, Scope)> {
+        self.enter(kind, target);
+        let body = self.block(branches);
+        Ok(self.leave(body?))
+    }
+
+    fn condition(&mut self, condition: &If) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        let mut ends = vec![];
+        self.branch(condition, "if", &mut lines, &mut ends)?;
+
+        // The breakers leaving a condition go after the whole chain
+        for scope in ends {
+            lines.push(format!("fly_end_{}: ;", scope));
+        }
+        Ok(lines)
+    }
+
+    /// Generate a condition and its fallbacks, starting with `keyword` (`if` or `} else if`)
+    fn branch(
+        &mut self,
+        condition: &If,
+        keyword: &str,
+        lines: &mut Vec<String>,
+        ends: &mut Vec<usize>,
+    ) -> LangResult<()> {
+        let test = self.test(&condition.condition)?.text;
+        let (body, scope) = self.scoped(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            &condition.process,
+        )?;
+        if scope.left {
+            ends.push(scope.id);
+        }
+        lines.push(format!("{} ({}) {{", keyword, test));
+        lines.extend(indent(body));
+
+        match condition.fallback.as_ref().map(|fallback| fallback.kind()) {
+            None => lines.push(String::from("}")),
+            Some(IfFallBack::Process(target, process)) => {
+                let (body, scope) = self.scoped(ScopeKind::Condition, target, process)?;
+                if scope.left {
+                    ends.push(scope.id);
+                }
+                lines.push(String::from("} else {"));
+                lines.extend(indent(body));
+                lines.push(String::from("}"));
+            }
+            Some(IfFallBack::If(other)) => self.branch(other.kind(), "} else if", lines, ends)?,
+        }
+
+        Ok(())
+    }
+
+    fn repeat(&mut self, repetition: &Loop) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        // The declaration of the iterator of an `each` loop
+        let mut iterator = None;
+        // The variables bound when the body is executed, like in the interpreter
+        let mut bindings = vec![];
+
+        let head = match &repetition.parameter {
+            LoopParameter::Through(each) => {
+                let iterable = self.node(&each.iterable)?.text;
+                let name = self.generated("fly_iterator");
+                let mut targets = vec![];
+                for word in [&each.index, &each.item] {
+                    targets.push(match word {
+                        Some(word) => match self.target(word.location()) {
+                            Some(target) => format!("&{}", target),
+                            None => {
+                                let temporary = self.temporary();
+                                let error = self.reassign(word.location(), Code::value(&temporary));
+                                bindings.push(format!("{};", error.text));
+                                format!("&{}", temporary)
+                            }
+                        },
+                        None => String::from("NULL"),
+                    });
+                }
+                let (index, item) = (&targets[0], &targets[1]);
+
+                iterator = Some(format!("FlyIterator {} = fly_iterate({});", name, iterable));
+                format!("while (fly_next(&{}, {}, {})) {{", name, index, item)
+            }
+            LoopParameter::Conditionnaly(condition) => match &condition.iteration_number {
+                None => format!("while ({}) {{", self.test(&condition.condition)?.text),
+                Some(word) => {
+                    let test = self.test(&condition.condition)?.text;
+                    let number = Code::value("fly_number(fly_iteration)");
+                    bindings.push(match self.target(word.location()) {
+                        Some(target) => format!("{} = {};", target, number.text),
+                        None => format!("{};", self.reassign(word.location(), number).text),
+                    });
+                    format!(
+                        "for (double fly_iteration = 0; {}; fly_iteration++) {{",
+                        test
+                    )
+                }
+            },
+        };
+
+        let (body, scope) = self.scoped(
+            ScopeKind::Loop,
+            &repetition.scope_target,
+            &repetition.process,
+        )?;
+        lines.push(head);
+        lines.extend(indent(bindings));
+        lines.extend(indent(body));
+        if scope.passed {
+            lines.push(format!("    fly_pass_{}: ;", scope.id));
+        }
+        lines.push(String::from("}"));
+        if scope.left {
+            lines.push(format!("fly_stop_{}: ;", scope.id));
+        }
+
+        // The iterator only lives during the loop
+        Ok(match iterator {
+            Some(iterator) => {
+                let mut block = vec![String::from("{"), format!("    {}", iterator)];
+                block.extend(indent(lines));
+                block.push(String::from("}"));
+                block
+            }
+            None => lines,
+        })
+    }
+
+    fn breaker(&mut self, breaker: &Break) -> LangResult<Vec<String>> {
+        let Ok(position) = breakers::resolve(&self.scopes, breaker) else {
+            return lang_err!(UnresolvedBreaker(breaker.keyword_location.clone()));
+        };
+        let (kind, value) = match &breaker.kind {
+            BreakKind::Stop(_) => ("FLY_STOP", None),
+            BreakKind::Pass(_) => ("FLY_PASS", None),
+            BreakKind::Return(_, value) => (
+                "FLY_RETURN",
+                match value {
+                    Some(value) => Some(self.node(value)?.text),
+                    None => None,
+                },
+            ),
+        };
+        let value = value.unwrap_or_else(|| String::from("fly_empty()"));
+
+        let frame = self.frames.len() - 1;
+        if self.scopes[position].frame != frame {
+            let scope = &self.scopes[position];
+            self.layout.raised.insert(scope.id);
+            return Ok(vec![format!(
+                "fly_throw({}, {}, {});",
+                string(&scope.label),
+                kind,
+                value
+            )]);
+        }
+
+        // The handlers of the scopes the breaker leaves are removed
+        let handler = self.scopes[position..]
+            .iter()
+            .find(|scope| scope.raised)
+            .map(|scope| format!("&fly_handler_{}", scope.id));
+        let innermost_loop = self.scopes[position + 1..]
+            .iter()
+            .all(|scope| scope.kind != ScopeKind::Loop);
+        let scope = &mut self.scopes[position];
+
+        let jump = match scope.kind {
+            ScopeKind::Function => {
+                return Ok(vec![match handler {
+                    Some(handler) => format!("return fly_leave({}, {});", handler, value),
+                    None => format!("return {};", value),
+                }]);
+            }
+            ScopeKind::Loop if innermost_loop => match kind {
+                "FLY_STOP" => String::from("break;"),
+                _ => String::from("continue;"),
+            },
+            ScopeKind::Loop if kind == "FLY_STOP" => {
+                scope.left = true;
+                format!("goto fly_stop_{};", scope.id)
+            }
+            ScopeKind::Loop => {
+                scope.passed = true;
+                format!("goto fly_pass_{};", scope.id)
+            }
+            ScopeKind::Condition => {
+                scope.left = true;
+                format!("goto fly_end_{};", scope.id)
+            }
+        };
+
+        let mut lines = vec![];
+        if let Some(handler) = handler {
+            lines.push(format!("fly_pop({});", handler));
+        }
+        lines.push(jump);
+        Ok(lines)
+    }
+
+    fn node(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        self.expression(node.kind(), node.location())
+    }
+
+    /// Generate an expression used as a condition (a C boolean)
+    fn test(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        self.truth(node.kind(), node.location())
+    }
+
+    fn truth(&mut self, expression: &Expressions, location: &LangModuleSlice) -> LangResult<Code> {
+        Ok(match expression {
+            Expressions::Literal(ParsedLiterals::True) => Code::constant("true"),
+            Expressions::Literal(ParsedLiterals::False) => Code::constant("false"),
+            Expressions::Prioritized(inner) => self.test(inner)?,
+            Expressions::Reverse(reverse) if matches!(reverse.kind, ReverseKind::Boolean) => {
+                let operand = self.test(&reverse.expression)?;
+                Code {
+                    text: format!("!{}", operand.text),
+                    ..operand
+                }
+            }
+            Expressions::Operation(Operations::Binary(binary)) => {
+                let operator = match binary.operator.kind() {
+                    BinaryOperator::And => "&&",
+                    BinaryOperator::Or => "||",
+                    BinaryOperator::Xor => "!=",
+                };
+                let (left, right) = &binary.operands;
+                let (left, right) = (self.test(left)?, self.test(right)?);
+
+                // `&&` and `||` evaluate their left operand first
+                match operator {
+                    "!=" => self.sequence(vec![left, right], |operands| {
+                        format!("({} != {})", operands[0], operands[1])
+                    }),
+                    _ => Code {
+                        text: format!("({} {} {})", left.text, operator, right.text),
+                        effects: left.effects || right.effects,
+                        constant: false,
+                    },
+                }
+            }
+            _ => {
+                let value = self.expression(expression, location)?;
+                Code {
+                    text: format!("fly_truthy({})", value.text),
+                    ..value
+                }
+            }
+        })
+    }
+
+    /// Generate operands evaluated from the left to the right.
+    /// The operands followed by an operand with effects are evaluated before, in temporaries.
+    fn sequence(&mut self, operands: Vec<Code>, build: impl FnOnce(Vec<String>) -> String) -> Code {
+        let effects = operands.iter().any(|operand| operand.effects);
+        let last_effect = operands.iter().rposition(|operand| operand.effects);
+
+        let mut before = vec![];
+        let mut texts = vec![];
+        for (position, operand) in operands.into_iter().enumerate() {
+            match last_effect.is_some_and(|last| position < last) && !operand.constant {
+                true => {
+                    let temporary = self.temporary();
+                    before.push(format!("{} = {}", temporary, operand.text));
+                    texts.push(temporary);
+                }
+                false => texts.push(operand.text),
+            }
+        }
+
+        let text = build(texts);
+        Code {
+            text: match before.is_empty() {
+                true => text,
+                false => format!("({}, {})", before.join(", "), text),
+            },
+            effects,
+            constant: false,
+        }
+    }
+
+    /// Generate a call to the runtime library with the given operands
+    fn helper(&mut self, name: &str, operands: Vec<Code>) -> Code {
+        self.sequence(operands, |operands| {
+            format!("{}({})", name, operands.join(", "))
+        })
+    }
+
+    /// Generate a list of values given to the runtime as an array (see `values`), after the `leading` operands
+    fn list(
+        &mut self,
+        name: &str,
+        leading: Vec<Code>,
+        items: &[Box<Node<Expressions>>],
+    ) -> LangResult<Code> {
+        let count = leading.len();
+        let mut operands = leading;
+        for item in items {
+            operands.push(self.node(item)?);
+        }
+
+        Ok(self.sequence(operands, |mut operands| {
+            let items = operands.split_off(count);
+            operands.push(values(&items));
+            format!("{}({})", name, operands.join(", "))
+        }))
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match expression {
+            Expressions::Literal(literal) => self.literal(literal, location)?,
+            Expressions::Defined(definable) => self.definable(definable, &[])?,
+            Expressions::Modifed(modified) => {
+                self.definable(modified.definable.kind(), &modified.modified_by)?
+            }
+            Expressions::Read(property) => self.read(property)?,
+            Expressions::ReturnOf(call) => self.call(call)?,
+            Expressions::Reverse(reverse) => match reverse.kind {
+                ReverseKind::Boolean => {
+                    let operand = self.test(&reverse.expression)?;
+                    Code {
+                        text: format!("fly_boolean(!{})", operand.text),
+                        ..operand
+                    }
+                }
+                ReverseKind::Sign => {
+                    let operand = self.node(&reverse.expression)?;
+                    self.helper("fly_negate", vec![operand])
+                }
+            },
+            Expressions::Operation(operation) => self.operation(operation, location)?,
+            Expressions::Prioritized(inner) => self.node(inner)?,
+            Expressions::Ternary(ternary) => {
+                let condition = self.test(&ternary.condition)?;
+                let yes = self.node(&ternary.yes)?;
+                let no = self.node(&ternary.no)?;
+
+                Code {
+                    text: format!("({} ? {} : {})", condition.text, yes.text, no.text),
+                    effects: condition.effects || yes.effects || no.effects,
+                    constant: false,
+                }
+            }
+            Expressions::Structure(structure) => {
+                let mut operands = vec![];
+                for entry in &structure.entries {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    operands.push(match entry.key.kind() {
+                        Expressions::Literal(ParsedLiterals::Word) => Code::constant(format!(
+                            "fly_string({})",
+                            string(entry.key.location().code())
+                        )),
+                        _ => self.node(&entry.key)?,
+                    });
+                    operands.push(self.node(&entry.value)?);
+                }
+
+                self.sequence(operands, |entries| match entries.is_empty() {
+                    true => String::from("fly_structure(0, NULL)"),
+                    false => format!(
+                        "fly_structure({}, (Fly[]){{{}}})",
+                        entries.len() / 2,
+                        entries.join(", ")
+                    ),
+                })
+            }
+            Expressions::Array(array) => self.list("fly_array", vec![], &array.entries)?,
+            Expressions::Instanciate(instanciation) => {
+                let class = self.node(&instanciation.class)?;
+                let mut code = self.list("fly_new", vec![class], &instanciation.arguments)?;
+                code.effects = true;
+                code
+            }
+            // Types are verified by the checker
+            Expressions::Typed(typed) => self.node(&typed.expression)?,
+        })
+    }
+
+    fn literal(
+        &mut self,
+        literal: &ParsedLiterals,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match literal {
+            ParsedLiterals::Word => self.word(location)?,
+            ParsedLiterals::True => Code::constant("fly_boolean(true)"),
+            ParsedLiterals::False => Code::constant("fly_boolean(false)"),
+            ParsedLiterals::Empty => Code::constant("fly_empty()"),
+            ParsedLiterals::Number => number(NumberRepresentation::from(location).into()),
+            ParsedLiterals::Computed(value) => number(*value),
+            ParsedLiterals::String(items) => {
+                let mut parts = vec![];
+                for item in items {
+                    parts.push(match item.kind() {
+                        ParsedStringItem::Literal(content) => {
+                            Code::constant(format!("fly_string({})", string(content)))
+                        }
+                        ParsedStringItem::Expression(expression) => self.node(expression)?,
+                    });
+                }
+
+                match parts.len() {
+                    0 => Code::constant("fly_string(\"\")"),
+                    1 if parts[0].constant => parts.remove(0),
+                    _ => self.sequence(parts, |parts| format!("fly_concat({})", values(&parts))),
+                }
+            }
+        })
+    }
+
+    /// Generate the value of a name : a variable, `self` or a builtin
+    fn word(&mut self, location: &LangModuleSlice) -> LangResult<Code> {
+        let name = location.code();
+        let kind = self.symbols.resolve(location).map(|symbol| symbol.kind);
+
+        let field = match (kind, name) {
+            (Some(SymbolKind::Receiver), "super") => {
+                return Self::unsupported(location, "`super` outside of a call or a property");
+            }
+            (Some(SymbolKind::Receiver), _) => String::from("self"),
+            (Some(SymbolKind::Builtin), "std") => return Ok(Code::constant("fly_std()")),
+            (Some(SymbolKind::Builtin), "assert") => return Ok(Code::constant("fly_assert()")),
+            _ => identifier(name),
+        };
+
+        Ok(Code::value(match self.variable(&field) {
+            Some(variable) => variable,
+            None => format!("fly_undefined({})", string(name)),
+        }))
+    }
+
+    /// Returns if the expression is `super`
+    fn is_super(&self, node: &Node<Expressions>) -> bool {
+        matches!(node.kind(), Expressions::Literal(ParsedLiterals::Word))
+            && node.location().code() == "super"
+            && self
+                .symbols
+                .resolve(node.location())
+                .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver)
+    }
+
+    /// Get a method of the classes following the current class in the method resolution order of `self`
+    /// (the constructor when `name` is `NULL`)
+    fn parent(&self, location: &LangModuleSlice, name: &str) -> LangResult<Code> {
+        match (self.variable("self"), self.variable("fly_owner")) {
+            (Some(receiver), Some(owner)) => Ok(Code::effect(format!(
+                "fly_super({}, {}, {})",
+                receiver, owner, name
+            ))),
+            _ => Self::unsupported(location, "`super` outside of a method"),
+        }
+    }
+
+    fn read(&mut self, property: &ReadProperty) -> LangResult<Code> {
+        let read = property.read.location();
+        if self.is_super(&property.from) {
+            return match property.read.kind() {
+                Property::Key => self.parent(property.from.location(), &string(read.code())),
+                _ => Self::unsupported(read, "the computed properties of `super`"),
+            };
+        }
+
+        let from = self.node(&property.from)?;
+        Ok(match property.read.kind() {
+            // The properties of the builtins do not change
+            Property::Key if from.constant => Code::constant(format!(
+                "fly_get_name({}, {})",
+                from.text,
+                string(read.code())
+            )),
+            Property::Key => Code {
+                text: format!("fly_get_name({}, {})", from.text, string(read.code())),
+                ..from
+            },
+            Property::Index => {
+                let key = number(NumberRepresentation::from(read).into());
+                self.helper("fly_get", vec![from, key])
+            }
+            Property::Expression(key) => {
+                let key = self.node(key)?;
+                self.helper("fly_get", vec![from, key])
+            }
+        })
+    }
+
+    fn call(&mut self, call: &Call) -> LangResult<Code> {
+        // Calling `super` calls the constructor of the parents
+        let callable = match self.is_super(&call.callable) {
+            true => self.parent(call.callable.location(), "NULL")?,
+            false => self.node(&call.callable)?,
+        };
+
+        let mut code = self.list("fly_call", vec![callable], &call.arguments)?;
+        code.effects = true;
+        Ok(code)
+    }
+
+    fn operation(
+        &mut self,
+        operation: &Operations,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        let (name, (left, right)) = match operation {
+            Operations::Numeric(numeric) => (
+                match numeric.operator.kind() {
+                    Operator::Add => "fly_add",
+                    Operator::Substract => "fly_subtract",
+                    Operator::Multiply => "fly_multiply",
+                    Operator::Divide => "fly_divide",
+                    Operator::Modulo => "fly_modulo",
+                    Operator::EuclidianDivision => "fly_quotient",
+                    Operator::Power => "fly_power",
+                },
+                &numeric.operands,
+            ),
+            Operations::Comparative(comparison) => (
+                match comparison.operator.kind() {
+                    Comparison::Equal => "fly_equal",
+                    Comparison::Less(true) => "fly_less",
+                    Comparison::Less(false) => "fly_less_equal",
+                    Comparison::Greater(true) => "fly_greater",
+                    Comparison::Greater(false) => "fly_greater_equal",
+                },
+                &comparison.operands,
+            ),
+            Operations::Binary(_) => {
+                let test = self.truth(&Expressions::Operation(operation.clone()), location)?;
+                return Ok(Code {
+                    text: format!("fly_boolean({})", test.text),
+                    ..test
+                });
+            }
+        };
+
+        let operands = vec![self.node(left)?, self.node(right)?];
+        Ok(self.helper(name, operands))
+    }
+
+    fn modifiers(&mut self, modifiers: &[Node<Modifier>]) -> LangResult<Vec<Code>> {
+        let mut codes = vec![];
+        for modifier in modifiers {
+            codes.push(match modifier.kind() {
+                Modifier::DefinedElement => self.word(modifier.location())?,
+                // The modifier is the function returned by the call
+                Modifier::CallReturn(call) => self.call(call)?,
+            });
+        }
+
+        Ok(codes)
+    }
+
+    /// Wrap a function or a class with its modifiers
+    fn modify(&mut self, value: Code, modifiers: Vec<Code>) -> Code {
+        if modifiers.is_empty() {
+            return value;
+        }
+
+        let mut operands = vec![value];
+        operands.extend(modifiers);
+        self.sequence(operands, |mut operands| {
+            let modifiers = operands.split_off(1);
+            format!("fly_modify({}, {})", operands[0], values(&modifiers))
+        })
+    }
+
+    /// Generate a definition used as an expression
+    fn definable(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+    ) -> LangResult<Code> {
+        Ok(match self.definition(definable, modifiers)? {
+            (Some(target), value) => Code::effect(format!("({} = {})", target, value.text)),
+            (None, value) => value,
+        })
+    }
+
+    /// Generate a definition : the variable it assigns (if there is one), and the assigned value
+    fn definition(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+    ) -> LangResult<(Option<String>, Code)> {
+        let modifiers = self.modifiers(modifiers)?;
+
+        Ok(match definable {
+            Definables::Variable(variable) => self.variable_definition(variable, modifiers)?,
+            // The variable is assigned before the body is generated, which can read it
+            Definables::Function(function) => {
+                let name = function.name.as_ref().map(|name| name.location());
+                let target = name.map(|name| self.target(name));
+                let value = self.function(function, "", false)?;
+                let value = self.modify(value, modifiers);
+                match (name, target) {
+                    (Some(name), Some(None)) => (None, self.reassign(name, value)),
+                    (_, target) => (target.flatten(), value),
+                }
+            }
+            Definables::Class(class) => {
+                let name = class.name.location();
+                let target = self.target(name);
+                let value = self.class(class)?;
+                let value = self.modify(value, modifiers);
+                match target {
+                    Some(target) => (Some(target), value),
+                    None => (None, self.reassign(name, value)),
+                }
+            }
+        })
+    }
+
+    /// Generate the assignment of a variable or of a property
+    fn variable_definition(
+        &mut self,
+        variable: &DefineVariable,
+        modifiers: Vec<Code>,
+    ) -> LangResult<(Option<String>, Code)> {
+        let emplacement = variable.emplacement.location();
+
+        Ok(match variable.emplacement.kind() {
+            VariableEmplacements::Scope => {
+                let value = self.value(variable, modifiers)?;
+                match self.target(emplacement) {
+                    Some(target) => (
+                        Some(target),
+                        Code {
+                            effects: true,
+                            ..value
+                        },
+                    ),
+                    None => (None, self.reassign(emplacement, value)),
+                }
+            }
+            VariableEmplacements::Property(property) => {
+                if self.is_super(&property.from) {
+                    return Self::unsupported(emplacement, "the assignment of a `super` property");
+                }
+
+                let target = self.node(&property.from)?;
+                let read = property.read.location();
+                let mut code = match property.read.kind() {
+                    Property::Key => {
+                        let key = string(read.code());
+                        let value = self.value(variable, modifiers)?;
+                        self.sequence(vec![target, value], |operands| {
+                            format!("fly_put_name({}, {}, {})", operands[0], key, operands[1])
+                        })
+                    }
+                    Property::Index => {
+                        let key = number(NumberRepresentation::from(read).into());
+                        let value = self.value(variable, modifiers)?;
+                        self.helper("fly_put", vec![target, key, value])
+                    }
+                    Property::Expression(key) => {
+                        let key = self.node(key)?;
+                        let value = self.value(variable, modifiers)?;
+                        self.helper("fly_put", vec![target, key, value])
+                    }
+                };
+                code.effects = true;
+                (None, code)
+            }
+            VariableEmplacements::Any(_) => {
+                return Self::unsupported(emplacement, "the assignment of an expression");
+            }
+        })
+    }
+
+    /// Generate the value of a variable, with its modifiers applied
+    fn value(&mut self, variable: &DefineVariable, modifiers: Vec<Code>) -> LangResult<Code> {
+        let value = self.node(&variable.value)?;
+        if modifiers.is_empty() {
+            return Ok(value);
+        }
+
+        let name = string(variable.emplacement.location().code());
+        let constant = variable.readonly;
+        let mut operands = vec![value];
+        operands.extend(modifiers);
+        let mut code = self.sequence(operands, |mut operands| {
+            let modifiers = operands.split_off(1);
+            format!(
+                "fly_modify_variable({}, {}, {}, {})",
+                name,
+                constant,
+                operands[0],
+                values(&modifiers)
+            )
+        });
+        code.effects = true;
+        Ok(code)
+    }
+
+    /// Generate a function (named after `prefix` and its name), returning the creation of its value.
+    /// The variables of methods include `self`.
+    fn function(
+        &mut self,
+        function: &DefineFunction,
+        prefix: &str,
+        method: bool,
+    ) -> LangResult<Code> {
+        let name = function.name.as_ref().map(|name| name.location().code());
+        let c_name = self.generated(&match name {
+            Some(name) => format!("{}{}", prefix, identifier(name)),
+            None => format!("{}fly_fn", prefix),
+        });
+        let arguments: Vec<String> = function
+            .arguments
+            .iter()
+            .map(|argument| identifier(argument.kind().name.location().code()))
+            .collect();
+
+        self.define(&c_name, Some(&arguments), method, |this| {
+            this.enter(ScopeKind::Function, &function.scope_target);
+            let body = this.block(&function.execution);
+            Ok(this.leave(body?).0)
+        })?;
+
+        Ok(Code::constant(format!(
+            "fly_function({}, {}, env)",
+            name.map_or(String::from("NULL"), string),
+            c_name
+        )))
+    }
+
+    /// Generate the creation of a class
+    fn class(&mut self, class: &DefineClass) -> LangResult<Code> {
+        let name = class.name.location().code();
+        let prefix = format!("{}_", identifier(name));
+
+        let mut operands = vec![];
+        for parent in &class.parents {
+            operands.push(self.word(parent.location())?);
+        }
+        let parents = operands.len();
+
+        // The members are written as `{kind, name, value}`, the value being an operand
+        let mut members = vec![];
+        let mut attributes = vec![];
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            match item.is_static {
+                true => {
+                    let modifiers = self.modifiers(&item.modifie_by)?;
+                    operands.push(self.value(&item.item, modifiers)?);
+                    members.push(("FLY_STATIC", Some(item.item.emplacement.location().code())));
+                }
+                false => attributes.push(item),
+            }
+        }
+
+        // The attributes of the instances are initialised by a method
+        if !attributes.is_empty() {
+            let function = self.generated(&format!("{}fly_attributes", prefix));
+            self.define(&function, Some(&[]), true, |this| {
+                let mut body = vec![];
+                for item in attributes {
+                    let modifiers = this.modifiers(&item.modifie_by)?;
+                    let value = this.value(&item.item, modifiers)?.text;
+                    body.push(format!(
+                        "fly_put_name(env->self, {}, {});",
+                        string(item.item.emplacement.location().code()),
+                        value
+                    ));
+                }
+                Ok(body)
+            })?;
+            operands.push(Code::constant(format!(
+                "fly_function(NULL, {}, env)",
+                function
+            )));
+            members.push(("FLY_ATTRIBUTES", None));
+        }
+
+        if let Some(constructor) = &class.constructor {
+            operands.push(self.function(constructor.kind(), &prefix, true)?);
+            members.push(("FLY_CONSTRUCTOR", None));
+        }
+
+        for method in &class.methods {
+            let item = method.kind();
+            let method = item.item.name.as_ref().map(|name| name.location().code());
+            let modifiers = self.modifiers(&item.modifie_by)?;
+
+            match item.is_static {
+                // Static methods are read from the class : they are modified once
+                true => {
+                    let function = self.function(&item.item, &prefix, false)?;
+                    operands.push(self.modify(function, modifiers));
+                    members.push(("FLY_STATIC_METHOD", method));
+                }
+                false => {
+                    operands.push(self.function(&item.item, &prefix, true)?);
+                    members.push(("FLY_METHOD", method));
+                    // Methods are modified each time they are read from an instance
+                    if !modifiers.is_empty() {
+                        let texts: Vec<String> = modifiers.iter().map(|m| m.text.clone()).collect();
+                        operands.push(Code {
+                            text: format!("fly_array({})", values(&texts)),
+                            effects: modifiers.iter().any(|modifier| modifier.effects),
+                            constant: false,
+                        });
+                        members.push(("FLY_MODIFIERS", method));
+                    }
+                }
+            }
+        }
+
+        let name = string(name);
+        let mut code = self.sequence(operands, |mut operands| {
+            let member_values = operands.split_off(parents);
+            let members: Vec<String> = members
+                .iter()
+                .zip(member_values)
+                .map(|((kind, name), value)| {
+                    let name = name.map_or(String::from("NULL"), string);
+                    format!("    {{{}, {}, {}}},", kind, name, value)
+                })
+                .collect();
+            let members = match members.is_empty() {
+                true => String::from("0, NULL"),
+                false => format!(
+                    "{}, (FlyMember[]){{\n{}\n}}",
+                    members.len(),
+                    members.join("\n")
+                ),
+            };
+            format!("fly_class({}, {}, {})", name, values(&operands), members)
+        });
+        code.effects = true;
+        Ok(code)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::flylang::{
+    errors::{ErrorType, RaisableErr, codes::ErrorId},
+    module::slice::LangModuleSlice,
+};
+
+pub struct CompilationFailed {
+    pub compiler: &'static str,
+    pub reason: String,
+}
+impl RaisableErr for CompilationFailed {
+    fn _id(&self) -> ErrorId {
+        ErrorId::CompilationFailed
+    }
+    fn _code(&self) -> i32 {
+        1
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("The {} compiler failed: {}", self.compiler, self.reason)
+    }
+}
+
+pub struct Unsupported {
+    pub location: LangModuleSlice,
+    pub compiler: &'static str,
+    pub feature: &'static str,
+}
+impl RaisableErr for Unsupported {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnsupportedByCompiler
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The {} compiler does not support {}.",
+            self.compiler, self.feature
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct UnresolvedBreaker(pub LangModuleSlice);
+impl RaisableErr for UnresolvedBreaker {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnresolvedBreaker
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        String::from("The scope stopped by this breaker cannot be found.")
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
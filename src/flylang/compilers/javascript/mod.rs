@@ -0,0 +1,1325 @@
+//! Translation of flylang to JavaScript (ES2020 modules).
+//!
+//! The generated module imports its runtime library (`flylang.mjs`), which implements the parts of the flylang
+//! semantics JavaScript does not share : truthiness, equality, display, multiple inheritance, modifiers, ...
+//!
+//! - Variables live in the function they are declared in (found by the analyser), so they are declared with
+//!   `let` at the top of this function.
+//! - Scope-targeted breakers use labels. A breaker going out of its function throws a `$.Breaker`, caught by
+//!   the scope it targets.
+//! - `use` instructions become ES module imports, and the names declared by a module are exported.
+//! - Operators, property reads and calls go through the runtime library, which raises the errors of the
+//!   interpreter (with their code) when the values do not fit.
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+use crate::flylang::{
+    analyser::{
+        Analyser,
+        symbols::{SymbolKind, SymbolTable},
+    },
+    compilers::{
+        Compiler,
+        errors::{CompilationFailed, UnresolvedBreaker, Unsupported},
+    },
+    errors::{LangResult, lang_err},
+    lexer::tokens::{
+        BinaryOperator, Comparison, Operator, ScopeTarget,
+        representations::number::NumberRepresentation,
+    },
+    module::{LangModule, slice::LangModuleSlice},
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::DefineClass,
+            functions::DefineFunction,
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::{Loop, LoopParameter},
+            r#use::{Package, PackageContentEmplacement, PackageIncludedContent, PackageSource},
+        },
+    },
+    utils::breakers::{self, BreakableScope, ScopeKind},
+};
+
+/// The runtime library imported by the generated modules
+pub const RUNTIME: &str = include_str!("runtime.mjs");
+const RUNTIME_FILE: &str = "flylang.mjs";
+
+const NAME: &str = "js";
+
+/// Words that cannot be used as JavaScript variables (they are prefixed with `$`)
+const RESERVED: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "globalThis",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "Infinity",
+    "instanceof",
+    "interface",
+    "let",
+    "NaN",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// The precedence of the JavaScript operators, used to know where parentheses are needed
+mod precedence {
+    pub const ASSIGNMENT: u8 = 2;
+    pub const OR: u8 = 3;
+    pub const AND: u8 = 4;
+    pub const EQUALITY: u8 = 8;
+    pub const UNARY: u8 = 14;
+    pub const CALL: u8 = 17;
+    pub const PRIMARY: u8 = 20;
+}
+use precedence::*;
+
+/// The JavaScript compiler, generating code executed by Node.js (or by browsers, with the runtime library)
+pub struct JavaScript;
+
+impl Compiler for JavaScript {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+    fn description(&self) -> &'static str {
+        "Translate the code to JavaScript (ES2020 modules), executed with Node.js"
+    }
+    fn extension(&self) -> &'static str {
+        "mjs"
+    }
+
+    fn compile(&self, _: &LangModule, branches: &Branches) -> LangResult<String> {
+        let analysis = Analyser::new().analyse(branches);
+        Generator::new(&analysis.symbols).module(branches)
+    }
+
+    fn runtime(&self) -> Vec<(&'static str, &'static str)> {
+        vec![(RUNTIME_FILE, RUNTIME)]
+    }
+
+    fn run(&self, file: &Path) -> LangResult<ExitStatus> {
+        match Command::new("node").arg(file).status() {
+            Ok(status) => Ok(status),
+            Err(error) => lang_err!(CompilationFailed {
+                compiler: NAME,
+                reason: format!("node cannot be started ({})", error),
+            }),
+        }
+    }
+}
+
+/// A JavaScript expression
+struct Code {
+    text: String,
+    precedence: u8,
+}
+impl Code {
+    fn new(text: impl Into<String>, precedence: u8) -> Self {
+        Self {
+            text: text.into(),
+            precedence,
+        }
+    }
+
+    /// Get the expression, surrounded by parentheses if its precedence is lower than `minimum`
+    fn at(self, minimum: u8) -> String {
+        match self.precedence < minimum {
+            true => format!("({})", self.text),
+            false => self.text,
+        }
+    }
+}
+
+/// A JavaScript function being generated
+#[derive(Default)]
+struct Frame {
+    /// The variables declared by the function, exported by the module
+    declared: Vec<String>,
+    /// The variables declared with `let` at the top of the function
+    variables: Vec<String>,
+    /// Number of blocks (conditions and loops) containing the code being generated
+    blocks: usize,
+    /// If the function is a method, using `self`
+    method: bool,
+    uses_self: bool,
+}
+
+/// A flylang scope containing the code being generated, that breakers can target
+struct Scope {
+    kind: ScopeKind,
+    name: Option<String>,
+    label: String,
+    /// The position of the function containing the scope, in the frames
+    frame: usize,
+    /// If a breaker uses the label of the scope
+    labelled: bool,
+    /// If a breaker written in another function targets this scope
+    thrown: bool,
+}
+impl BreakableScope for Scope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+struct Generator<'a> {
+    symbols: &'a SymbolTable,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    imports: Vec<String>,
+    /// The names of the classes containing the code being generated
+    classes: Vec<String>,
+    /// Number of labels created, to give them a unique name
+    labels: usize,
+}
+
+/// Convert a flylang name to a valid JavaScript identifier
+fn identifier(name: &str) -> String {
+    let mut escaped: String = name
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '_' {
+            true => c.to_string(),
+            false => format!("$u{:x}", c as u32),
+        })
+        .collect();
+
+    if RESERVED.contains(&escaped.as_str()) {
+        escaped.insert(0, '$');
+    }
+    escaped
+}
+
+/// Get the key of a property, written after a `.` when possible
+fn property(name: &str) -> String {
+    match name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        true => format!(".{}", name),
+        false => format!("[{}]", string(name)),
+    }
+}
+
+/// Get the key of an object or a class member
+fn key(name: &str) -> String {
+    match name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        true => name.to_string(),
+        false => string(name),
+    }
+}
+
+fn string(content: &str) -> String {
+    serde_json::to_string(content).unwrap()
+}
+
+fn number(value: f64) -> Code {
+    let text = if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0f64 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        })
+    } else if value.fract() == 0f64 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    };
+
+    let precedence = match text.starts_with('-') {
+        true => UNARY,
+        false => PRIMARY,
+    };
+    Code::new(text, precedence)
+}
+
+/// Generate an infix operation (operators are left-associative)
+fn infix(operator: &str, precedence: u8, (left, right): (Code, Code)) -> Code {
+    let (left, right) = (left.at(precedence), right.at(precedence + 1));
+    Code::new(format!("{} {} {}", left, operator, right), precedence)
+}
+
+/// Indent the lines of a block
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| format!("  {}", line))
+        .collect()
+}
+
+/// Split a generated text in lines
+fn lines(text: String) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
+/// Returns if the expression always gives a boolean
+fn is_boolean(expression: &Expressions) -> bool {
+    match expression {
+        Expressions::Literal(ParsedLiterals::True | ParsedLiterals::False) => true,
+        Expressions::Operation(Operations::Binary(_) | Operations::Comparative(_)) => true,
+        Expressions::Reverse(reverse) => matches!(reverse.kind, ReverseKind::Boolean),
+        Expressions::Prioritized(inner) => is_boolean(inner.kind()),
+        Expressions::Typed(typed) => is_boolean(typed.expression.kind()),
+        _ => false,
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbols: &'a SymbolTable) -> Self {
+        Self {
+            symbols,
+            frames: vec![],
+            scopes: vec![],
+            imports: vec![],
+            classes: vec![],
+            labels: 0,
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn unsupported<R>(location: &LangModuleSlice, feature: &'static str) -> LangResult<R> {
+        lang_err!(Unsupported {
+            location: location.clone(),
+            compiler: NAME,
+            feature,
+        })
+    }
+
+    /// Generate a whole module
+    fn module(mut self, branches: &Branches) -> LangResult<String> {
+        self.frames.push(Frame::default());
+        let body = self.block(branches)?;
+        let frame = self.frames.pop().unwrap();
+
+        let mut lines = vec![
+            format!("import * as $ from \"./{}\";", RUNTIME_FILE),
+            format!("import {{ std, assert }} from \"./{}\";", RUNTIME_FILE),
+        ];
+        lines.append(&mut self.imports);
+        lines.push(String::new());
+        if !frame.variables.is_empty() {
+            lines.push(format!("let {};", frame.variables.join(", ")));
+            lines.push(String::new());
+        }
+        lines.extend(body);
+        if !frame.declared.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("export {{ {} }};", frame.declared.join(", ")));
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// Get the name of an assigned variable, declaring it in the current function if the assignment
+    /// declares it (according to the analyser)
+    fn assign(&mut self, location: &LangModuleSlice) -> String {
+        let name = identifier(location.code());
+        if self.declares(location) {
+            self.frame().declared.push(name.clone());
+            self.frame().variables.push(name.clone());
+        }
+        name
+    }
+
+    /// Returns if the variable is declared at this location
+    fn declares(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Variable
+                    | SymbolKind::Constant
+                    | SymbolKind::Function
+                    | SymbolKind::Class
+                    | SymbolKind::Binding
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_some_and(|declaration| declaration.range() == location.range())
+        })
+    }
+
+    /// Returns if the assignment redefines a constant (or a receiver or a built-in element)
+    fn reassigns(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Constant | SymbolKind::Receiver | SymbolKind::Builtin
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_none_or(|declaration| declaration.range() != location.range())
+        })
+    }
+
+    /// Generate the assignment of a value to a variable
+    fn bind(&mut self, location: &LangModuleSlice, value: String) -> Code {
+        match self.reassigns(location) {
+            true => Code::new(
+                format!("$.reassign({}, {})", string(location.code()), value),
+                CALL,
+            ),
+            false => {
+                let name = self.assign(location);
+                Code::new(format!("{} = {}", name, value), ASSIGNMENT)
+            }
+        }
+    }
+
+    fn block(&mut self, branches: &Branches) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        for instruction in branches {
+            lines.extend(self.instruction(instruction)?);
+        }
+
+        Ok(lines)
+    }
+
+    fn instruction(&mut self, instruction: &Node) -> LangResult<Vec<String>> {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => self.statement(expression, instruction.location()),
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => self.repeat(repetition),
+            Instructions::Break(breaker) => self.breaker(breaker),
+            Instructions::Use(package) => {
+                self.import(package);
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn statement(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Vec<String>> {
+        // Functions and classes declared in the body of a function are declarations
+        if self.frame().blocks == 0 {
+            match expression {
+                Expressions::Defined(Definables::Function(function))
+                    if function
+                        .name
+                        .as_ref()
+                        .is_some_and(|name| self.declares(name.location())) =>
+                {
+                    let name = identifier(function.name.as_ref().unwrap().location().code());
+                    self.frame().declared.push(name);
+                    return Ok(lines(self.function(function)?));
+                }
+                Expressions::Defined(Definables::Class(class))
+                    if self.declares(class.name.location()) =>
+                {
+                    let name = identifier(class.name.location().code());
+                    self.frame().declared.push(name.clone());
+
+                    let (class, statics) = self.class(class)?;
+                    let mut code = lines(class);
+                    if let Some(statics) = statics {
+                        code.push(format!("Object.assign({}, {});", name, statics));
+                    }
+                    return Ok(code);
+                }
+                _ => {}
+            }
+        }
+
+        let text = self.expression(expression, location)?.text;
+        // Expressions starting like a block or a declaration would be read as such
+        let text = match ["{", "function", "class"]
+            .iter()
+            .any(|start| text.starts_with(start))
+        {
+            true => format!("({});", text),
+            false => format!("{};", text),
+        };
+        Ok(lines(text))
+    }
+
+    /// Enter a scope, that breakers can target
+    fn enter(&mut self, kind: ScopeKind, target: &Option<Node<ScopeTarget>>) {
+        let name = match target.as_ref().map(|target| target.kind()) {
+            Some(ScopeTarget::Named(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let label = match &name {
+            Some(name) => identifier(name),
+            None => {
+                self.labels += 1;
+                match kind {
+                    ScopeKind::Function => format!("$fn{}", self.labels),
+                    ScopeKind::Loop => format!("$loop{}", self.labels),
+                    ScopeKind::Condition => format!("$if{}", self.labels),
+                }
+            }
+        };
+
+        self.scopes.push(Scope {
+            kind,
+            name,
+            label,
+            frame: self.frames.len() - 1,
+            labelled: false,
+            thrown: false,
+        });
+    }
+
+    /// Leave the current scope. The breakers thrown to it are caught around its body.
+    fn leave(&mut self, body: Vec<String>) -> (Scope, Vec<String>) {
+        let mut scope = self.scopes.pop().unwrap();
+        if !scope.thrown {
+            return (scope, body);
+        }
+
+        let action = match scope.kind {
+            ScopeKind::Function => vec![String::from("return $error.value;")],
+            ScopeKind::Loop => {
+                scope.labelled = true;
+                vec![
+                    String::from("if ($error.kind === \"stop\") {"),
+                    format!("  break {};", scope.label),
+                    String::from("}"),
+                    format!("continue {};", scope.label),
+                ]
+            }
+            ScopeKind::Condition => vec![],
+        };
+
+        let mut lines = vec![String::from("try {")];
+        lines.extend(indent(body));
+        lines.push(String::from("} catch ($error) {"));
+        lines.push(format!(
+            "  if (!$.caught($error, {})) {{",
+            string(&scope.label)
+        ));
+        lines.push(String::from("    throw $error;"));
+        lines.push(String::from("  }"));
+        lines.extend(indent(action));
+        lines.push(String::from("}"));
+
+        (scope, lines)
+    }
+
+    /// Generate the instructions of a block (of a condition or a loop).
+    /// Returns the label of the block (if a breaker uses it) and its lines.
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &Branches,
+    ) -> LangResult<(Option<String>, Vec<String>)> {
+        self.enter(kind, target);
+        self.frame().blocks += 1;
+        let body = self.block(branches);
+        self.frame().blocks -= 1;
+        let (scope, body) = self.leave(body?);
+
+        Ok((scope.labelled.then_some(scope.label), body))
+    }
+
+    fn condition(&mut self, condition: &If) -> LangResult<Vec<String>> {
+        let test = self.test(&condition.condition)?.text;
+        let (label, body) = self.scoped(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            &condition.process,
+        )?;
+
+        let label = label.map_or(String::new(), |label| format!("{}: ", label));
+        let mut lines = vec![format!("if ({}) {}{{", test, label)];
+        lines.extend(indent(body));
+
+        match condition.fallback.as_ref().map(|fallback| fallback.kind()) {
+            None => lines.push(String::from("}")),
+            Some(IfFallBack::Process(target, process)) => {
+                let (label, body) = self.scoped(ScopeKind::Condition, target, process)?;
+                let label = label.map_or(String::new(), |label| format!("{}: ", label));
+
+                lines.push(format!("}} else {}{{", label));
+                lines.extend(indent(body));
+                lines.push(String::from("}"));
+            }
+            Some(IfFallBack::If(other)) => {
+                let mut other = self.condition(other.kind())?;
+                other[0] = format!("}} else {}", other[0]);
+                lines.extend(other);
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Generate a loop. Its variables are bound each time its body is executed, like in the interpreter : they
+    /// keep the values of the last execution, and stay unassigned if the body is never executed.
+    fn repeat(&mut self, repetition: &Loop) -> LangResult<Vec<String>> {
+        let mut bindings = vec![];
+        let head = match &repetition.parameter {
+            LoopParameter::Through(each) => {
+                let iterable = self.node(&each.iterable)?.at(ASSIGNMENT);
+                for (word, entry) in [(&each.index, "$index"), (&each.item, "$item")] {
+                    if let Some(word) = word {
+                        bindings.push(self.bind(word.location(), entry.to_string()).text + ";");
+                    }
+                }
+
+                format!("for (const [$index, $item] of $.entries({}))", iterable)
+            }
+            LoopParameter::Conditionnaly(condition) => {
+                let test = self.test(&condition.condition)?.text;
+                match &condition.iteration_number {
+                    None => format!("while ({})", test),
+                    Some(word) => {
+                        let number = String::from("$iteration");
+                        bindings.push(self.bind(word.location(), number.clone()).text + ";");
+                        format!("for (let {0} = 0; {1}; {0}++)", number, test)
+                    }
+                }
+            }
+        };
+
+        let (label, body) = self.scoped(
+            ScopeKind::Loop,
+            &repetition.scope_target,
+            &repetition.process,
+        )?;
+        let label = label.map_or(String::new(), |label| format!("{}: ", label));
+
+        let mut lines = vec![format!("{}{} {{", label, head)];
+        lines.extend(indent(bindings));
+        lines.extend(indent(body));
+        lines.push(String::from("}"));
+        Ok(lines)
+    }
+
+    fn breaker(&mut self, breaker: &Break) -> LangResult<Vec<String>> {
+        let Ok(position) = breakers::resolve(&self.scopes, breaker) else {
+            return lang_err!(UnresolvedBreaker(breaker.keyword_location.clone()));
+        };
+        let (kind, value) = match &breaker.kind {
+            BreakKind::Stop(_) => ("stop", None),
+            BreakKind::Pass(_) => ("pass", None),
+            BreakKind::Return(_, value) => (
+                "return",
+                match value {
+                    Some(value) => Some(self.node(value)?.at(ASSIGNMENT)),
+                    None => None,
+                },
+            ),
+        };
+
+        // The scope is in another function : the breaker is thrown to it
+        let frame = self.frames.len() - 1;
+        let innermost_loop = self.scopes[position + 1..]
+            .iter()
+            .all(|scope| scope.kind != ScopeKind::Loop);
+        let scope = &mut self.scopes[position];
+        if scope.frame != frame {
+            scope.thrown = true;
+            let value = value.map_or(String::new(), |value| format!(", {}", value));
+            return Ok(vec![format!(
+                "throw new $.Breaker({}, \"{}\"{});",
+                string(&scope.label),
+                kind,
+                value
+            )]);
+        }
+
+        let line = match scope.kind {
+            ScopeKind::Function => match value {
+                Some(value) => format!("return {};", value),
+                None => String::from("return;"),
+            },
+            ScopeKind::Loop => {
+                let keyword = match kind {
+                    "stop" => "break",
+                    _ => "continue",
+                };
+                match innermost_loop {
+                    true => format!("{};", keyword),
+                    false => {
+                        scope.labelled = true;
+                        format!("{} {};", keyword, scope.label)
+                    }
+                }
+            }
+            ScopeKind::Condition => {
+                scope.labelled = true;
+                format!("break {};", scope.label)
+            }
+        };
+        Ok(vec![line])
+    }
+
+    fn import(&mut self, package: &Package) {
+        let source = match &package.source {
+            PackageSource::File(path) => {
+                let mut path = PathBuf::from(path);
+                path.set_extension("mjs");
+                let path = path.to_string_lossy().replace('\\', "/");
+
+                match path.starts_with('.') {
+                    true => path,
+                    false => format!("./{}", path),
+                }
+            }
+            PackageSource::Package(name) => name.clone(),
+        };
+        let source = string(&source);
+
+        match (&package.included, &package.emplacement) {
+            (_, PackageContentEmplacement::Variable(variable)) => self.imports.push(format!(
+                "import * as {} from {};",
+                identifier(variable.location().code()),
+                source
+            )),
+            (PackageIncludedContent::Only(names), PackageContentEmplacement::Global) => {
+                let names: Vec<String> = names
+                    .iter()
+                    .map(|name| identifier(name.location().code()))
+                    .collect();
+                self.imports.push(format!(
+                    "import {{ {} }} from {};",
+                    names.join(", "),
+                    source
+                ));
+            }
+            // The names of the module cannot be known : they are made global
+            (PackageIncludedContent::All, PackageContentEmplacement::Global) => {
+                self.labels += 1;
+                let module = format!("$module{}", self.labels);
+                self.imports
+                    .push(format!("import * as {} from {};", module, source));
+                self.imports
+                    .push(format!("Object.assign(globalThis, {});", module));
+            }
+        }
+    }
+
+    fn node(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        self.expression(node.kind(), node.location())
+    }
+
+    /// Generate an expression used as a condition
+    fn test(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        let boolean = is_boolean(node.kind());
+        let code = self.node(node)?;
+
+        Ok(match boolean {
+            true => code,
+            false => Code::new(format!("$.truthy({})", code.text), CALL),
+        })
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match expression {
+            Expressions::Literal(literal) => self.literal(literal, location)?,
+            Expressions::Defined(definable) => self.definable(definable, &[])?,
+            Expressions::Modifed(modified) => {
+                self.definable(modified.definable.kind(), &modified.modified_by)?
+            }
+            Expressions::Read(property) => self.read(property)?,
+            Expressions::ReturnOf(call) => self.call(call)?,
+            Expressions::Reverse(reverse) => match reverse.kind {
+                ReverseKind::Boolean => {
+                    let operand = match is_boolean(reverse.expression.kind()) {
+                        true => self.node(&reverse.expression)?.at(UNARY),
+                        false => self.test(&reverse.expression)?.text,
+                    };
+                    Code::new(format!("!{}", operand), UNARY)
+                }
+                ReverseKind::Sign => {
+                    let operand = self.node(&reverse.expression)?.at(ASSIGNMENT);
+                    Code::new(format!("$.negate({})", operand), CALL)
+                }
+            },
+            Expressions::Operation(operation) => self.operation(operation)?,
+            Expressions::Prioritized(inner) => {
+                let inner = self.node(inner)?;
+                Code::new(inner.at(PRIMARY), PRIMARY)
+            }
+            Expressions::Ternary(ternary) => {
+                let condition = self.test(&ternary.condition)?.at(OR);
+                let yes = self.node(&ternary.yes)?.at(ASSIGNMENT);
+                let no = self.node(&ternary.no)?.at(ASSIGNMENT);
+
+                Code::new(format!("{} ? {} : {}", condition, yes, no), ASSIGNMENT)
+            }
+            Expressions::Structure(structure) => {
+                let mut entries = vec![];
+                for entry in &structure.entries {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    let key = match entry.key.kind() {
+                        Expressions::Literal(ParsedLiterals::Word) => {
+                            key(entry.key.location().code())
+                        }
+                        Expressions::Literal(ParsedLiterals::String(items))
+                            if items.iter().all(|item| {
+                                matches!(item.kind(), ParsedStringItem::Literal(_))
+                            }) =>
+                        {
+                            self.node(&entry.key)?.text
+                        }
+                        _ => format!("[{}]", self.node(&entry.key)?.text),
+                    };
+                    entries.push(format!(
+                        "{}: {}",
+                        key,
+                        self.node(&entry.value)?.at(ASSIGNMENT)
+                    ));
+                }
+
+                match entries.is_empty() {
+                    true => Code::new("{}", PRIMARY),
+                    false => Code::new(format!("{{ {} }}", entries.join(", ")), PRIMARY),
+                }
+            }
+            Expressions::Array(array) => {
+                let items = self.arguments(&array.entries)?;
+                Code::new(format!("[{}]", items), PRIMARY)
+            }
+            Expressions::Instanciate(instanciation) => {
+                let class = &instanciation.class;
+                let simple = matches!(class.kind(), Expressions::Literal(ParsedLiterals::Word))
+                    || matches!(class.kind(), Expressions::Read(read) if matches!(read.read.kind(), Property::Key));
+                let class = match simple {
+                    true => self.node(class)?.text,
+                    false => self.node(class)?.at(PRIMARY + 1),
+                };
+
+                let arguments = self.arguments(&instanciation.arguments)?;
+                Code::new(format!("new {}({})", class, arguments), CALL)
+            }
+            // Types are verified by the checker
+            Expressions::Typed(typed) => self.node(&typed.expression)?,
+        })
+    }
+
+    fn arguments(&mut self, arguments: &[Box<Node<Expressions>>]) -> LangResult<String> {
+        let mut codes = vec![];
+        for argument in arguments {
+            codes.push(self.node(argument)?.at(ASSIGNMENT));
+        }
+
+        Ok(codes.join(", "))
+    }
+
+    fn literal(
+        &mut self,
+        literal: &ParsedLiterals,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match literal {
+            ParsedLiterals::Word => {
+                let name = location.code();
+                let kind = self.symbols.resolve(location).map(|symbol| symbol.kind);
+                let receiver = kind == Some(SymbolKind::Receiver);
+
+                match (receiver, name) {
+                    (true, "super") => {
+                        return Self::unsupported(
+                            location,
+                            "`super` outside of a call or a property",
+                        );
+                    }
+                    (true, _) => {
+                        if let Some(frame) = self.frames.iter_mut().rev().find(|frame| frame.method)
+                        {
+                            frame.uses_self = true;
+                        }
+                        Code::new(name, PRIMARY)
+                    }
+                    // The variables declared nowhere (by the analyser) are not declared by `let`
+                    (false, _) if kind.is_none() => {
+                        Code::new(format!("$.undefinedVariable({})", string(name)), CALL)
+                    }
+                    (false, _) => Code::new(identifier(name), PRIMARY),
+                }
+            }
+            ParsedLiterals::True => Code::new("true", PRIMARY),
+            ParsedLiterals::False => Code::new("false", PRIMARY),
+            ParsedLiterals::Empty => Code::new("null", PRIMARY),
+            ParsedLiterals::Number => number(NumberRepresentation::from(location).into()),
+            ParsedLiterals::Computed(value) => number(*value),
+            ParsedLiterals::String(items) => {
+                if let [item] = items.as_slice()
+                    && let ParsedStringItem::Literal(content) = item.kind()
+                {
+                    return Ok(Code::new(string(content), PRIMARY));
+                }
+
+                let mut template = String::new();
+                for item in items {
+                    match item.kind() {
+                        ParsedStringItem::Literal(content) => template.push_str(
+                            &content
+                                .replace('\\', "\\\\")
+                                .replace('`', "\\`")
+                                .replace("${", "\\${")
+                                .replace('\n', "\\n")
+                                .replace('\r', "\\r"),
+                        ),
+                        ParsedStringItem::Expression(expression) => {
+                            let code = self.node(expression)?.text;
+                            template.push_str(&format!("${{$.display({})}}", code));
+                        }
+                    }
+                }
+                Code::new(format!("`{}`", template), PRIMARY)
+            }
+        })
+    }
+
+    /// Returns if the expression is `super`
+    fn is_super(&self, node: &Node<Expressions>) -> bool {
+        matches!(node.kind(), Expressions::Literal(ParsedLiterals::Word))
+            && node.location().code() == "super"
+            && self
+                .symbols
+                .resolve(node.location())
+                .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver)
+    }
+
+    /// Generate the object a property is read from.
+    /// `super` gives the methods following the current class in the method resolution order of `self`.
+    fn object(&mut self, node: &Node<Expressions>) -> LangResult<String> {
+        if !self.is_super(node) {
+            return Ok(self.node(node)?.at(CALL));
+        }
+
+        let Some(class) = self.classes.last().cloned() else {
+            return Self::unsupported(node.location(), "`super` outside of a class");
+        };
+        if let Some(frame) = self.frames.iter_mut().rev().find(|frame| frame.method) {
+            frame.uses_self = true;
+        }
+        Ok(format!("$.parent(self, {})", class))
+    }
+
+    /// Read a property. The runtime verifies it is defined (the methods read from `super` are verified by
+    /// `$.parent`).
+    fn read(&mut self, property: &ReadProperty) -> LangResult<Code> {
+        let read = property.read.location();
+        if self.is_super(&property.from) {
+            let from = self.object(&property.from)?;
+            return match property.read.kind() {
+                Property::Key => Ok(Code::new(
+                    format!("{}{}", from, self::property(read.code())),
+                    CALL,
+                )),
+                _ => Self::unsupported(read, "the index of `super`"),
+            };
+        }
+
+        let from = self.node(&property.from)?.at(ASSIGNMENT);
+        let key = match property.read.kind() {
+            Property::Key => string(read.code()),
+            Property::Index => number(NumberRepresentation::from(read).into()).text,
+            Property::Expression(key) => self.node(key)?.at(ASSIGNMENT),
+        };
+        Ok(Code::new(format!("$.at({}, {})", from, key), CALL))
+    }
+
+    /// Call a function. The runtime verifies the value is callable and the number of arguments.
+    fn call(&mut self, call: &Call) -> LangResult<Code> {
+        // Calling `super` calls the constructor of the parents
+        let callable = match self.is_super(&call.callable) {
+            true => format!("{}.$construct", self.object(&call.callable)?),
+            false => self.node(&call.callable)?.at(ASSIGNMENT),
+        };
+        let arguments = match call.arguments.is_empty() {
+            true => String::new(),
+            false => format!(", {}", self.arguments(&call.arguments)?),
+        };
+
+        Ok(Code::new(
+            format!("$.call({}{})", callable, arguments),
+            CALL,
+        ))
+    }
+
+    /// Generate an operation implemented by the runtime library
+    fn helper(
+        &mut self,
+        name: &str,
+        (left, right): &(Box<Node<Expressions>>, Box<Node<Expressions>>),
+    ) -> LangResult<Code> {
+        let left = self.node(left)?.at(ASSIGNMENT);
+        let right = self.node(right)?.at(ASSIGNMENT);
+        Ok(Code::new(format!("$.{}({}, {})", name, left, right), CALL))
+    }
+
+    fn operation(&mut self, operation: &Operations) -> LangResult<Code> {
+        Ok(match operation {
+            Operations::Numeric(numeric) => {
+                let name = match numeric.operator.kind() {
+                    Operator::Add => "add",
+                    Operator::Divide => "divide",
+                    Operator::Modulo => "modulo",
+                    Operator::EuclidianDivision => "quotient",
+                    Operator::Substract => "subtract",
+                    Operator::Multiply => "multiply",
+                    Operator::Power => "power",
+                };
+                self.helper(name, &numeric.operands)?
+            }
+            Operations::Binary(binary) => {
+                let (operator, precedence) = match binary.operator.kind() {
+                    BinaryOperator::And => ("&&", AND),
+                    BinaryOperator::Or => ("||", OR),
+                    BinaryOperator::Xor => ("!==", EQUALITY),
+                };
+                let (left, right) = &binary.operands;
+                let operands = (self.test(left)?, self.test(right)?);
+                infix(operator, precedence, operands)
+            }
+            Operations::Comparative(comparison) => {
+                let name = match comparison.operator.kind() {
+                    Comparison::Equal => "equal",
+                    Comparison::Less(true) => "less",
+                    Comparison::Less(false) => "lessEqual",
+                    Comparison::Greater(true) => "greater",
+                    Comparison::Greater(false) => "greaterEqual",
+                };
+                self.helper(name, &comparison.operands)?
+            }
+        })
+    }
+
+    fn modifiers(&mut self, modifiers: &[Node<Modifier>]) -> LangResult<Vec<String>> {
+        let mut codes = vec![];
+        for modifier in modifiers {
+            codes.push(match modifier.kind() {
+                Modifier::DefinedElement => self
+                    .literal(&ParsedLiterals::Word, modifier.location())?
+                    .at(ASSIGNMENT),
+                // The modifier is the function returned by the call
+                Modifier::CallReturn(call) => self.call(call)?.text,
+            });
+        }
+
+        Ok(codes)
+    }
+
+    /// Wrap the code of a function or a class with its modifiers
+    fn modify(code: Code, modifiers: Vec<String>) -> Code {
+        match modifiers.is_empty() {
+            true => code,
+            false => Code::new(
+                format!("$.modify({}, {})", code.text, modifiers.join(", ")),
+                CALL,
+            ),
+        }
+    }
+
+    fn definable(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+    ) -> LangResult<Code> {
+        let modifiers = self.modifiers(modifiers)?;
+
+        match definable {
+            Definables::Variable(variable) => {
+                let value = self.value(variable, modifiers)?;
+                let emplacement = variable.emplacement.location();
+
+                Ok(match variable.emplacement.kind() {
+                    VariableEmplacements::Scope => self.bind(emplacement, value),
+                    VariableEmplacements::Property(property) => {
+                        let target = self.object(&property.from)?;
+                        let read = property.read.location();
+                        match property.read.kind() {
+                            Property::Key => Code::new(
+                                format!("{}{} = {}", target, self::property(read.code()), value),
+                                ASSIGNMENT,
+                            ),
+                            Property::Index => {
+                                let index = NumberRepresentation::from(read).into();
+                                Code::new(
+                                    format!("$.put({}, {}, {})", target, number(index).text, value),
+                                    CALL,
+                                )
+                            }
+                            Property::Expression(key) => {
+                                let key = self.node(key)?.at(ASSIGNMENT);
+                                Code::new(format!("$.put({}, {}, {})", target, key, value), CALL)
+                            }
+                        }
+                    }
+                    VariableEmplacements::Any(_) => {
+                        return Self::unsupported(emplacement, "the assignment of an expression");
+                    }
+                })
+            }
+            Definables::Function(function) => {
+                let code = Code::new(self.function(function)?, ASSIGNMENT);
+                let code = Self::modify(code, modifiers);
+
+                Ok(match &function.name {
+                    Some(name) => self.bind(name.location(), code.text),
+                    None => code,
+                })
+            }
+            Definables::Class(class) => {
+                let (code, statics) = self.class(class)?;
+                let code = match statics {
+                    Some(statics) => {
+                        Code::new(format!("Object.assign({}, {})", code, statics), CALL)
+                    }
+                    None => Code::new(code, ASSIGNMENT),
+                };
+                let code = Self::modify(code, modifiers);
+
+                Ok(self.bind(class.name.location(), code.text))
+            }
+        }
+    }
+
+    /// Generate the value of a variable, with its modifiers applied
+    fn value(&mut self, variable: &DefineVariable, modifiers: Vec<String>) -> LangResult<String> {
+        let value = self.node(&variable.value)?.at(ASSIGNMENT);
+        if modifiers.is_empty() {
+            return Ok(value);
+        }
+
+        Ok(format!(
+            "$.modifyVariable({}, {}, {}, {})",
+            string(variable.emplacement.location().code()),
+            variable.readonly,
+            value,
+            modifiers.join(", ")
+        ))
+    }
+
+    /// Generate the body of a function, in its own frame
+    fn body(&mut self, function: &DefineFunction, method: bool) -> LangResult<Vec<String>> {
+        self.frames.push(Frame {
+            method,
+            ..Default::default()
+        });
+        self.enter(ScopeKind::Function, &function.scope_target);
+        let body = self.block(&function.execution);
+        let (_, body) = self.leave(body?);
+        let frame = self.frames.pop().unwrap();
+
+        let mut lines = vec![];
+        if frame.uses_self {
+            lines.push(String::from("const self = this;"));
+        }
+        if !frame.variables.is_empty() {
+            lines.push(format!("let {};", frame.variables.join(", ")));
+        }
+        lines.extend(body);
+
+        Ok(lines)
+    }
+
+    fn parameters(function: &DefineFunction) -> String {
+        let parameters: Vec<String> = function
+            .arguments
+            .iter()
+            .map(|argument| identifier(argument.kind().name.location().code()))
+            .collect();
+
+        parameters.join(", ")
+    }
+
+    fn function(&mut self, function: &DefineFunction) -> LangResult<String> {
+        let head = match &function.name {
+            Some(name) => format!(
+                "function {}({})",
+                identifier(name.location().code()),
+                Self::parameters(function)
+            ),
+            None => format!("function ({})", Self::parameters(function)),
+        };
+
+        let body = self.body(function, false)?;
+        Ok(Self::braces(head, body))
+    }
+
+    /// Generate a method of a class (static methods do not have `self`)
+    fn method(&mut self, function: &DefineFunction, is_static: bool) -> LangResult<String> {
+        let name = function
+            .name
+            .as_ref()
+            .map_or("", |name| name.location().code());
+        let head = format!("{}({})", key(name), Self::parameters(function));
+
+        let body = self.body(function, !is_static)?;
+        Ok(match is_static {
+            true => format!("static {}", Self::braces(head, body)),
+            false => Self::braces(head, body),
+        })
+    }
+
+    /// Surround the body with braces
+    fn braces(head: String, body: Vec<String>) -> String {
+        match body.is_empty() {
+            true => format!("{} {{}}", head),
+            false => {
+                let mut lines = vec![format!("{} {{", head)];
+                lines.extend(indent(body));
+                lines.push(String::from("}"));
+                lines.join("\n")
+            }
+        }
+    }
+
+    /// Generate a class. Returns the class, and its static members (the members of its `Object.assign`)
+    fn class(&mut self, class: &DefineClass) -> LangResult<(String, Option<String>)> {
+        let name = identifier(class.name.location().code());
+        let base = match class.parents.is_empty() {
+            true => String::from("$.Instance"),
+            false => {
+                let parents: Vec<String> = class
+                    .parents
+                    .iter()
+                    .map(|parent| identifier(parent.location().code()))
+                    .collect();
+                format!("$.inherit({})", parents.join(", "))
+            }
+        };
+
+        let mut members = vec![];
+        let mut statics = vec![];
+        self.classes.push(name.clone());
+
+        let mut attributes = vec![];
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            let modifiers = self.modifiers(&item.modifie_by)?;
+            let value = self.value(&item.item, modifiers)?;
+            let name = item.item.emplacement.location().code();
+
+            match item.is_static {
+                true => statics.push(format!("{}: {}", key(name), value)),
+                false => attributes.push(format!("$instance{} = {};", property(name), value)),
+            }
+        }
+        if !attributes.is_empty() {
+            members.push(Self::braces(
+                String::from("static $attributes($instance)"),
+                attributes,
+            ));
+        }
+
+        if let Some(constructor) = &class.constructor {
+            let body = self.body(constructor.kind(), true)?;
+            let head = format!("$construct({})", Self::parameters(constructor.kind()));
+            members.push(Self::braces(head, body));
+        }
+
+        let mut modified = vec![];
+        for method in &class.methods {
+            let item = method.kind();
+            let name = item
+                .item
+                .name
+                .as_ref()
+                .map_or("", |name| name.location().code());
+            let modifiers = self.modifiers(&item.modifie_by)?;
+
+            match (item.is_static, modifiers.is_empty()) {
+                (is_static, true) => members.push(self.method(&item.item, is_static)?),
+                // Methods are modified each time they are bound to an instance
+                (false, false) => {
+                    members.push(self.method(&item.item, false)?);
+                    modified.push(format!("{}: [{}]", key(name), modifiers.join(", ")));
+                }
+                // Static methods are read from the class : they are modified once
+                (true, false) => {
+                    let function = Code::new(self.function(&item.item)?, ASSIGNMENT);
+                    statics.push(format!(
+                        "{}: {}",
+                        key(name),
+                        Self::modify(function, modifiers).text
+                    ));
+                }
+            }
+        }
+        if !modified.is_empty() {
+            statics.push(format!("$modifiers: {{ {} }}", modified.join(", ")));
+        }
+        self.classes.pop();
+
+        let code = match members.is_empty() {
+            true => format!("class {} extends {} {{}}", name, base),
+            false => {
+                let mut lines = vec![format!("class {} extends {} {{", name, base)];
+                lines.extend(indent(members.into_iter().flat_map(self::lines).collect()));
+                lines.push(String::from("}"));
+                lines.join("\n")
+            }
+        };
+        let statics = (!statics.is_empty()).then(|| format!("{{ {} }}", statics.join(", ")));
+
+        Ok((code, statics))
+    }
+}
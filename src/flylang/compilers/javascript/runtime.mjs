@@ -0,0 +1,510 @@
+// The runtime library of the JavaScript code generated from flylang.
+// It implements the parts of the flylang semantics JavaScript does not share (truthiness, equality, display, ...).
+
+/** An error raised by the flylang semantics, with the code of the error the interpreter raises (if any) */
+export class FlyError extends Error {
+  constructor(message, code) {
+    super(message);
+    this.name = code === undefined ? "FlyError" : `FlyError[${code}]`;
+    this.code = code;
+  }
+}
+
+/** A breaker going out of the function it is written in, to the scope labelled `scope` */
+export class Breaker {
+  constructor(scope, kind, value) {
+    this.scope = scope;
+    this.kind = kind;
+    this.value = value;
+  }
+}
+
+/** Returns if the error is a breaker stopping at the scope labelled `scope` */
+export function caught(error, scope) {
+  return error instanceof Breaker && error.scope === scope;
+}
+
+// The element modified by each modified element
+const targets = new WeakMap();
+
+/** Get an element without its modifiers */
+export function unmodified(value) {
+  while (targets.has(value)) {
+    value = targets.get(value);
+  }
+  return value;
+}
+
+/**
+ * Wrap a function or a class with modifiers : calling it (or instanciating it) calls the modifier with the
+ * element and the given arguments. The first modifier is the most inner one.
+ */
+export function modify(target, ...modifiers) {
+  return modifiers.reduce((target, modifier) => {
+    const modified = new Proxy(target, {
+      apply: (_, __, args) => modifier(target, args),
+      construct: (_, args) => modifier(target, args),
+    });
+    targets.set(modified, target);
+    return modified;
+  }, target);
+}
+
+/** Apply the modifiers of a variable on its value */
+export function modifyVariable(name, constant, value, ...modifiers) {
+  return modifiers.reduce((value, modifier) => modifier(name, [constant, value]), value);
+}
+
+/** Compute the ancestors of a class from its parents (C3 linearization), or null if no order exists */
+function linearize(parents) {
+  const sequences = [...parents.map(mro), [...parents]];
+  const ancestors = [];
+
+  for (;;) {
+    const remaining = sequences.filter((sequence) => sequence.length > 0);
+    if (remaining.length === 0) {
+      return ancestors;
+    }
+
+    const head = remaining
+      .map((sequence) => sequence[0])
+      .find((head) => !remaining.some((sequence) => sequence.indexOf(head) > 0));
+    if (head === undefined) {
+      return null;
+    }
+
+    for (const sequence of remaining) {
+      if (sequence[0] === head) {
+        sequence.shift();
+      }
+    }
+    ancestors.push(head);
+  }
+}
+
+/** The method resolution order of a class : the class itself, followed by its ancestors */
+export function mro(cls) {
+  cls = unmodified(cls);
+  if (cls === Instance) {
+    return [];
+  }
+
+  const base = Object.getPrototypeOf(cls);
+  const parents = Object.prototype.hasOwnProperty.call(base, "$parents") ? base.$parents : [];
+  return [cls, ...linearize(parents)];
+}
+
+const own = (object, key) => Object.prototype.hasOwnProperty.call(object, key);
+
+/** The base of every flylang class */
+export class Instance {
+  constructor(...args) {
+    const classes = mro(this.constructor);
+
+    // Methods read from an instance stay bound to it
+    for (let prototype = Object.getPrototypeOf(this); prototype !== Instance.prototype; prototype = Object.getPrototypeOf(prototype)) {
+      for (const name of Object.getOwnPropertyNames(prototype)) {
+        if (name === "constructor" || name.startsWith("$") || own(this, name)) {
+          continue;
+        }
+
+        const owner = prototype.constructor;
+        const modifiers = own(owner, "$modifiers") ? owner.$modifiers[name] ?? [] : [];
+        this[name] = modify(prototype[name].bind(this), ...modifiers);
+      }
+    }
+
+    // Attributes are initialised from the farthest ancestor to the class itself, then the constructor is called
+    for (const cls of [...classes].reverse()) {
+      if (own(cls, "$attributes")) {
+        cls.$attributes(this);
+      }
+    }
+    this.$construct(...args);
+  }
+
+  $construct(...args) {
+    if (args.length > 0) {
+      throw tooMany(0, args.length);
+    }
+  }
+}
+
+/**
+ * Get the methods of the classes following `cls` in the method resolution order of the instance (`super`),
+ * bound to the instance. Its `$construct` method calls their constructor.
+ */
+export function parent(instance, cls) {
+  const classes = mro(instance.constructor);
+  const next = [...classes.slice(classes.indexOf(cls) + 1), Instance];
+
+  return new Proxy({}, {
+    get: (_, name) => {
+      const owner = next.find((cls) => own(cls.prototype, name));
+      if (owner === undefined) {
+        throw undefinedProperty(name);
+      }
+      return owner.prototype[name].bind(instance);
+    },
+  });
+}
+
+/** Create the base of a class inheriting from the given parents */
+export function inherit(...parents) {
+  parents = parents.map(unmodified);
+  const ancestors = linearize(parents);
+  if (ancestors === null) {
+    throw new FlyError(`The parents ${parents.map((parent) => parent.name).join(", ")} cannot be ordered.`, "E0032");
+  }
+
+  let base = parents.length === 1 ? parents[0] : Instance;
+  if (parents.length > 1) {
+    // The prototype chain follows the method resolution order
+    for (const ancestor of [...ancestors].reverse()) {
+      base = class extends base {};
+      for (const [target, source] of [[base.prototype, ancestor.prototype], [base, ancestor]]) {
+        for (const name of Object.getOwnPropertyNames(source)) {
+          if (!["constructor", "prototype", "name", "length", "$attributes", "$modifiers"].includes(name)) {
+            Object.defineProperty(target, name, Object.getOwnPropertyDescriptor(source, name));
+          }
+        }
+      }
+    }
+  }
+
+  const inherited = class extends base {};
+  inherited.$parents = parents;
+  return inherited;
+}
+
+function isStructure(value) {
+  return value !== null && typeof value === "object" && !Array.isArray(value) && !(value instanceof Instance);
+}
+
+function isClass(value) {
+  value = unmodified(value);
+  return typeof value === "function" && (value === Instance || value.prototype instanceof Instance);
+}
+
+/** The name of the value's type, as displayed to the user */
+export function typeOf(value) {
+  if (value === null || value === undefined) {
+    return "empty";
+  }
+  if (Array.isArray(value)) {
+    return "array";
+  }
+  if (value instanceof Instance) {
+    return "instance";
+  }
+  if (typeof value === "function") {
+    return isClass(value) ? "class" : "function";
+  }
+  return typeof value === "object" ? "structure" : typeof value;
+}
+
+/** Returns if the value is considered as `true` in a condition */
+export function truthy(value) {
+  if (value === null || value === undefined) {
+    return false;
+  }
+  if (typeof value === "number") {
+    return value !== 0 && !Number.isNaN(value);
+  }
+  if (typeof value === "string" || Array.isArray(value)) {
+    return value.length > 0;
+  }
+  if (isStructure(value)) {
+    return Object.keys(value).length > 0;
+  }
+  return value !== false;
+}
+
+/** Compare two values : primitives, arrays and structures by their content, the others by reference */
+export function equal(a, b) {
+  a = unmodified(a);
+  b = unmodified(b);
+  if (a === b || (a == null && b == null)) {
+    return true;
+  }
+  if (Array.isArray(a) && Array.isArray(b)) {
+    return a.length === b.length && a.every((item, index) => equal(item, b[index]));
+  }
+  if (isStructure(a) && isStructure(b)) {
+    const keys = Object.keys(a);
+    return keys.length === Object.keys(b).length && keys.every((key) => own(b, key) && equal(a[key], b[key]));
+  }
+  return false;
+}
+
+/** Convert a value to a string, the way `std.out` displays it */
+export function display(value) {
+  value = unmodified(value);
+  if (value === null || value === undefined) {
+    return "()";
+  }
+  if (typeof value === "number") {
+    return Number.isFinite(value) || Number.isNaN(value) ? String(value) : value > 0 ? "inf" : "-inf";
+  }
+  if (Array.isArray(value)) {
+    return `{${value.map(repr).join(", ")}}`;
+  }
+  if (value instanceof Instance) {
+    return `<${value.constructor.name} instance>`;
+  }
+  if (typeof value === "function") {
+    return isClass(value) ? `<cs ${value.name}>` : `<fn ${value.name.replace(/^bound /, "") || "anonymous"}>`;
+  }
+  if (isStructure(value)) {
+    const entries = Object.entries(value);
+    if (entries.length === 0) {
+      return "{:}";
+    }
+
+    const key = (key) => (/^[^0-9]\w*$/u.test(key) ? key : repr(key));
+    return `{${entries.map(([k, v]) => `${key(k)}: ${repr(v)}`).join(", ")}}`;
+  }
+  return String(value);
+}
+
+/** Same as `display`, but strings are quoted (used inside arrays and structures) */
+function repr(value) {
+  return typeof value === "string" ? JSON.stringify(value) : display(value);
+}
+
+function invalid(operator, ...operands) {
+  return new FlyError(
+    `The operation ${operator} cannot be applied to ${operands.map(typeOf).join(" and ")}.`,
+    "E0019",
+  );
+}
+
+function tooMany(expected, found) {
+  return new FlyError(`Too many arguments : expected at most ${expected}, found ${found}.`, "E0021");
+}
+
+function undefinedProperty(key) {
+  return new FlyError(`The property ${repr(key)} is not defined.`, "E0025");
+}
+
+/** The `+` operator : adds numbers, or concatenates strings */
+export function add(a, b) {
+  if (typeof a === "number" && typeof b === "number") {
+    return a + b;
+  }
+  if (typeof a === "string" || typeof b === "string") {
+    return display(a) + display(b);
+  }
+  throw invalid("+", a, b);
+}
+
+function numbers(operator, a, b) {
+  if (typeof a !== "number" || typeof b !== "number") {
+    throw invalid(operator, a, b);
+  }
+}
+
+/** The `-` operator */
+export function subtract(a, b) {
+  numbers("-", a, b);
+  return a - b;
+}
+
+/** The `*` operator */
+export function multiply(a, b) {
+  numbers("*", a, b);
+  return a * b;
+}
+
+/** The `**` operator */
+export function power(a, b) {
+  numbers("**", a, b);
+  return a ** b;
+}
+
+/** The `-` sign */
+export function negate(value) {
+  if (typeof value !== "number") {
+    throw invalid("-", value);
+  }
+  return -value;
+}
+
+/** Compare two numbers or two strings : returns a negative number, zero or a positive number */
+function compare(operator, a, b) {
+  const ordered = typeof a === typeof b && (typeof a === "string" || (typeof a === "number" && a === a && b === b));
+  if (!ordered) {
+    throw invalid(operator, a, b);
+  }
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+/** The `<` operator */
+export function less(a, b) {
+  return compare("<", a, b) < 0;
+}
+
+/** The `<=` operator */
+export function lessEqual(a, b) {
+  return compare("<=", a, b) <= 0;
+}
+
+/** The `>` operator */
+export function greater(a, b) {
+  return compare(">", a, b) > 0;
+}
+
+/** The `>=` operator */
+export function greaterEqual(a, b) {
+  return compare(">=", a, b) >= 0;
+}
+
+function divisor(operator, a, b) {
+  if (typeof a !== "number" || typeof b !== "number") {
+    throw invalid(operator, a, b);
+  }
+  if (b === 0) {
+    throw new FlyError("Division by zero.", "E0020");
+  }
+  return b;
+}
+
+/** The `/` operator */
+export function divide(a, b) {
+  return a / divisor("/", a, b);
+}
+
+/** The `%` operator */
+export function modulo(a, b) {
+  return a % divisor("%", a, b);
+}
+
+/** The `//` operator (euclidian division) */
+export function quotient(a, b) {
+  const q = Math.trunc(a / divisor("//", a, b));
+  return a % b < 0 ? (b > 0 ? q - 1 : q + 1) : q;
+}
+
+function position(key, length) {
+  if (typeof key !== "number") {
+    throw new FlyError(`Expected a number as index, but found ${typeOf(key)}.`, "E0028");
+  }
+
+  const position = key < 0 ? length + key : key;
+  if (!Number.isInteger(position) || position < 0 || position >= length) {
+    throw new FlyError(`The index ${key} is out of range (length ${length}).`, "E0027");
+  }
+  return position;
+}
+
+/** Read a property. Negative indexes start from the end of arrays and strings. */
+export function at(from, key) {
+  from = unmodified(from);
+  if (Array.isArray(from)) {
+    return from[position(key, from.length)];
+  }
+  if (typeof from === "string") {
+    const characters = Array.from(from);
+    return characters[position(key, characters.length)];
+  }
+  if (!isStructure(from) && !(from instanceof Instance) && !isClass(from)) {
+    throw new FlyError(`Cannot read the property ${repr(key)} of ${typeOf(from)}.`, "E0026");
+  }
+
+  // The static members of a class are the ones of its ancestors too
+  const owners = isClass(from) ? mro(from) : [from];
+  if (!owners.some((owner) => own(owner, key))) {
+    throw undefinedProperty(key);
+  }
+  return from[key];
+}
+
+/** Write a property. Writing right after the last item of an array appends it. */
+export function put(target, key, value) {
+  target = unmodified(target);
+  if (Array.isArray(target)) {
+    target[key === target.length ? key : position(key, target.length)] = value;
+  } else if (target !== null && (typeof target === "object" || typeof target === "function")) {
+    target[key] = value;
+  } else {
+    throw new FlyError(`Cannot write the property ${repr(key)} of ${typeOf(target)}.`, "E0026");
+  }
+  return value;
+}
+
+/**
+ * Get the [index, item] couples an `each` loop goes through : the items of arrays and strings with their
+ * position, the values of structures with their key, and the numbers from 0 to `n` (excluded).
+ */
+export function entries(iterable) {
+  iterable = unmodified(iterable);
+  if (Array.isArray(iterable) || typeof iterable === "string") {
+    return Array.from(iterable).map((item, index) => [index, item]);
+  }
+  if (typeof iterable === "number") {
+    const numbers = [];
+    for (let n = 0; n < iterable; n++) {
+      numbers.push([n, n]);
+    }
+    return numbers;
+  }
+  if (isStructure(iterable)) {
+    return Object.entries(iterable);
+  }
+  throw new FlyError(`Cannot go through ${typeOf(iterable)}.`, "E0028");
+}
+
+/** Get the number of items in an array, a structure or a string */
+function len(value) {
+  if (Array.isArray(value)) {
+    return value.length;
+  }
+  if (typeof value === "string") {
+    return Array.from(value).length;
+  }
+  if (isStructure(value)) {
+    return Object.keys(value).length;
+  }
+  throw new FlyError(`Expected array, structure or string, but found ${typeOf(value)}.`, "E0028");
+}
+
+/** Call a function, after verifying it is not given more arguments than it accepts */
+export function call(callable, ...args) {
+  if (typeof callable !== "function" || isClass(callable)) {
+    throw new FlyError(`The value is not callable (found a value of type ${typeOf(callable)}).`, "E0018");
+  }
+  // Modifiers receive the arguments as an array, and the functions of the runtime accept any number of them
+  if (!targets.has(callable) && !natives.has(callable) && args.length > callable.length) {
+    throw tooMany(callable.length, args.length);
+  }
+  return callable(...args);
+}
+
+/**
+ * Raise the error of the assignment of a constant (or of a receiver or a built-in element). The assigned value
+ * is computed before, like the interpreter does.
+ */
+export function reassign(name, _value) {
+  throw new FlyError(`${JSON.stringify(name)} is a constant and cannot be reassigned.`, "E0029");
+}
+
+/** Raise the error of the reading of a variable defined nowhere */
+export function undefinedVariable(name) {
+  throw new FlyError(`${JSON.stringify(name)} is not defined.`, "E0017");
+}
+
+export const std = {
+  out: (...values) => console.log(values.map(display).join(" ")),
+  err: (...values) => console.error(values.map(display).join(" ")),
+  len,
+  type: typeOf,
+};
+
+/** Raise an error if one of the given values is not truthy */
+export function assert(...values) {
+  if (!values.every(truthy)) {
+    throw new FlyError("Assertion failed.", "E0023");
+  }
+}
+
+const natives = new WeakSet([...Object.values(std), assert]);
@@ -0,0 +1,1439 @@
+//! Translation of flylang to Lua 5.4.
+//!
+//! The generated module loads its runtime library (`flylang.lua`, written next to it), which implements the parts
+//! of the flylang semantics Lua does not share : truthiness, equality, display, modifiers, ...
+//!
+//! - Arrays and structures are tables handled by the runtime library : the items of arrays are at the Lua index
+//!   following their flylang index, and structures keep the order of their keys.
+//! - Classes are the metatables of their instances, and read the members they do not define from their parents
+//!   through `__index` chains (following the method resolution order of flylang).
+//! - The variables of a function are Lua locals, declared at its start. Lua statements cannot be written inside
+//!   expressions : the assignments written in an expression are done by a function called in place.
+//! - Breakers leave the scopes of their function with `break`, `return` and `goto`. Breakers going out of their
+//!   function raise an error, caught (with `pcall`) around the body of the scope they target : the breakers
+//!   leaving a caught body return what they do, which is done after the `pcall`.
+//!
+//! The code is generated twice : the first generation finds the scopes breakers reach from another function,
+//! whose body the second one catches the errors around.
+
+use std::{
+    collections::HashSet,
+    mem,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+use crate::flylang::{
+    analyser::{
+        Analyser,
+        symbols::{SymbolKind, SymbolTable},
+    },
+    compilers::{
+        Compiler,
+        errors::{CompilationFailed, UnresolvedBreaker, Unsupported},
+    },
+    errors::{LangResult, lang_err},
+    lexer::tokens::{
+        BinaryOperator, Comparison, Operator, ScopeTarget,
+        representations::number::NumberRepresentation,
+    },
+    module::{LangModule, slice::LangModuleSlice},
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::DefineClass,
+            functions::DefineFunction,
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::{Loop, LoopParameter},
+            r#use::{Package, PackageContentEmplacement, PackageIncludedContent, PackageSource},
+        },
+    },
+    utils::breakers::{self, BreakableScope, ScopeKind},
+};
+
+/// The runtime library loaded by the generated modules
+pub const RUNTIME: &str = include_str!("runtime.lua");
+const RUNTIME_MODULE: &str = "flylang";
+
+const NAME: &str = "lua";
+
+/// The keywords of Lua
+const KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// The names used by the generated code, that variables cannot take
+const RESERVED: &[&str] = &["self"];
+
+/// The precedence of the Lua operators, used to know where parentheses are needed
+mod precedence {
+    pub const OR: u8 = 1;
+    pub const AND: u8 = 2;
+    pub const COMPARISON: u8 = 3;
+    pub const CONCATENATION: u8 = 4;
+    pub const UNARY: u8 = 7;
+    /// Literals, which cannot be called or indexed without parentheses
+    pub const VALUE: u8 = 9;
+    /// Names, parenthesized expressions and calls giving a single value
+    pub const PREFIX: u8 = 10;
+    /// Calls of flylang values. They can give no value, and are the only expressions that are statements.
+    pub const CALL: u8 = 11;
+}
+use precedence::*;
+
+/// The Lua compiler, generating code executed by Lua 5.4
+pub struct Lua;
+
+impl Compiler for Lua {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+    fn description(&self) -> &'static str {
+        "Translate the code to Lua 5.4, executed with lua"
+    }
+    fn extension(&self) -> &'static str {
+        "lua"
+    }
+
+    fn compile(&self, _: &LangModule, branches: &Branches) -> LangResult<String> {
+        let analysis = Analyser::new().analyse(branches);
+        let (_, layout) = Generator::new(&analysis.symbols, Layout::default()).module(branches)?;
+        let (code, _) = Generator::new(&analysis.symbols, layout).module(branches)?;
+        Ok(code)
+    }
+
+    fn runtime(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("flylang.lua", RUNTIME)]
+    }
+
+    /// Execute the generated file with the Lua interpreter of the `LUA` environment variable (`lua` by default)
+    fn run(&self, file: &Path) -> LangResult<ExitStatus> {
+        let interpreter = std::env::var("LUA").unwrap_or_else(|_| String::from("lua"));
+        match Command::new(&interpreter).arg(file).status() {
+            Ok(status) => Ok(status),
+            Err(error) => lang_err!(CompilationFailed {
+                compiler: NAME,
+                reason: format!("{} cannot be started ({})", interpreter, error),
+            }),
+        }
+    }
+}
+
+/// A Lua expression
+struct Code {
+    text: String,
+    precedence: u8,
+}
+impl Code {
+    fn new(text: impl Into<String>, precedence: u8) -> Self {
+        Self {
+            text: text.into(),
+            precedence,
+        }
+    }
+
+    /// Get the expression, surrounded by parentheses if its precedence is lower than `minimum`
+    fn at(self, minimum: u8) -> String {
+        match self.precedence < minimum {
+            true => format!("({})", self.text),
+            false => self.text,
+        }
+    }
+}
+
+/// What the first generation finds, and the second one uses
+#[derive(Default)]
+struct Layout {
+    /// The scopes (numbered in the order they are entered) that breakers leave their function to reach
+    raised: HashSet<usize>,
+}
+
+/// A Lua function being generated
+#[derive(Default)]
+struct Frame {
+    /// The variables declared by the function, exported by the module
+    declared: Vec<String>,
+    /// The locals declared at the start of the function
+    variables: Vec<String>,
+    arguments: Vec<String>,
+}
+
+/// A flylang scope containing the code being generated, that breakers can target
+struct Scope {
+    kind: ScopeKind,
+    name: Option<String>,
+    /// The label breakers going out of their function look for
+    label: String,
+    /// The number of the scope, naming its Lua labels
+    id: usize,
+    /// The position of the function containing the scope, in the frames
+    frame: usize,
+    /// If a breaker reaches this scope from another function : the errors are caught around its body
+    raised: bool,
+    /// If the code being generated is in the caught body of the scope
+    caught: bool,
+    /// The breakers leaving the caught body, as (position of the scope they target, kind, if they give a value).
+    /// The body returns the number of the breaker (starting at 1) and its value.
+    exits: Vec<(usize, &'static str, bool)>,
+    /// If a breaker jumps after the scope (`fly_stop_<id>` or `fly_end_<id>`)
+    left: bool,
+    /// If a breaker jumps to the next iteration of the loop (`fly_pass_<id>`)
+    passed: bool,
+}
+impl BreakableScope for Scope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+struct Generator<'a> {
+    symbols: &'a SymbolTable,
+    /// The layout found by a previous generation
+    known: Layout,
+    layout: Layout,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    /// The parameters receiving the classes being generated, read by `super`
+    classes: Vec<String>,
+    /// Number of generated names, to make them unique
+    names: usize,
+    /// Number of scopes entered
+    entered: usize,
+}
+
+/// Convert a flylang name to a valid Lua identifier.
+/// The names Lua or the generated code reserve, and the names ending with `_`, are followed by `_`.
+fn identifier(name: &str) -> String {
+    let mut escaped: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c.to_string(),
+            false => format!("_u{:x}", c as u32),
+        })
+        .collect();
+
+    if KEYWORDS.contains(&escaped.as_str())
+        || RESERVED.contains(&escaped.as_str())
+        || escaped == "fly"
+        || escaped.starts_with("fly_")
+        || escaped.ends_with('_')
+    {
+        escaped.push('_');
+    }
+    escaped
+}
+
+fn string(content: &str) -> String {
+    let mut text = String::from("\"");
+    for c in content.chars() {
+        match c {
+            '"' => text.push_str("\\\""),
+            '\\' => text.push_str("\\\\"),
+            '\n' => text.push_str("\\n"),
+            '\r' => text.push_str("\\r"),
+            '\t' => text.push_str("\\t"),
+            c if c.is_control() => text.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => text.push(c),
+        }
+    }
+    text.push('"');
+    text
+}
+
+/// Numbers are floats, like in flylang (Lua integers overflow)
+fn number(value: f64) -> Code {
+    let text = if value.is_nan() {
+        String::from("fly.nan")
+    } else if value.is_infinite() {
+        String::from(if value > 0f64 {
+            "fly.infinity"
+        } else {
+            "-fly.infinity"
+        })
+    } else {
+        format!("{:?}", value)
+    };
+
+    let precedence = match (text.starts_with('-'), text.starts_with("fly")) {
+        (true, _) => UNARY,
+        (_, true) => PREFIX,
+        _ => VALUE,
+    };
+    Code::new(text, precedence)
+}
+
+/// An index of an array, written as an integer
+fn index(value: f64) -> String {
+    match value.fract() == 0f64 && value.abs() < 1e15 {
+        true => format!("{}", value as i64),
+        false => number(value).text,
+    }
+}
+
+/// Generate an infix operation. Operators are left-associative, except `..`, and comparisons are not chained.
+fn infix(operator: &str, precedence: u8, (left, right): (Code, Code)) -> Code {
+    let (left, right) = match precedence {
+        CONCATENATION => (left.at(precedence + 1), right.at(precedence)),
+        COMPARISON => (left.at(precedence + 1), right.at(precedence + 1)),
+        _ => (left.at(precedence), right.at(precedence + 1)),
+    };
+    Code::new(format!("{} {} {}", left, operator, right), precedence)
+}
+
+/// Join the arguments of a call. A call giving no value would give no argument : the last one is a single value.
+fn list(codes: Vec<Code>) -> String {
+    let count = codes.len();
+    codes
+        .into_iter()
+        .enumerate()
+        .map(
+            |(position, code)| match position + 1 == count && code.precedence == CALL {
+                true => format!("({})", code.text),
+                false => code.text,
+            },
+        )
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Indent the lines of a block (the lines of the functions written in an expression included)
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| format!("    {}", line.replace('\n', "\n    ")))
+        .collect()
+}
+
+/// Returns if the expression always gives a boolean
+fn is_boolean(expression: &Expressions) -> bool {
+    match expression {
+        Expressions::Literal(ParsedLiterals::True | ParsedLiterals::False) => true,
+        Expressions::Operation(Operations::Binary(_) | Operations::Comparative(_)) => true,
+        Expressions::Reverse(reverse) => matches!(reverse.kind, ReverseKind::Boolean),
+        Expressions::Prioritized(inner) => is_boolean(inner.kind()),
+        Expressions::Typed(typed) => is_boolean(typed.expression.kind()),
+        _ => false,
+    }
+}
+
+/// Returns if the expression never gives `nil` or `false`, the values Lua considers as false
+fn is_truthy(expression: &Expressions) -> bool {
+    match expression {
+        Expressions::Literal(literal) => matches!(
+            literal,
+            ParsedLiterals::True
+                | ParsedLiterals::Number
+                | ParsedLiterals::Computed(_)
+                | ParsedLiterals::String(_)
+        ),
+        Expressions::Defined(Definables::Function(_) | Definables::Class(_))
+        | Expressions::Structure(_)
+        | Expressions::Array(_)
+        | Expressions::Operation(Operations::Numeric(_)) => true,
+        Expressions::Prioritized(inner) => is_truthy(inner.kind()),
+        Expressions::Typed(typed) => is_truthy(typed.expression.kind()),
+        _ => false,
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbols: &'a SymbolTable, known: Layout) -> Self {
+        Self {
+            symbols,
+            known,
+            layout: Layout::default(),
+            frames: vec![],
+            scopes: vec![],
+            classes: vec![],
+            names: 0,
+            entered: 0,
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    /// Create a name for a generated element
+    fn generated(&mut self, kind: &str) -> String {
+        self.names += 1;
+        format!("fly_{}{}", kind, self.names)
+    }
+
+    fn unsupported<R>(location: &LangModuleSlice, feature: &'static str) -> LangResult<R> {
+        lang_err!(Unsupported {
+            location: location.clone(),
+            compiler: NAME,
+            feature,
+        })
+    }
+
+    /// Declare the locals of a function
+    fn locals(frame: &Frame) -> Vec<String> {
+        match frame.variables.is_empty() {
+            true => vec![],
+            false => vec![format!("local {}", frame.variables.join(", "))],
+        }
+    }
+
+    /// Generate a whole module, returning its code and its layout
+    fn module(mut self, branches: &Branches) -> LangResult<(String, Layout)> {
+        self.frames.push(Frame::default());
+        let body = self.block(branches)?;
+        let frame = self.frames.pop().unwrap();
+
+        // The runtime library is searched next to the module
+        let mut lines = vec![
+            String::from(r#"local fly_file = debug.getinfo(1, "S").source:match("^@(.*)$") or """#),
+            String::from(
+                r#"package.path = fly_file:match("^(.-)[^/\\]*$") .. "?.lua;" .. package.path"#,
+            ),
+            format!("local fly = require({})", string(RUNTIME_MODULE)),
+            String::from("local std, assert = fly.std, fly.assert"),
+        ];
+        lines.extend(Self::locals(&frame));
+        lines.push(String::new());
+        lines.extend(body);
+        if !frame.declared.is_empty() {
+            let entries: Vec<String> = frame
+                .declared
+                .iter()
+                .map(|name| format!("{}, {}", string(name), identifier(name)))
+                .collect();
+            lines.push(String::new());
+            lines.push(format!("return fly.structure({})", entries.join(", ")));
+        }
+
+        Ok((lines.join("\n") + "\n", self.layout))
+    }
+
+    /// Declare a local of the current function
+    fn local(&mut self, name: &str) {
+        let frame = self.frame();
+        if !frame
+            .variables
+            .iter()
+            .chain(&frame.arguments)
+            .any(|variable| variable == name)
+        {
+            frame.variables.push(name.to_string());
+        }
+    }
+
+    /// Get the name of an assigned variable. It is a local of the current function if the assignment declares
+    /// it (according to the analyser), otherwise the local of an enclosing function.
+    fn assign(&mut self, location: &LangModuleSlice) -> String {
+        let name = identifier(location.code());
+        if self.declares(location) {
+            if self.frames.len() == 1 && !self.frame().variables.contains(&name) {
+                self.frame().declared.push(location.code().to_string());
+            }
+            self.local(&name);
+        }
+        name
+    }
+
+    /// Returns if the assignment redefines a constant (or a receiver or a built-in element)
+    fn reassigns(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Constant | SymbolKind::Receiver | SymbolKind::Builtin
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_none_or(|declaration| declaration.range() != location.range())
+        })
+    }
+
+    /// Get the name of an assigned variable, or `None` if the assignment redefines a constant
+    fn target(&mut self, location: &LangModuleSlice) -> Option<String> {
+        (!self.reassigns(location)).then(|| self.assign(location))
+    }
+
+    /// Generate the error of the redefinition of a constant, raised once the value is computed
+    fn reassign(location: &LangModuleSlice, value: &str) -> Code {
+        Code::new(
+            format!("fly.reassign({}, {})", string(location.code()), value),
+            CALL,
+        )
+    }
+
+    /// Generate the statement binding a value to a loop variable
+    fn bind(&mut self, location: &LangModuleSlice, value: &str) -> String {
+        match self.target(location) {
+            Some(name) => format!("{} = {}", name, value),
+            None => Self::reassign(location, value).text,
+        }
+    }
+
+    /// Returns if the variable is declared at this location
+    fn declares(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Variable
+                    | SymbolKind::Constant
+                    | SymbolKind::Function
+                    | SymbolKind::Class
+                    | SymbolKind::Binding
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_some_and(|declaration| declaration.range() == location.range())
+        })
+    }
+
+    fn block(&mut self, branches: &Branches) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        for (position, instruction) in branches.iter().enumerate() {
+            let mut generated = self.instruction(instruction)?;
+            // `return` ends a Lua block : the instructions following it (never executed) are written after its own
+            if position + 1 < branches.len()
+                && let Some(last) = generated.last_mut()
+                && last.starts_with("return")
+            {
+                *last = format!("do {} end", last);
+            }
+            lines.extend(generated);
+        }
+
+        Ok(lines)
+    }
+
+    fn instruction(&mut self, instruction: &Node) -> LangResult<Vec<String>> {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => self.statement(expression, instruction.location()),
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => self.repeat(repetition),
+            Instructions::Break(breaker) => self.breaker(breaker),
+            Instructions::Use(package) => self.import(package, instruction.location()),
+        }
+    }
+
+    fn statement(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Vec<String>> {
+        let (assigned, code) = match expression {
+            Expressions::Defined(definable) => self.definition(definable, &[])?,
+            Expressions::Modifed(modified) => {
+                self.definition(modified.definable.kind(), &modified.modified_by)?
+            }
+            _ => (None, self.expression(expression, location)?),
+        };
+
+        Ok(vec![match assigned {
+            Some(name) => format!("{} = {}", name, code.text),
+            None if code.precedence == CALL => code.text,
+            // The other expressions are only evaluated
+            None => format!("do local _ = {} end", code.text),
+        }])
+    }
+
+    /// Enter a scope, that breakers can target
+    fn enter(&mut self, kind: ScopeKind, target: &Option<Node<ScopeTarget>>) {
+        let name = match target.as_ref().map(|target| target.kind()) {
+            Some(ScopeTarget::Named(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let id = self.entered;
+        self.entered += 1;
+        let label = match &name {
+            Some(name) => name.clone(),
+            None => match kind {
+                ScopeKind::Function => format!("fn{}", id),
+                ScopeKind::Loop => format!("loop{}", id),
+                ScopeKind::Condition => format!("if{}", id),
+            },
+        };
+
+        let raised = self.known.raised.contains(&id);
+        self.scopes.push(Scope {
+            kind,
+            name,
+            label,
+            id,
+            frame: self.frames.len() - 1,
+            raised,
+            caught: raised,
+            exits: vec![],
+            left: false,
+            passed: false,
+        });
+    }
+
+    /// Leave the current scope. When breakers reach it from another function, the errors are caught around its
+    /// body, then the breakers stopping at the scope (or leaving its body) are done.
+    fn leave(&mut self, body: Vec<String>) -> (Vec<String>, Scope) {
+        let position = self.scopes.len() - 1;
+        if !self.scopes[position].raised {
+            return (body, self.scopes.pop().unwrap());
+        }
+        self.scopes[position].caught = false;
+
+        let mut actions = match self.scopes[position].kind {
+            ScopeKind::Function => vec![(
+                String::from(r#"fly_exit == "return" or fly_exit == "pass""#),
+                String::from("return fly_value"),
+            )],
+            ScopeKind::Loop => vec![(String::from(r#"fly_exit == "stop""#), String::from("break"))],
+            ScopeKind::Condition => vec![],
+        };
+        let exits = mem::take(&mut self.scopes[position].exits);
+        for (number, (target, kind, value)) in exits.into_iter().enumerate() {
+            let value = value.then(|| String::from("fly_value"));
+            actions.push((
+                format!("fly_exit == {}", number + 1),
+                self.jump(target, kind, value),
+            ));
+        }
+
+        let scope = self.scopes.pop().unwrap();
+        let mut lines = vec![String::from(
+            "local fly_ok, fly_exit, fly_value = fly.try(function()",
+        )];
+        lines.extend(indent(body));
+        lines.push(String::from("end)"));
+        lines.push(format!(
+            "if not fly_ok then fly_exit, fly_value = fly.caught(fly_exit, {}) end",
+            string(&scope.label)
+        ));
+        for (number, (test, action)) in actions.iter().enumerate() {
+            let keyword = match number {
+                0 => "if",
+                _ => "elseif",
+            };
+            lines.push(format!("{} {} then", keyword, test));
+            lines.push(format!("    {}", action));
+        }
+        if !actions.is_empty() {
+            lines.push(String::from("end"));
+        }
+
+        // The locals of the `pcall` only live until the end of the body
+        let mut block = vec![String::from("do")];
+        block.extend(indent(lines));
+        block.push(String::from("end"));
+        (block, scope)
+    }
+
+    /// Generate the instructions of a block (of a condition or a loop)
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &Branches,
+    ) -> LangResult<(Vec<String>, Scope)> {
+        self.enter(kind, target);
+        let body = self.block(branches);
+        Ok(self.leave(body?))
+    }
+
+    fn condition(&mut self, condition: &If) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        // The scopes left with `goto fly_end_<id>`, whose labels follow the condition
+        let mut ends = vec![];
+        self.branch(condition, "if", &mut lines, &mut ends)?;
+
+        lines.push(String::from("end"));
+        for scope in ends {
+            lines.push(format!("::fly_end_{}::", scope));
+        }
+        Ok(lines)
+    }
+
+    /// Generate a condition and its fallbacks, starting with `keyword` (`if` or `elseif`)
+    fn branch(
+        &mut self,
+        condition: &If,
+        keyword: &str,
+        lines: &mut Vec<String>,
+        ends: &mut Vec<usize>,
+    ) -> LangResult<()> {
+        let test = self.test(&condition.condition)?.text;
+        let (body, scope) = self.scoped(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            &condition.process,
+        )?;
+        if scope.left {
+            ends.push(scope.id);
+        }
+        lines.push(format!("{} {} then", keyword, test));
+        lines.extend(indent(body));
+
+        match condition.fallback.as_ref().map(|fallback| fallback.kind()) {
+            None => {}
+            Some(IfFallBack::Process(target, process)) => {
+                let (body, scope) = self.scoped(ScopeKind::Condition, target, process)?;
+                if scope.left {
+                    ends.push(scope.id);
+                }
+                lines.push(String::from("else"));
+                lines.extend(indent(body));
+            }
+            Some(IfFallBack::If(other)) => self.branch(other.kind(), "elseif", lines, ends)?,
+        }
+
+        Ok(())
+    }
+
+    fn repeat(&mut self, repetition: &Loop) -> LangResult<Vec<String>> {
+        // The instructions starting each iteration
+        let mut start = vec![];
+
+        let head = match &repetition.parameter {
+            LoopParameter::Through(each) => {
+                let iterable = self.node(&each.iterable)?.text;
+                if let Some(word) = &each.index {
+                    start.push(self.bind(word.location(), "fly_index"));
+                }
+                if let Some(word) = &each.item {
+                    start.push(self.bind(word.location(), "fly_item"));
+                }
+                format!("for fly_index, fly_item in fly.entries({}) do", iterable)
+            }
+            LoopParameter::Conditionnaly(condition) => match &condition.iteration_number {
+                None => format!("while {} do", self.test(&condition.condition)?.text),
+                // The iteration number is only bound when the body is executed, like in the interpreter
+                Some(word) => {
+                    let test = self.test(&condition.condition)?;
+                    start.push(format!("if not {} then break end", test.at(UNARY)));
+                    start.push(self.bind(word.location(), "fly_iteration"));
+                    String::from("for fly_iteration = 0, fly.infinity do")
+                }
+            },
+        };
+
+        let (body, scope) = self.scoped(
+            ScopeKind::Loop,
+            &repetition.scope_target,
+            &repetition.process,
+        )?;
+        start.extend(body);
+        if scope.passed {
+            start.push(format!("::fly_pass_{}::", scope.id));
+        }
+
+        let mut lines = vec![head];
+        lines.extend(indent(start));
+        lines.push(String::from("end"));
+        if scope.left {
+            lines.push(format!("::fly_stop_{}::", scope.id));
+        }
+        Ok(lines)
+    }
+
+    fn breaker(&mut self, breaker: &Break) -> LangResult<Vec<String>> {
+        let Ok(position) = breakers::resolve(&self.scopes, breaker) else {
+            return lang_err!(UnresolvedBreaker(breaker.keyword_location.clone()));
+        };
+        let (kind, value) = match &breaker.kind {
+            BreakKind::Stop(_) => ("stop", None),
+            BreakKind::Pass(_) => ("pass", None),
+            BreakKind::Return(_, value) => (
+                "return",
+                match value {
+                    Some(value) => Some(self.node(value)?.text),
+                    None => None,
+                },
+            ),
+        };
+
+        Ok(vec![self.jump(position, kind, value)])
+    }
+
+    /// Generate a breaker going to the scope at `position` from the current code
+    fn jump(&mut self, position: usize, kind: &'static str, value: Option<String>) -> String {
+        let frame = self.frames.len() - 1;
+        if self.scopes[position].frame != frame {
+            let scope = &self.scopes[position];
+            self.layout.raised.insert(scope.id);
+            let value = value.map_or(String::new(), |value| format!(", {}", value));
+            return format!("fly.throw({}, \"{}\"{})", string(&scope.label), kind, value);
+        }
+
+        // The innermost caught body the breaker leaves returns it
+        if let Some(caught) = self.scopes[position..]
+            .iter()
+            .rposition(|scope| scope.caught)
+        {
+            let scope = &mut self.scopes[position + caught];
+            scope.exits.push((position, kind, value.is_some()));
+            return match value {
+                Some(value) => format!("return {}, {}", scope.exits.len(), value),
+                None => format!("return {}", scope.exits.len()),
+            };
+        }
+
+        let innermost_loop = self.scopes[position + 1..]
+            .iter()
+            .all(|scope| scope.kind != ScopeKind::Loop);
+        let scope = &mut self.scopes[position];
+        match scope.kind {
+            ScopeKind::Function => match value {
+                Some(value) => format!("return {}", value),
+                None => String::from("return"),
+            },
+            ScopeKind::Loop if kind == "stop" && innermost_loop => String::from("break"),
+            ScopeKind::Loop if kind == "stop" => {
+                scope.left = true;
+                format!("goto fly_stop_{}", scope.id)
+            }
+            // Lua has no `continue`
+            ScopeKind::Loop => {
+                scope.passed = true;
+                format!("goto fly_pass_{}", scope.id)
+            }
+            ScopeKind::Condition => {
+                scope.left = true;
+                format!("goto fly_end_{}", scope.id)
+            }
+        }
+    }
+
+    fn import(&mut self, package: &Package, location: &LangModuleSlice) -> LangResult<Vec<String>> {
+        let module = match &package.source {
+            PackageSource::File(path) => {
+                let mut path = PathBuf::from(path);
+                path.set_extension("lua");
+                let path = path.to_string_lossy().replace('\\', "/");
+                format!("fly.load(fly_file, {})", string(&path))
+            }
+            PackageSource::Package(name) => format!("fly.package({})", string(name)),
+        };
+
+        match (&package.included, &package.emplacement) {
+            (_, PackageContentEmplacement::Variable(variable)) => {
+                let name = identifier(variable.location().code());
+                self.local(&name);
+                Ok(vec![format!("{} = {}", name, module)])
+            }
+            (PackageIncludedContent::Only(names), PackageContentEmplacement::Global) => {
+                let variable = self.generated("module");
+                self.local(&variable);
+                let mut lines = vec![format!("{} = {}", variable, module)];
+                for name in names {
+                    let code = name.location().code();
+                    self.local(&identifier(code));
+                    lines.push(format!(
+                        "{} = fly.at({}, {})",
+                        identifier(code),
+                        variable,
+                        string(code)
+                    ));
+                }
+                Ok(lines)
+            }
+            // The names of the module cannot be known : Lua locals cannot be created at runtime
+            (PackageIncludedContent::All, PackageContentEmplacement::Global) => {
+                Self::unsupported(location, "the import of every name of a package")
+            }
+        }
+    }
+
+    fn node(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        self.expression(node.kind(), node.location())
+    }
+
+    /// Generate an expression used as a condition
+    fn test(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        let boolean = is_boolean(node.kind());
+        let code = self.node(node)?;
+
+        Ok(match boolean {
+            true => code,
+            false => Code::new(format!("fly.truthy({})", code.text), PREFIX),
+        })
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match expression {
+            Expressions::Literal(literal) => self.literal(literal, location)?,
+            Expressions::Defined(definable) => {
+                let definition = self.definition(definable, &[])?;
+                Self::assigned(definition)
+            }
+            Expressions::Modifed(modified) => {
+                let definition =
+                    self.definition(modified.definable.kind(), &modified.modified_by)?;
+                Self::assigned(definition)
+            }
+            Expressions::Read(property) => self.read(property)?,
+            Expressions::ReturnOf(call) => self.call(call)?,
+            Expressions::Reverse(reverse) => match reverse.kind {
+                ReverseKind::Boolean => {
+                    let operand = self.test(&reverse.expression)?.at(UNARY);
+                    Code::new(format!("not {}", operand), UNARY)
+                }
+                ReverseKind::Sign => {
+                    let operand = self.node(&reverse.expression)?;
+                    Code::new(format!("fly.negate({})", operand.text), PREFIX)
+                }
+            },
+            Expressions::Operation(operation) => self.operation(operation)?,
+            Expressions::Prioritized(inner) => {
+                let inner = self.node(inner)?;
+                match inner.precedence < PREFIX {
+                    true => Code::new(format!("({})", inner.text), PREFIX),
+                    false => inner,
+                }
+            }
+            Expressions::Ternary(ternary) => {
+                let condition = self.test(&ternary.condition)?;
+                let yes = self.node(&ternary.yes)?;
+                let no = self.node(&ternary.no)?;
+
+                // `and`/`or` give the second operand when the first one is `nil` or `false`
+                match is_truthy(ternary.yes.kind()) {
+                    true => Code::new(
+                        format!(
+                            "{} and {} or {}",
+                            condition.at(AND),
+                            yes.at(AND + 1),
+                            no.at(OR + 1)
+                        ),
+                        OR,
+                    ),
+                    false => Code::new(
+                        format!(
+                            "(function() if {} then return {} end return {} end)()",
+                            condition.text, yes.text, no.text
+                        ),
+                        PREFIX,
+                    ),
+                }
+            }
+            Expressions::Structure(structure) => {
+                let mut entries = vec![];
+                for entry in &structure.entries {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    let key = match entry.key.kind() {
+                        Expressions::Literal(ParsedLiterals::Word) => {
+                            Code::new(string(entry.key.location().code()), VALUE)
+                        }
+                        _ => self.node(&entry.key)?,
+                    };
+                    entries.push(key);
+                    entries.push(self.node(&entry.value)?);
+                }
+
+                Code::new(format!("fly.structure({})", list(entries)), PREFIX)
+            }
+            Expressions::Array(array) => {
+                let items = self.arguments(&array.entries)?;
+                Code::new(format!("fly.array({})", items), PREFIX)
+            }
+            Expressions::Instanciate(instanciation) => {
+                let mut arguments = vec![self.node(&instanciation.class)?];
+                for argument in &instanciation.arguments {
+                    arguments.push(self.node(argument)?);
+                }
+                Code::new(format!("fly.new({})", list(arguments)), CALL)
+            }
+            // Types are verified by the checker
+            Expressions::Typed(typed) => self.node(&typed.expression)?,
+        })
+    }
+
+    fn arguments(&mut self, arguments: &[Box<Node<Expressions>>]) -> LangResult<String> {
+        let mut codes = vec![];
+        for argument in arguments {
+            codes.push(self.node(argument)?);
+        }
+
+        Ok(list(codes))
+    }
+
+    fn literal(
+        &mut self,
+        literal: &ParsedLiterals,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match literal {
+            ParsedLiterals::Word => {
+                let name = location.code();
+                let receiver = self
+                    .symbols
+                    .resolve(location)
+                    .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver);
+
+                match (receiver, name) {
+                    (true, "super") => {
+                        return Self::unsupported(
+                            location,
+                            "`super` outside of a call or a property",
+                        );
+                    }
+                    (true, _) => Code::new(name, PREFIX),
+                    (false, _) => Code::new(identifier(name), PREFIX),
+                }
+            }
+            ParsedLiterals::True => Code::new("true", VALUE),
+            ParsedLiterals::False => Code::new("false", VALUE),
+            ParsedLiterals::Empty => Code::new("nil", VALUE),
+            ParsedLiterals::Number => number(NumberRepresentation::from(location).into()),
+            ParsedLiterals::Computed(value) => number(*value),
+            ParsedLiterals::String(items) => {
+                let mut parts = vec![];
+                for item in items {
+                    parts.push(match item.kind() {
+                        ParsedStringItem::Literal(content) => Code::new(string(content), VALUE),
+                        ParsedStringItem::Expression(expression) => Code::new(
+                            format!("fly.display({})", self.node(expression)?.text),
+                            PREFIX,
+                        ),
+                    });
+                }
+
+                match parts.len() {
+                    0 => Code::new("\"\"", VALUE),
+                    1 => parts.remove(0),
+                    _ => Code::new(
+                        parts
+                            .into_iter()
+                            .map(|part| part.text)
+                            .collect::<Vec<String>>()
+                            .join(" .. "),
+                        CONCATENATION,
+                    ),
+                }
+            }
+        })
+    }
+
+    /// Returns if the expression is `super`
+    fn is_super(&self, node: &Node<Expressions>) -> bool {
+        matches!(node.kind(), Expressions::Literal(ParsedLiterals::Word))
+            && node.location().code() == "super"
+            && self
+                .symbols
+                .resolve(node.location())
+                .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver)
+    }
+
+    /// The parameter receiving the class whose methods are being generated
+    fn class_parameter(&self) -> &str {
+        self.classes.last().map_or("nil", String::as_str)
+    }
+
+    /// Generate the object a property is read from.
+    /// `super` gives the methods following the current class in the method resolution order of `self`.
+    fn object(&mut self, node: &Node<Expressions>) -> LangResult<String> {
+        match self.is_super(node) {
+            true => Ok(format!("fly.super(self, {})", self.class_parameter())),
+            false => Ok(self.node(node)?.text),
+        }
+    }
+
+    /// Generate the key of a property
+    fn key(&mut self, property: &Node<Property>) -> LangResult<String> {
+        let read = property.location();
+        Ok(match property.kind() {
+            Property::Key => string(read.code()),
+            Property::Index => index(NumberRepresentation::from(read).into()),
+            Property::Expression(key) => self.node(key)?.text,
+        })
+    }
+
+    fn read(&mut self, property: &ReadProperty) -> LangResult<Code> {
+        let from = self.object(&property.from)?;
+        let key = self.key(&property.read)?;
+        Ok(Code::new(format!("fly.at({}, {})", from, key), PREFIX))
+    }
+
+    fn call(&mut self, call: &Call) -> LangResult<Code> {
+        // Calling `super` calls the constructor of the parents
+        if self.is_super(&call.callable) {
+            let mut arguments = vec![
+                Code::new("self", PREFIX),
+                Code::new(self.class_parameter(), PREFIX),
+            ];
+            for argument in &call.arguments {
+                arguments.push(self.node(argument)?);
+            }
+            return Ok(Code::new(
+                format!("fly.construct({})", list(arguments)),
+                CALL,
+            ));
+        }
+
+        let callable = self.node(&call.callable)?.at(PREFIX);
+        let arguments = self.arguments(&call.arguments)?;
+        Ok(Code::new(format!("{}({})", callable, arguments), CALL))
+    }
+
+    /// Generate an operation implemented by the runtime library
+    fn helper(
+        &mut self,
+        name: &str,
+        (left, right): &(Box<Node<Expressions>>, Box<Node<Expressions>>),
+    ) -> LangResult<Code> {
+        let left = self.node(left)?.text;
+        let right = self.node(right)?.text;
+        Ok(Code::new(
+            format!("fly.{}({}, {})", name, left, right),
+            PREFIX,
+        ))
+    }
+
+    fn operation(&mut self, operation: &Operations) -> LangResult<Code> {
+        Ok(match operation {
+            Operations::Numeric(numeric) => {
+                let name = match numeric.operator.kind() {
+                    Operator::Add => "add",
+                    Operator::Substract => "subtract",
+                    Operator::Multiply => "multiply",
+                    Operator::Divide => "divide",
+                    Operator::Modulo => "modulo",
+                    Operator::EuclidianDivision => "quotient",
+                    Operator::Power => "power",
+                };
+                self.helper(name, &numeric.operands)?
+            }
+            Operations::Binary(binary) => {
+                let (operator, precedence) = match binary.operator.kind() {
+                    BinaryOperator::And => ("and", AND),
+                    BinaryOperator::Or => ("or", OR),
+                    BinaryOperator::Xor => ("~=", COMPARISON),
+                };
+                let (left, right) = &binary.operands;
+                let operands = (self.test(left)?, self.test(right)?);
+                infix(operator, precedence, operands)
+            }
+            Operations::Comparative(comparison) => {
+                let name = match comparison.operator.kind() {
+                    Comparison::Equal => "equal",
+                    Comparison::Less(true) => "less",
+                    Comparison::Less(false) => "less_equal",
+                    Comparison::Greater(true) => "greater",
+                    Comparison::Greater(false) => "greater_equal",
+                };
+                self.helper(name, &comparison.operands)?
+            }
+        })
+    }
+
+    fn modifiers(&mut self, modifiers: &[Node<Modifier>]) -> LangResult<Vec<String>> {
+        let mut codes = vec![];
+        for modifier in modifiers {
+            codes.push(match modifier.kind() {
+                Modifier::DefinedElement => {
+                    self.literal(&ParsedLiterals::Word, modifier.location())?
+                        .text
+                }
+                // The modifier is the function returned by the call
+                Modifier::CallReturn(call) => self.call(call)?.text,
+            });
+        }
+
+        Ok(codes)
+    }
+
+    /// Wrap a function or a class with its modifiers
+    fn modify(code: Code, modifiers: &[String]) -> Code {
+        match modifiers.is_empty() {
+            true => code,
+            false => Code::new(
+                format!("fly.modify({}, {})", code.text, modifiers.join(", ")),
+                PREFIX,
+            ),
+        }
+    }
+
+    /// Generate a definition, returning the variable it assigns (if any) and its value
+    fn definition(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+    ) -> LangResult<(Option<String>, Code)> {
+        let modifiers = self.modifiers(modifiers)?;
+
+        match definable {
+            Definables::Variable(variable) => self.variable(variable, modifiers),
+            // The variable is assigned before the body is generated, which can read it
+            Definables::Function(function) => {
+                let location = function.name.as_ref().map(|name| name.location());
+                let name = location.map(|location| self.target(location));
+                let code = Self::modify(self.function(function)?, &modifiers);
+                Ok(match (location, name) {
+                    (Some(location), Some(None)) => (None, Self::reassign(location, &code.text)),
+                    (_, name) => (name.flatten(), code),
+                })
+            }
+            Definables::Class(class) => {
+                let location = class.name.location();
+                let name = self.target(location);
+                let code = Self::modify(self.class(class)?, &modifiers);
+                Ok(match name {
+                    Some(name) => (Some(name), code),
+                    None => (None, Self::reassign(location, &code.text)),
+                })
+            }
+        }
+    }
+
+    /// Use a definition as an expression. The assignments are done by a function called in place.
+    fn assigned((name, code): (Option<String>, Code)) -> Code {
+        match name {
+            Some(name) => Code::new(
+                format!("(function() {0} = {1} return {0} end)()", name, code.text),
+                PREFIX,
+            ),
+            None => code,
+        }
+    }
+
+    /// Generate the assignment of a variable, returning the assigned variable (if it is not a property) and the
+    /// assigned value
+    fn variable(
+        &mut self,
+        variable: &DefineVariable,
+        modifiers: Vec<String>,
+    ) -> LangResult<(Option<String>, Code)> {
+        let value = self.value(variable, modifiers)?;
+        let emplacement = variable.emplacement.location();
+
+        match variable.emplacement.kind() {
+            VariableEmplacements::Scope => Ok(match self.target(emplacement) {
+                Some(name) => (Some(name), value),
+                None => (None, Self::reassign(emplacement, &value.text)),
+            }),
+            VariableEmplacements::Property(property) => {
+                if self.is_super(&property.from) {
+                    return Self::unsupported(emplacement, "the assignment of a `super` property");
+                }
+
+                let target = self.node(&property.from)?.text;
+                let key = self.key(&property.read)?;
+                Ok((
+                    None,
+                    Code::new(
+                        format!("fly.put({}, {}, {})", target, key, value.text),
+                        CALL,
+                    ),
+                ))
+            }
+            VariableEmplacements::Any(_) => {
+                Self::unsupported(emplacement, "the assignment of an expression")
+            }
+        }
+    }
+
+    /// Generate the value of a variable, with its modifiers applied
+    fn value(&mut self, variable: &DefineVariable, modifiers: Vec<String>) -> LangResult<Code> {
+        let value = self.node(&variable.value)?;
+        if modifiers.is_empty() {
+            return Ok(value);
+        }
+
+        Ok(Code::new(
+            format!(
+                "fly.modify_variable({}, {}, {}, {})",
+                string(variable.emplacement.location().code()),
+                variable.readonly,
+                value.text,
+                modifiers.join(", ")
+            ),
+            PREFIX,
+        ))
+    }
+
+    /// Generate the body of a function, in its own frame
+    fn body(
+        &mut self,
+        function: &DefineFunction,
+        arguments: Vec<String>,
+    ) -> LangResult<Vec<String>> {
+        self.frames.push(Frame {
+            arguments,
+            ..Default::default()
+        });
+        self.enter(ScopeKind::Function, &function.scope_target);
+        let body = self.block(&function.execution);
+        let (body, _) = self.leave(body?);
+        let frame = self.frames.pop().unwrap();
+
+        let mut lines = Self::locals(&frame);
+        lines.extend(body);
+        Ok(lines)
+    }
+
+    /// Generate a Lua function. `self` is the first argument of methods.
+    /// The arguments are optional, like in flylang : the missing ones are `nil`.
+    fn define(&mut self, function: &DefineFunction, method: bool) -> LangResult<String> {
+        let arguments: Vec<String> = function
+            .arguments
+            .iter()
+            .map(|argument| identifier(argument.kind().name.location().code()))
+            .collect();
+        let mut parameters = arguments.clone();
+        if method {
+            parameters.insert(0, String::from("self"));
+        }
+
+        let body = self.body(function, arguments)?;
+        let mut lines = vec![format!("function({})", parameters.join(", "))];
+        lines.extend(indent(body));
+        lines.push(String::from("end"));
+        Ok(lines.join("\n"))
+    }
+
+    /// Generate a function. Named functions are registered with their name, displayed by the runtime library.
+    fn function(&mut self, function: &DefineFunction) -> LangResult<Code> {
+        let code = self.define(function, false)?;
+        Ok(match &function.name {
+            Some(name) => Code::new(
+                format!("fly.fn({}, {})", string(name.location().code()), code),
+                PREFIX,
+            ),
+            None => Code::new(code, VALUE),
+        })
+    }
+
+    /// Generate a class. The function building it receives the class, read by `super`.
+    fn class(&mut self, class: &DefineClass) -> LangResult<Code> {
+        let parents: Vec<String> = class
+            .parents
+            .iter()
+            .map(|parent| identifier(parent.location().code()))
+            .collect();
+        let parameter = self.generated("class");
+        self.classes.push(parameter.clone());
+        self.frames.push(Frame::default());
+
+        let mut members = vec![];
+        let mut statics = vec![];
+        let mut attributes = vec![];
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            let name = string(item.item.emplacement.location().code());
+
+            match item.is_static {
+                true => {
+                    let modifiers = self.modifiers(&item.modifie_by)?;
+                    let value = self.value(&item.item, modifiers)?;
+                    statics.push(format!("{{{}, {}}},", name, value.text));
+                }
+                false => attributes.push(item),
+            }
+        }
+
+        // The attributes of the instances are initialised by a function
+        if !attributes.is_empty() {
+            self.frames.push(Frame::default());
+            let mut body = vec![];
+            for item in attributes {
+                let modifiers = self.modifiers(&item.modifie_by)?;
+                let value = self.value(&item.item, modifiers)?;
+                body.push(format!(
+                    "fly.put(self, {}, {})",
+                    string(item.item.emplacement.location().code()),
+                    value.text
+                ));
+            }
+            let frame = self.frames.pop().unwrap();
+
+            members.push(String::from("attributes = function(self)"));
+            members.extend(indent(Self::locals(&frame)));
+            members.extend(indent(body));
+            members.push(String::from("end,"));
+        }
+
+        if let Some(constructor) = &class.constructor {
+            members.push(format!(
+                "constructor = {},",
+                self.define(constructor.kind(), true)?
+            ));
+        }
+
+        let mut methods = vec![];
+        for method in &class.methods {
+            let item = method.kind();
+            let name = item
+                .item
+                .name
+                .as_ref()
+                .map_or("", |name| name.location().code());
+            let modifiers = self.modifiers(&item.modifie_by)?;
+
+            match item.is_static {
+                // Static methods are read from the class : they are modified once
+                true => {
+                    let function = self.function(&item.item)?;
+                    let function = Self::modify(function, &modifiers);
+                    statics.push(format!("{{{}, {}}},", string(name), function.text));
+                }
+                // Methods are modified each time they are bound to an instance
+                false => {
+                    let function = self.define(&item.item, true)?;
+                    methods.push(match modifiers.is_empty() {
+                        true => format!("{{{}, {}}},", string(name), function),
+                        false => format!(
+                            "{{{}, {}, {{{}}}}},",
+                            string(name),
+                            function,
+                            modifiers.join(", ")
+                        ),
+                    });
+                }
+            }
+        }
+        for (field, entries) in [("statics", statics), ("methods", methods)] {
+            if !entries.is_empty() {
+                members.push(format!("{} = {{", field));
+                members.extend(indent(entries));
+                members.push(String::from("},"));
+            }
+        }
+
+        let frame = self.frames.pop().unwrap();
+        self.classes.pop();
+
+        let mut body = Self::locals(&frame);
+        match members.is_empty() {
+            true => body.push(String::from("return {}")),
+            false => {
+                body.push(String::from("return {"));
+                body.extend(indent(members));
+                body.push(String::from("}"));
+            }
+        }
+
+        let mut lines = vec![format!(
+            "fly.class({}, {{{}}}, function({})",
+            string(class.name.location().code()),
+            parents.join(", "),
+            parameter
+        )];
+        lines.extend(indent(body));
+        lines.push(String::from("end)"));
+        Ok(Code::new(lines.join("\n"), PREFIX))
+    }
+}
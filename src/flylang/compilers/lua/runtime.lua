@@ -0,0 +1,748 @@
+-- The runtime library of the Lua code generated from flylang.
+-- It implements the parts of the flylang semantics Lua does not share (truthiness, equality, display, ...).
+
+local fly = {}
+
+fly.try = pcall
+fly.infinity = math.huge
+fly.nan = 0.0 / 0.0
+
+-- Lua tables cannot hold nil : the empty values of arrays, structures and instances are stored as `NONE`
+local NONE = setmetatable({}, { __name = "none" })
+
+local function stored(value)
+    if value == nil then
+        return NONE
+    end
+    return value
+end
+
+local function restored(value)
+    if value == NONE then
+        return nil
+    end
+    return value
+end
+
+local function fail(message)
+    error(message, 0)
+end
+
+-- The metatables of the flylang values. Instances have their class as metatable.
+local Array = { __name = "array" }
+local Structure = { __name = "structure" }
+local Class = { __name = "class" }
+local Method = { __name = "method" }
+local Modified = { __name = "modified" }
+local Super = { __name = "super" }
+local Breaker = { __name = "breaker" }
+
+-- The names of the functions, and the class and name of the methods
+local names = setmetatable({}, { __mode = "k" })
+local methods = setmetatable({}, { __mode = "k" })
+-- The methods bound to each instance
+local bound = setmetatable({}, { __mode = "k" })
+
+-- A breaker going out of a scope Lua cannot leave directly, to the scope labelled `scope`
+function fly.throw(scope, kind, value)
+    error(setmetatable({ scope = scope, kind = kind, value = value }, Breaker), 0)
+end
+
+-- Get the kind and the value of a breaker stopping at the scope labelled `scope`.
+-- The other errors (and breakers) keep going out.
+function fly.caught(error_, scope)
+    if getmetatable(error_) == Breaker and error_.scope == scope then
+        return error_.kind, error_.value
+    end
+    error(error_, 0)
+end
+
+function fly.fn(name, function_)
+    names[function_] = name
+    return function_
+end
+
+-- Get an element without its modifiers
+local function unmodified(value)
+    while getmetatable(value) == Modified do
+        value = value.target
+    end
+    return value
+end
+
+local function is_instance(value)
+    return type(value) == "table" and getmetatable(getmetatable(value)) == Class
+end
+
+local function is_number(value)
+    return type(value) == "number"
+end
+
+function fly.array(...)
+    local array = setmetatable({}, Array)
+    for i = 1, select("#", ...) do
+        array[i] = stored((select(i, ...)))
+    end
+    return array
+end
+
+-- Define a key of a structure, which keeps the order its keys are defined in
+local function define(structure, key, value)
+    if key == nil then
+        fail("Cannot use empty as a key.")
+    end
+    if structure.values[key] == nil then
+        table.insert(structure.keys, key)
+    end
+    structure.values[key] = stored(value)
+end
+
+-- Create a structure from its keys, each followed by its value
+function fly.structure(...)
+    local structure = setmetatable({ keys = {}, values = {} }, Structure)
+    for i = 1, select("#", ...), 2 do
+        local key, value = select(i, ...)
+        define(structure, key, value)
+    end
+    return structure
+end
+
+-- Order the ancestors of a class (C3 linearization, like the multiple inheritance of flylang)
+local function linearize(class)
+    local sequences = {}
+    for _, parent in ipairs(class.parents) do
+        table.insert(sequences, table.move(parent.mro, 1, #parent.mro, 1, {}))
+    end
+    table.insert(sequences, table.move(class.parents, 1, #class.parents, 1, {}))
+
+    local mro = { class }
+    while true do
+        local remaining = {}
+        for _, sequence in ipairs(sequences) do
+            if #sequence > 0 then
+                table.insert(remaining, sequence)
+            end
+        end
+        sequences = remaining
+        if #sequences == 0 then
+            return mro
+        end
+
+        -- The next class is the first head which is not in the tail of a sequence
+        local head
+        for _, sequence in ipairs(sequences) do
+            local candidate, tail = sequence[1], false
+            for _, other in ipairs(sequences) do
+                for i = 2, #other do
+                    tail = tail or other[i] == candidate
+                end
+            end
+            if not tail then
+                head = candidate
+                break
+            end
+        end
+        if head == nil then
+            fail("The parents of " .. class.name .. " cannot be ordered.")
+        end
+
+        table.insert(mro, head)
+        for _, sequence in ipairs(sequences) do
+            if sequence[1] == head then
+                table.remove(sequence, 1)
+            end
+        end
+    end
+end
+
+-- Create a class. `build` receives the class and returns its members :
+-- `attributes` (initialising the attributes of an instance), `constructor`, `methods` and `statics`,
+-- the methods being `{name, function, modifiers}` and the static members `{name, value}`.
+function fly.class(name, parents, build)
+    local class = setmetatable({ name = name, parents = {}, members = {} }, Class)
+    for i, parent in ipairs(parents) do
+        parent = unmodified(parent)
+        if getmetatable(parent) ~= Class then
+            fail("The parent of " .. name .. " is not a class.")
+        end
+        class.parents[i] = parent
+    end
+    class.mro = linearize(class)
+
+    -- The members not defined by the class are read from its parents
+    if #class.parents == 1 then
+        setmetatable(class.members, { __index = class.parents[1].members })
+    elseif #class.parents > 1 then
+        setmetatable(class.members, {
+            __index = function(_, key)
+                for i = 2, #class.mro do
+                    local value = rawget(class.mro[i].members, key)
+                    if value ~= nil then
+                        return value
+                    end
+                end
+            end,
+        })
+    end
+    class.__index = class.members
+
+    local definition = build(class)
+    class.attributes = definition.attributes
+    class.constructor = definition.constructor
+    for _, static in ipairs(definition.statics or {}) do
+        class.members[static[1]] = stored(static[2])
+    end
+    for _, method in ipairs(definition.methods or {}) do
+        methods[method[2]] = { class = class, name = method[1], modifiers = method[3] or {} }
+        class.members[method[1]] = method[2]
+    end
+    return class
+end
+
+-- Wrap a function or a class with modifiers. The first modifier is the most inner one.
+function fly.modify(target, ...)
+    for i = 1, select("#", ...) do
+        target = setmetatable({ target = target, modifier = (select(i, ...)) }, Modified)
+    end
+    return target
+end
+
+-- Calling a modified element calls the modifier with the element and the arguments
+Modified.__call = function(modified, ...)
+    return modified.modifier(modified.target, fly.array(...))
+end
+
+-- Apply the modifiers of a variable on its value
+function fly.modify_variable(name, constant, value, ...)
+    for i = 1, select("#", ...) do
+        value = (select(i, ...))(name, fly.array(constant, value))
+    end
+    return value
+end
+
+-- A method bound to an instance. Methods are modified each time they are bound.
+local function bind(instance, function_)
+    bound[instance] = bound[instance] or {}
+    if bound[instance][function_] == nil then
+        local method = setmetatable({ receiver = instance, method = function_ }, Method)
+        bound[instance][function_] = fly.modify(method, table.unpack(methods[function_].modifiers))
+    end
+    return bound[instance][function_]
+end
+
+Method.__call = function(method, ...)
+    return method.method(method.receiver, ...)
+end
+
+-- Call the first constructor of the method resolution order of an instance, starting at `from`
+local function construct(instance, from, ...)
+    local mro = getmetatable(instance).mro
+    for i = from, #mro do
+        if mro[i].constructor then
+            return mro[i].constructor(instance, ...)
+        end
+    end
+    if select("#", ...) > 0 then
+        fail(string.format("Too many arguments given. Expected at most 0 but found %d.", select("#", ...)))
+    end
+end
+
+local function following(instance, class)
+    local mro = getmetatable(instance).mro
+    for i, ancestor in ipairs(mro) do
+        if ancestor == class then
+            return i + 1
+        end
+    end
+    return #mro + 1
+end
+
+-- Attributes are initialised from the farthest ancestor to the class itself, then the constructor is called
+function fly.new(class, ...)
+    if getmetatable(class) == Modified then
+        return class(...)
+    end
+    if getmetatable(class) ~= Class then
+        fail("Cannot instanciate " .. fly.type(class) .. ".")
+    end
+
+    local instance = setmetatable({}, class)
+    for i = #class.mro, 1, -1 do
+        if class.mro[i].attributes then
+            class.mro[i].attributes(instance)
+        end
+    end
+    construct(instance, 1, ...)
+    return instance
+end
+
+-- Call the constructor of the parents (`super(...)` in a method of `class`)
+function fly.construct(instance, class, ...)
+    return construct(instance, following(instance, class), ...)
+end
+
+-- The methods following `class` in the method resolution order of the instance (`super` in a method of `class`)
+function fly.super(instance, class)
+    return setmetatable({ instance = instance, from = following(instance, class) }, Super)
+end
+
+function fly.type(value)
+    value = unmodified(value)
+    if value == nil then
+        return "empty"
+    end
+    local kind = getmetatable(value)
+    if type(value) == "boolean" or type(value) == "number" or type(value) == "string" then
+        return type(value)
+    elseif kind == Array then
+        return "array"
+    elseif kind == Class then
+        return "class"
+    elseif kind == Super then
+        return "super"
+    elseif is_instance(value) then
+        return "instance"
+    elseif type(value) == "table" and kind ~= Method then
+        return "structure"
+    end
+    return "function"
+end
+
+-- Returns if the value is considered as `true` in a condition
+function fly.truthy(value)
+    if value == nil or value == false then
+        return false
+    elseif is_number(value) then
+        return value ~= 0 and value == value
+    elseif type(value) == "string" then
+        return value ~= ""
+    elseif getmetatable(value) == Array then
+        return #value > 0
+    elseif getmetatable(value) == Structure then
+        return #value.keys > 0
+    end
+    return true
+end
+
+-- Compare two values : primitives, arrays and structures by their content, the others by reference
+function fly.equal(a, b)
+    a, b = unmodified(a), unmodified(b)
+    local kind = getmetatable(a)
+    if kind ~= getmetatable(b) or type(a) ~= type(b) then
+        return false
+    elseif kind == Array then
+        if #a ~= #b then
+            return false
+        end
+        for i = 1, #a do
+            if not fly.equal(restored(a[i]), restored(b[i])) then
+                return false
+            end
+        end
+        return true
+    elseif kind == Structure then
+        if #a.keys ~= #b.keys then
+            return false
+        end
+        for _, key in ipairs(a.keys) do
+            if b.values[key] == nil or not fly.equal(restored(a.values[key]), restored(b.values[key])) then
+                return false
+            end
+        end
+        return true
+    elseif kind == Method then
+        return a.receiver == b.receiver and a.method == b.method
+    end
+    return rawequal(a, b)
+end
+
+local function number(value)
+    if value ~= value then
+        return "NaN"
+    elseif value == math.huge or value == -math.huge then
+        return value > 0 and "inf" or "-inf"
+    elseif math.type(value) == "integer" then
+        return tostring(value)
+    elseif value == math.floor(value) and math.abs(value) < 1e15 then
+        return string.format("%d", value)
+    end
+
+    -- The shortest digits giving back the same number, written without exponent (like the interpreter)
+    local text
+    for precision = 0, 16 do
+        text = string.format("%." .. precision .. "e", value)
+        if tonumber(text) == value then
+            break
+        end
+    end
+    local sign, digits, exponent = text:match("^(-?)([%d%.]+)e([-+]%d+)$")
+    digits, exponent = digits:gsub("%.", ""), tonumber(exponent)
+    if exponent >= #digits - 1 then
+        return sign .. digits .. string.rep("0", exponent - #digits + 1)
+    elseif exponent >= 0 then
+        return sign .. digits:sub(1, exponent + 1) .. "." .. digits:sub(exponent + 2)
+    end
+    return sign .. "0." .. string.rep("0", -exponent - 1) .. digits
+end
+
+local escapes = { ['"'] = '\\"', ["\\"] = "\\\\", ["\n"] = "\\n", ["\r"] = "\\r", ["\t"] = "\\t" }
+
+-- Same as `display`, but strings are quoted (used inside arrays and structures)
+local function representation(value)
+    if type(value) ~= "string" then
+        return fly.display(value)
+    end
+    return '"' .. value:gsub('[%c"\\]', function(c)
+        return escapes[c] or string.format("\\u%04x", c:byte())
+    end) .. '"'
+end
+
+local function key_representation(key)
+    if type(key) == "string" and key:match("^[%a_][%w_]*$") then
+        return key
+    end
+    return representation(key)
+end
+
+-- Convert a value to a string, the way `std.out` displays it
+function fly.display(value)
+    value = unmodified(value)
+    local kind = getmetatable(value)
+    if value == nil then
+        return "()"
+    elseif type(value) == "boolean" then
+        return value and "true" or "false"
+    elseif is_number(value) then
+        return number(value)
+    elseif type(value) == "string" then
+        return value
+    elseif kind == Array then
+        local items = {}
+        for i, item in ipairs(value) do
+            items[i] = representation(restored(item))
+        end
+        return "{" .. table.concat(items, ", ") .. "}"
+    elseif kind == Structure then
+        if #value.keys == 0 then
+            return "{:}"
+        end
+        local entries = {}
+        for i, key in ipairs(value.keys) do
+            entries[i] = key_representation(key) .. ": " .. representation(restored(value.values[key]))
+        end
+        return "{" .. table.concat(entries, ", ") .. "}"
+    elseif kind == Class then
+        return "<cs " .. value.name .. ">"
+    elseif kind == Method then
+        local method = methods[value.method]
+        return "<fn " .. method.class.name .. "." .. method.name .. ">"
+    elseif kind == Super then
+        return "<super>"
+    elseif is_instance(value) then
+        return "<" .. kind.name .. " instance>"
+    elseif type(value) == "table" then
+        return "<table>"
+    end
+    return "<fn " .. (names[value] or "anonymous") .. ">"
+end
+
+local function invalid(operator, ...)
+    local types = {}
+    for i = 1, select("#", ...) do
+        types[i] = fly.type((select(i, ...)))
+    end
+    fail("The operation " .. operator .. " cannot be applied to " .. table.concat(types, " and ") .. ".")
+end
+
+-- The `+` operator : adds numbers, or concatenates strings
+function fly.add(a, b)
+    if is_number(a) and is_number(b) then
+        return a + b
+    elseif type(a) == "string" or type(b) == "string" then
+        return fly.display(a) .. fly.display(b)
+    end
+    invalid("+", a, b)
+end
+
+local function numbers(operator, a, b)
+    if not is_number(a) or not is_number(b) then
+        invalid(operator, a, b)
+    end
+end
+
+-- The `-` operator
+function fly.subtract(a, b)
+    numbers("-", a, b)
+    return a - b
+end
+
+-- The `*` operator
+function fly.multiply(a, b)
+    numbers("*", a, b)
+    return a * b
+end
+
+-- The `**` operator
+function fly.power(a, b)
+    numbers("**", a, b)
+    return a ^ b
+end
+
+-- The `-` sign
+function fly.negate(value)
+    if not is_number(value) then
+        invalid("-", value)
+    end
+    return -value
+end
+
+-- Compare two numbers or two strings
+local function compare(operator, a, b)
+    local ordered = is_number(a) and is_number(b) and a == a and b == b
+    if not ordered and (type(a) ~= "string" or type(b) ~= "string") then
+        invalid(operator, a, b)
+    end
+end
+
+-- The `<` operator
+function fly.less(a, b)
+    compare("<", a, b)
+    return a < b
+end
+
+-- The `<=` operator
+function fly.less_equal(a, b)
+    compare("<=", a, b)
+    return a <= b
+end
+
+-- The `>` operator
+function fly.greater(a, b)
+    compare(">", a, b)
+    return a > b
+end
+
+-- The `>=` operator
+function fly.greater_equal(a, b)
+    compare(">=", a, b)
+    return a >= b
+end
+
+local function divisor(operator, a, b)
+    if not is_number(a) or not is_number(b) then
+        invalid(operator, a, b)
+    elseif b == 0 then
+        fail("Division by zero.")
+    end
+    return b
+end
+
+-- The `/` operator
+function fly.divide(a, b)
+    return a / divisor("/", a, b)
+end
+
+-- The `%` operator (the result has the sign of `a`)
+function fly.modulo(a, b)
+    b = divisor("%", a, b)
+    return math.fmod(a + 0.0, b)
+end
+
+-- The `//` operator (euclidian division)
+function fly.quotient(a, b)
+    b = divisor("//", a, b)
+    local quotient = a / b
+    quotient = (quotient >= 0 and math.floor(quotient) or math.ceil(quotient)) + 0.0
+    if math.fmod(a + 0.0, b) < 0 then
+        return b > 0 and quotient - 1 or quotient + 1
+    end
+    return quotient
+end
+
+-- Get the position (starting at 0) of an index. Negative indexes start from the end.
+local function position(key, length)
+    if not is_number(key) then
+        fail("Expected a number as index, but found " .. fly.type(key) .. ".")
+    end
+
+    local index = key < 0 and length + key or key
+    if index ~= math.floor(index) or index < 0 or index >= length then
+        fail("The index " .. fly.display(key) .. " is out of range (length " .. length .. ").")
+    end
+    return math.tointeger(index)
+end
+
+-- Read a member of a class, starting at the position `from` of a method resolution order
+local function member(instance, mro, from, key)
+    for i = from, #mro do
+        local value = rawget(mro[i].members, key)
+        if value ~= nil then
+            if methods[value] then
+                return bind(instance, value)
+            end
+            return restored(value)
+        end
+    end
+    fail("The property " .. representation(key) .. " is not defined.")
+end
+
+-- Read a property. Arrays are indexed from 0, and their Lua index is the next one.
+function fly.at(source, key)
+    source = unmodified(source)
+    local kind = getmetatable(source)
+    if kind == Array then
+        return restored(source[position(key, #source) + 1])
+    elseif type(source) == "string" then
+        -- The characters of the UTF-8 strings are made of several bytes
+        local index = position(key, utf8.len(source)) + 1
+        return source:sub(utf8.offset(source, index), utf8.offset(source, index + 1) - 1)
+    elseif kind == Structure then
+        if source.values[key] == nil then
+            fail("The property " .. representation(key) .. " is not defined.")
+        end
+        return restored(source.values[key])
+    elseif kind == Class then
+        local value = source.members[key]
+        if value == nil then
+            fail("The property " .. representation(key) .. " is not defined.")
+        end
+        return restored(value)
+    elseif kind == Super then
+        return member(source.instance, getmetatable(source.instance).mro, source.from, key)
+    elseif is_instance(source) then
+        local value = rawget(source, key)
+        if value ~= nil then
+            return restored(value)
+        end
+        return member(source, kind.mro, 1, key)
+    elseif source == nil then
+        fail("Cannot read the property " .. representation(key) .. " of empty.")
+    elseif type(source) == "table" then
+        -- A table of a Lua package
+        return source[key]
+    end
+    fail("The property " .. representation(key) .. " is not defined.")
+end
+
+-- Write a property. Writing right after the last item of an array appends it.
+function fly.put(target, key, value)
+    target = unmodified(target)
+    local kind = getmetatable(target)
+    if kind == Array then
+        if key == #target then
+            target[#target + 1] = stored(value)
+        else
+            target[position(key, #target) + 1] = stored(value)
+        end
+    elseif kind == Structure then
+        define(target, key, value)
+    elseif kind == Class then
+        rawset(target.members, key, stored(value))
+    elseif is_instance(target) then
+        rawset(target, key, stored(value))
+    elseif type(target) == "table" and kind == nil then
+        target[key] = value
+    else
+        fail("Cannot write the property " .. representation(key) .. " of " .. fly.type(target) .. ".")
+    end
+    return value
+end
+
+-- Go through the (index, item) couples of an `each` loop : the items of arrays and strings with their position,
+-- the values of structures with their key, and the numbers from 0 to `n` (excluded).
+-- The couples are read before the loop starts.
+function fly.entries(iterable)
+    iterable = unmodified(iterable)
+    local kind = getmetatable(iterable)
+    local keys, values = {}, {}
+    if kind == Array then
+        for i, item in ipairs(iterable) do
+            keys[i], values[i] = i - 1, restored(item)
+        end
+    elseif type(iterable) == "string" then
+        for _, code in utf8.codes(iterable) do
+            local i = #keys + 1
+            keys[i], values[i] = i - 1, utf8.char(code)
+        end
+    elseif is_number(iterable) then
+        for n = 0, math.ceil(iterable) - 1 do
+            keys[n + 1], values[n + 1] = n, n
+        end
+    elseif kind == Structure then
+        for i, key in ipairs(iterable.keys) do
+            keys[i], values[i] = key, restored(iterable.values[key])
+        end
+    else
+        fail("Cannot go through " .. fly.type(iterable) .. ".")
+    end
+
+    local i = 0
+    return function()
+        i = i + 1
+        if i <= #keys then
+            return keys[i], values[i]
+        end
+    end
+end
+
+-- Raise the error of the assignment of a constant (or of a receiver or a built-in element). The assigned value is
+-- computed before, like the interpreter does.
+function fly.reassign(name, _)
+    fail(string.format("%q is a constant and cannot be reassigned.", name))
+end
+
+local loaded = {}
+
+-- Load a file generated from flylang, from its path relative to the loading file
+function fly.load(file, path)
+    path = file:match("^(.-)[^/\\]*$") .. path
+    if loaded[path] == nil then
+        loaded[path] = dofile(path)
+    end
+    return loaded[path]
+end
+
+-- Load a Lua package
+function fly.package(name)
+    return require(name)
+end
+
+-- Get the number of items in an array, a structure or a string
+local function length(value)
+    local kind = getmetatable(value)
+    if kind == Array then
+        return #value
+    elseif type(value) == "string" then
+        return utf8.len(value)
+    elseif kind == Structure then
+        return #value.keys
+    end
+    fail("Expected array, structure or string, but found " .. fly.type(value) .. ".")
+end
+
+local function write(file, ...)
+    local texts = {}
+    for i = 1, select("#", ...) do
+        texts[i] = fly.display((select(i, ...)))
+    end
+    file:write(table.concat(texts, " "), "\n")
+end
+
+-- The `std` builtin
+fly.std = fly.structure(
+    "out", fly.fn("out", function(...) write(io.stdout, ...) end),
+    "err", fly.fn("err", function(...) write(io.stderr, ...) end),
+    "len", fly.fn("len", length),
+    "type", fly.fn("type", fly.type)
+)
+
+-- Raise an error if one of the given values is not truthy
+fly.assert = fly.fn("assert", function(...)
+    for i = 1, select("#", ...) do
+        if not fly.truthy((select(i, ...))) then
+            fail("Assertion failed.")
+        end
+    end
+end)
+
+return fly
@@ -0,0 +1,100 @@
+//! The built-in compilers, translating the parsed instructions to other languages.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+};
+
+use crate::flylang::{
+    compilers::{
+        c::C, errors::CompilationFailed, javascript::JavaScript, lua::Lua, python::Python,
+    },
+    errors::{LangResult, lang_err},
+    module::LangModule,
+    parser::ast::Branches,
+};
+
+pub mod c;
+pub mod errors;
+pub mod javascript;
+pub mod lua;
+pub mod python;
+
+/// A built-in compiler, selected with `flylang exec --parser <name>` or `flylang compile --parser <name>`
+pub trait Compiler {
+    /// The name used to select the compiler
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    /// The extension of the generated files
+    fn extension(&self) -> &'static str;
+
+    /// Generate the code of a module
+    fn compile(&self, module: &LangModule, branches: &Branches) -> LangResult<String>;
+
+    /// The files the generated code depends on (like a runtime library), as (file name, content).
+    /// They are written next to the generated files.
+    fn runtime(&self) -> Vec<(&'static str, &'static str)> {
+        vec![]
+    }
+
+    /// Execute a generated file
+    fn run(&self, file: &Path) -> LangResult<ExitStatus>;
+}
+
+/// Get every built-in compiler
+pub fn compilers() -> Vec<Box<dyn Compiler>> {
+    vec![
+        Box::new(JavaScript),
+        Box::new(Python),
+        Box::new(C),
+        Box::new(Lua),
+    ]
+}
+
+/// Get a built-in compiler by its name
+pub fn find(name: &str) -> Option<Box<dyn Compiler>> {
+    compilers()
+        .into_iter()
+        .find(|compiler| compiler.name() == name)
+}
+
+/// Generate the code of a module in the given folder, with the runtime files of the compiler.
+/// Returns the path of the generated file.
+pub fn write(
+    compiler: &dyn Compiler,
+    module: &LangModule,
+    branches: &Branches,
+    folder: &Path,
+) -> LangResult<PathBuf> {
+    let code = compiler.compile(module, branches)?;
+
+    let mut file = folder.join(module.path().file_stem().unwrap_or("module".as_ref()));
+    file.set_extension(compiler.extension());
+
+    let mut written = fs::create_dir_all(folder).and_then(|_| fs::write(&file, code));
+    for (name, content) in compiler.runtime() {
+        written = written.and_then(|_| fs::write(folder.join(name), content));
+    }
+
+    match written {
+        Ok(()) => Ok(file),
+        Err(error) => lang_err!(CompilationFailed {
+            compiler: compiler.name(),
+            reason: format!("the generated code cannot be written ({})", error),
+        }),
+    }
+}
+
+/// Generate the code of a module in a temporary folder, and execute it.
+/// Returns the exit code of the execution.
+pub fn execute(
+    compiler: &dyn Compiler,
+    module: &LangModule,
+    branches: &Branches,
+) -> LangResult<i32> {
+    let folder = std::env::temp_dir().join("flylang").join(compiler.name());
+    let file = write(compiler, module, branches, &folder)?;
+
+    Ok(compiler.run(&file)?.code().unwrap_or(1))
+}
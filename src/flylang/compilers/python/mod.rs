@@ -0,0 +1,1322 @@
+//! Translation of flylang to Python 3.
+//!
+//! The generated module imports its runtime library (`flylang_runtime.py`), which implements the parts of the
+//! flylang semantics Python does not share : truthiness, equality, display, modifiers, ...
+//!
+//! - Structures are dicts, arrays are lists and classes use the Python (C3) multiple inheritance.
+//! - Python statements cannot be written inside expressions : the functions and classes defined in an
+//!   expression are defined just before its statement, and the assignments become `:=` expressions.
+//! - Assigning a variable of an enclosing function declares it `nonlocal` (or `global`), even if the enclosing
+//!   function assigns it after the function.
+//! - Operators go through the runtime library, which raises the errors of the interpreter (with their code)
+//!   when the values do not fit.
+//! - Breakers Python cannot express (leaving an outer loop or a condition, or going out of a function) raise a
+//!   `_fly.Breaker`, caught by the scope they target.
+
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+
+use crate::flylang::{
+    analyser::{
+        Analyser,
+        symbols::{NamespaceId, NamespaceKind, Symbol, SymbolKind, SymbolTable},
+    },
+    compilers::{
+        Compiler,
+        errors::{CompilationFailed, UnresolvedBreaker, Unsupported},
+    },
+    errors::{LangResult, lang_err},
+    lexer::tokens::{
+        BinaryOperator, Comparison, Operator, ScopeTarget,
+        representations::number::NumberRepresentation,
+    },
+    module::{LangModule, slice::LangModuleSlice},
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::DefineClass,
+            functions::DefineFunction,
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::{Break, BreakKind},
+            conditionnal::{If, IfFallBack},
+            loops::{Loop, LoopParameter},
+            r#use::{Package, PackageContentEmplacement, PackageIncludedContent, PackageSource},
+        },
+    },
+    utils::breakers::{self, BreakableScope, ScopeKind},
+};
+
+/// The runtime library imported by the generated modules
+pub const RUNTIME: &str = include_str!("runtime.py");
+const RUNTIME_MODULE: &str = "flylang_runtime";
+
+const NAME: &str = "py";
+
+/// The keywords of Python (`keyword.kwlist`)
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// The names used by the generated code, that variables cannot take
+const RESERVED: &[&str] = &["__class__", "__file__", "globals", "staticmethod", "super"];
+
+/// The precedence of the Python operators, used to know where parentheses are needed
+mod precedence {
+    pub const TERNARY: u8 = 1;
+    pub const OR: u8 = 2;
+    pub const AND: u8 = 3;
+    pub const NOT: u8 = 4;
+    pub const COMPARISON: u8 = 5;
+    pub const ADDITIVE: u8 = 6;
+    pub const UNARY: u8 = 8;
+    pub const CALL: u8 = 10;
+    pub const PRIMARY: u8 = 11;
+}
+use precedence::*;
+
+/// The Python compiler, generating code executed by Python 3
+pub struct Python;
+
+impl Compiler for Python {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+    fn description(&self) -> &'static str {
+        "Translate the code to Python 3, executed with python3"
+    }
+    fn extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn compile(&self, _: &LangModule, branches: &Branches) -> LangResult<String> {
+        let analysis = Analyser::new().analyse(branches);
+        Generator::new(&analysis.symbols).module(branches)
+    }
+
+    fn runtime(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("flylang_runtime.py", RUNTIME)]
+    }
+
+    fn run(&self, file: &Path) -> LangResult<ExitStatus> {
+        match Command::new("python3").arg(file).status() {
+            Ok(status) => Ok(status),
+            Err(error) => lang_err!(CompilationFailed {
+                compiler: NAME,
+                reason: format!("python3 cannot be started ({})", error),
+            }),
+        }
+    }
+}
+
+/// A Python expression
+struct Code {
+    text: String,
+    precedence: u8,
+}
+impl Code {
+    fn new(text: impl Into<String>, precedence: u8) -> Self {
+        Self {
+            text: text.into(),
+            precedence,
+        }
+    }
+
+    /// Get the expression, surrounded by parentheses if its precedence is lower than `minimum`
+    fn at(self, minimum: u8) -> String {
+        match self.precedence < minimum {
+            true => format!("({})", self.text),
+            false => self.text,
+        }
+    }
+}
+
+/// A Python function being generated
+#[derive(Default)]
+struct Frame {
+    /// The variables declared by the function, exported by the module
+    declared: Vec<String>,
+    /// The variables bound in the function (its arguments included)
+    variables: Vec<String>,
+    /// The variables of the enclosing functions assigned by the function, with `true` for the module's ones
+    outer: Vec<(String, bool)>,
+    /// If the function is a method (directly written in a class)
+    method: bool,
+}
+
+/// A flylang scope containing the code being generated, that breakers can target
+struct Scope {
+    kind: ScopeKind,
+    name: Option<String>,
+    label: String,
+    /// The position of the function containing the scope, in the frames
+    frame: usize,
+    /// If a breaker raises a `_fly.Breaker` to this scope
+    raised: bool,
+}
+impl BreakableScope for Scope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+struct Generator<'a> {
+    symbols: &'a SymbolTable,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    imports: Vec<String>,
+    /// The statements to write before the statement being generated (the definitions of its expressions)
+    hoisted: Vec<String>,
+    /// Number of generated names, to make them unique
+    names: usize,
+}
+
+/// Convert a flylang name to a valid Python identifier.
+/// The names Python or the generated code reserve, and the names ending with `_`, are followed by `_`.
+fn identifier(name: &str) -> String {
+    let mut escaped: String = name
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '_' {
+            true => c.to_string(),
+            false => format!("_u{:x}", c as u32),
+        })
+        .collect();
+
+    if KEYWORDS.contains(&escaped.as_str())
+        || RESERVED.contains(&escaped.as_str())
+        || escaped.starts_with("_fly")
+        || escaped.ends_with('_')
+    {
+        escaped.push('_');
+    }
+    escaped
+}
+
+/// Convert the name of a member to the name of its Python attribute (the runtime library does the same)
+fn attribute(name: &str) -> String {
+    match KEYWORDS.contains(&name) || name.ends_with('_') {
+        true => format!("{}_", name),
+        false => name.to_string(),
+    }
+}
+
+fn string(content: &str) -> String {
+    serde_json::to_string(content).unwrap()
+}
+
+fn number(value: f64) -> Code {
+    let text = if value.is_nan() {
+        String::from("float(\"nan\")")
+    } else if value.is_infinite() {
+        String::from(if value > 0f64 {
+            "float(\"inf\")"
+        } else {
+            "-float(\"inf\")"
+        })
+    } else if value.fract() == 0f64 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:?}", value)
+    };
+
+    let precedence = match text.starts_with('-') {
+        true => UNARY,
+        false => PRIMARY,
+    };
+    Code::new(text, precedence)
+}
+
+/// Generate an infix operation. Operators are left-associative, and comparisons are not chained.
+fn infix(operator: &str, precedence: u8, (left, right): (Code, Code)) -> Code {
+    let (left, right) = match precedence {
+        COMPARISON => (left.at(precedence + 1), right.at(precedence + 1)),
+        _ => (left.at(precedence), right.at(precedence + 1)),
+    };
+    Code::new(format!("{} {} {}", left, operator, right), precedence)
+}
+
+/// Indent the lines of a block
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| format!("    {}", line))
+        .collect()
+}
+
+/// Indent the lines of a block, which cannot be empty in Python
+fn suite(lines: Vec<String>) -> Vec<String> {
+    match lines.is_empty() {
+        true => vec![String::from("    pass")],
+        false => indent(lines),
+    }
+}
+
+/// Returns if the expression always gives a boolean
+fn is_boolean(expression: &Expressions) -> bool {
+    match expression {
+        Expressions::Literal(ParsedLiterals::True | ParsedLiterals::False) => true,
+        Expressions::Operation(Operations::Binary(_) | Operations::Comparative(_)) => true,
+        Expressions::Reverse(reverse) => matches!(reverse.kind, ReverseKind::Boolean),
+        Expressions::Prioritized(inner) => is_boolean(inner.kind()),
+        Expressions::Typed(typed) => is_boolean(typed.expression.kind()),
+        _ => false,
+    }
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbols: &'a SymbolTable) -> Self {
+        Self {
+            symbols,
+            frames: vec![],
+            scopes: vec![],
+            imports: vec![],
+            hoisted: vec![],
+            names: 0,
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    /// Create a name for a generated element
+    fn generated(&mut self, kind: &str) -> String {
+        self.names += 1;
+        format!("_fly_{}{}", kind, self.names)
+    }
+
+    fn unsupported<R>(location: &LangModuleSlice, feature: &'static str) -> LangResult<R> {
+        lang_err!(Unsupported {
+            location: location.clone(),
+            compiler: NAME,
+            feature,
+        })
+    }
+
+    /// Generate a whole module
+    fn module(mut self, branches: &Branches) -> LangResult<String> {
+        self.frames.push(Frame::default());
+        let body = self.block(branches)?;
+        let frame = self.frames.pop().unwrap();
+
+        let mut lines = vec![
+            format!("import {} as _fly", RUNTIME_MODULE),
+            format!("from {} import std, assert_", RUNTIME_MODULE),
+        ];
+        lines.append(&mut self.imports);
+        lines.push(String::new());
+        lines.extend(body);
+        if !frame.declared.is_empty() {
+            let names: Vec<String> = frame.declared.iter().map(|name| string(name)).collect();
+            lines.push(String::new());
+            lines.push(format!("__all__ = [{}]", names.join(", ")));
+        }
+
+        Ok(lines.join("\n") + "\n")
+    }
+
+    /// Get the name of an assigned variable. The variable is bound in the current function if it owns it (see
+    /// `owner`), or declared `nonlocal` (or `global`) if it belongs to an enclosing function (or to the module).
+    fn assign(&mut self, location: &LangModuleSlice) -> String {
+        let name = identifier(location.code());
+        if let Some((symbol, owner)) = self.owner(location) {
+            let module = self.symbols.namespace(owner).kind == NamespaceKind::Module;
+            if symbol.namespace == owner && self.declares(location) {
+                if self.frames.len() == 1 {
+                    self.frame().declared.push(name.clone());
+                }
+                self.frame().variables.push(name.clone());
+            } else if self.frames.len() > 1 {
+                let outer = (name.clone(), module);
+                if !self.frame().outer.contains(&outer) {
+                    self.frame().outer.push(outer);
+                }
+            }
+            return name;
+        }
+        if self.frame().variables.contains(&name) {
+            return name;
+        }
+
+        let current = self.frames.len() - 1;
+        let owner = self.frames[..current]
+            .iter()
+            .rposition(|frame| frame.variables.contains(&name));
+        if let Some(owner) = owner {
+            let outer = (name.clone(), owner == 0);
+            if !self.frame().outer.contains(&outer) {
+                self.frame().outer.push(outer);
+            }
+        }
+        name
+    }
+
+    /// Find the namespace owning an assigned variable, the way the interpreter finds it while running : the
+    /// variable belongs to the outermost enclosing function (or module) assigning this name, even after the
+    /// assignment, unless a nearer function has it as argument.
+    fn owner(&self, location: &LangModuleSlice) -> Option<(&Symbol, NamespaceId)> {
+        let symbol = self.symbols.resolve(location)?;
+        let mut owner = symbol.namespace;
+        if symbol.kind == SymbolKind::Argument {
+            return Some((symbol, owner));
+        }
+
+        let mut namespace = self.symbols.namespace(owner).parent;
+        while let Some(current) = namespace {
+            let found = match self.symbols.namespace(current).kind {
+                NamespaceKind::Module | NamespaceKind::Function => {
+                    self.symbols.declared_in(current).find(|other| {
+                        other.name == symbol.name
+                            && !matches!(
+                                other.kind,
+                                SymbolKind::Builtin | SymbolKind::Receiver | SymbolKind::Member
+                            )
+                    })
+                }
+                NamespaceKind::Builtins | NamespaceKind::Class => None,
+            };
+            if let Some(found) = found {
+                owner = current;
+                if found.kind == SymbolKind::Argument {
+                    break;
+                }
+            }
+            namespace = self.symbols.namespace(current).parent;
+        }
+
+        Some((symbol, owner))
+    }
+
+    /// Returns if the assignment redefines a constant (or a receiver or a built-in element)
+    fn reassigns(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Constant | SymbolKind::Receiver | SymbolKind::Builtin
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_none_or(|declaration| declaration.range() != location.range())
+        })
+    }
+
+    /// Get the name a function or a class is defined with. Redefining a constant defines it with a generated
+    /// name, then raises the error.
+    fn definition(&mut self, location: &LangModuleSlice, kind: &str) -> (String, Option<String>) {
+        match self.reassigns(location) {
+            true => {
+                let name = self.generated(kind);
+                let error = format!("_fly.reassign({}, {})", string(location.code()), name);
+                (name, Some(error))
+            }
+            false => (self.assign(location), None),
+        }
+    }
+
+    /// Generate the statement binding a value to a loop variable
+    fn bind(&mut self, location: &LangModuleSlice, value: &str) -> String {
+        match self.reassigns(location) {
+            true => format!("_fly.reassign({}, {})", string(location.code()), value),
+            false => format!("{} = {}", self.assign(location), value),
+        }
+    }
+
+    /// Returns if the variable is declared at this location
+    fn declares(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Variable
+                    | SymbolKind::Constant
+                    | SymbolKind::Function
+                    | SymbolKind::Class
+                    | SymbolKind::Binding
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_some_and(|declaration| declaration.range() == location.range())
+        })
+    }
+
+    fn block(&mut self, branches: &Branches) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        for instruction in branches {
+            lines.extend(self.instruction(instruction)?);
+        }
+
+        Ok(lines)
+    }
+
+    fn instruction(&mut self, instruction: &Node) -> LangResult<Vec<String>> {
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => self.statement(expression, instruction.location()),
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repetition) => self.repeat(repetition),
+            Instructions::Break(breaker) => self.breaker(breaker),
+            Instructions::Use(package) => {
+                self.import(package);
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn statement(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Vec<String>> {
+        let (definable, modifiers) = match expression {
+            Expressions::Defined(definable) => (Some(definable), &[][..]),
+            Expressions::Modifed(modified) => {
+                (Some(modified.definable.kind()), &modified.modified_by[..])
+            }
+            _ => (None, &[][..]),
+        };
+
+        let line = match definable {
+            // Named functions and classes are only definitions
+            Some(Definables::Function(function)) if function.name.is_some() => {
+                self.definable(definable.unwrap(), modifiers)?;
+                None
+            }
+            Some(Definables::Class(_)) => {
+                self.definable(definable.unwrap(), modifiers)?;
+                None
+            }
+            Some(Definables::Variable(variable)) => {
+                let modifiers = self.modifiers(modifiers)?;
+                Some(self.variable(variable, modifiers, true)?.text)
+            }
+            _ => Some(self.expression(expression, location)?.text),
+        };
+
+        let mut lines = mem::take(&mut self.hoisted);
+        lines.extend(line);
+        Ok(lines)
+    }
+
+    /// Enter a scope, that breakers can target
+    fn enter(&mut self, kind: ScopeKind, target: &Option<Node<ScopeTarget>>) {
+        let name = match target.as_ref().map(|target| target.kind()) {
+            Some(ScopeTarget::Named(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let label = match &name {
+            Some(name) => name.clone(),
+            None => {
+                self.names += 1;
+                match kind {
+                    ScopeKind::Function => format!("fn{}", self.names),
+                    ScopeKind::Loop => format!("loop{}", self.names),
+                    ScopeKind::Condition => format!("if{}", self.names),
+                }
+            }
+        };
+
+        self.scopes.push(Scope {
+            kind,
+            name,
+            label,
+            frame: self.frames.len() - 1,
+            raised: false,
+        });
+    }
+
+    /// Leave the current scope. The breakers raised to it are caught around its body.
+    fn leave(&mut self, body: Vec<String>) -> Vec<String> {
+        let scope = self.scopes.pop().unwrap();
+        if !scope.raised {
+            return body;
+        }
+
+        let action = match scope.kind {
+            ScopeKind::Function => vec![String::from("return _fly_error.value")],
+            ScopeKind::Loop => vec![
+                String::from("if _fly_error.kind == \"stop\":"),
+                String::from("    break"),
+            ],
+            ScopeKind::Condition => vec![],
+        };
+
+        let mut lines = vec![String::from("try:")];
+        lines.extend(suite(body));
+        lines.push(String::from("except _fly.Breaker as _fly_error:"));
+        lines.push(format!(
+            "    if not _fly.caught(_fly_error, {}):",
+            string(&scope.label)
+        ));
+        lines.push(String::from("        raise"));
+        lines.extend(indent(action));
+
+        lines
+    }
+
+    /// Generate the instructions of a block (of a condition or a loop)
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &Branches,
+    ) -> LangResult<Vec<String>> {
+        self.enter(kind, target);
+        let body = self.block(branches);
+        Ok(self.leave(body?))
+    }
+
+    fn condition(&mut self, condition: &If) -> LangResult<Vec<String>> {
+        let test = self.test(&condition.condition)?.text;
+        let mut lines = mem::take(&mut self.hoisted);
+
+        let body = self.scoped(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            &condition.process,
+        )?;
+        lines.push(format!("if {}:", test));
+        lines.extend(suite(body));
+
+        match condition.fallback.as_ref().map(|fallback| fallback.kind()) {
+            None => {}
+            Some(IfFallBack::Process(target, process)) => {
+                let body = self.scoped(ScopeKind::Condition, target, process)?;
+                lines.push(String::from("else:"));
+                lines.extend(suite(body));
+            }
+            Some(IfFallBack::If(other)) => {
+                let mut other = self.condition(other.kind())?;
+                // The definitions of the other condition are only evaluated when it is reached
+                match other[0].starts_with("if ") {
+                    true => {
+                        other[0] = format!("el{}", other[0]);
+                        lines.extend(other);
+                    }
+                    false => {
+                        lines.push(String::from("else:"));
+                        lines.extend(indent(other));
+                    }
+                }
+            }
+        }
+
+        Ok(lines)
+    }
+
+    fn repeat(&mut self, repetition: &Loop) -> LangResult<Vec<String>> {
+        let mut lines = vec![];
+        // The instructions starting each iteration
+        let mut start = vec![];
+
+        match &repetition.parameter {
+            LoopParameter::Through(each) => {
+                let iterable = self.node(&each.iterable)?.text;
+                lines.append(&mut self.hoisted);
+
+                let mut names = vec![];
+                for (word, entry) in [(&each.index, "_fly_index"), (&each.item, "_fly_item")] {
+                    names.push(match word {
+                        Some(word) if !self.reassigns(word.location()) => {
+                            self.assign(word.location())
+                        }
+                        Some(word) => {
+                            start.push(self.bind(word.location(), entry));
+                            entry.to_string()
+                        }
+                        None => entry.to_string(),
+                    });
+                }
+                lines.push(format!(
+                    "for {}, {} in _fly.entries({}):",
+                    names[0], names[1], iterable
+                ));
+            }
+            LoopParameter::Conditionnaly(condition) => {
+                let test = self.test(&condition.condition)?;
+                let hoisted = mem::take(&mut self.hoisted);
+
+                let head = match &condition.iteration_number {
+                    None if hoisted.is_empty() => {
+                        lines.push(format!("while {}:", test.text));
+                        None
+                    }
+                    None => Some(String::from("while True:")),
+                    Some(_) => Some(String::from("for _fly_iteration in _fly.count():")),
+                };
+                // Otherwise, the condition is verified at the start of each iteration
+                if let Some(head) = head {
+                    lines.push(head);
+                    start = hoisted;
+                    start.push(format!("if not {}:", test.at(NOT + 1)));
+                    start.push(String::from("    break"));
+                }
+                // The iteration number is only bound when the body is executed, like in the interpreter
+                if let Some(word) = &condition.iteration_number {
+                    start.push(self.bind(word.location(), "_fly_iteration"));
+                }
+            }
+        }
+
+        let body = self.scoped(
+            ScopeKind::Loop,
+            &repetition.scope_target,
+            &repetition.process,
+        )?;
+        start.extend(body);
+        lines.extend(suite(start));
+        Ok(lines)
+    }
+
+    fn breaker(&mut self, breaker: &Break) -> LangResult<Vec<String>> {
+        let Ok(position) = breakers::resolve(&self.scopes, breaker) else {
+            return lang_err!(UnresolvedBreaker(breaker.keyword_location.clone()));
+        };
+        let (kind, value) = match &breaker.kind {
+            BreakKind::Stop(_) => ("stop", None),
+            BreakKind::Pass(_) => ("pass", None),
+            BreakKind::Return(_, value) => (
+                "return",
+                match value {
+                    Some(value) => Some(self.node(value)?.at(TERNARY)),
+                    None => None,
+                },
+            ),
+        };
+        let mut lines = mem::take(&mut self.hoisted);
+
+        // Python only leaves the current function and the innermost loop
+        let frame = self.frames.len() - 1;
+        let innermost_loop = self.scopes[position + 1..]
+            .iter()
+            .all(|scope| scope.kind != ScopeKind::Loop);
+        let scope = &mut self.scopes[position];
+        let line = match (&scope.kind, scope.frame == frame) {
+            (ScopeKind::Function, true) => match value {
+                Some(value) => format!("return {}", value),
+                None => String::from("return"),
+            },
+            (ScopeKind::Loop, true) if innermost_loop => match kind {
+                "stop" => String::from("break"),
+                _ => String::from("continue"),
+            },
+            _ => {
+                scope.raised = true;
+                let value = value.map_or(String::new(), |value| format!(", {}", value));
+                format!(
+                    "raise _fly.Breaker({}, \"{}\"{})",
+                    string(&scope.label),
+                    kind,
+                    value
+                )
+            }
+        };
+
+        lines.push(line);
+        Ok(lines)
+    }
+
+    fn import(&mut self, package: &Package) {
+        let module = match &package.source {
+            PackageSource::File(path) => {
+                let mut path = PathBuf::from(path);
+                path.set_extension("py");
+                let path = path.to_string_lossy().replace('\\', "/");
+                format!("_fly.load(__file__, {})", string(&path))
+            }
+            PackageSource::Package(name) => format!("_fly.package({})", string(name)),
+        };
+
+        match (&package.included, &package.emplacement) {
+            (_, PackageContentEmplacement::Variable(variable)) => self.imports.push(format!(
+                "{} = {}",
+                identifier(variable.location().code()),
+                module
+            )),
+            (PackageIncludedContent::Only(names), PackageContentEmplacement::Global) => {
+                let variable = self.generated("module");
+                self.imports.push(format!("{} = {}", variable, module));
+                for name in names {
+                    let name = identifier(name.location().code());
+                    self.imports
+                        .push(format!("{} = {}.{}", name, variable, name));
+                }
+            }
+            // The names of the module cannot be known : they are made global
+            (PackageIncludedContent::All, PackageContentEmplacement::Global) => self
+                .imports
+                .push(format!("globals().update(_fly.exports({}))", module)),
+        }
+    }
+
+    fn node(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        self.expression(node.kind(), node.location())
+    }
+
+    /// Generate an expression used as a condition
+    fn test(&mut self, node: &Node<Expressions>) -> LangResult<Code> {
+        let boolean = is_boolean(node.kind());
+        let code = self.node(node)?;
+
+        Ok(match boolean {
+            true => code,
+            false => Code::new(format!("_fly.truthy({})", code.text), CALL),
+        })
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match expression {
+            Expressions::Literal(literal) => self.literal(literal, location)?,
+            Expressions::Defined(definable) => self.definable(definable, &[])?,
+            Expressions::Modifed(modified) => {
+                self.definable(modified.definable.kind(), &modified.modified_by)?
+            }
+            Expressions::Read(property) => self.read(property)?,
+            Expressions::ReturnOf(call) => self.call(call)?,
+            Expressions::Reverse(reverse) => match reverse.kind {
+                ReverseKind::Boolean => {
+                    let operand = self.test(&reverse.expression)?.at(NOT);
+                    Code::new(format!("not {}", operand), NOT)
+                }
+                ReverseKind::Sign => {
+                    let operand = self.node(&reverse.expression)?.at(TERNARY);
+                    Code::new(format!("_fly.negate({})", operand), CALL)
+                }
+            },
+            Expressions::Operation(operation) => self.operation(operation)?,
+            Expressions::Prioritized(inner) => {
+                let inner = self.node(inner)?;
+                Code::new(inner.at(PRIMARY), PRIMARY)
+            }
+            Expressions::Ternary(ternary) => {
+                let condition = self.test(&ternary.condition)?.at(OR);
+                let yes = self.node(&ternary.yes)?.at(OR);
+                let no = self.node(&ternary.no)?.at(TERNARY);
+
+                Code::new(format!("{} if {} else {}", yes, condition, no), TERNARY)
+            }
+            Expressions::Structure(structure) => {
+                let mut entries = vec![];
+                for entry in &structure.entries {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    let key = match entry.key.kind() {
+                        Expressions::Literal(ParsedLiterals::Word) => {
+                            string(entry.key.location().code())
+                        }
+                        _ => self.node(&entry.key)?.at(TERNARY),
+                    };
+                    entries.push(format!("{}: {}", key, self.node(&entry.value)?.at(TERNARY)));
+                }
+
+                Code::new(format!("{{{}}}", entries.join(", ")), PRIMARY)
+            }
+            Expressions::Array(array) => {
+                let items = self.arguments(&array.entries)?;
+                Code::new(format!("[{}]", items), PRIMARY)
+            }
+            Expressions::Instanciate(instanciation) => {
+                let class = self.node(&instanciation.class)?.at(CALL);
+                let arguments = self.arguments(&instanciation.arguments)?;
+                Code::new(format!("{}({})", class, arguments), CALL)
+            }
+            // Types are verified by the checker
+            Expressions::Typed(typed) => self.node(&typed.expression)?,
+        })
+    }
+
+    fn arguments(&mut self, arguments: &[Box<Node<Expressions>>]) -> LangResult<String> {
+        let mut codes = vec![];
+        for argument in arguments {
+            codes.push(self.node(argument)?.at(TERNARY));
+        }
+
+        Ok(codes.join(", "))
+    }
+
+    fn literal(
+        &mut self,
+        literal: &ParsedLiterals,
+        location: &LangModuleSlice,
+    ) -> LangResult<Code> {
+        Ok(match literal {
+            ParsedLiterals::Word => {
+                let name = location.code();
+                let receiver = self
+                    .symbols
+                    .resolve(location)
+                    .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver);
+
+                match (receiver, name) {
+                    (true, "super") => {
+                        return Self::unsupported(
+                            location,
+                            "`super` outside of a call or a property",
+                        );
+                    }
+                    (true, _) => Code::new(name, PRIMARY),
+                    (false, _) => Code::new(identifier(name), PRIMARY),
+                }
+            }
+            ParsedLiterals::True => Code::new("True", PRIMARY),
+            ParsedLiterals::False => Code::new("False", PRIMARY),
+            ParsedLiterals::Empty => Code::new("None", PRIMARY),
+            ParsedLiterals::Number => number(NumberRepresentation::from(location).into()),
+            ParsedLiterals::Computed(value) => number(*value),
+            ParsedLiterals::String(items) => {
+                let mut parts = vec![];
+                for item in items {
+                    parts.push(match item.kind() {
+                        ParsedStringItem::Literal(content) => string(content),
+                        ParsedStringItem::Expression(expression) => {
+                            format!("_fly.display({})", self.node(expression)?.text)
+                        }
+                    });
+                }
+
+                match parts.len() {
+                    0 => Code::new("\"\"", PRIMARY),
+                    1 => Code::new(parts.remove(0), CALL),
+                    _ => Code::new(parts.join(" + "), ADDITIVE),
+                }
+            }
+        })
+    }
+
+    /// Returns if the expression is `super`
+    fn is_super(&self, node: &Node<Expressions>) -> bool {
+        matches!(node.kind(), Expressions::Literal(ParsedLiterals::Word))
+            && node.location().code() == "super"
+            && self
+                .symbols
+                .resolve(node.location())
+                .is_some_and(|symbol| symbol.kind == SymbolKind::Receiver)
+    }
+
+    /// Returns if the properties of the expression are Python attributes : it is `self`, `std` or a class
+    fn has_attributes(&self, node: &Node<Expressions>) -> bool {
+        matches!(node.kind(), Expressions::Literal(ParsedLiterals::Word))
+            && self.symbols.resolve(node.location()).is_some_and(|symbol| {
+                matches!(
+                    symbol.kind,
+                    SymbolKind::Receiver | SymbolKind::Builtin | SymbolKind::Class
+                )
+            })
+    }
+
+    /// Generate the object a property is read from.
+    /// `super` gives the methods following the current class in the method resolution order of `self`.
+    fn object(&mut self, node: &Node<Expressions>) -> LangResult<String> {
+        if !self.is_super(node) {
+            return Ok(self.node(node)?.at(CALL));
+        }
+
+        // Nested functions do not have the implicit arguments of `super`
+        Ok(match self.frame().method {
+            true => String::from("super()"),
+            false => String::from("super(__class__, self)"),
+        })
+    }
+
+    fn read(&mut self, property: &ReadProperty) -> LangResult<Code> {
+        let attributes = self.is_super(&property.from) || self.has_attributes(&property.from);
+        let from = self.object(&property.from)?;
+        let read = property.read.location();
+
+        let key = match property.read.kind() {
+            Property::Key if attributes => {
+                return Ok(Code::new(
+                    format!("{}.{}", from, attribute(read.code())),
+                    CALL,
+                ));
+            }
+            Property::Key => string(read.code()),
+            Property::Index => number(NumberRepresentation::from(read).into()).text,
+            Property::Expression(key) => self.node(key)?.at(TERNARY),
+        };
+        Ok(Code::new(format!("_fly.at({}, {})", from, key), CALL))
+    }
+
+    fn call(&mut self, call: &Call) -> LangResult<Code> {
+        // Calling `super` calls the constructor of the parents
+        let callable = match self.is_super(&call.callable) {
+            true => format!("{}._construct", self.object(&call.callable)?),
+            false => self.node(&call.callable)?.at(CALL),
+        };
+        let arguments = self.arguments(&call.arguments)?;
+
+        Ok(Code::new(format!("{}({})", callable, arguments), CALL))
+    }
+
+    /// Generate an operation implemented by the runtime library
+    fn helper(
+        &mut self,
+        name: &str,
+        (left, right): &(Box<Node<Expressions>>, Box<Node<Expressions>>),
+    ) -> LangResult<Code> {
+        let left = self.node(left)?.at(TERNARY);
+        let right = self.node(right)?.at(TERNARY);
+        Ok(Code::new(
+            format!("_fly.{}({}, {})", name, left, right),
+            CALL,
+        ))
+    }
+
+    fn operation(&mut self, operation: &Operations) -> LangResult<Code> {
+        Ok(match operation {
+            Operations::Numeric(numeric) => {
+                let name = match numeric.operator.kind() {
+                    Operator::Add => "add",
+                    Operator::Divide => "divide",
+                    Operator::Modulo => "modulo",
+                    Operator::EuclidianDivision => "quotient",
+                    Operator::Substract => "subtract",
+                    Operator::Multiply => "multiply",
+                    Operator::Power => "power",
+                };
+                self.helper(name, &numeric.operands)?
+            }
+            Operations::Binary(binary) => {
+                let (operator, precedence) = match binary.operator.kind() {
+                    BinaryOperator::And => ("and", AND),
+                    BinaryOperator::Or => ("or", OR),
+                    BinaryOperator::Xor => ("!=", COMPARISON),
+                };
+                let (left, right) = &binary.operands;
+                let operands = (self.test(left)?, self.test(right)?);
+                infix(operator, precedence, operands)
+            }
+            Operations::Comparative(comparison) => {
+                let name = match comparison.operator.kind() {
+                    Comparison::Equal => "equal",
+                    Comparison::Less(true) => "less",
+                    Comparison::Less(false) => "less_equal",
+                    Comparison::Greater(true) => "greater",
+                    Comparison::Greater(false) => "greater_equal",
+                };
+                self.helper(name, &comparison.operands)?
+            }
+        })
+    }
+
+    fn modifiers(&mut self, modifiers: &[Node<Modifier>]) -> LangResult<Vec<String>> {
+        let mut codes = vec![];
+        for modifier in modifiers {
+            codes.push(match modifier.kind() {
+                Modifier::DefinedElement => self
+                    .literal(&ParsedLiterals::Word, modifier.location())?
+                    .at(TERNARY),
+                // The modifier is the function returned by the call
+                Modifier::CallReturn(call) => self.call(call)?.text,
+            });
+        }
+
+        Ok(codes)
+    }
+
+    /// Wrap a function or a class with its modifiers
+    fn modify(name: &str, modifiers: &[String]) -> String {
+        format!("_fly.modify({}, {})", name, modifiers.join(", "))
+    }
+
+    /// Generate a definition used as an expression. Functions and classes are defined before the statement.
+    fn definable(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+    ) -> LangResult<Code> {
+        let modifiers = self.modifiers(modifiers)?;
+
+        let name = match definable {
+            Definables::Variable(variable) => return self.variable(variable, modifiers, false),
+            Definables::Function(function) => {
+                let (name, error) = match &function.name {
+                    Some(name) => self.definition(name.location(), "fn"),
+                    None => (self.generated("fn"), None),
+                };
+                let code = self.function(function, &name)?;
+                self.hoisted.extend(code);
+                (name, error)
+            }
+            Definables::Class(class) => {
+                let (name, error) = self.definition(class.name.location(), "class");
+                let code = self.class(class, &name)?;
+                self.hoisted.extend(code);
+                (name, error)
+            }
+        };
+
+        if !modifiers.is_empty() {
+            let modified = Self::modify(&name.0, &modifiers);
+            self.hoisted.push(format!("{} = {}", name.0, modified));
+        }
+        self.hoisted.extend(name.1);
+        Ok(Code::new(name.0, PRIMARY))
+    }
+
+    /// Generate the assignment of a variable, as a statement or as an expression
+    fn variable(
+        &mut self,
+        variable: &DefineVariable,
+        modifiers: Vec<String>,
+        statement: bool,
+    ) -> LangResult<Code> {
+        let value = self.value(variable, modifiers)?;
+        let emplacement = variable.emplacement.location();
+
+        Ok(match variable.emplacement.kind() {
+            VariableEmplacements::Scope if self.reassigns(emplacement) => Code::new(
+                format!("_fly.reassign({}, {})", string(emplacement.code()), value),
+                CALL,
+            ),
+            VariableEmplacements::Scope => {
+                let name = self.assign(emplacement);
+                match statement {
+                    true => Code::new(format!("{} = {}", name, value), TERNARY),
+                    false => Code::new(format!("({} := {})", name, value), PRIMARY),
+                }
+            }
+            VariableEmplacements::Property(property) => {
+                if self.is_super(&property.from) {
+                    return Self::unsupported(emplacement, "the assignment of a `super` property");
+                }
+
+                let attributes = self.has_attributes(&property.from);
+                let target = self.object(&property.from)?;
+                let read = property.read.location();
+                let key = match property.read.kind() {
+                    Property::Key if attributes && statement => {
+                        return Ok(Code::new(
+                            format!("{}.{} = {}", target, attribute(read.code()), value),
+                            TERNARY,
+                        ));
+                    }
+                    Property::Key => string(read.code()),
+                    Property::Index => number(NumberRepresentation::from(read).into()).text,
+                    Property::Expression(key) => self.node(key)?.at(TERNARY),
+                };
+                Code::new(format!("_fly.put({}, {}, {})", target, key, value), CALL)
+            }
+            VariableEmplacements::Any(_) => {
+                return Self::unsupported(emplacement, "the assignment of an expression");
+            }
+        })
+    }
+
+    /// Generate the value of a variable, with its modifiers applied
+    fn value(&mut self, variable: &DefineVariable, modifiers: Vec<String>) -> LangResult<String> {
+        let value = self.node(&variable.value)?.at(TERNARY);
+        if modifiers.is_empty() {
+            return Ok(value);
+        }
+
+        Ok(format!(
+            "_fly.modify_variable({}, {}, {}, {})",
+            string(variable.emplacement.location().code()),
+            match variable.readonly {
+                true => "True",
+                false => "False",
+            },
+            value,
+            modifiers.join(", ")
+        ))
+    }
+
+    /// Generate the body of a function, in its own frame
+    fn body(
+        &mut self,
+        function: &DefineFunction,
+        arguments: Vec<String>,
+        method: bool,
+    ) -> LangResult<Vec<String>> {
+        let hoisted = mem::take(&mut self.hoisted);
+        self.frames.push(Frame {
+            variables: arguments,
+            method,
+            ..Default::default()
+        });
+        self.enter(ScopeKind::Function, &function.scope_target);
+        let body = self.block(&function.execution);
+        let body = self.leave(body?);
+        let frame = self.frames.pop().unwrap();
+        self.hoisted = hoisted;
+
+        let mut lines = vec![];
+        for (global, keyword) in [(true, "global"), (false, "nonlocal")] {
+            let names: Vec<&str> = frame
+                .outer
+                .iter()
+                .filter(|(_, outer)| *outer == global)
+                .map(|(name, _)| name.as_str())
+                .collect();
+            if !names.is_empty() {
+                lines.push(format!("{} {}", keyword, names.join(", ")));
+            }
+        }
+        lines.extend(body);
+
+        Ok(lines)
+    }
+
+    /// The arguments of a function. They are optional, like in flylang.
+    fn arguments_of(function: &DefineFunction) -> Vec<String> {
+        function
+            .arguments
+            .iter()
+            .map(|argument| identifier(argument.kind().name.location().code()))
+            .collect()
+    }
+
+    /// Generate a `def` statement. `self` is the first argument of methods.
+    fn define(
+        &mut self,
+        function: &DefineFunction,
+        name: &str,
+        method: bool,
+    ) -> LangResult<Vec<String>> {
+        let arguments = Self::arguments_of(function);
+        let mut parameters: Vec<String> = arguments
+            .iter()
+            .map(|argument| format!("{}=None", argument))
+            .collect();
+        if method {
+            parameters.insert(0, String::from("self"));
+        }
+
+        let body = self.body(function, arguments, method)?;
+        let mut lines = vec![format!("def {}({}):", name, parameters.join(", "))];
+        lines.extend(suite(body));
+        Ok(lines)
+    }
+
+    fn function(&mut self, function: &DefineFunction, name: &str) -> LangResult<Vec<String>> {
+        self.define(function, name, false)
+    }
+
+    /// Generate a class, with the given name
+    fn class(&mut self, class: &DefineClass, name: &str) -> LangResult<Vec<String>> {
+        let parents: Vec<String> = class
+            .parents
+            .iter()
+            .map(|parent| identifier(parent.location().code()))
+            .collect();
+        let parents = match parents.is_empty() {
+            true => String::from("_fly.Instance"),
+            false => parents.join(", "),
+        };
+
+        let mut members = vec![];
+
+        // The statements of the class body are written in it
+        let hoisted = mem::take(&mut self.hoisted);
+        let mut attributes = vec![];
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            let name = self::attribute(item.item.emplacement.location().code());
+
+            match item.is_static {
+                true => {
+                    let modifiers = self.modifiers(&item.modifie_by)?;
+                    let value = self.value(&item.item, modifiers)?;
+                    members.append(&mut self.hoisted);
+                    members.push(format!("{} = {}", name, value));
+                }
+                false => attributes.push(item),
+            }
+        }
+        self.hoisted = hoisted;
+
+        // The attributes of the instances are initialised by a method
+        if !attributes.is_empty() {
+            let hoisted = mem::take(&mut self.hoisted);
+            self.frames.push(Frame {
+                method: true,
+                ..Default::default()
+            });
+            let mut body = vec![];
+            for item in attributes {
+                let modifiers = self.modifiers(&item.modifie_by)?;
+                let value = self.value(&item.item, modifiers)?;
+                body.append(&mut self.hoisted);
+                body.push(format!(
+                    "self.{} = {}",
+                    self::attribute(item.item.emplacement.location().code()),
+                    value
+                ));
+            }
+            self.frames.pop();
+            self.hoisted = hoisted;
+
+            members.push(String::from("def _attributes(self):"));
+            members.extend(indent(body));
+        }
+
+        if let Some(constructor) = &class.constructor {
+            members.extend(self.define(constructor.kind(), "_construct", true)?);
+        }
+
+        let mut modified = vec![];
+        for method in &class.methods {
+            let item = method.kind();
+            let name = attribute(
+                item.item
+                    .name
+                    .as_ref()
+                    .map_or("", |name| name.location().code()),
+            );
+            let modifiers = self.modifiers(&item.modifie_by)?;
+
+            match item.is_static {
+                true => {
+                    let mut function = self.define(&item.item, &name, false)?;
+                    match modifiers.is_empty() {
+                        true => members.push(String::from("@staticmethod")),
+                        // Static methods are read from the class : they are modified once
+                        false => function.push(format!(
+                            "{} = staticmethod({})",
+                            name,
+                            Self::modify(&name, &modifiers)
+                        )),
+                    }
+                    members.extend(function);
+                }
+                false => {
+                    members.extend(self.define(&item.item, &name, true)?);
+                    // Methods are modified each time they are bound to an instance
+                    if !modifiers.is_empty() {
+                        modified.push(format!("{}: [{}]", string(&name), modifiers.join(", ")));
+                    }
+                }
+            }
+        }
+        if !modified.is_empty() {
+            members.push(format!("_modifiers = {{{}}}", modified.join(", ")));
+        }
+
+        let mut lines = vec![format!("class {}({}):", name, parents)];
+        lines.extend(suite(members));
+        Ok(lines)
+    }
+}
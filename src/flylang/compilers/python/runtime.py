@@ -0,0 +1,451 @@
+# The runtime library of the Python code generated from flylang.
+# It implements the parts of the flylang semantics Python does not share (truthiness, equality, display, ...).
+
+import importlib
+import importlib.util
+import itertools
+import json
+import keyword
+import math
+import sys
+from pathlib import Path
+
+
+class FlyError(Exception):
+    """An error raised by the flylang semantics, with the code of the error the interpreter raises (if any)"""
+
+    def __init__(self, message, code=None):
+        super().__init__(message if code is None else f"[{code}] {message}")
+        self.code = code
+
+
+class Breaker(Exception):
+    """A breaker going out of a scope Python cannot leave directly, to the scope labelled `scope`"""
+
+    def __init__(self, scope, kind, value=None):
+        super().__init__(scope)
+        self.scope = scope
+        self.kind = kind
+        self.value = value
+
+
+def caught(error, scope):
+    """Returns if the breaker stops at the scope labelled `scope`"""
+    return error.scope == scope
+
+
+def attribute(name):
+    """The name of the Python attribute storing a member (the same escaping as the generated code)"""
+    return name + "_" if keyword.iskeyword(name) or name.endswith("_") else name
+
+
+class Modified:
+    """A function or a class wrapped with a modifier : calling it calls the modifier with the element and the arguments"""
+
+    def __init__(self, target, modifier):
+        self.target = target
+        self.modifier = modifier
+
+    def __call__(self, *args):
+        return self.modifier(self.target, list(args))
+
+    # The members of a modified class are the ones of the class
+    def __getattr__(self, name):
+        return getattr(self.target, name)
+
+    def __setattr__(self, name, value):
+        if name in ("target", "modifier"):
+            object.__setattr__(self, name, value)
+        else:
+            setattr(self.target, name, value)
+
+    # A modified class can be inherited
+    def __mro_entries__(self, bases):
+        return (unmodified(self),)
+
+
+def unmodified(value):
+    """Get an element without its modifiers"""
+    while isinstance(value, Modified):
+        value = value.target
+    return value
+
+
+def modify(target, *modifiers):
+    """Wrap a function or a class with modifiers. The first modifier is the most inner one."""
+    for modifier in modifiers:
+        target = Modified(target, modifier)
+    return target
+
+
+def modify_variable(name, constant, value, *modifiers):
+    """Apply the modifiers of a variable on its value"""
+    for modifier in modifiers:
+        value = modifier(name, [constant, value])
+    return value
+
+
+class Instance:
+    """The base of every flylang class"""
+
+    def __init__(self, *args):
+        classes = type(self).__mro__
+
+        # Methods read from an instance are modified each time they are bound to it
+        for name in {name for cls in classes for name in cls.__dict__.get("_modifiers", {})}:
+            owner = next(cls for cls in classes if name in cls.__dict__)
+            modifiers = owner.__dict__.get("_modifiers", {}).get(name, [])
+            setattr(self, name, modify(getattr(self, name), *modifiers))
+
+        # Attributes are initialised from the farthest ancestor to the class itself, then the constructor is called
+        for cls in reversed(classes):
+            if "_attributes" in cls.__dict__:
+                cls.__dict__["_attributes"](self)
+        self._construct(*args)
+
+    def _construct(self, *args):
+        if args:
+            raise FlyError(f"Too many arguments given. Expected at most 0 but found {len(args)}.", "E0021")
+
+
+def is_class(value):
+    value = unmodified(value)
+    return isinstance(value, type) and issubclass(value, Instance)
+
+
+def is_number(value):
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def type_of(value):
+    """The name of the value's type, as displayed to the user"""
+    value = unmodified(value)
+    if value is None:
+        return "empty"
+    if isinstance(value, bool):
+        return "boolean"
+    if is_number(value):
+        return "number"
+    if isinstance(value, str):
+        return "string"
+    if isinstance(value, list):
+        return "array"
+    if isinstance(value, dict):
+        return "structure"
+    if isinstance(value, Instance):
+        return "instance"
+    if is_class(value):
+        return "class"
+    return "function"
+
+
+def truthy(value):
+    """Returns if the value is considered as `true` in a condition"""
+    if isinstance(value, float) and math.isnan(value):
+        return False
+    return bool(value)
+
+
+def equal(a, b):
+    """Compare two values : primitives, arrays and structures by their content, the others by reference"""
+    a = unmodified(a)
+    b = unmodified(b)
+    if is_number(a) and is_number(b):
+        return a == b
+    if isinstance(a, list) and isinstance(b, list):
+        return len(a) == len(b) and all(equal(x, y) for x, y in zip(a, b))
+    if isinstance(a, dict) and isinstance(b, dict):
+        return len(a) == len(b) and all(k in b and equal(v, b[k]) for k, v in a.items())
+    if type(a) is not type(b):
+        return False
+    if isinstance(a, (bool, str)) or a is None:
+        return a == b
+    # Bound methods are created each time they are read
+    if hasattr(a, "__self__") and hasattr(b, "__self__"):
+        return a.__self__ is b.__self__ and a.__func__ is b.__func__
+    return a is b
+
+
+def function_name(function):
+    name = getattr(function, "__name__", "anonymous")
+    return "anonymous" if name.startswith("_fly") else name
+
+
+def display(value):
+    """Convert a value to a string, the way `std.out` displays it"""
+    value = unmodified(value)
+    if value is None:
+        return "()"
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    if is_number(value):
+        if isinstance(value, float):
+            if math.isnan(value):
+                return "NaN"
+            if math.isinf(value):
+                return "inf" if value > 0 else "-inf"
+            if value.is_integer() and abs(value) < 1e15:
+                return str(int(value))
+        return str(value)
+    if isinstance(value, str):
+        return value
+    if isinstance(value, list):
+        return "{" + ", ".join(repr_(item) for item in value) + "}"
+    if isinstance(value, dict):
+        if not value:
+            return "{:}"
+        return "{" + ", ".join(f"{key_repr(k)}: {repr_(v)}" for k, v in value.items()) + "}"
+    if isinstance(value, Instance):
+        return f"<{type(value).__name__} instance>"
+    if is_class(value):
+        return f"<cs {value.__name__}>"
+    if hasattr(value, "__self__") and isinstance(value.__self__, Instance):
+        owner = next(cls for cls in type(value.__self__).__mro__ if value.__name__ in cls.__dict__)
+        return f"<fn {owner.__name__}.{value.__name__}>"
+    return f"<fn {function_name(value)}>"
+
+
+def repr_(value):
+    """Same as `display`, but strings are quoted (used inside arrays and structures)"""
+    return json.dumps(value, ensure_ascii=False) if isinstance(value, str) else display(value)
+
+
+def key_repr(key):
+    if isinstance(key, str) and key and not key[0].isdigit() and all(c.isalnum() or c == "_" for c in key):
+        return key
+    return repr_(key)
+
+
+def invalid(operator, *operands):
+    return FlyError(
+        f"The operation {operator} cannot be applied to {' and '.join(map(type_of, operands))}.",
+        "E0019",
+    )
+
+
+def add(a, b):
+    """The `+` operator : adds numbers, or concatenates strings"""
+    if is_number(a) and is_number(b):
+        return a + b
+    if isinstance(a, str) or isinstance(b, str):
+        return display(a) + display(b)
+    raise invalid("+", a, b)
+
+
+def numbers(operator, a, b):
+    if not is_number(a) or not is_number(b):
+        raise invalid(operator, a, b)
+
+
+def subtract(a, b):
+    """The `-` operator"""
+    numbers("-", a, b)
+    return a - b
+
+
+def multiply(a, b):
+    """The `*` operator"""
+    numbers("*", a, b)
+    return a * b
+
+
+def power(a, b):
+    """The `**` operator (a negative number raised to a fractional power gives NaN, like in flylang)"""
+    numbers("**", a, b)
+    try:
+        result = a**b
+    except ZeroDivisionError:
+        return math.inf
+    except OverflowError:
+        return math.inf if a > 0 or b % 2 == 0 else -math.inf
+    return math.nan if isinstance(result, complex) else result
+
+
+def negate(value):
+    """The `-` sign"""
+    if not is_number(value):
+        raise invalid("-", value)
+    return -value
+
+
+def compare(operator, a, b):
+    """Compare two numbers or two strings : returns a negative number, zero or a positive number"""
+    ordered = (is_number(a) and is_number(b) and not math.isnan(a) and not math.isnan(b)) or (
+        isinstance(a, str) and isinstance(b, str)
+    )
+    if not ordered:
+        raise invalid(operator, a, b)
+    return (a > b) - (a < b)
+
+
+def less(a, b):
+    """The `<` operator"""
+    return compare("<", a, b) < 0
+
+
+def less_equal(a, b):
+    """The `<=` operator"""
+    return compare("<=", a, b) <= 0
+
+
+def greater(a, b):
+    """The `>` operator"""
+    return compare(">", a, b) > 0
+
+
+def greater_equal(a, b):
+    """The `>=` operator"""
+    return compare(">=", a, b) >= 0
+
+
+def divisor(operator, a, b):
+    if not is_number(a) or not is_number(b):
+        raise invalid(operator, a, b)
+    if b == 0:
+        raise FlyError("Division by zero.", "E0020")
+    return b
+
+
+def divide(a, b):
+    """The `/` operator"""
+    return a / divisor("/", a, b)
+
+
+def modulo(a, b):
+    """The `%` operator (the result has the sign of `a`)"""
+    b = divisor("%", a, b)
+    result = math.fmod(a, b)
+    return int(result) if isinstance(a, int) and isinstance(b, int) else result
+
+
+def quotient(a, b):
+    """The `//` operator (euclidian division)"""
+    b = divisor("//", a, b)
+    q = math.trunc(a / b)
+    if math.fmod(a, b) < 0:
+        return q - 1 if b > 0 else q + 1
+    return q
+
+
+def position(key, length):
+    if not is_number(key):
+        raise FlyError(f"Expected a number as index, but found {type_of(key)}.", "E0028")
+
+    index = length + key if key < 0 else key
+    if index != int(index) or not 0 <= index < length:
+        raise FlyError(f"The index {display(key)} is out of range (length {length}).", "E0027")
+    return int(index)
+
+
+def at(source, key):
+    """Read a property. Negative indexes start from the end of arrays and strings."""
+    source = unmodified(source)
+    if isinstance(source, (list, str)):
+        return source[position(key, len(source))]
+    if isinstance(source, dict):
+        if key not in source:
+            raise FlyError(f"The property {repr_(key)} is not defined.", "E0025")
+        return source[key]
+    if source is None:
+        raise FlyError(f"Cannot read the property {repr_(key)} of empty.", "E0026")
+    try:
+        return getattr(source, attribute(key))
+    except (AttributeError, TypeError):
+        raise FlyError(f"The property {repr_(key)} is not defined.", "E0025") from None
+
+
+def put(target, key, value):
+    """Write a property. Writing right after the last item of an array appends it."""
+    target = unmodified(target)
+    if isinstance(target, list):
+        if key == len(target):
+            target.append(value)
+        else:
+            target[position(key, len(target))] = value
+    elif isinstance(target, dict):
+        target[key] = value
+    elif target is not None and not isinstance(target, (bool, int, float, str)):
+        setattr(target, attribute(key), value)
+    else:
+        raise FlyError(f"Cannot write the property {repr_(key)} of {type_of(target)}.", "E0026")
+    return value
+
+
+def entries(iterable):
+    """
+    Get the (index, item) couples an `each` loop goes through : the items of arrays and strings with their
+    position, the values of structures with their key, and the numbers from 0 to `n` (excluded).
+    """
+    iterable = unmodified(iterable)
+    if isinstance(iterable, (list, str)):
+        return list(enumerate(iterable))
+    if is_number(iterable):
+        return [(n, n) for n in range(math.ceil(iterable))]
+    if isinstance(iterable, dict):
+        return list(iterable.items())
+    raise FlyError(f"Cannot go through {type_of(iterable)}.", "E0028")
+
+
+def count():
+    """The iteration numbers of a `while` loop"""
+    return itertools.count()
+
+
+def load(module, path):
+    """Import a file generated from flylang, from its path relative to the importing module"""
+    file = (Path(module).parent / path).resolve()
+    name = f"_fly_module_{abs(hash(str(file)))}"
+    if name not in sys.modules:
+        spec = importlib.util.spec_from_file_location(name, file)
+        sys.modules[name] = importlib.util.module_from_spec(spec)
+        spec.loader.exec_module(sys.modules[name])
+    return sys.modules[name]
+
+
+def package(name):
+    """Import a Python package"""
+    return importlib.import_module(name)
+
+
+def exports(module):
+    """The names declared by an imported module"""
+    return {name: getattr(module, name) for name in getattr(module, "__all__", [])}
+
+
+def length(value):
+    """Get the number of items in an array, a structure or a string"""
+    if isinstance(value, (list, dict, str)):
+        return len(value)
+    raise FlyError(f"Expected array, structure or string, but found {type_of(value)}.", "E0028")
+
+
+def reassign(name, value):
+    """
+    Raise the error of the assignment of a constant (or of a receiver or a built-in element). The assigned value
+    is computed before, like the interpreter does.
+    """
+    raise FlyError(f"{json.dumps(name)} is a constant and cannot be reassigned.", "E0029")
+
+
+class Std:
+    """The `std` builtin"""
+
+    @staticmethod
+    def out(*values):
+        print(" ".join(map(display, values)))
+
+    @staticmethod
+    def err(*values):
+        print(" ".join(map(display, values)), file=sys.stderr)
+
+    len = staticmethod(length)
+    type = staticmethod(type_of)
+
+
+std = Std()
+
+
+def assert_(*values):
+    """Raise an error if one of the given values is not truthy"""
+    if not all(map(truthy, values)):
+        raise FlyError("Assertion failed.", "E0023")
@@ -0,0 +1,159 @@
+//! Concrete syntax tree of a module.
+//!
+//! Unlike the parsed instructions, the concrete syntax tree keeps every character of the code : its tokens are grouped
+//! by the scopes (`(...)` and `{...}`) they are in, and each of them keeps its trivia (the whitespace and the comments
+//! preceding it). The code of the module is rebuilt from the tree byte-for-byte, so the tools rewriting the code
+//! (like the formatter) can keep what the parser ignores.
+
+use std::{fmt::Display, rc::Rc};
+
+use crate::flylang::{
+    errors::Diagnostics,
+    lexer::{
+        Lexer,
+        tokens::{Toggleable, Token, Tokens, Trivia},
+    },
+    module::LangModule,
+};
+
+#[derive(Debug, Clone)]
+pub enum CstNode {
+    Token(Token),
+    Scope(CstScope),
+}
+
+/// A `(...)` or `{...}` scope, and the nodes inside it
+#[derive(Debug, Clone)]
+pub struct CstScope {
+    pub opening: Token,
+    pub content: Vec<CstNode>,
+    /// The token closing the scope, or None if the scope is not closed
+    pub closing: Option<Token>,
+}
+
+#[derive(Debug)]
+pub struct Cst {
+    module: Rc<LangModule>,
+    nodes: Vec<CstNode>,
+    /// The trivia following the last token of the module
+    trailing: Vec<Token<Trivia>>,
+    /// The errors found while lexing the module
+    diagnostics: Diagnostics,
+}
+
+impl Cst {
+    /// Build the concrete syntax tree of a module.
+    /// The errors found in the code are collected (see `diagnostics`) : the tree is built anyway, the invalid
+    /// characters being kept as trivia.
+    pub fn new(module: &Rc<LangModule>) -> Self {
+        let mut lexer = Lexer::new_with_trivia(module);
+        let tokens = lexer.tokenize().clone();
+
+        // The opened scopes, the last one containing the current nodes
+        let mut scopes: Vec<CstScope> = vec![];
+        let mut nodes = vec![];
+
+        for token in tokens {
+            match token.kind() {
+                Tokens::Block(Toggleable::Openning) | Tokens::Object(Toggleable::Openning) => {
+                    scopes.push(CstScope {
+                        opening: token,
+                        content: vec![],
+                        closing: None,
+                    });
+                }
+                Tokens::Block(Toggleable::Closing) | Tokens::Object(Toggleable::Closing)
+                    if !scopes.is_empty() =>
+                {
+                    let mut scope = scopes.pop().unwrap();
+                    scope.closing = Some(token);
+                    match scopes.last_mut() {
+                        Some(parent) => parent.content.push(CstNode::Scope(scope)),
+                        None => nodes.push(CstNode::Scope(scope)),
+                    }
+                }
+                _ => match scopes.last_mut() {
+                    Some(scope) => scope.content.push(CstNode::Token(token)),
+                    None => nodes.push(CstNode::Token(token)),
+                },
+            }
+        }
+
+        // The scopes which are not closed
+        while let Some(scope) = scopes.pop() {
+            match scopes.last_mut() {
+                Some(parent) => parent.content.push(CstNode::Scope(scope)),
+                None => nodes.push(CstNode::Scope(scope)),
+            }
+        }
+
+        Self {
+            module: Rc::clone(module),
+            nodes,
+            trailing: lexer.trailing().clone(),
+            diagnostics: lexer.take_diagnostics(),
+        }
+    }
+
+    /// Get the module of the tree
+    pub fn module(&self) -> &Rc<LangModule> {
+        &self.module
+    }
+    /// Get the nodes at the root of the module
+    pub fn nodes(&self) -> &Vec<CstNode> {
+        &self.nodes
+    }
+    /// Get the trivia following the last token of the module
+    pub fn trailing(&self) -> &Vec<Token<Trivia>> {
+        &self.trailing
+    }
+    /// Get the errors found in the code of the module
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+    /// Take the errors found in the code of the module
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Get every token of the tree, in the order of the code
+    pub fn tokens(&self) -> Vec<&Token> {
+        fn collect<'a>(nodes: &'a [CstNode], tokens: &mut Vec<&'a Token>) {
+            for node in nodes {
+                match node {
+                    CstNode::Token(token) => tokens.push(token),
+                    CstNode::Scope(scope) => {
+                        tokens.push(&scope.opening);
+                        collect(&scope.content, tokens);
+                        tokens.extend(&scope.closing);
+                    }
+                }
+            }
+        }
+
+        let mut tokens = vec![];
+        collect(&self.nodes, &mut tokens);
+        tokens
+    }
+
+    /// Get the code of the tree, identical to the code of its module
+    pub fn code(&self) -> String {
+        let mut code = String::new();
+        for token in self.tokens() {
+            for trivia in token.trivia() {
+                code.push_str(trivia.location().code());
+            }
+            code.push_str(token.location().code());
+        }
+        for trivia in &self.trailing {
+            code.push_str(trivia.location().code());
+        }
+        code
+    }
+}
+
+impl Display for Cst {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
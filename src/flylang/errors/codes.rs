@@ -0,0 +1,197 @@
+//! Registry of the error codes.
+//!
+//! Each error has a unique code (shown as `E0003`), given by its `_id` method. A code is never reused nor changed, so
+//! it can be relied on by the tools reading the errors (see `docs/errors.md`).
+
+use std::{fmt::Display, str::FromStr};
+
+/// Define the codes, and the list of every code
+macro_rules! registry {
+    ($($(#[$meta:meta])* $name:ident = $number:literal,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum ErrorId {
+            $($(#[$meta])* $name = $number,)*
+        }
+
+        impl ErrorId {
+            /// Every code, in their order
+            pub const ALL: &[ErrorId] = &[$(ErrorId::$name,)*];
+        }
+    };
+}
+
+registry! {
+    // Lexer
+    /// A character cannot be part of any token
+    UnknownCharacter = 1,
+    /// A character is not expected here (like a closing character without an opened scope)
+    UnexpectedCharacter = 2,
+    /// A scope is closed by the closing character of another kind of scope
+    InvalidScopeEnding = 3,
+    /// A scope is still opened at the end of the code
+    UnclosedScope = 4,
+
+    // Parser
+    /// An instruction is not expected here
+    UnexpectedNode = 5,
+    /// A token is not expected here
+    UnexpectedToken = 6,
+    /// Something else is expected after a part of the code
+    Expected = 7,
+    /// A scope does not contain anything
+    EmptyScope = 8,
+    /// An expression is not valid
+    UnableToParse = 9,
+
+    // Analyser
+    /// A name is used but never defined
+    UndefinedVariable = 10,
+    /// A breaker targets a scope which does not exist
+    UnknownScope = 11,
+    /// A breaker targets a scope of the wrong kind
+    InvalidScopeTarget = 12,
+    /// A breaker is used outside of the scopes it can stop
+    MisplacedBreaker = 13,
+
+    // Checker
+    /// A value does not have the type it is declared with
+    TypeMismatch = 14,
+    /// A type does not exist
+    UnknownType = 15,
+    /// An operator cannot be applied on the types of its operands
+    InvalidOperands = 16,
+
+    // Interpreter (and virtual machine)
+    /// A variable read while running the code is not defined
+    RuntimeUndefinedVariable = 17,
+    /// A value which is not a function nor a class is called
+    NotCallable = 18,
+    /// An operator cannot be applied on the values of its operands
+    InvalidOperation = 19,
+    /// A number is divided by zero
+    DivisionByZero = 20,
+    /// A function is called with more arguments than it accepts
+    TooManyArguments = 21,
+    /// A breaker is used outside of the scopes it can stop, while running the code
+    UncaughtBreaker = 22,
+    /// An assertion is false
+    AssertionFailed = 23,
+    /// A feature is not supported by the interpreter
+    UnsupportedByInterpreter = 24,
+    /// A property read on a value is not defined
+    UndefinedProperty = 25,
+    /// A value has no property with this kind of key
+    InvalidProperty = 26,
+    /// An index is outside of a list
+    IndexOutOfRange = 27,
+    /// A value does not have the expected type
+    UnexpectedType = 28,
+    /// A constant is defined again
+    ConstantReassignment = 29,
+    /// A breaker targets a scope which is not executing
+    RuntimeUnknownScope = 30,
+    /// A breaker targets an executing scope of the wrong kind
+    RuntimeInvalidScopeTarget = 31,
+    /// The parents of a class cannot be ordered (there is no consistent method resolution order)
+    InvalidInheritance = 32,
+    /// A member of a class is accessed from where it is not accessible
+    InaccessibleMember = 33,
+
+    // Compilers
+    /// A compiler cannot compile the code
+    CompilationFailed = 34,
+    /// A feature is not supported by a compiler
+    UnsupportedByCompiler = 35,
+    /// The scope stopped by a breaker cannot be found while compiling
+    UnresolvedBreaker = 36,
+
+    // Modules
+    /// The entry point is not a file
+    InvalidEntryPoint = 37,
+    /// A module does not have the `.fly` extension
+    WeirdExtension = 38,
+    /// The cache of a module cannot be written
+    CacheNotWritten = 39,
+
+    // Formatter
+    /// Files are not formatted (with `flylang fmt --check`)
+    NotFormatted = 40,
+    /// A formatted file cannot be written
+    FormattedNotWritten = 41,
+
+    // Behaviors
+    /// A behavior file cannot be read
+    InvalidPath = 42,
+    /// A behavior file does not exist
+    PathNotFound = 43,
+    /// A behavior file is not a valid TOML file
+    InvalidBehaviorSyntax = 44,
+    /// A behavior has an invalid value
+    InvalidKeyValue = 45,
+
+    // CLI
+    /// A parser is not installed
+    UnknownParser = 46,
+    /// A parser is not a compiler
+    NotACompiler = 47,
+
+    // Add-ons
+    /// An add-on is not valid
+    InvalidAddon = 48,
+    /// An add-on cannot be found
+    AddonNotFound = 49,
+    /// An add-on failed
+    AddonFailed = 50,
+    /// An add-on is already installed
+    AddonAlreadyInstalled = 51,
+    /// There is no folder to install the add-ons in
+    NoAddonsFolder = 52,
+
+    // Packages (interpreter and virtual machine)
+    /// The module of a package used with `use` cannot be found
+    PackageNotFound = 53,
+    /// A module is used while it is being loaded : modules use each other
+    CircularUse = 54,
+}
+
+impl ErrorId {
+    /// The codes of the diagnostics which can be reported as warnings : the other ones are always errors
+    pub const WARNINGS: &[ErrorId] = &[
+        ErrorId::EmptyScope,
+        ErrorId::WeirdExtension,
+        ErrorId::CacheNotWritten,
+        ErrorId::PathNotFound,
+        ErrorId::InvalidKeyValue,
+        ErrorId::InvalidAddon,
+        ErrorId::AddonAlreadyInstalled,
+    ];
+
+    /// If the diagnostics of this code can be reported as warnings (see `WARNINGS`)
+    pub fn is_warning(&self) -> bool {
+        Self::WARNINGS.contains(self)
+    }
+    /// Get the number of the code
+    pub fn number(&self) -> u16 {
+        *self as u16
+    }
+}
+
+/// Parse a code shown as `E0003`
+impl FromStr for ErrorId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number: u16 = s.strip_prefix('E').ok_or(())?.parse().map_err(|_| ())?;
+        Self::ALL
+            .iter()
+            .find(|id| id.number() == number)
+            .copied()
+            .ok_or(())
+    }
+}
+
+impl Display for ErrorId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{:04}", self.number())
+    }
+}
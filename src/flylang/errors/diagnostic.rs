@@ -0,0 +1,369 @@
+//! Rendering of the errors.
+//!
+//! An error is shown with its kind, its code and its message, followed by the lines of code it concerns : the part of
+//! the code causing the error is underlined with `^`, and the other parts related to it with `-` and the text of their
+//! label. Its notes and its help are written last.
+//!
+//! ```text
+//! Error[E0003]: Unclosed Block. Expected to close it but found "}".
+//!  --> main.fly:1:10
+//!   |
+//! 1 | x: f(1, 2}
+//!   |          ^
+//!   |     - this block is opened here
+//! ```
+//!
+//! The errors can also be printed as JSON objects (one per line), to be read by other tools.
+
+use std::{
+    fmt::Write,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde_json::{Value, json};
+
+use crate::flylang::{
+    errors::{ErrorType, RaisableErr},
+    module::{LangModule, slice::LangModuleSlice},
+};
+
+/// The number of spaces replacing a tabulation in the shown code
+const TAB_WIDTH: usize = 4;
+/// The lines of a part of the code spanning more lines are not all shown
+const MAX_SPANNED_LINES: usize = 4;
+
+static COLORED: AtomicBool = AtomicBool::new(false);
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Set if the errors are rendered with colors (disabled by default)
+pub fn set_colored(colored: bool) {
+    COLORED.store(colored, Ordering::Relaxed);
+}
+/// If the errors are rendered with colors
+pub fn colored() -> bool {
+    COLORED.load(Ordering::Relaxed)
+}
+/// Set if the errors are printed as JSON (see `serialize`) instead of being rendered (disabled by default)
+pub fn set_json(json: bool) {
+    JSON.store(json, Ordering::Relaxed);
+}
+/// If the errors are printed as JSON
+pub fn json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// A part of the code related to an error, explained by a text
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub location: LangModuleSlice,
+    pub text: String,
+}
+impl Label {
+    pub fn new(location: &LangModuleSlice, text: impl Into<String>) -> Self {
+        Self {
+            location: location.clone(),
+            text: text.into(),
+        }
+    }
+}
+
+/// The ANSI styles of the rendered parts
+mod style {
+    pub const ERROR: &str = "1;31";
+    pub const WARNING: &str = "1;33";
+    pub const HINT: &str = "1;36";
+    pub const GUTTER: &str = "1;34";
+    pub const BOLD: &str = "1";
+}
+
+fn paint(text: &str, style: &str, colored: bool) -> String {
+    match colored && !text.is_empty() {
+        true => format!("\x1b[{}m{}\x1b[0m", style, text),
+        false => text.to_string(),
+    }
+}
+
+fn kind_style(kind: &ErrorType) -> &'static str {
+    match kind {
+        ErrorType::Stop => style::ERROR,
+        ErrorType::Warn => style::WARNING,
+        ErrorType::Hint => style::HINT,
+    }
+}
+
+/// The width of a code once shown (the tabulations are replaced by spaces)
+fn width(code: &str) -> usize {
+    code.chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// A part of the code to underline, with the byte offsets of its range
+#[derive(Clone)]
+struct Span {
+    start: usize,
+    end: usize,
+    primary: bool,
+    text: Option<String>,
+}
+
+/// The lines of the code of a module, with their byte offsets
+struct Lines<'a> {
+    module: &'a LangModule,
+    code: &'a [u8],
+    starts: &'a [usize],
+}
+impl<'a> Lines<'a> {
+    fn new(module: &'a LangModule) -> Self {
+        Self {
+            module,
+            code: module.code().as_bytes(),
+            starts: module.line_starts(),
+        }
+    }
+
+    /// The line (starting at 0) containing the byte at the given offset
+    fn line(&self, offset: usize) -> usize {
+        self.module.line(offset)
+    }
+
+    /// The range of a line, without its line break
+    fn range(&self, line: usize) -> (usize, usize) {
+        let start = self.starts[line];
+        let mut end = self
+            .starts
+            .get(line + 1)
+            .map_or(self.code.len(), |next| next - 1);
+        if end > start && self.code[end - 1] == b'\r' {
+            end -= 1;
+        }
+        (start, end)
+    }
+
+    fn text(&self, start: usize, end: usize) -> String {
+        String::from_utf8_lossy(&self.code[start..end.max(start)])
+            .replace('\t', &" ".repeat(TAB_WIDTH))
+    }
+
+    /// The column (starting at 1) of the byte at the given offset
+    fn column(&self, offset: usize) -> usize {
+        let (start, _) = self.range(self.line(offset));
+        self.module.index(offset.max(start)) - self.module.index(start) + 1
+    }
+}
+
+/// Render the parts of the code of a module related to an error
+fn snippet(
+    output: &mut String,
+    module: &Rc<LangModule>,
+    spans: &[Span],
+    kind: &ErrorType,
+    colored: bool,
+) {
+    let lines = Lines::new(module);
+    let gutter = |text: &str| paint(text, style::GUTTER, colored);
+
+    // The lines to show, the first and last lines of a span covering too many lines
+    let mut shown: Vec<usize> = vec![];
+    for span in spans {
+        let (first, last) = (
+            lines.line(span.start),
+            lines.line(span.end.saturating_sub(1).max(span.start)),
+        );
+        if last - first < MAX_SPANNED_LINES {
+            shown.extend(first..=last);
+        } else {
+            shown.extend([first, first + 1, last]);
+        }
+    }
+    shown.sort();
+    shown.dedup();
+
+    let number_width = (shown.last().unwrap() + 1).to_string().len();
+    let blank = " ".repeat(number_width);
+
+    let first = &spans[0];
+    let _ = writeln!(
+        output,
+        "{}{} {}:{}:{}",
+        blank,
+        gutter("-->"),
+        module,
+        lines.line(first.start) + 1,
+        lines.column(first.start)
+    );
+    let _ = writeln!(output, "{} {}", blank, gutter("|"));
+
+    for (index, line) in shown.iter().enumerate() {
+        if index > 0 && shown[index - 1] + 1 < *line {
+            let _ = writeln!(output, "{}", gutter("..."));
+        }
+
+        let (start, end) = lines.range(*line);
+        let _ = writeln!(
+            output,
+            "{} {}",
+            gutter(&format!("{:>width$} |", line + 1, width = number_width)),
+            lines.text(start, end)
+        );
+
+        for span in spans {
+            let last = lines.line(span.end.saturating_sub(1).max(span.start));
+            if !(lines.line(span.start)..=last).contains(line) {
+                continue;
+            }
+
+            let from = span.start.max(start);
+            let to = span.end.min(end);
+            let offset = width(&lines.text(start, from));
+            let length = width(&lines.text(from, to)).max(1);
+
+            let (mark, style) = match span.primary {
+                true => ("^", kind_style(kind)),
+                false => ("-", style::GUTTER),
+            };
+            let mut underline = format!(
+                "{}{}",
+                " ".repeat(offset),
+                paint(&mark.repeat(length), style, colored)
+            );
+            if let Some(text) = span.text.as_ref().filter(|_| *line == last) {
+                underline.push(' ');
+                underline.push_str(&paint(text, style, colored));
+            }
+            let _ = writeln!(output, "{} {} {}", blank, gutter("|"), underline);
+        }
+    }
+}
+
+/// Get the error as it is printed : rendered, or as JSON (see `set_json`)
+pub fn output<E: RaisableErr + ?Sized>(error: &E) -> String {
+    match json() {
+        true => serialize(error).to_string(),
+        false => render(error, colored()),
+    }
+}
+
+/// Get the rendering of an error
+pub fn render<E: RaisableErr + ?Sized>(error: &E, colored: bool) -> String {
+    let kind = error.reported_kind().unwrap_or_else(|| error._kind());
+    let mut output = format!(
+        "{} {}\n",
+        paint(
+            &format!("{}[{}]:", kind, error._id()),
+            kind_style(&kind),
+            colored
+        ),
+        error._message()
+    );
+
+    let mut spans: Vec<(Rc<LangModule>, Span)> = vec![];
+    if let Some(location) = error._location() {
+        let range = location.range();
+        spans.push((
+            Rc::clone(location.module()),
+            Span {
+                start: range.start,
+                end: range.end,
+                primary: true,
+                text: None,
+            },
+        ));
+    }
+    for label in error._labels() {
+        let range = label.location.range();
+        spans.push((
+            Rc::clone(label.location.module()),
+            Span {
+                start: range.start,
+                end: range.end,
+                primary: false,
+                text: Some(label.text),
+            },
+        ));
+    }
+
+    // The spans of each module are shown together
+    let mut modules: Vec<Rc<LangModule>> = vec![];
+    for (module, _) in &spans {
+        if !modules
+            .iter()
+            .any(|shown| Rc::ptr_eq(shown, module) || shown == module)
+        {
+            modules.push(Rc::clone(module));
+        }
+    }
+    for module in &modules {
+        let spans: Vec<Span> = spans
+            .iter()
+            .filter(|(of, _)| Rc::ptr_eq(of, module) || of == module)
+            .map(|(_, span)| span.clone())
+            .collect();
+        snippet(&mut output, module, &spans, &kind, colored);
+    }
+
+    for note in error._notes() {
+        let _ = writeln!(
+            output,
+            " {} {}",
+            paint("= note:", style::BOLD, colored),
+            note
+        );
+    }
+    if let Some(help) = error._help() {
+        let _ = writeln!(
+            output,
+            " {} {}",
+            paint("= help:", style::BOLD, colored),
+            help
+        );
+    }
+
+    output.truncate(output.trim_end().len());
+    output
+}
+
+/// The position of a byte of a module : its offset, and its line and column (starting at 1)
+fn position(lines: &Lines, offset: usize) -> Value {
+    json!({
+        "byte": offset,
+        "line": lines.line(offset) + 1,
+        "column": lines.column(offset),
+    })
+}
+
+/// The location of a part of the code : the path of its module, and its start and (excluded) end positions
+fn location(slice: &LangModuleSlice) -> Value {
+    let lines = Lines::new(slice.module());
+    let range = slice.range();
+
+    json!({
+        "path": slice.module().path().display().to_string(),
+        "start": position(&lines, range.start),
+        "end": position(&lines, range.end),
+    })
+}
+
+/// Get an error as a JSON object
+pub fn serialize<E: RaisableErr + ?Sized>(error: &E) -> Value {
+    let labels: Vec<Value> = error
+        ._labels()
+        .iter()
+        .map(|label| json!({"location": location(&label.location), "text": label.text}))
+        .collect();
+
+    json!({
+        "severity": match error.reported_kind().unwrap_or_else(|| error._kind()) {
+            ErrorType::Stop => "error",
+            ErrorType::Warn => "warning",
+            ErrorType::Hint => "hint",
+        },
+        "code": error._id().to_string(),
+        "message": error._message(),
+        "location": error._location().as_ref().map(location),
+        "labels": labels,
+        "notes": error._notes(),
+        "help": error._help(),
+    })
+}
@@ -0,0 +1,147 @@
+//! Levels of the diagnostics.
+//!
+//! The warnings and the hints can be allowed (they are not reported), warned or denied (they are reported as errors,
+//! stopping the process) by their code, with the `language.diagnostics` behavior :
+//!
+//! ```toml
+//! [language.diagnostics]
+//! E0038 = "allow"
+//! E0039 = "deny"
+//! ```
+//!
+//! The errors always stop the process : their level cannot be changed.
+
+use std::{collections::BTreeMap, path::Path, str::FromStr, sync::RwLock};
+
+use toml::Value;
+
+use crate::{
+    behavior::{LangBehavior, errors::InvalidKeyValue},
+    flylang::errors::{ErrorType, RaisableErr, codes::ErrorId},
+};
+
+/// How a diagnostic is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Not reported
+    Allow,
+    /// Reported as a warning
+    Warn,
+    /// Reported as an error
+    Deny,
+}
+
+impl Level {
+    const NAMES: [&str; 3] = ["allow", "warn", "deny"];
+}
+
+impl FromStr for Level {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The levels of the diagnostics, by code
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Levels {
+    levels: BTreeMap<ErrorId, Level>,
+    deny_warnings: bool,
+}
+
+static LEVELS: RwLock<Levels> = RwLock::new(Levels::new());
+
+/// Set the levels the errors are reported with (every diagnostic keeps its own kind by default)
+pub fn set_levels(levels: Levels) {
+    *LEVELS.write().unwrap() = levels;
+}
+/// Get the kind an error is reported with, using the current levels (see `Levels::apply`)
+pub fn reported_kind(id: ErrorId, kind: ErrorType) -> Option<ErrorType> {
+    LEVELS.read().unwrap().apply(id, kind)
+}
+
+impl Levels {
+    const KEY: &str = "language.diagnostics";
+
+    pub const fn new() -> Self {
+        Self {
+            levels: BTreeMap::new(),
+            deny_warnings: false,
+        }
+    }
+
+    /// Set the level of a diagnostic
+    pub fn with(mut self, id: ErrorId, level: Level) -> Self {
+        self.levels.insert(id, level);
+        self
+    }
+    /// Report the warnings as errors (except the allowed ones)
+    pub fn deny_warnings(mut self, deny: bool) -> Self {
+        self.deny_warnings = deny;
+        self
+    }
+
+    /// Set the levels given by the `language.diagnostics` behavior.
+    /// The invalid entries (including the ones setting the level of an error) are reported and ignored.
+    pub fn configured(mut self, behavior: &LangBehavior, from_file: &Path) -> Self {
+        let invalid = |key: String, value: &Value, expected: Option<Vec<Value>>| InvalidKeyValue {
+            from_file: from_file.into(),
+            key,
+            value_found: Some(value.clone()),
+            expected,
+
+            kind: ErrorType::Warn,
+        };
+
+        let table = match behavior.get(Self::KEY) {
+            None => return self,
+            Some(Value::Table(table)) => table,
+            Some(value) => {
+                invalid(Self::KEY.into(), &value, None).controlled_raise();
+                return self;
+            }
+        };
+
+        for (code, value) in &table {
+            let key = format!("{}.{}", Self::KEY, code);
+            // The level of the errors cannot be changed
+            let Some(id) = code.parse::<ErrorId>().ok().filter(ErrorId::is_warning) else {
+                invalid(key, value, None).controlled_raise();
+                continue;
+            };
+            let Some(Ok(level)) = value.as_str().map(str::parse::<Level>) else {
+                let expected = Level::NAMES.map(|name| Value::String(name.into()));
+                invalid(key, value, Some(expected.into())).controlled_raise();
+                continue;
+            };
+
+            self.levels.insert(id, level);
+        }
+
+        self
+    }
+
+    /// Get the kind a diagnostic of the given kind is reported with, or `None` if it is allowed
+    pub fn apply(&self, id: ErrorId, kind: ErrorType) -> Option<ErrorType> {
+        if kind == ErrorType::Stop {
+            return Some(kind);
+        }
+
+        let kind = match self.levels.get(&id) {
+            Some(Level::Allow) => return None,
+            Some(Level::Warn) => ErrorType::Warn,
+            Some(Level::Deny) => ErrorType::Stop,
+            None => kind,
+        };
+        match kind {
+            ErrorType::Warn if self.deny_warnings => Some(ErrorType::Stop),
+            kind => Some(kind),
+        }
+    }
+}
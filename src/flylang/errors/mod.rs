@@ -1,5 +1,14 @@
 use std::fmt::{Debug, Display};
 
+use crate::flylang::{
+    errors::{codes::ErrorId, diagnostic::Label},
+    module::slice::LangModuleSlice,
+};
+
+pub mod codes;
+pub mod diagnostic;
+pub mod levels;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ErrorType {
     Warn,
@@ -18,7 +27,10 @@ impl Display for ErrorType {
 }
 
 pub trait RaisableErr {
+    /// The code identifying the error (see the `codes` registry)
+    fn _id(&self) -> ErrorId;
     fn _kind(&self) -> ErrorType;
+    /// The exit code of the process stopped by the error
     fn _code(&self) -> i32 {
         1
     }
@@ -26,8 +38,32 @@ pub trait RaisableErr {
     fn _message(&self) -> String {
         format!("{}", self._kind())
     }
+    /// The part of the code causing the error
+    fn _location(&self) -> Option<LangModuleSlice> {
+        None
+    }
+    /// The other parts of the code related to the error
+    fn _labels(&self) -> Vec<Label> {
+        vec![]
+    }
+    /// Additional explanations about the error
+    fn _notes(&self) -> Vec<String> {
+        vec![]
+    }
+    /// How the error can be fixed
+    fn _help(&self) -> Option<String> {
+        None
+    }
+    /// The kind the error is reported with, once its level is applied (see `levels`).
+    /// `None` if the error is allowed.
+    fn reported_kind(&self) -> Option<ErrorType> {
+        levels::reported_kind(self._id(), self._kind())
+    }
+    /// Print the error to the console, unless it is allowed
     fn print(&self) {
-        println!("{} ({}):\n{}", self._kind(), self._code(), self._message())
+        if self.reported_kind().is_some() {
+            println!("{}", diagnostic::output(self));
+        }
     }
     fn kill(&self) -> ! {
         #[cfg(debug_assertions)]
@@ -40,17 +76,17 @@ pub trait RaisableErr {
         }
     }
     fn raise(&self) -> ! {
-        self.print();
+        println!("{}", diagnostic::output(self));
         self.kill();
     }
-    /// Raise the error only if its `_kind()` if of type `Stop`.
+    /// Raise the error only if it is reported with the `Stop` kind (see `reported_kind`).
     /// Else, it just prints to the console.
     fn controlled_raise(&self) {
-        if self._kind() == ErrorType::Stop {
-            self.raise();
-        } else {
-            self.print();
-        };
+        match self.reported_kind() {
+            Some(ErrorType::Stop) => self.raise(),
+            Some(_) => self.print(),
+            None => {}
+        }
     }
 }
 
@@ -62,6 +98,61 @@ impl Debug for dyn RaisableErr {
 
 pub type LangResult<R> = Result<R, Box<dyn RaisableErr>>;
 
+/// The errors found while processing a code.
+/// They are collected instead of being raised, so every error of a code can be shown at once.
+#[derive(Debug, Default)]
+pub struct Diagnostics(Vec<Box<dyn RaisableErr>>);
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: Box<dyn RaisableErr>) {
+        self.0.push(error);
+    }
+    pub fn extend(&mut self, diagnostics: Diagnostics) {
+        self.0.extend(diagnostics.0);
+    }
+
+    /// Get the collected errors, in the order they were found
+    pub fn errors(&self) -> &Vec<Box<dyn RaisableErr>> {
+        &self.0
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// If one of the collected errors stops the process (see `RaisableErr::reported_kind`)
+    pub fn has_errors(&self) -> bool {
+        self.0
+            .iter()
+            .any(|error| error.reported_kind() == Some(ErrorType::Stop))
+    }
+
+    /// Print every collected error, then stop with the first one stopping the process (if any)
+    pub fn raise(self) {
+        for error in &self.0 {
+            error.print();
+        }
+        if let Some(error) = self
+            .0
+            .iter()
+            .find(|error| error.reported_kind() == Some(ErrorType::Stop))
+        {
+            error.kill();
+        }
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Box<dyn RaisableErr>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 macro_rules! lang_err {
     ($e:expr) => {
         Err(Box::new($e))
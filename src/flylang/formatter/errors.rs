@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::flylang::errors::{ErrorType, RaisableErr, codes::ErrorId};
+
+pub struct NotFormatted(pub Vec<PathBuf>);
+impl RaisableErr for NotFormatted {
+    fn _id(&self) -> ErrorId {
+        ErrorId::NotFormatted
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        let files: Vec<String> = self
+            .0
+            .iter()
+            .map(|file| format!("- {}", file.display()))
+            .collect();
+        format!(
+            "{} file(s) are not formatted:\n{}\nRun `flylang fmt` to format them.",
+            self.0.len(),
+            files.join("\n")
+        )
+    }
+}
+
+pub struct FormattedNotWritten {
+    pub path: PathBuf,
+    pub reason: String,
+}
+impl RaisableErr for FormattedNotWritten {
+    fn _id(&self) -> ErrorId {
+        ErrorId::FormattedNotWritten
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The formatted code cannot be written in ({}): {}.",
+            self.path.display(),
+            self.reason
+        )
+    }
+}
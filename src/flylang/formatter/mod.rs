@@ -0,0 +1,260 @@
+//! Formatter of the flylang code.
+//!
+//! The code is rewritten token by token : the tokens keep their text, but the spaces between them are normalized
+//! (a space around the operators, none before a `,`, `;` or `:`, ...). The line breaks of the code are kept (with at most
+//! one empty line between two lines), and the lines are indented by the scopes (`(...)` and `{...}`) they are in.
+//!
+//! The comments are read from the trivia of the tokens (see the concrete syntax tree), and written back as they are.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::flylang::{
+    cst::Cst,
+    errors::Diagnostics,
+    lexer::tokens::{
+        Keywords, Literals, Operator, Toggleable, Token, Tokens, Trivia, VarDefinition,
+    },
+    module::LangModule,
+};
+
+pub mod errors;
+
+/// The characters indenting a scope
+pub const INDENTATION: &str = "  ";
+/// The maximum number of following line breaks (one empty line)
+const LINE_BREAKS: usize = 2;
+
+/// If the token ends a value (an operator following it is not unary)
+fn is_value(token: &Tokens) -> bool {
+    matches!(
+        token,
+        Tokens::Literal(_)
+            | Tokens::Block(Toggleable::Closing)
+            | Tokens::Object(Toggleable::Closing)
+    )
+}
+
+/// If two following tokens are separated by a space.
+/// `unary` is true if the previous token is a unary `-`, and `joined` if the tokens were not separated in the code :
+/// some tokens are kept as they were, as joining or separating them could change how they are read.
+fn is_spaced(previous: &Tokens, next: &Tokens, unary: bool, joined: bool) -> bool {
+    if unary {
+        // A `-` joined to a number would be read as a negative number
+        if let Tokens::Literal(Literals::Number) = next {
+            return !joined;
+        }
+        return !matches!(
+            next,
+            Tokens::Literal(
+                Literals::Word | Literals::True | Literals::False | Literals::String(_)
+            ) | Tokens::Block(Toggleable::Openning)
+                | Tokens::Object(Toggleable::Openning)
+                | Tokens::Not
+        );
+    }
+
+    match (previous, next) {
+        // A `.` following a number could be read as its decimal part
+        (Tokens::Literal(Literals::Number), Tokens::Accessor) => !joined,
+        (
+            _,
+            Tokens::ArgSeparator
+            | Tokens::EndOfInstruction
+            | Tokens::Accessor
+            | Tokens::Block(Toggleable::Closing)
+            | Tokens::Object(Toggleable::Closing),
+        ) => false,
+        (Tokens::VarDef(_), Tokens::VarDef(_)) => true,
+        (_, Tokens::VarDef(VarDefinition::Normal | VarDefinition::Constant)) => false,
+        (
+            Tokens::Block(Toggleable::Openning)
+            | Tokens::Object(Toggleable::Openning)
+            | Tokens::Accessor
+            | Tokens::Not
+            | Tokens::Modifier,
+            _,
+        ) => false,
+        (
+            Tokens::Literal(Literals::Word)
+            | Tokens::Block(Toggleable::Closing)
+            | Tokens::Object(Toggleable::Closing)
+            | Tokens::ScopeTarget(_)
+            | Tokens::Keyword(
+                Keywords::Fn
+                | Keywords::Cs
+                | Keywords::Kind
+                | Keywords::If
+                | Keywords::Else
+                | Keywords::While
+                | Keywords::Until
+                | Keywords::Each,
+            ),
+            Tokens::Block(Toggleable::Openning),
+        ) => false,
+        _ => true,
+    }
+}
+
+/// The text of a token in the formatted code
+fn text(token: &Token) -> String {
+    match token.kind() {
+        Tokens::VarDef(VarDefinition::Normal) => String::from(":"),
+        Tokens::VarDef(VarDefinition::Constant) => String::from("::"),
+        Tokens::VarDef(VarDefinition::WithOperation(operator)) => {
+            format!("{}:", operator.location().code())
+        }
+        _ => token.location().code().to_string(),
+    }
+}
+
+/// Rewrite the code of a module with a canonical style
+#[derive(Debug, Default)]
+pub struct Formatter {
+    output: String,
+    /// The number of line breaks to write before the next token or comment
+    breaks: usize,
+    /// The indentation of the current line
+    line: usize,
+    /// The indentation of the lines opening the current scopes
+    scopes: Vec<usize>,
+    /// The previous token (None after a comment), and if it is a unary `-`
+    previous: Option<(Tokens, bool)>,
+}
+
+impl Formatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the formatted code of a module.
+    /// A code containing errors is not formatted : its errors are returned.
+    pub fn format(mut self, module: &Rc<LangModule>) -> Result<String, Diagnostics> {
+        let mut cst = Cst::new(module);
+        if cst.diagnostics().has_errors() {
+            return Err(cst.take_diagnostics());
+        }
+
+        for token in cst.tokens() {
+            self.trivia(token.trivia());
+            self.token(token, token.trivia().is_empty());
+        }
+        self.trivia(cst.trailing());
+
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        Ok(self.output)
+    }
+
+    /// The indentation of the items in the current scope
+    fn level(&self) -> usize {
+        self.scopes.last().map_or(0, |level| level + 1)
+    }
+
+    /// Write a text, on a new line (at the given indentation) if line breaks are waiting
+    fn write(&mut self, text: &str, spaced: bool, level: usize) {
+        if self.breaks > 0 && !self.output.is_empty() {
+            self.output
+                .push_str(&"\n".repeat(self.breaks.min(LINE_BREAKS)));
+            self.output.push_str(&INDENTATION.repeat(level));
+            self.line = level;
+        } else if spaced && !self.output.is_empty() {
+            self.output.push(' ');
+        }
+
+        self.breaks = 0;
+        self.output.push_str(text);
+    }
+
+    fn trivia(&mut self, trivia: &[Token<Trivia>]) {
+        for trivia in trivia {
+            let code = trivia.location().code();
+            let comment = match trivia.kind() {
+                Trivia::Whitespace => {
+                    self.breaks += code.matches('\n').count();
+                    continue;
+                }
+                // The following ends of instruction are removed
+                Trivia::EndOfInstruction => continue,
+                Trivia::LineComment => code.trim_end(),
+                Trivia::BlockComment | Trivia::Invalid => code,
+            };
+
+            let spaced = !matches!(
+                self.previous,
+                Some((
+                    Tokens::Block(Toggleable::Openning) | Tokens::Object(Toggleable::Openning),
+                    _
+                ))
+            );
+            self.write(comment, spaced, self.level());
+            self.previous = None;
+
+            // The line break ending a line comment
+            if code.ends_with('\n') {
+                self.breaks += 1;
+            }
+        }
+    }
+
+    fn token(&mut self, token: &Token, joined: bool) {
+        let kind = token.kind();
+
+        let closing = matches!(
+            kind,
+            Tokens::Block(Toggleable::Closing) | Tokens::Object(Toggleable::Closing)
+        );
+        let level = match closing {
+            true => self.scopes.pop().unwrap_or(0),
+            false => self.level(),
+        };
+
+        let spaced = match &self.previous {
+            Some((previous, unary)) => is_spaced(previous, kind, *unary, joined),
+            // After a comment
+            None => !matches!(kind, Tokens::ArgSeparator | Tokens::EndOfInstruction) && !closing,
+        };
+        self.write(&text(token), spaced, level);
+
+        if matches!(
+            kind,
+            Tokens::Block(Toggleable::Openning) | Tokens::Object(Toggleable::Openning)
+        ) {
+            self.scopes.push(self.line);
+        }
+
+        let unary = matches!(kind, Tokens::Operator(Operator::Substract))
+            && !self
+                .previous
+                .as_ref()
+                .is_some_and(|(previous, _)| is_value(previous));
+        self.previous = Some((kind.clone(), unary));
+    }
+}
+
+/// The flylang files to format at the given path : the file itself, or the `.fly` files of the folder (and its sub-folders)
+pub fn sources(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    entries.sort();
+
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.is_dir()
+                || entry
+                    .extension()
+                    .is_some_and(|extension| extension == "fly")
+        })
+        .flat_map(|entry| sources(entry))
+        .collect()
+}
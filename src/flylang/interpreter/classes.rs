@@ -0,0 +1,346 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::flylang::{
+    interpreter::{
+        Evaluation, Interpreter, Interruption,
+        errors::{
+            InaccessibleMember, InvalidInheritance, TooManyArguments, UndefinedVariable,
+            UnexpectedType,
+        },
+        interrupt,
+        value::{Class, ClassMethod, Fields, Function, Instance, Method, Super, Value},
+    },
+    module::slice::LangModuleSlice,
+    parser::ast::{
+        Node,
+        definables::{
+            class::{ClassItem, ClassItemVisibility, DefineClass},
+            variables::DefineVariable,
+        },
+    },
+    utils::linearization::linearize,
+};
+
+impl Interpreter {
+    /// Create a class from its definition
+    pub(super) fn class(
+        &mut self,
+        class: &DefineClass,
+        location: &LangModuleSlice,
+    ) -> Evaluation<Rc<Class>> {
+        let mut parents = vec![];
+        for parent in &class.parents {
+            match self
+                .get(parent.location().code())
+                .as_ref()
+                .map(Value::unmodified)
+            {
+                Some(Value::Class(parent)) => parents.push(Rc::clone(parent)),
+                Some(other) => {
+                    return interrupt!(UnexpectedType {
+                        location: parent.location().clone(),
+                        expected: "class",
+                        found: other.type_name(),
+                    });
+                }
+                None => return interrupt!(UndefinedVariable(parent.location().clone())),
+            }
+        }
+
+        let Some(ancestors) = linearize(&parents, |parent| Some(parent.mro()), Rc::ptr_eq) else {
+            return interrupt!(InvalidInheritance(class.name.location().clone()));
+        };
+
+        let closure = Rc::clone(&self.environment);
+        let constructor = class.constructor.as_ref().map(|constructor| {
+            Rc::new(Function {
+                definition: constructor.clone(),
+                closure: Rc::clone(&closure),
+            })
+        });
+
+        let mut methods = vec![];
+        for method in &class.methods {
+            let ClassItem {
+                item,
+                modifie_by,
+                visibility,
+                is_static,
+            } = method.kind();
+
+            methods.push(ClassMethod {
+                visibility: *visibility,
+                is_static: *is_static,
+                // Methods always have a name (verified by the parser)
+                name: item
+                    .name
+                    .as_ref()
+                    .map_or("", |name| name.location().code())
+                    .to_string(),
+                function: Rc::new(Function {
+                    definition: Node::new(item.clone(), method.location()),
+                    closure: Rc::clone(&closure),
+                }),
+                modifiers: self
+                    .modifiers(modifie_by)?
+                    .into_iter()
+                    .map(|(modifier, _)| modifier)
+                    .collect(),
+            });
+        }
+
+        // Static attributes are initialised once, when the class is defined
+        let mut statics = Fields::new();
+        for attribute in class.attributes.iter().filter(|item| item.kind().is_static) {
+            let variable = &attribute.kind().item;
+            statics.set(
+                Value::new_string(variable.emplacement.location().code()),
+                self.attribute(attribute)?,
+            );
+        }
+
+        Ok(Rc::new(Class {
+            definition: Node::new(class.clone(), location),
+            parents,
+            ancestors,
+            closure,
+            constructor,
+            methods,
+            statics: RefCell::new(statics),
+        }))
+    }
+
+    /// Get the initial value of an attribute (in the current environment)
+    fn attribute(&mut self, attribute: &Node<ClassItem<DefineVariable>>) -> Evaluation {
+        let ClassItem {
+            item: variable,
+            modifie_by,
+            ..
+        } = attribute.kind();
+
+        let modifiers = self.modifiers(modifie_by)?;
+        let value = self.expression(variable.value.kind(), variable.value.location())?;
+        self.modify_variable(variable, value, &modifiers, attribute.location())
+    }
+
+    /// Create an instance of the given class.
+    ///
+    /// The attributes are initialised from the farthest ancestor to the class itself (so a class can override
+    /// the attributes of its parents), then the first constructor found in the method resolution order is called.
+    pub(super) fn instanciate(
+        &mut self,
+        class: &Value,
+        arguments: Vec<Value>,
+        class_location: &LangModuleSlice,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        let class = match class {
+            Value::Class(class) => Rc::clone(class),
+            // The modifier creates the instance itself
+            Value::Modified(modified)
+                if matches!(modified.target.unmodified(), Value::Class(_)) =>
+            {
+                return self.invoke(
+                    &modified.modifier,
+                    vec![modified.target.clone(), Value::new_array(arguments)],
+                    class_location,
+                    location,
+                );
+            }
+            other => {
+                return interrupt!(UnexpectedType {
+                    location: class_location.clone(),
+                    expected: "class",
+                    found: other.type_name(),
+                });
+            }
+        };
+
+        let instance = Rc::new(RefCell::new(Instance {
+            class: Rc::clone(&class),
+            fields: Fields::new(),
+        }));
+
+        let mro = class.mro();
+        for owner in mro.iter().rev() {
+            let attributes = owner.definition.kind().attributes.iter();
+            for attribute in attributes.filter(|item| !item.kind().is_static) {
+                let variable = &attribute.kind().item;
+                // Attributes are evaluated in the environment of their class
+                let value = self.within(Rc::clone(&owner.closure), |interpreter| {
+                    interpreter.attribute(attribute)
+                })?;
+
+                instance.borrow_mut().fields.set(
+                    Value::new_string(variable.emplacement.location().code()),
+                    value,
+                );
+            }
+        }
+
+        self.construct(&instance, &mro, arguments, location)?;
+        Ok(Value::Instance(instance))
+    }
+
+    /// Call the first constructor defined by the given classes
+    fn construct(
+        &mut self,
+        receiver: &Rc<RefCell<Instance>>,
+        classes: &[Rc<Class>],
+        arguments: Vec<Value>,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        let owner = classes.iter().find(|class| class.constructor.is_some());
+
+        match owner {
+            Some(owner) => {
+                let constructor = owner.constructor.as_ref().unwrap();
+                self.call_function(
+                    constructor,
+                    arguments,
+                    Some(owner),
+                    Some(receiver),
+                    location,
+                )?;
+                Ok(Value::Empty)
+            }
+            // Without constructor, no argument can be given
+            None if arguments.is_empty() => Ok(Value::Empty),
+            None => interrupt!(TooManyArguments {
+                location: location.clone(),
+                expected: 0,
+                found: arguments.len(),
+            }),
+        }
+    }
+
+    /// Call the constructor of the parents (`super(...)`)
+    pub(super) fn construct_super(
+        &mut self,
+        parent: &Super,
+        arguments: Vec<Value>,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        self.construct(
+            &parent.receiver,
+            &Self::next_classes(parent),
+            arguments,
+            location,
+        )
+    }
+
+    /// The classes following the one of `super` in the method resolution order of the receiver's class
+    pub(super) fn next_classes(parent: &Super) -> Vec<Rc<Class>> {
+        let mro = parent.receiver.borrow().class.mro();
+        let position = mro
+            .iter()
+            .position(|class| Rc::ptr_eq(class, &parent.class))
+            .map_or(mro.len(), |position| position + 1);
+
+        mro[position..].to_vec()
+    }
+
+    /// Find a method in the given classes, and bind it to the receiver (if the method is not static)
+    pub(super) fn bound_method(
+        receiver: Option<&Rc<RefCell<Instance>>>,
+        classes: &[Rc<Class>],
+        key: &Value,
+    ) -> Option<Value> {
+        let Value::String(name) = key else {
+            return None;
+        };
+
+        classes.iter().find_map(|class| {
+            let method = class.method(name, receiver.is_none())?;
+
+            Some(Self::modify(
+                Value::Method(Rc::new(Method {
+                    receiver: receiver.map(Rc::clone),
+                    class: Rc::clone(class),
+                    function: Rc::clone(&method.function),
+                })),
+                method.modifiers.iter().cloned(),
+            ))
+        })
+    }
+
+    /// Verify that the current code can access a member declared by one of the given classes :
+    ///
+    /// - private members can only be accessed from the methods of the class declaring them,
+    /// - protected members can also be accessed from the methods of its child classes.
+    ///
+    /// Returns the class declaring the member, if any.
+    pub(super) fn check_access(
+        &self,
+        classes: &[Rc<Class>],
+        key: &Value,
+        is_static: bool,
+        location: &LangModuleSlice,
+    ) -> Evaluation<Option<Rc<Class>>> {
+        let Value::String(name) = key else {
+            return Ok(None);
+        };
+        let Some((owner, visibility)) = classes.iter().find_map(|class| {
+            let visibility = class.declaration(name, is_static)?;
+            Some((Rc::clone(class), visibility))
+        }) else {
+            return Ok(None);
+        };
+
+        let current = self.environment.borrow().class();
+        let allowed = match visibility {
+            ClassItemVisibility::Public => true,
+            ClassItemVisibility::Private => current.is_some_and(|class| Rc::ptr_eq(&class, &owner)),
+            ClassItemVisibility::Protected => current.is_some_and(|class| {
+                class
+                    .mro()
+                    .iter()
+                    .any(|ancestor| Rc::ptr_eq(ancestor, &owner))
+            }),
+        };
+
+        if !allowed {
+            return interrupt!(InaccessibleMember {
+                location: location.clone(),
+                member: name.to_string(),
+                class: owner.name().to_string(),
+                visibility: visibility.name(),
+            });
+        }
+
+        Ok(Some(owner))
+    }
+
+    /// Read a static member of a class (or of its parents)
+    pub(super) fn read_static(
+        &self,
+        class: &Rc<Class>,
+        key: &Value,
+        location: &LangModuleSlice,
+    ) -> Evaluation<Option<Value>> {
+        let mro = class.mro();
+        self.check_access(&mro, key, true, location)?;
+
+        let attribute = mro
+            .iter()
+            .find_map(|class| class.statics.borrow().get(key).cloned());
+        Ok(attribute.or_else(|| Self::bound_method(None, &mro, key)))
+    }
+
+    /// Write a static attribute of a class.
+    /// If the attribute is declared by one of its parents, it is modified on the parent.
+    pub(super) fn write_static(
+        &self,
+        class: &Rc<Class>,
+        key: Value,
+        value: Value,
+        location: &LangModuleSlice,
+    ) -> Evaluation<()> {
+        let owner = self
+            .check_access(&class.mro(), &key, true, location)?
+            .unwrap_or_else(|| Rc::clone(class));
+
+        owner.statics.borrow_mut().set(key, value);
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, rc::Rc};
+
+use crate::flylang::{
+    errors::{LangResult, lang_err},
+    interpreter::{
+        errors::ConstantReassignment,
+        value::{Class, Value},
+    },
+    module::slice::LangModuleSlice,
+};
+
+pub type SharedEnvironment = Rc<RefCell<Environment>>;
+
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub value: Value,
+    pub readonly: bool,
+    /// Where the variable has been defined (None for the interpreter's built-in elements)
+    pub defined_at: Option<LangModuleSlice>,
+}
+
+/// A scope of variables.
+///
+/// Environments are chained : when a variable is not found in an environment, it is searched in its parent.
+/// A new environment is created for each function call, whose parent is the environment the function has been
+/// defined in (its closure).
+#[derive(Default)]
+pub struct Environment {
+    variables: HashMap<String, Variable>,
+    parent: Option<SharedEnvironment>,
+    /// The class whose method is executed in this environment
+    class: Option<Rc<Class>>,
+}
+
+impl Environment {
+    pub fn new(parent: Option<&SharedEnvironment>) -> SharedEnvironment {
+        Rc::new(RefCell::new(Self {
+            variables: HashMap::new(),
+            parent: parent.map(Rc::clone),
+            class: None,
+        }))
+    }
+
+    /// Create the environment of a method of the given class
+    pub fn new_method(parent: &SharedEnvironment, class: &Rc<Class>) -> SharedEnvironment {
+        let environment = Self::new(Some(parent));
+        environment.borrow_mut().class = Some(Rc::clone(class));
+        environment
+    }
+
+    /// Get the class whose method is being executed (searching in the parent environments if needed).
+    /// This is used to know if the private and protected members of a class can be accessed.
+    pub fn class(&self) -> Option<Rc<Class>> {
+        match &self.class {
+            Some(class) => Some(Rc::clone(class)),
+            None => self.parent.as_ref()?.borrow().class(),
+        }
+    }
+
+    pub fn parent(&self) -> Option<&SharedEnvironment> {
+        self.parent.as_ref()
+    }
+
+    /// Get a variable, searching in the parent environments if needed
+    pub fn lookup(&self, name: &str) -> Option<Variable> {
+        match self.variables.get(name) {
+            Some(variable) => Some(variable.clone()),
+            None => self.parent.as_ref()?.borrow().lookup(name),
+        }
+    }
+
+    /// Get the value of a variable, searching in the parent environments if needed
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.lookup(name).map(|variable| variable.value)
+    }
+
+    /// The values of the variables defined in this environment (and not in its parents), sorted by their name
+    pub fn variables(&self) -> Vec<(String, Value)> {
+        let mut variables: Vec<(String, Value)> = self
+            .variables
+            .iter()
+            .map(|(name, variable)| (name.clone(), variable.value.clone()))
+            .collect();
+        variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+        variables
+    }
+
+    /// Define a variable in this environment, without any verification.
+    pub fn define(&mut self, name: &str, variable: Variable) {
+        self.variables.insert(name.to_string(), variable);
+    }
+
+    /// Assign a value to a variable.
+    ///
+    /// If the variable exists in this environment (or one of its parents), its value is replaced.
+    /// Else, the variable is created in this environment.
+    ///
+    /// Returns an error if the variable is a constant.
+    pub fn assign(
+        environment: &SharedEnvironment,
+        name: &str,
+        value: Value,
+        readonly: bool,
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        let mut current = Rc::clone(environment);
+        loop {
+            let parent = {
+                let mut scope = current.borrow_mut();
+                if let Some(variable) = scope.variables.get_mut(name) {
+                    if variable.readonly {
+                        return lang_err!(ConstantReassignment {
+                            location: location.clone(),
+                            defined_at: variable.defined_at.clone(),
+                        });
+                    }
+
+                    variable.value = value;
+                    variable.readonly = readonly;
+                    return Ok(());
+                }
+
+                scope.parent.clone()
+            };
+
+            match parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+
+        environment.borrow_mut().define(
+            name,
+            Variable {
+                value,
+                readonly,
+                defined_at: Some(location.clone()),
+            },
+        );
+        Ok(())
+    }
+}
+
+impl Debug for Environment {
+    // Values can reference their own environment (closures), so we only display the variables' names.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Environment")
+            .field("variables", &self.variables.keys().collect::<Vec<_>>())
+            .field(
+                "class",
+                &self.class.as_ref().map(|class| class.name().to_string()),
+            )
+            .field("parent", &self.parent)
+            .finish()
+    }
+}
@@ -0,0 +1,475 @@
+use std::path::PathBuf;
+
+use crate::flylang::{
+    errors::{ErrorType, RaisableErr, codes::ErrorId, diagnostic::Label},
+    module::slice::LangModuleSlice,
+};
+
+pub struct UndefinedVariable(pub LangModuleSlice);
+impl RaisableErr for UndefinedVariable {
+    fn _id(&self) -> ErrorId {
+        ErrorId::RuntimeUndefinedVariable
+    }
+    fn _code(&self) -> i32 {
+        1
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("\"{}\" is not defined.", self.0.code())
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct NotCallable(pub LangModuleSlice, pub &'static str);
+impl RaisableErr for NotCallable {
+    fn _id(&self) -> ErrorId {
+        ErrorId::NotCallable
+    }
+    fn _code(&self) -> i32 {
+        2
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" is not callable (found a value of type {}).",
+            self.0.code(),
+            self.1
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct InvalidOperation {
+    pub location: LangModuleSlice,
+    pub operator: String,
+    pub operands: Vec<&'static str>,
+}
+impl RaisableErr for InvalidOperation {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidOperation
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Cannot apply \"{}\" on {}.",
+            self.operator,
+            self.operands.join(" and ")
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct DivisionByZero(pub LangModuleSlice);
+impl RaisableErr for DivisionByZero {
+    fn _id(&self) -> ErrorId {
+        ErrorId::DivisionByZero
+    }
+    fn _code(&self) -> i32 {
+        3
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        String::from("Division by zero.")
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct TooManyArguments {
+    pub location: LangModuleSlice,
+    pub expected: usize,
+    pub found: usize,
+}
+impl RaisableErr for TooManyArguments {
+    fn _id(&self) -> ErrorId {
+        ErrorId::TooManyArguments
+    }
+    fn _code(&self) -> i32 {
+        4
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Too many arguments given. Expected at most {} but found {}.",
+            self.expected, self.found
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// A breaker (`return`, `stop`, `pass`) used outside of any scope it can break
+pub struct UncaughtBreaker(pub LangModuleSlice);
+impl RaisableErr for UncaughtBreaker {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UncaughtBreaker
+    }
+    fn _code(&self) -> i32 {
+        5
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" has been used outside of a scope it can break.",
+            self.0.code()
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct AssertionFailed(pub LangModuleSlice);
+impl RaisableErr for AssertionFailed {
+    fn _id(&self) -> ErrorId {
+        ErrorId::AssertionFailed
+    }
+    fn _code(&self) -> i32 {
+        6
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        String::from("Assertion failed.")
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+/// The interpreter does not know how to execute the node (yet)
+pub struct Unsupported(pub LangModuleSlice, pub &'static str);
+impl RaisableErr for Unsupported {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnsupportedByInterpreter
+    }
+    fn _code(&self) -> i32 {
+        7
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("The interpreter does not support {} yet.", self.1)
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct UndefinedProperty(pub LangModuleSlice, pub String);
+impl RaisableErr for UndefinedProperty {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UndefinedProperty
+    }
+    fn _code(&self) -> i32 {
+        8
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("Property {} does not exist.", self.1)
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+pub struct InvalidProperty {
+    pub location: LangModuleSlice,
+    pub key: String,
+    pub of: &'static str,
+}
+impl RaisableErr for InvalidProperty {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidProperty
+    }
+    fn _code(&self) -> i32 {
+        8
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Cannot access the property {} of a value of type {}.",
+            self.key, self.of
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct IndexOutOfRange {
+    pub location: LangModuleSlice,
+    pub index: f64,
+    pub length: usize,
+}
+impl RaisableErr for IndexOutOfRange {
+    fn _id(&self) -> ErrorId {
+        ErrorId::IndexOutOfRange
+    }
+    fn _code(&self) -> i32 {
+        8
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Index {} is out of range (length is {}).",
+            self.index, self.length
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+pub struct UnexpectedType {
+    pub location: LangModuleSlice,
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+impl RaisableErr for UnexpectedType {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnexpectedType
+    }
+    fn _code(&self) -> i32 {
+        9
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Expected a value of type {} but found {}.",
+            self.expected, self.found
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// A constant (defined using `::`) is being modified
+pub struct ConstantReassignment {
+    pub location: LangModuleSlice,
+    pub defined_at: Option<LangModuleSlice>,
+}
+impl RaisableErr for ConstantReassignment {
+    fn _id(&self) -> ErrorId {
+        ErrorId::ConstantReassignment
+    }
+    fn _code(&self) -> i32 {
+        10
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" is a constant and cannot be reassigned.",
+            self.location.code()
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+    fn _labels(&self) -> Vec<Label> {
+        self.defined_at
+            .iter()
+            .map(|location| Label::new(location, "the constant is defined here"))
+            .collect()
+    }
+    fn _notes(&self) -> Vec<String> {
+        match self.defined_at {
+            Some(_) => vec![],
+            None => vec![String::from("the constant is a built-in element")],
+        }
+    }
+}
+
+/// A breaker targets a scope that is not executing
+pub struct UnknownScope {
+    pub location: LangModuleSlice,
+    pub target: String,
+}
+impl RaisableErr for UnknownScope {
+    fn _id(&self) -> ErrorId {
+        ErrorId::RuntimeUnknownScope
+    }
+    fn _code(&self) -> i32 {
+        11
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" targets the scope \"{}\", but no such scope is being executed.",
+            self.location.code(),
+            self.target
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// A breaker targets a scope it cannot break (ex: `stop` targeting a function)
+pub struct InvalidScopeTarget {
+    pub location: LangModuleSlice,
+    pub target: String,
+    pub expected: &'static str,
+    pub found: &'static str,
+}
+impl RaisableErr for InvalidScopeTarget {
+    fn _id(&self) -> ErrorId {
+        ErrorId::RuntimeInvalidScopeTarget
+    }
+    fn _code(&self) -> i32 {
+        11
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "\"{}\" can only target a {} scope, but \"{}\" is a {} scope.",
+            self.location.code(),
+            self.expected,
+            self.target,
+            self.found
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// The parents of a class cannot be ordered (see the C3 linearization)
+pub struct InvalidInheritance(pub LangModuleSlice);
+impl RaisableErr for InvalidInheritance {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidInheritance
+    }
+    fn _code(&self) -> i32 {
+        12
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "Cannot define a consistent method resolution order for the class \"{}\". Check the order of its parents.",
+            self.0.code()
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+}
+
+/// A private or protected member of a class is accessed from outside the class
+pub struct InaccessibleMember {
+    pub location: LangModuleSlice,
+    pub member: String,
+    pub class: String,
+    pub visibility: &'static str,
+}
+impl RaisableErr for InaccessibleMember {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InaccessibleMember
+    }
+    fn _code(&self) -> i32 {
+        13
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The member \"{}\" of the class \"{}\" is {} and cannot be accessed here.",
+            self.member, self.class, self.visibility
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// The module of a package used with `use` cannot be found
+pub struct PackageNotFound {
+    pub location: LangModuleSlice,
+    pub package: String,
+}
+impl RaisableErr for PackageNotFound {
+    fn _id(&self) -> ErrorId {
+        ErrorId::PackageNotFound
+    }
+    fn _code(&self) -> i32 {
+        14
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!("The package \"{}\" cannot be found.", self.package)
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
+
+/// A module is used while it is being loaded (it uses itself, through the modules it uses)
+pub struct CircularUse {
+    pub location: LangModuleSlice,
+    pub path: PathBuf,
+}
+impl RaisableErr for CircularUse {
+    fn _id(&self) -> ErrorId {
+        ErrorId::CircularUse
+    }
+    fn _code(&self) -> i32 {
+        15
+    }
+    fn _kind(&self) -> ErrorType {
+        ErrorType::Stop
+    }
+    fn _message(&self) -> String {
+        format!(
+            "The module {:?} is used while it is being loaded : modules cannot use each other.",
+            self.path
+        )
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.location.clone())
+    }
+}
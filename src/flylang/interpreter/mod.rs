@@ -0,0 +1,1027 @@
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+
+use crate::{
+    behavior::LangBehavior,
+    flylang::{
+        errors::{LangResult, RaisableErr},
+        interpreter::{
+            environment::{Environment, SharedEnvironment, Variable},
+            errors::{
+                DivisionByZero, IndexOutOfRange, InvalidOperation, InvalidProperty,
+                InvalidScopeTarget, NotCallable, TooManyArguments, UncaughtBreaker,
+                UndefinedProperty, UndefinedVariable, UnexpectedType, UnknownScope,
+            },
+            value::{Class, Fields, Function, Instance, Modified, Super, Value},
+        },
+        lexer::tokens::{
+            BinaryOperator, Comparison, Operator, ScopeTarget,
+            representations::number::NumberRepresentation,
+        },
+        module::slice::LangModuleSlice,
+        parser::ast::{
+            Branches, Node,
+            definables::{
+                Definables,
+                functions::DefineFunction,
+                variables::{DefineVariable, VariableEmplacements},
+            },
+            expressions::{
+                Expressions,
+                call::Call,
+                literals::{ParsedLiterals, ParsedStringItem, Word},
+                modified::Modifier,
+                objects::StructureEntry,
+                operations::Operations,
+                property::Property,
+                reverse::ReverseKind,
+            },
+            instructions::{
+                Instructions,
+                breakers::{Break, BreakKind},
+                conditionnal::{If, IfFallBack},
+                loops::{Loop, LoopParameter},
+            },
+        },
+        runtime::builtins,
+        utils::breakers::{self, BreakableScope, ScopeKind, Unresolved},
+    },
+};
+
+mod classes;
+pub mod environment;
+pub mod errors;
+mod packages;
+pub mod value;
+
+/// A breaker that is going up the scopes to find the one it breaks
+#[derive(Debug)]
+pub enum Breaker {
+    Stop,
+    Pass,
+    Return(Value),
+}
+
+/// A scope being executed
+#[derive(Debug)]
+struct ActiveScope {
+    kind: ScopeKind,
+    name: Option<String>,
+}
+impl BreakableScope for ActiveScope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// The reason why the evaluation of a node has been interrupted
+pub enum Interruption {
+    Error(Box<dyn RaisableErr>),
+    /// `scope` is the depth (in the active scopes) of the scope the breaker stops at.
+    Break {
+        breaker: Breaker,
+        scope: usize,
+        location: LangModuleSlice,
+    },
+}
+impl From<Box<dyn RaisableErr>> for Interruption {
+    fn from(value: Box<dyn RaisableErr>) -> Self {
+        Self::Error(value)
+    }
+}
+
+type Evaluation<T = Value> = Result<T, Interruption>;
+
+macro_rules! interrupt {
+    ($e:expr) => {
+        Err(Interruption::Error(Box::new($e)))
+    };
+}
+pub(crate) use interrupt;
+
+/// Tree-walking interpreter executing the parsed instructions
+#[derive(Debug)]
+pub struct Interpreter {
+    /// The environment of the code being executed
+    environment: SharedEnvironment,
+    /// The scopes (functions, loops and conditions) being executed, from the outer to the inner one
+    scopes: Vec<ActiveScope>,
+    /// The environment of the built-in elements, parent of the environment of each module
+    builtins: SharedEnvironment,
+    /// The behaviors used to find and to load the packages
+    behavior: LangBehavior,
+    /// The value of each module already used (see `packages`), by its path
+    modules: HashMap<PathBuf, Value>,
+    /// The modules being loaded, from the first one used
+    loading: Vec<PathBuf>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        // Built-in elements are constants living in a parent of the script's environment
+        let builtins = Environment::new(None);
+        for (name, value) in builtins::globals() {
+            builtins.borrow_mut().define(
+                name,
+                Variable {
+                    value,
+                    readonly: true,
+                    defined_at: None,
+                },
+            );
+        }
+
+        Self {
+            environment: Environment::new(Some(&builtins)),
+            scopes: vec![],
+            builtins,
+            behavior: LangBehavior::default(),
+            modules: HashMap::new(),
+            loading: vec![],
+        }
+    }
+
+    /// Create an interpreter finding and loading the packages with the given behaviors
+    pub fn with_behavior(behavior: &LangBehavior) -> Self {
+        Self {
+            behavior: behavior.clone(),
+            ..Self::new()
+        }
+    }
+
+    /// Execute the given instructions and return the value of the last one
+    pub fn run(&mut self, branches: &Branches) -> LangResult<Value> {
+        Self::uncaught(self.execute(branches))
+    }
+
+    /// Evaluate a single expression in the current environment
+    pub fn evaluate(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<Value> {
+        Self::uncaught(self.expression(expression, location))
+    }
+
+    fn uncaught(evaluation: Evaluation) -> LangResult<Value> {
+        match evaluation {
+            Ok(value) => Ok(value),
+            Err(Interruption::Error(e)) => Err(e),
+            Err(Interruption::Break { location, .. }) => Err(Box::new(UncaughtBreaker(location))),
+        }
+    }
+
+    /// Get the value of a variable from the current environment
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.environment.borrow().get(name)
+    }
+
+    /// Set the value of a variable in the current environment (see `Environment::assign`)
+    pub fn set(
+        &mut self,
+        name: &str,
+        value: Value,
+        readonly: bool,
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        Environment::assign(&self.environment, name, value, readonly, location)
+    }
+
+    /// Run the callback in another environment, then restore the current one.
+    fn within<R>(
+        &mut self,
+        environment: SharedEnvironment,
+        callback: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = callback(self);
+        self.environment = previous;
+
+        result
+    }
+
+    /// Execute the instructions inside a new scope.
+    ///
+    /// Returns the depth of the scope, and the result of the execution.
+    fn scoped(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        branches: &Branches,
+    ) -> (usize, Evaluation) {
+        self.scopes.push(ActiveScope {
+            kind,
+            name: match target.as_ref().map(|target| target.kind()) {
+                Some(ScopeTarget::Named(name)) => Some(name.clone()),
+                _ => None,
+            },
+        });
+        let result = self.execute(branches);
+        self.scopes.pop();
+
+        (self.scopes.len(), result)
+    }
+
+    /// Find the depth of the scope a breaker stops at
+    fn resolve(&self, breaker: &Break) -> Evaluation<usize> {
+        let location = breaker.keyword_location.clone();
+        match breakers::resolve(&self.scopes, breaker) {
+            Ok(depth) => Ok(depth),
+            Err(Unresolved::Uncaught { .. }) => interrupt!(UncaughtBreaker(location)),
+            Err(Unresolved::Invalid {
+                target,
+                expected,
+                found,
+            }) => interrupt!(InvalidScopeTarget {
+                location,
+                target,
+                expected: expected.map_or("", |kind| kind.name()),
+                found: found.name(),
+            }),
+            Err(Unresolved::Unknown { target }) => interrupt!(UnknownScope { location, target }),
+        }
+    }
+
+    fn execute(&mut self, branches: &Branches) -> Evaluation {
+        let mut last = Value::Empty;
+        for instruction in branches {
+            last = self.instruction(instruction)?;
+        }
+
+        Ok(last)
+    }
+
+    fn instruction(&mut self, node: &Node<Instructions>) -> Evaluation {
+        match node.kind() {
+            Instructions::ValueOf(expression) => self.expression(expression, node.location()),
+            Instructions::If(condition) => self.condition(condition),
+            Instructions::Loop(repeat) => self.repeat(repeat),
+            Instructions::Break(breaker) => {
+                let scope = self.resolve(breaker)?;
+                let kind = match &breaker.kind {
+                    BreakKind::Stop(_) => Breaker::Stop,
+                    BreakKind::Pass(_) => Breaker::Pass,
+                    BreakKind::Return(_, value) => Breaker::Return(match value {
+                        Some(expression) => {
+                            self.expression(expression.kind(), expression.location())?
+                        }
+                        None => Value::Empty,
+                    }),
+                };
+
+                Err(Interruption::Break {
+                    breaker: kind,
+                    scope,
+                    location: breaker.keyword_location.clone(),
+                })
+            }
+            Instructions::Use(package) => self.import(package, node.location()),
+        }
+    }
+
+    fn condition(&mut self, condition: &If) -> Evaluation {
+        let passed = self
+            .expression(condition.condition.kind(), condition.condition.location())?
+            .truthy();
+
+        let (depth, result) = if passed {
+            self.scoped(
+                ScopeKind::Condition,
+                &condition.scope_target,
+                &condition.process,
+            )
+        } else {
+            match &condition.fallback {
+                None => return Ok(Value::Empty),
+                Some(fallback) => match fallback.kind() {
+                    IfFallBack::Process(target, process) => {
+                        self.scoped(ScopeKind::Condition, target, process)
+                    }
+                    IfFallBack::If(other) => return self.condition(other.kind()),
+                },
+            }
+        };
+
+        match result {
+            Err(Interruption::Break { scope, .. }) if scope == depth => Ok(Value::Empty),
+            other => other,
+        }
+    }
+
+    fn repeat(&mut self, repeat: &Loop) -> Evaluation {
+        match &repeat.parameter {
+            LoopParameter::Conditionnaly(parameter) => {
+                let mut iteration = 0usize;
+                while self
+                    .expression(parameter.condition.kind(), parameter.condition.location())?
+                    .truthy()
+                {
+                    if let Some(word) = &parameter.iteration_number {
+                        self.bind(word, Value::Number(iteration as f64))?;
+                    }
+                    iteration += 1;
+
+                    if !self.iteration(repeat)? {
+                        break;
+                    }
+                }
+            }
+            LoopParameter::Through(parameter) => {
+                let iterable =
+                    self.expression(parameter.iterable.kind(), parameter.iterable.location())?;
+
+                for (index, item) in Self::entries(iterable, parameter.iterable.location())? {
+                    if let Some(word) = &parameter.item {
+                        self.bind(word, item)?;
+                    }
+                    if let Some(word) = &parameter.index {
+                        self.bind(word, index)?;
+                    }
+
+                    if !self.iteration(repeat)? {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(Value::Empty)
+    }
+
+    /// Execute one iteration of a loop.
+    /// Returns if the loop must continue.
+    fn iteration(&mut self, repeat: &Loop) -> Evaluation<bool> {
+        match self.scoped(ScopeKind::Loop, &repeat.scope_target, &repeat.process) {
+            (_, Ok(_)) => Ok(true),
+            (depth, Err(Interruption::Break { breaker, scope, .. })) if scope == depth => {
+                Ok(!matches!(breaker, Breaker::Stop))
+            }
+            (_, Err(other)) => Err(other),
+        }
+    }
+
+    /// Get the (index, item) couples an `each` loop goes through.
+    ///
+    /// - Arrays and strings give their items with their position
+    /// - Structures give their values with their key
+    /// - Numbers are ranges : `n` gives the numbers from 0 (included) to `n` (excluded)
+    ///
+    /// Arrays and structures are copied, so modifying them inside the loop does not change the iterations.
+    fn entries(
+        iterable: Value,
+        location: &LangModuleSlice,
+    ) -> Evaluation<Box<dyn Iterator<Item = (Value, Value)>>> {
+        let position = |(index, item): (usize, Value)| (Value::Number(index as f64), item);
+
+        Ok(match iterable {
+            Value::Array(items) => {
+                Box::new(items.borrow().clone().into_iter().enumerate().map(position))
+            }
+            Value::Structure(fields) => Box::new(
+                fields
+                    .borrow()
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Value::String(s) => Box::new(
+                s.chars()
+                    .map(|c| Value::String(c.to_string().into()))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .enumerate()
+                    .map(position),
+            ),
+            Value::Number(end) => Box::new(
+                (0usize..)
+                    .take_while(move |n| (*n as f64) < end)
+                    .map(|n| (Value::Number(n as f64), Value::Number(n as f64))),
+            ),
+            other => {
+                return interrupt!(UnexpectedType {
+                    location: location.clone(),
+                    expected: "array, structure, string or number",
+                    found: other.type_name(),
+                });
+            }
+        })
+    }
+
+    /// Set the value of a variable bound by a loop
+    fn bind(&mut self, word: &Node<Word>, value: Value) -> Evaluation<()> {
+        Ok(self.set(word.location().code(), value, false, word.location())?)
+    }
+
+    fn expression(&mut self, expression: &Expressions, location: &LangModuleSlice) -> Evaluation {
+        match expression {
+            Expressions::Literal(literal) => self.literal(literal, location),
+            Expressions::Prioritized(inner) => self.expression(inner.kind(), inner.location()),
+            // Types are verified by the checker, before running
+            Expressions::Typed(typed) => {
+                self.expression(typed.expression.kind(), typed.expression.location())
+            }
+            Expressions::Operation(operation) => self.operation(operation, location),
+            Expressions::Reverse(reverse) => {
+                let value =
+                    self.expression(reverse.expression.kind(), reverse.expression.location())?;
+
+                match (&reverse.kind, value) {
+                    (ReverseKind::Boolean, value) => Ok(Value::Boolean(!value.truthy())),
+                    (ReverseKind::Sign, Value::Number(n)) => Ok(Value::Number(-n)),
+                    (ReverseKind::Sign, value) => interrupt!(InvalidOperation {
+                        location: location.clone(),
+                        operator: String::from("-"),
+                        operands: vec![value.type_name()],
+                    }),
+                }
+            }
+            Expressions::Ternary(ternary) => {
+                let branch = if self
+                    .expression(ternary.condition.kind(), ternary.condition.location())?
+                    .truthy()
+                {
+                    &ternary.yes
+                } else {
+                    &ternary.no
+                };
+
+                self.expression(branch.kind(), branch.location())
+            }
+            Expressions::ReturnOf(call) => self.call(call, location),
+            Expressions::Defined(definable) => self.define(definable, &[], location),
+            Expressions::Read(property) => {
+                let from = self.expression(property.from.kind(), property.from.location())?;
+                let key = self.property_key(&property.read)?;
+
+                self.read_property(&from, &key, location)
+            }
+            Expressions::Structure(structure) => {
+                let mut fields = Fields::new();
+                for entry in &structure.entries {
+                    let StructureEntry { key, value } = entry.kind();
+                    let key = match key.kind() {
+                        // Words are the name of the key (and not the value of a variable)
+                        Expressions::Literal(ParsedLiterals::Word) => {
+                            Value::new_string(key.location().code())
+                        }
+                        other => self.expression(other, key.location())?,
+                    };
+
+                    fields.set(key, self.expression(value.kind(), value.location())?);
+                }
+
+                Ok(Value::new_structure(fields))
+            }
+            Expressions::Array(array) => {
+                let mut items = vec![];
+                for item in &array.entries {
+                    items.push(self.expression(item.kind(), item.location())?);
+                }
+
+                Ok(Value::new_array(items))
+            }
+            Expressions::Modifed(modified) => self.define(
+                modified.definable.kind(),
+                &modified.modified_by,
+                modified.definable.location(),
+            ),
+            Expressions::Instanciate(instanciation) => {
+                let class =
+                    self.expression(instanciation.class.kind(), instanciation.class.location())?;
+
+                let mut arguments = vec![];
+                for argument in &instanciation.arguments {
+                    arguments.push(self.expression(argument.kind(), argument.location())?);
+                }
+
+                self.instanciate(&class, arguments, instanciation.class.location(), location)
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &ParsedLiterals, location: &LangModuleSlice) -> Evaluation {
+        Ok(match literal {
+            ParsedLiterals::Empty => Value::Empty,
+            ParsedLiterals::True => Value::Boolean(true),
+            ParsedLiterals::False => Value::Boolean(false),
+            ParsedLiterals::Number => Value::Number(NumberRepresentation::from(location).into()),
+            ParsedLiterals::Computed(number) => Value::Number(*number),
+            ParsedLiterals::Word => match self.get(location.code()) {
+                Some(value) => value,
+                None => return interrupt!(UndefinedVariable(location.clone())),
+            },
+            ParsedLiterals::String(parts) => {
+                let mut content = String::new();
+                for part in parts {
+                    match part.kind() {
+                        ParsedStringItem::Literal(literal) => content.push_str(literal),
+                        ParsedStringItem::Expression(expression) => content.push_str(
+                            &self
+                                .expression(expression.kind(), expression.location())?
+                                .to_string(),
+                        ),
+                    }
+                }
+
+                Value::String(content.into())
+            }
+        })
+    }
+
+    fn operation(&mut self, operation: &Operations, location: &LangModuleSlice) -> Evaluation {
+        macro_rules! operands {
+            ($operation:expr) => {{
+                let (left, right) = &$operation.operands;
+                (
+                    self.expression(left.kind(), left.location())?,
+                    self.expression(right.kind(), right.location())?,
+                )
+            }};
+        }
+        let invalid = |operator: &LangModuleSlice, left: &Value, right: &Value| {
+            interrupt!(InvalidOperation {
+                location: location.clone(),
+                operator: operator.code().to_string(),
+                operands: vec![left.type_name(), right.type_name()],
+            })
+        };
+
+        match operation {
+            Operations::Numeric(numeric) => {
+                let (left, right) = operands!(numeric);
+                let operator = numeric.operator.kind();
+
+                match (&left, &right) {
+                    (Value::Number(a), Value::Number(b)) => {
+                        if matches!(
+                            operator,
+                            Operator::Divide | Operator::EuclidianDivision | Operator::Modulo
+                        ) && *b == 0f64
+                        {
+                            return interrupt!(DivisionByZero(location.clone()));
+                        }
+
+                        Ok(Value::Number(match operator {
+                            Operator::Add => a + b,
+                            Operator::Substract => a - b,
+                            Operator::Multiply => a * b,
+                            Operator::Divide => a / b,
+                            Operator::Power => a.powf(*b),
+                            Operator::Modulo => a % b,
+                            Operator::EuclidianDivision => a.div_euclid(*b),
+                        }))
+                    }
+                    (Value::String(_), _) | (_, Value::String(_))
+                        if matches!(operator, Operator::Add) =>
+                    {
+                        Ok(Value::String(format!("{}{}", left, right).into()))
+                    }
+                    _ => invalid(numeric.operator.location(), &left, &right),
+                }
+            }
+            Operations::Binary(binary) => {
+                let (left, right) = &binary.operands;
+                let left = self.expression(left.kind(), left.location())?.truthy();
+
+                // `&` and `?` operators are short-circuiting
+                Ok(Value::Boolean(match binary.operator.kind() {
+                    BinaryOperator::And if !left => false,
+                    BinaryOperator::Or if left => true,
+                    operator => {
+                        let right = self.expression(right.kind(), right.location())?.truthy();
+                        match operator {
+                            BinaryOperator::Xor => left != right,
+                            _ => right,
+                        }
+                    }
+                }))
+            }
+            Operations::Comparative(comparison) => {
+                let (left, right) = operands!(comparison);
+
+                let ordering = match (&left, &right) {
+                    (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+                    (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+                    _ => None,
+                };
+
+                Ok(Value::Boolean(match comparison.operator.kind() {
+                    Comparison::Equal => left == right,
+                    Comparison::Less(strict) | Comparison::Greater(strict) => {
+                        let Some(ordering) = ordering else {
+                            return invalid(comparison.operator.location(), &left, &right);
+                        };
+
+                        if *strict {
+                            ordering
+                                == if matches!(comparison.operator.kind(), Comparison::Less(_)) {
+                                    std::cmp::Ordering::Less
+                                } else {
+                                    std::cmp::Ordering::Greater
+                                }
+                        } else if matches!(comparison.operator.kind(), Comparison::Less(_)) {
+                            ordering.is_le()
+                        } else {
+                            ordering.is_ge()
+                        }
+                    }
+                }))
+            }
+        }
+    }
+
+    /// Define a variable, a function or a class, after applying the given modifiers on it
+    fn define(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        let modifiers = self.modifiers(modifiers)?;
+
+        match definable {
+            Definables::Variable(variable) => {
+                let value = self.expression(variable.value.kind(), variable.value.location())?;
+                let value = self.modify_variable(variable, value, &modifiers, location)?;
+
+                match variable.emplacement.kind() {
+                    VariableEmplacements::Scope => self.set(
+                        variable.emplacement.location().code(),
+                        value.clone(),
+                        variable.readonly,
+                        variable.emplacement.location(),
+                    )?,
+                    VariableEmplacements::Property(property) => {
+                        let target =
+                            self.expression(property.from.kind(), property.from.location())?;
+                        let key = self.property_key(&property.read)?;
+
+                        self.write_property(
+                            &target,
+                            key,
+                            value.clone(),
+                            variable.emplacement.location(),
+                        )?;
+                    }
+                    VariableEmplacements::Any(_) => {
+                        return interrupt!(UnexpectedType {
+                            location: variable.emplacement.location().clone(),
+                            expected: "variable or property",
+                            found: "expression",
+                        });
+                    }
+                }
+
+                Ok(value)
+            }
+            Definables::Function(function) => {
+                let value = Self::modify(
+                    Value::Function(Rc::new(Function {
+                        definition: Node::new(function.clone(), location),
+                        closure: Rc::clone(&self.environment),
+                    })),
+                    modifiers.into_iter().map(|(modifier, _)| modifier),
+                );
+
+                if let Some(name) = &function.name {
+                    self.set(
+                        name.location().code(),
+                        value.clone(),
+                        false,
+                        name.location(),
+                    )?;
+                }
+
+                Ok(value)
+            }
+            Definables::Class(class) => {
+                let value = Self::modify(
+                    Value::Class(self.class(class, location)?),
+                    modifiers.into_iter().map(|(modifier, _)| modifier),
+                );
+                self.set(
+                    class.name.location().code(),
+                    value.clone(),
+                    false,
+                    class.name.location(),
+                )?;
+
+                Ok(value)
+            }
+        }
+    }
+
+    /// Get the functions used as modifiers (with their location)
+    fn modifiers(
+        &mut self,
+        modifiers: &[Node<Modifier>],
+    ) -> Evaluation<Vec<(Value, LangModuleSlice)>> {
+        let mut values = vec![];
+        for modifier in modifiers {
+            let value = match modifier.kind() {
+                Modifier::DefinedElement => {
+                    self.literal(&ParsedLiterals::Word, modifier.location())?
+                }
+                // The modifier is the function returned by the call
+                Modifier::CallReturn(call) => self.call(call, modifier.location())?,
+            };
+
+            values.push((value, modifier.location().clone()));
+        }
+
+        Ok(values)
+    }
+
+    /// Wrap a function or a class with its modifiers.
+    /// The first modifier is applied first, so it is the most inner one.
+    fn modify(target: Value, modifiers: impl IntoIterator<Item = Value>) -> Value {
+        modifiers.into_iter().fold(target, |target, modifier| {
+            Value::Modified(Rc::new(Modified { modifier, target }))
+        })
+    }
+
+    /// Apply the modifiers of a variable on its value.
+    /// Variables' modifiers are directly called with the variable's name, and return its value.
+    fn modify_variable(
+        &mut self,
+        variable: &DefineVariable,
+        mut value: Value,
+        modifiers: &[(Value, LangModuleSlice)],
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        for (modifier, modifier_location) in modifiers {
+            value = self.invoke(
+                modifier,
+                vec![
+                    Value::new_string(variable.emplacement.location().code()),
+                    Value::new_array(vec![Value::Boolean(variable.readonly), value]),
+                ],
+                modifier_location,
+                location,
+            )?;
+        }
+
+        Ok(value)
+    }
+
+    /// Get the key read by a property accessor (`.<key>`)
+    fn property_key(&mut self, property: &Node<Property>) -> Evaluation {
+        Ok(match property.kind() {
+            Property::Key => Value::new_string(property.location().code()),
+            Property::Index => {
+                Value::Number(NumberRepresentation::from(property.location()).into())
+            }
+            Property::Expression(expression) => {
+                self.expression(expression.kind(), expression.location())?
+            }
+        })
+    }
+
+    /// Convert the key to a valid index of a sequence of the given length.
+    /// Negative indexes start from the end of the sequence.
+    fn sequence_index(key: &Value, length: usize, location: &LangModuleSlice) -> Evaluation<usize> {
+        let Value::Number(index) = key else {
+            return interrupt!(UnexpectedType {
+                location: location.clone(),
+                expected: "number",
+                found: key.type_name(),
+            });
+        };
+
+        let position = if *index < 0f64 {
+            length as f64 + index
+        } else {
+            *index
+        };
+        if position.fract() != 0f64 || position < 0f64 || position >= length as f64 {
+            return interrupt!(IndexOutOfRange {
+                location: location.clone(),
+                index: *index,
+                length,
+            });
+        }
+
+        Ok(position as usize)
+    }
+
+    fn read_property(
+        &mut self,
+        from: &Value,
+        key: &Value,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        match from {
+            Value::Structure(fields) => fields.borrow().get(key).cloned(),
+            Value::Instance(instance) => {
+                let mro = instance.borrow().class.mro();
+                self.check_access(&mro, key, false, location)?;
+
+                let field = instance.borrow().fields.get(key).cloned();
+                // Fields hide the methods of the same name
+                field.or_else(|| Self::bound_method(Some(instance), &mro, key))
+            }
+            Value::Super(parent) => {
+                let classes = Self::next_classes(parent);
+                self.check_access(&classes, key, false, location)?;
+                Self::bound_method(Some(&parent.receiver), &classes, key)
+            }
+            Value::Class(class) => self.read_static(class, key, location)?,
+            // Static members can be read through a modified class
+            Value::Modified(modified) => {
+                return self.read_property(&modified.target, key, location);
+            }
+            Value::Array(items) => {
+                let items = items.borrow();
+                let index = Self::sequence_index(key, items.len(), location)?;
+                Some(items[index].clone())
+            }
+            Value::String(s) => {
+                let index = Self::sequence_index(key, s.chars().count(), location)?;
+                s.chars()
+                    .nth(index)
+                    .map(|c| Value::String(c.to_string().into()))
+            }
+            other => {
+                return interrupt!(InvalidProperty {
+                    location: location.clone(),
+                    key: key.repr(),
+                    of: other.type_name(),
+                });
+            }
+        }
+        .map_or_else(
+            || interrupt!(UndefinedProperty(location.clone(), key.repr())),
+            Ok,
+        )
+    }
+
+    fn write_property(
+        &mut self,
+        target: &Value,
+        key: Value,
+        value: Value,
+        location: &LangModuleSlice,
+    ) -> Evaluation<()> {
+        match target {
+            Value::Structure(fields) => fields.borrow_mut().set(key, value),
+            Value::Instance(instance) => {
+                let mro = instance.borrow().class.mro();
+                self.check_access(&mro, &key, false, location)?;
+                instance.borrow_mut().fields.set(key, value)
+            }
+            Value::Class(class) => self.write_static(class, key, value, location)?,
+            Value::Modified(modified) => {
+                return self.write_property(&modified.target, key, value, location);
+            }
+            Value::Array(items) => {
+                let mut items = items.borrow_mut();
+                // Writing right after the last item appends it
+                if key == Value::Number(items.len() as f64) {
+                    items.push(value);
+                } else {
+                    let index = Self::sequence_index(&key, items.len(), location)?;
+                    items[index] = value;
+                }
+            }
+            other => {
+                return interrupt!(InvalidProperty {
+                    location: location.clone(),
+                    key: key.repr(),
+                    of: other.type_name(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn call(&mut self, call: &Call, location: &LangModuleSlice) -> Evaluation {
+        let callable = self.expression(call.callable.kind(), call.callable.location())?;
+
+        let mut arguments = vec![];
+        for argument in &call.arguments {
+            arguments.push(self.expression(argument.kind(), argument.location())?);
+        }
+
+        self.invoke(&callable, arguments, call.callable.location(), location)
+    }
+
+    /// Call a callable value with the given arguments
+    fn invoke(
+        &mut self,
+        callable: &Value,
+        arguments: Vec<Value>,
+        callable_location: &LangModuleSlice,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        match callable {
+            Value::Function(function) => {
+                self.call_function(function, arguments, None, None, location)
+            }
+            Value::Method(method) => self.call_function(
+                &method.function,
+                arguments,
+                Some(&method.class),
+                method.receiver.as_ref(),
+                location,
+            ),
+            Value::Super(parent) => self.construct_super(parent, arguments, location),
+            Value::Native(native) => Ok((native.callback)(self, arguments, location)?),
+            Value::Modified(modified) => self.invoke(
+                &modified.modifier,
+                vec![modified.target.clone(), Value::new_array(arguments)],
+                callable_location,
+                location,
+            ),
+            other => interrupt!(NotCallable(callable_location.clone(), other.type_name())),
+        }
+    }
+
+    /// Call a function defined in a script.
+    ///
+    /// If the function is a method, it can access the private members of its class. If it is not static,
+    /// `self` is bound to the receiver and `super` gives access to the parents' methods.
+    fn call_function(
+        &mut self,
+        function: &Rc<Function>,
+        arguments: Vec<Value>,
+        class: Option<&Rc<Class>>,
+        receiver: Option<&Rc<RefCell<Instance>>>,
+        location: &LangModuleSlice,
+    ) -> Evaluation {
+        let definition: &DefineFunction = function.definition.kind();
+        if arguments.len() > definition.arguments.len() {
+            return interrupt!(TooManyArguments {
+                location: location.clone(),
+                expected: definition.arguments.len(),
+                found: arguments.len(),
+            });
+        }
+
+        let scope = match class {
+            Some(class) => Environment::new_method(&function.closure, class),
+            None => Environment::new(Some(&function.closure)),
+        };
+        if let (Some(instance), Some(class)) = (receiver, class) {
+            let bindings = [
+                ("self", Value::Instance(Rc::clone(instance))),
+                (
+                    "super",
+                    Value::Super(Rc::new(Super {
+                        receiver: Rc::clone(instance),
+                        class: Rc::clone(class),
+                    })),
+                ),
+            ];
+            for (name, value) in bindings {
+                scope.borrow_mut().define(
+                    name,
+                    Variable {
+                        value,
+                        readonly: true,
+                        defined_at: None,
+                    },
+                );
+            }
+        }
+
+        let mut arguments = arguments.into_iter();
+        for argument in definition.arguments.iter() {
+            let name = &argument.kind().name;
+            scope.borrow_mut().define(
+                name.location().code(),
+                Variable {
+                    value: arguments.next().unwrap_or(Value::Empty),
+                    readonly: false,
+                    defined_at: Some(name.location().clone()),
+                },
+            );
+        }
+
+        let (depth, result) = self.within(scope, |interpreter| {
+            interpreter.scoped(
+                ScopeKind::Function,
+                &definition.scope_target,
+                &definition.execution,
+            )
+        });
+
+        match result {
+            Ok(_) => Ok(Value::Empty),
+            Err(Interruption::Break { breaker, scope, .. }) if scope == depth => match breaker {
+                Breaker::Return(value) => Ok(value),
+                _ => Ok(Value::Empty),
+            },
+            Err(other) => Err(other),
+        }
+    }
+}
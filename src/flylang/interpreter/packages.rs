@@ -0,0 +1,108 @@
+use std::rc::Rc;
+
+use crate::flylang::{
+    interpreter::{
+        Evaluation, Interpreter, Interruption,
+        environment::Environment,
+        errors::{CircularUse, PackageNotFound, UndefinedProperty},
+        interrupt,
+        value::{Fields, Value},
+    },
+    module::slice::LangModuleSlice,
+    parser::ast::instructions::r#use::{
+        Package, PackageContentEmplacement, PackageIncludedContent, PackageSource,
+    },
+};
+
+impl Interpreter {
+    /// Execute a `use` instruction : load the module of the package, and bind the variables it defines
+    pub(super) fn import(&mut self, package: &Package, location: &LangModuleSlice) -> Evaluation {
+        let exports = self.package(package, location)?;
+        let Value::Structure(fields) = &exports else {
+            unreachable!("the value of a module is a structure");
+        };
+
+        let bound: Vec<(String, Value, &LangModuleSlice)> =
+            match (&package.included, &package.emplacement) {
+                (_, PackageContentEmplacement::Variable(variable)) => vec![(
+                    variable.location().code().to_string(),
+                    exports.clone(),
+                    variable.location(),
+                )],
+                (PackageIncludedContent::Only(names), PackageContentEmplacement::Global) => {
+                    let fields = fields.borrow();
+                    let mut bound = vec![];
+                    for name in names {
+                        let code = name.location().code();
+                        let Some(value) = fields.get(&Value::new_string(code)) else {
+                            return interrupt!(UndefinedProperty(
+                                name.location().clone(),
+                                code.to_string()
+                            ));
+                        };
+                        bound.push((code.to_string(), value.clone(), name.location()));
+                    }
+                    bound
+                }
+                (PackageIncludedContent::All, PackageContentEmplacement::Global) => fields
+                    .borrow()
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone(), location))
+                    .collect(),
+            };
+
+        for (name, value, location) in bound {
+            self.set(&name, value, false, location)?;
+        }
+        Ok(Value::Empty)
+    }
+
+    /// Get the value of the module of a package : a structure of the variables it defines.
+    /// The module is executed the first time it is used, in its own environment.
+    fn package(&mut self, package: &Package, location: &LangModuleSlice) -> Evaluation {
+        let not_found = || {
+            let (PackageSource::File(package) | PackageSource::Package(package)) = &package.source;
+            interrupt!(PackageNotFound {
+                location: location.clone(),
+                package: package.clone(),
+            })
+        };
+
+        let Some(path) = package.path(&self.behavior) else {
+            return not_found();
+        };
+        if let Some(exports) = self.modules.get(&path) {
+            return Ok(exports.clone());
+        }
+        if self.loading.contains(&path) {
+            return interrupt!(CircularUse {
+                location: location.clone(),
+                path,
+            });
+        }
+        let Some(loaded) = package.load(&self.behavior) else {
+            return not_found();
+        };
+        let (_, branches) = loaded?;
+
+        // The module does not see the variables nor the scopes of the code using it
+        let environment = Environment::new(Some(&self.builtins));
+        let scopes = std::mem::take(&mut self.scopes);
+        self.loading.push(path.clone());
+        let result = self.within(Rc::clone(&environment), |this| this.execute(&branches));
+        self.loading.pop();
+        self.scopes = scopes;
+        Self::uncaught(result)?;
+
+        let exports = Value::new_structure(
+            environment
+                .borrow()
+                .variables()
+                .into_iter()
+                .map(|(name, value)| (Value::new_string(&name), value))
+                .collect::<Fields>(),
+        );
+        self.modules.insert(path, exports.clone());
+        Ok(exports)
+    }
+}
@@ -0,0 +1,111 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::flylang::{
+    interpreter::{Interpreter, environment::SharedEnvironment},
+    parser::ast::{
+        Node,
+        definables::{
+            class::{ClassItemVisibility, DefineClass},
+            functions::DefineFunction,
+        },
+    },
+    runtime::{Engine, value},
+};
+
+/// The values of the interpreter (see `runtime::value::Value`)
+pub type Value = value::Value<Interpreter>;
+pub type Fields = value::Fields<Value>;
+pub type NativeCallback = value::NativeCallback<Interpreter>;
+pub type NativeFunction = value::NativeFunction<Interpreter>;
+pub type Method = value::Method<Interpreter>;
+pub type Super = value::Super<Interpreter>;
+pub type Modified = value::Modified<Interpreter>;
+pub type Instance = value::Instance<Interpreter>;
+
+impl Engine for Interpreter {
+    type Function = Function;
+    type Class = Class;
+
+    fn function_name(function: &Function) -> Option<&str> {
+        function.name()
+    }
+    fn class_name(class: &Class) -> &str {
+        class.name()
+    }
+}
+
+/// A function defined in a flylang script.
+#[derive(Debug)]
+pub struct Function {
+    pub definition: Node<DefineFunction>,
+    /// The environment the function has been defined in
+    pub closure: SharedEnvironment,
+}
+impl Function {
+    /// Get the name of the function (if it is not anonymous)
+    pub fn name(&self) -> Option<&str> {
+        self.definition
+            .kind()
+            .name
+            .as_ref()
+            .map(|name| name.location().code())
+    }
+}
+
+/// A method of a class
+#[derive(Debug)]
+pub struct ClassMethod {
+    pub name: String,
+    pub visibility: ClassItemVisibility,
+    pub is_static: bool,
+    pub function: Rc<Function>,
+    /// The modifiers of the method, applied each time the method is read from an instance
+    pub modifiers: Vec<Value>,
+}
+
+/// A class defined in a flylang script.
+#[derive(Debug)]
+pub struct Class {
+    pub definition: Node<DefineClass>,
+    pub parents: Vec<Rc<Class>>,
+    /// The ancestors of the class, in the order their methods are resolved (C3 linearization)
+    pub ancestors: Vec<Rc<Class>>,
+    /// The environment the class has been defined in
+    pub closure: SharedEnvironment,
+    pub constructor: Option<Rc<Function>>,
+    pub methods: Vec<ClassMethod>,
+    /// The values of the static attributes of the class
+    pub statics: RefCell<Fields>,
+}
+impl Class {
+    pub fn name(&self) -> &str {
+        self.definition.kind().name.location().code()
+    }
+
+    /// The method resolution order : the class itself, followed by its ancestors
+    pub fn mro(self: &Rc<Self>) -> Vec<Rc<Class>> {
+        let mut mro = vec![Rc::clone(self)];
+        mro.extend(self.ancestors.iter().cloned());
+        mro
+    }
+
+    /// Get a method defined by this class (and not by its parents)
+    pub fn method(&self, name: &str, is_static: bool) -> Option<&ClassMethod> {
+        self.methods
+            .iter()
+            .find(|method| method.name == name && method.is_static == is_static)
+    }
+
+    /// Get the visibility of an attribute or a method declared by this class (and not by its parents)
+    pub fn declaration(&self, name: &str, is_static: bool) -> Option<ClassItemVisibility> {
+        let attribute = self.definition.kind().attributes.iter().find(|attribute| {
+            let attribute = attribute.kind();
+            attribute.is_static == is_static && attribute.item.emplacement.location().code() == name
+        });
+
+        match attribute {
+            Some(attribute) => Some(attribute.kind().visibility),
+            None => self.method(name, is_static).map(|method| method.visibility),
+        }
+    }
+}
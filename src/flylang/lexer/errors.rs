@@ -1,13 +1,26 @@
 use std::rc::Rc;
 
 use crate::flylang::{
-    errors::{ErrorType, RaisableErr},
+    errors::{ErrorType, RaisableErr, codes::ErrorId, diagnostic::Label},
     module::slice::LangModuleSlice,
     utils::scoper::Scope,
 };
 
+/// The characters closing a scope
+fn closing(scope: &Scope<LangModuleSlice>) -> Option<&str> {
+    match scope {
+        Scope::Block(_) => Some(")"),
+        Scope::Object(_) => Some("}"),
+        Scope::String(openning) => Some(openning.code()),
+        Scope::Module(_) => None,
+    }
+}
+
 pub struct UnknownCharacter(pub Rc<LangModuleSlice>);
 impl RaisableErr for UnknownCharacter {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnknownCharacter
+    }
     fn _code(&self) -> i32 {
         2
     }
@@ -16,16 +29,21 @@ impl RaisableErr for UnknownCharacter {
     }
     fn _message(&self) -> String {
         format!(
-            "Cannot understand character{} \"{}\"\n{:#}",
+            "Cannot understand character{} \"{}\"",
             if self.0.range().len() > 1 { "s" } else { "" },
-            self.0.code(),
-            self.0
+            self.0.code()
         )
     }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some((*self.0).clone())
+    }
 }
 
 pub struct UnexpectedCharacter(pub Rc<LangModuleSlice>, pub Option<&'static str>);
 impl RaisableErr for UnexpectedCharacter {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnexpectedCharacter
+    }
     fn _code(&self) -> i32 {
         3
     }
@@ -34,20 +52,25 @@ impl RaisableErr for UnexpectedCharacter {
     }
     fn _message(&self) -> String {
         format!(
-            "Unexpected character \"{}\"{}.\n{:#}",
+            "Unexpected character \"{}\"{}.",
             self.0.code(),
             if let Some(expected) = self.1 {
                 format!(" (Expected matching \"{}\")", expected)
             } else {
                 "".into()
-            },
-            self.0
+            }
         )
     }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some((*self.0).clone())
+    }
 }
 
 pub struct InvalidScopeEnding(pub Rc<LangModuleSlice>, pub Scope<LangModuleSlice>);
 impl RaisableErr for InvalidScopeEnding {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidScopeEnding
+    }
     fn _code(&self) -> i32 {
         3
     }
@@ -56,18 +79,30 @@ impl RaisableErr for InvalidScopeEnding {
     }
     fn _message(&self) -> String {
         format!(
-            "Unclosed {}. Expected to close {} at {} but found \"{}\".\n{:#}",
-            self.1,
+            "Unclosed {}. Expected to close it but found \"{}\".",
             self.1,
-            self.1.data(),
-            self.0.code(),
-            self.0
+            self.0.code()
         )
     }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some((*self.0).clone())
+    }
+    fn _labels(&self) -> Vec<Label> {
+        vec![Label::new(
+            self.1.data(),
+            format!("this {} is opened here", self.1.to_string().to_lowercase()),
+        )]
+    }
+    fn _help(&self) -> Option<String> {
+        closing(&self.1).map(|closing| format!("close it with \"{}\" before", closing))
+    }
 }
 
 pub struct UnclosedScope(pub Scope<LangModuleSlice>);
 impl RaisableErr for UnclosedScope {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnclosedScope
+    }
     fn _code(&self) -> i32 {
         3
     }
@@ -75,6 +110,18 @@ impl RaisableErr for UnclosedScope {
         ErrorType::Stop
     }
     fn _message(&self) -> String {
-        format!("Unclosed {} (openned at {}).", self.0, self.0.data())
+        format!("Unclosed {}.", self.0)
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.data().clone())
+    }
+    fn _notes(&self) -> Vec<String> {
+        vec![format!(
+            "the end of the code is reached before the {} is closed",
+            self.0.to_string().to_lowercase()
+        )]
+    }
+    fn _help(&self) -> Option<String> {
+        closing(&self.0).map(|closing| format!("close it with \"{}\"", closing))
     }
 }
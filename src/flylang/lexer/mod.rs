@@ -1,11 +1,11 @@
 use crate::{
     flylang::{
-        errors::{LangResult, RaisableErr, lang_err},
+        errors::{Diagnostics, LangResult, lang_err},
         lexer::{
             errors::{InvalidScopeEnding, UnclosedScope, UnexpectedCharacter, UnknownCharacter},
             ranges::CharacterRange,
             tokens::{
-                Literals, ScopeTarget, Toggleable, Token, Tokens,
+                Literals, ScopeTarget, Toggleable, Token, Tokens, Trivia,
                 representations::number::NumberRepresentation,
             },
         },
@@ -28,6 +28,12 @@ pub struct Lexer {
     scope: Vec<Scope<LangModuleSlice>>,
     analyser: Analyser<LangModuleChar>,
     lexified: Vec<Token<Tokens>>,
+    /// If the whitespace and the comments are kept as trivia of the tokens
+    keep_trivia: bool,
+    /// The trivia found after the last token, given to the next one
+    trivia: Vec<Token<Trivia>>,
+    /// The errors found in the code
+    diagnostics: Diagnostics,
 }
 
 impl Lexer {
@@ -37,6 +43,17 @@ impl Lexer {
             scope: vec![],
             analyser: Analyser::new(module.chars().collect()),
             lexified: vec![],
+            keep_trivia: false,
+            trivia: vec![],
+            diagnostics: Diagnostics::new(),
+        }
+    }
+    /// Create a lexer keeping the code it ignores (whitespace, comments, ...) as trivia of the tokens.
+    /// The code of the module can then be rebuilt from the tokens.
+    pub fn new_with_trivia(module: &Rc<LangModule>) -> Self {
+        Self {
+            keep_trivia: true,
+            ..Self::new(module)
         }
     }
     /// Get the lexer's module
@@ -51,7 +68,38 @@ impl Lexer {
     /// Validate the current slice (`get_slice` method) with the given token.
     /// This inserts the created token to the lexified vector.
     fn validate_analyser(&mut self, kind: Tokens) {
-        self.lexified.push(Token::new(kind, &self.get_slice()));
+        let mut token = Token::new(kind, &self.get_slice());
+        token.set_trivia(take(&mut self.trivia));
+        self.lexified.push(token);
+
+        self.analyser.next(0, 0);
+    }
+
+    /// Remove the last validated token. Its trivia are given back to the next token.
+    fn invalidate_last(&mut self) -> Token<Tokens> {
+        let token = self.lexified.pop().unwrap();
+        self.trivia.splice(0..0, token.trivia().clone());
+        token
+    }
+
+    /// Ignore the current slice, which is kept as a trivia of the next token if the lexer keeps them
+    fn skip(&mut self, kind: Trivia) {
+        if self.keep_trivia {
+            let slice = self.get_slice();
+            match self.trivia.last_mut() {
+                // Following whitespace characters are kept as one trivia
+                Some(last)
+                    if matches!(
+                        (last.kind(), &kind),
+                        (Trivia::Whitespace, Trivia::Whitespace)
+                    ) =>
+                {
+                    let range = last.location().range().start..slice.range().end;
+                    *last = Token::new(kind, &LangModuleSlice::new_with(&self.module, range));
+                }
+                _ => self.trivia.push(Token::new(kind, &slice)),
+            }
+        }
 
         self.analyser.next(0, 0);
     }
@@ -149,7 +197,7 @@ impl Lexer {
                 return lang_err!(UnclosedScope(self.scope.pop().unwrap()));
             }
 
-            self.process().unwrap_or_else(|e| e.controlled_raise());
+            self.step();
         }
 
         // Because the expression has been saved to the global lexified array,
@@ -160,8 +208,9 @@ impl Lexer {
         self.lexified = Vec::from(lexified);
 
         // Remove the closing character and set the cursor on it
+        let closing = expression.pop().unwrap().location().range();
         self.analyser
-            .set(expression.pop().unwrap().location().range());
+            .set(self.module.index(closing.start)..self.module.index(closing.end));
 
         Ok(expression)
     }
@@ -184,6 +233,35 @@ impl Lexer {
         self.scope.push(Scope::String(self.get_slice()));
         self.analyser.next(0, 0);
 
+        // The trivia preceding the string must not be given to the tokens of its expressions
+        let trivia = take(&mut self.trivia);
+        let split_at = self.lexified.len();
+        let content = match self.string_items(&toggler) {
+            Ok(content) => content,
+            Err(error) => {
+                // The string is invalid as a whole : the tokens of its expressions are removed
+                self.lexified.truncate(split_at);
+                self.trivia = trivia;
+                self.analyser
+                    .set(toggler.index()..self.analyser.range().end);
+                return Err(error);
+            }
+        };
+
+        // Recalibrate the analyser to match the whole string
+        self.analyser
+            .set(toggler.index()..self.analyser.range().end);
+        self.trivia = trivia;
+        self.validate_analyser(Tokens::Literal(tokens::Literals::String(content)));
+
+        empty_result::ok!()
+    }
+
+    /// Parse the content of a string opened by the `toggler` character, until its closing character
+    fn string_items(
+        &mut self,
+        toggler: &LangModuleChar,
+    ) -> LangResult<Vec<Token<tokens::StringItem>>> {
         let allow_expressions = toggler.code() == '"';
         let mut content = vec![];
         let mut literal_value = String::from("");
@@ -292,12 +370,8 @@ impl Lexer {
                 }
             }
         }
-        // Recalibrate the analyser to match the whole string
-        self.analyser
-            .set(toggler.index()..self.analyser.range().end);
-        self.validate_analyser(Tokens::Literal(tokens::Literals::String(content)));
 
-        empty_result::ok!()
+        Ok(content)
     }
 
     /// Parse the current slice as a number
@@ -432,7 +506,7 @@ impl Lexer {
 
         // Skipping useless whitespace
         if self.analyser.range().len() == 1 && self.analyser.get()[0].code().is_whitespace() {
-            self.analyser.next(0, 0);
+            self.skip(Trivia::Whitespace);
             return self.process();
         }
 
@@ -446,32 +520,14 @@ impl Lexer {
                 self.validate_analyser(Tokens::Block(Toggleable::Openning));
             }
             ")" => {
-                let current_scope = self.scope.pop();
-                if let Some(scope) = current_scope {
-                    if scope.is(&Scope::Block(())) {
-                        self.validate_analyser(Tokens::Block(Toggleable::Closing));
-                    } else {
-                        return lang_err!(InvalidScopeEnding(self.get_slice().into(), scope));
-                    }
-                } else {
-                    return lang_err!(UnexpectedCharacter(self.get_slice().into(), None));
-                }
+                self.close(Scope::Block(()));
             }
             "{" => {
                 self.scope.push(Scope::Object(self.get_slice()));
                 self.validate_analyser(Tokens::Object(Toggleable::Openning));
             }
             "}" => {
-                let current_scope = self.scope.pop();
-                if let Some(scope) = current_scope {
-                    if scope.is(&Scope::Object(())) {
-                        self.validate_analyser(Tokens::Object(Toggleable::Closing));
-                    } else {
-                        return lang_err!(InvalidScopeEnding(self.get_slice().into(), scope));
-                    }
-                } else {
-                    return lang_err!(UnexpectedCharacter(self.get_slice().into(), None));
-                }
+                self.close(Scope::Object(()));
             }
             "." => {
                 let snapshot = self.analyser.range();
@@ -481,6 +537,14 @@ impl Lexer {
                 }
             }
             "-" => {
+                if let Some(slice) = self.analyser.lookup(0, 1)
+                    && slice[0].code() == '>'
+                {
+                    self.analyser.increase(1);
+                    self.validate_analyser(Tokens::Typing);
+                    return empty_result::ok!();
+                }
+
                 let snapshot = self.analyser.range();
                 if self.number().is_err() {
                     self.analyser.set(snapshot);
@@ -540,6 +604,15 @@ impl Lexer {
                 } else {
                     let expectation = String::from("multiple '<', an integer < 0 or a word");
 
+                    // "@-<number>" is the same as "@<number>"
+                    let relative = self
+                        .analyser
+                        .lookup(0, 1)
+                        .is_some_and(|slice| slice[0].code() == '-');
+                    if relative {
+                        self.analyser.next(0, 1);
+                    }
+
                     // handle as a variable
                     if !self.analyser.able_to(0, 1) {
                         return lang_err!(Expected {
@@ -551,15 +624,19 @@ impl Lexer {
                     self.analyser.next(0, 1);
 
                     self.literal()?;
-                    let lexified = self.lexified.pop().unwrap();
+                    let lexified = self.invalidate_last();
                     self.analyser
                         .set(slice.range().start..lexified.location().range().end);
 
                     self.validate_analyser(match lexified.kind() {
                         Tokens::Literal(Literals::Word | Literals::True | Literals::False)
-                        | Tokens::Keyword(_) => Tokens::ScopeTarget(ScopeTarget::Named(
-                            lexified.location().code().to_string(),
-                        )),
+                        | Tokens::Keyword(_)
+                            if !relative =>
+                        {
+                            Tokens::ScopeTarget(ScopeTarget::Named(
+                                lexified.location().code().to_string(),
+                            ))
+                        }
                         Tokens::Literal(Literals::Number) => {
                             let num = NumberRepresentation::from(lexified.location());
 
@@ -595,10 +672,8 @@ impl Lexer {
                     && let Tokens::EndOfInstruction = last.kind()
                 {
                     // Here we prevent following end of instruction (useless)
-                    self.analyser.next(0, 0);
-                }
-                // Empty only if the analyser has been nexted (see above).
-                if !self.analyser.range().is_empty() {
+                    self.skip(Trivia::EndOfInstruction);
+                } else {
                     self.validate_analyser(Tokens::EndOfInstruction);
                 }
             }
@@ -637,8 +712,9 @@ impl Lexer {
                             .range(),
                     );
 
-                    // Remove the operation token
-                    self.lexified.pop();
+                    // Remove the operation token : the trivia found after it are part of the definition
+                    self.trivia.clear();
+                    self.invalidate_last();
                     self.validate_analyser(kind);
 
                     return empty_result::ok!();
@@ -697,7 +773,10 @@ impl Lexer {
                 }
 
                 // We do not add a comment as a token
-                self.analyser.next(0, 0);
+                self.skip(match self.get_slice().code().starts_with("||") {
+                    true => Trivia::LineComment,
+                    false => Trivia::BlockComment,
+                });
             }
             _ => return self.literal(),
         };
@@ -705,22 +784,129 @@ impl Lexer {
         empty_result::ok!()
     }
 
-    /// Execute the lexer if needed and return the vector of tokens
-    pub fn lexify(&mut self) -> &Vec<Token> {
+    /// Close the current scope with the character of the analyser (`)` or `}`).
+    ///
+    /// If the character does not close the current scope, the error is collected and the lexer recovers :
+    /// - if an enclosing scope is closed by the character, the scopes opened inside it are closed with it
+    /// - else, the character closes the current scope
+    ///
+    /// The tokens always have matching openning and closing tokens : the scopes closed without a character
+    /// are closed by empty tokens.
+    fn close(&mut self, closed: Scope<()>) {
+        let closing = |scope: &Scope<LangModuleSlice>| match scope {
+            Scope::Object(_) => Tokens::Object(Toggleable::Closing),
+            _ => Tokens::Block(Toggleable::Closing),
+        };
+
+        // The scopes of a string cannot be closed from one of its expressions
+        let depth = self
+            .scope
+            .iter()
+            .rev()
+            .take_while(|scope| scope.is(&Scope::Block(())) || scope.is(&Scope::Object(())))
+            .position(|scope| scope.is(&closed));
+
+        let Some(current) = self.scope.last().cloned() else {
+            self.diagnostics
+                .push(Box::new(UnexpectedCharacter(self.get_slice().into(), None)));
+            self.skip(Trivia::Invalid);
+            return;
+        };
+
+        match depth {
+            Some(0) => {}
+            Some(depth) => {
+                self.diagnostics.push(Box::new(InvalidScopeEnding(
+                    self.get_slice().into(),
+                    current,
+                )));
+
+                let at = self.analyser.range().start;
+                for _ in 0..depth {
+                    let scope = self.scope.pop().unwrap();
+                    self.lexified.push(Token::new(
+                        closing(&scope),
+                        &LangModuleSlice::new_with(&self.module, at..at),
+                    ));
+                }
+            }
+            None => {
+                self.diagnostics.push(Box::new(InvalidScopeEnding(
+                    self.get_slice().into(),
+                    current.clone(),
+                )));
+                self.scope.pop();
+                self.validate_analyser(closing(&current));
+                return;
+            }
+        }
+
+        let scope = self.scope.pop().unwrap();
+        self.validate_analyser(closing(&scope));
+    }
+
+    /// Process the next characters. If they are not valid, the error is collected and they are skipped.
+    fn step(&mut self) {
+        let start = self.analyser.range().start;
+        if let Err(error) = self.process() {
+            self.diagnostics.push(error);
+
+            // Skip at least a character (the characters of the validated tokens are not skipped again)
+            let end = self
+                .analyser
+                .range()
+                .end
+                .max(start + 1)
+                .min(self.analyser.stream().len());
+            let skipped = self.analyser.range().start.min(end)..end;
+            self.analyser.set(skipped);
+            if !self.analyser.range().is_empty() {
+                self.skip(Trivia::Invalid);
+            }
+        }
+    }
+
+    /// Execute the lexer if needed and return the vector of tokens.
+    /// The errors found are collected (see `diagnostics`) instead of being raised : the lexer recovers from them
+    /// to find the next ones.
+    pub fn tokenize(&mut self) -> &Vec<Token> {
         while !self.analyser.process_finished() {
-            self.process().unwrap_or_else(|e| e.controlled_raise());
+            self.step();
         }
 
-        if !self.scope.is_empty() {
-            UnclosedScope(self.scope.pop().unwrap()).controlled_raise();
+        while let Some(scope) = self.scope.pop() {
+            self.diagnostics.push(Box::new(UnclosedScope(scope)));
         }
 
         &self.lexified
     }
+    /// Execute the lexer if needed and return the vector of tokens.
+    /// The errors found are raised.
+    pub fn lexify(&mut self) -> &Vec<Token> {
+        self.tokenize();
+        take(&mut self.diagnostics).raise();
+
+        &self.lexified
+    }
+    /// Get the errors found by the lexer
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+    /// Take the errors found by the lexer
+    pub(crate) fn take_diagnostics(&mut self) -> Diagnostics {
+        take(&mut self.diagnostics)
+    }
+    /// Get the trivia following the last token (kept only by a lexer created with `new_with_trivia`)
+    pub fn trailing(&self) -> &Vec<Token<Trivia>> {
+        &self.trivia
+    }
 
     /// Clear the lexified tokens and rebuild it
     pub fn relexify(&mut self) -> &Vec<Token> {
         self.lexified = vec![];
+        self.trivia = vec![];
+        self.scope = vec![];
+        self.diagnostics = Diagnostics::new();
         self.analyser.set(0..0);
 
         self.lexify()
@@ -107,18 +107,38 @@ pub enum Tokens {
     ArgSeparator,
     VarDef(VarDefinition),
     ScopeTarget(ScopeTarget),
+    /// The `->` arrow, followed by a type
+    Typing,
+}
+
+/// The code ignored by the parser, kept by the lexer only when it is asked to (see `Lexer::new_with_trivia`)
+#[derive(Debug, Clone)]
+pub enum Trivia {
+    /// Following whitespace characters
+    Whitespace,
+    /// A `|| ...` comment, until the end of its line (the line break is included)
+    LineComment,
+    /// A `|...|` comment
+    BlockComment,
+    /// A `;` following another one
+    EndOfInstruction,
+    /// Characters skipped by the lexer because they are not valid (an error is found)
+    Invalid,
 }
 
 #[derive(Debug, Clone)]
 pub struct Token<K = Tokens> {
     kind: K,
     location: LangModuleSlice,
+    /// The trivia preceding the token
+    trivia: Vec<Token<Trivia>>,
 }
 impl<K> Token<K> {
     pub fn new(kind: K, location: &LangModuleSlice) -> Self {
         Self {
             kind,
             location: location.clone(),
+            trivia: vec![],
         }
     }
     pub fn kind(&self) -> &K {
@@ -127,4 +147,11 @@ impl<K> Token<K> {
     pub fn location(&self) -> &LangModuleSlice {
         &self.location
     }
+    /// Get the trivia found between the previous token and this one
+    pub fn trivia(&self) -> &Vec<Token<Trivia>> {
+        &self.trivia
+    }
+    pub fn set_trivia(&mut self, trivia: Vec<Token<Trivia>>) {
+        self.trivia = trivia;
+    }
 }
@@ -61,20 +61,22 @@ pub struct NumberRepresentation {
     pub negative: bool,
     pub integer: u64,
     pub decimal: Option<u64>,
+    /// Number of digits written in the decimal part (including leading zeros)
+    pub decimal_length: u32,
     pub represented_as: NumberRepresentationBases,
 }
-impl Into<f64> for NumberRepresentation {
-    fn into(self) -> f64 {
+impl From<NumberRepresentation> for f64 {
+    fn from(value: NumberRepresentation) -> Self {
         let mut num = 0f64;
-        num += self.integer as f64;
+        num += value.integer as f64;
 
-        num += if let Some(dec) = self.decimal {
-            (dec as f64) / 10f64.powf(dec.to_string().len() as f64)
+        num += if let Some(dec) = value.decimal {
+            (dec as f64) / 10f64.powi(value.decimal_length as i32)
         } else {
             0f64
         };
 
-        if self.negative {
+        if value.negative {
             num *= -1f64;
         }
         num
@@ -87,6 +89,7 @@ impl From<&LangModuleSlice> for NumberRepresentation {
 
         let mut integer = 0;
         let mut decimal = None;
+        let mut decimal_length = 0;
         let negative = code.starts_with('-');
 
         if negative {
@@ -116,7 +119,9 @@ impl From<&LangModuleSlice> for NumberRepresentation {
                 '.' => {
                     assert!(decimal.is_none(), "Found 2 '.' characters in the number.");
                     decimal = Some(take(&mut integer));
-                    index_shift = index;
+                    decimal_length = (index - index_shift) as u32;
+                    // The next digit is the first one of the integer part
+                    index_shift = index + 1;
                 }
                 '_' => {
                     index_shift += 1;
@@ -139,6 +144,7 @@ impl From<&LangModuleSlice> for NumberRepresentation {
             negative,
             integer,
             decimal,
+            decimal_length,
             represented_as,
         }
     }
@@ -2,11 +2,21 @@ use std::{path::PathBuf, rc::Rc};
 
 use crate::flylang::{lexer::Lexer, module::LangModule, parser::Parser};
 
+pub mod analyser;
+pub mod cache;
+pub mod checker;
+pub mod compilers;
+pub mod cst;
 pub mod errors;
+pub mod formatter;
+pub mod interpreter;
 pub mod lexer;
 pub mod module;
+pub mod optimizer;
 pub mod parser;
+pub mod runtime;
 pub mod utils;
+pub mod vm;
 
 pub struct FlyLang();
 
@@ -24,9 +34,8 @@ impl FlyLang {
         let mut lexer = Self::lexer(path);
         #[cfg(debug_assertions)]
         {
-            dbg!(&lexer.lexify());
+            dbg!(&lexer.tokenize());
         }
-        lexer.lexify();
 
         Parser::from(&mut lexer)
     }
@@ -41,9 +50,8 @@ impl FlyLang {
         let mut lexer = Self::anonymous_lexer(script, label);
         #[cfg(debug_assertions)]
         {
-            dbg!(&lexer.lexify());
+            dbg!(&lexer.tokenize());
         }
-        lexer.lexify();
 
         Parser::from(&mut lexer)
     }
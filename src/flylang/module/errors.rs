@@ -1,9 +1,12 @@
 use std::{ffi::OsStr, path::PathBuf};
 
-use crate::flylang::errors::{ErrorType, RaisableErr};
+use crate::flylang::errors::{ErrorType, RaisableErr, codes::ErrorId};
 
 pub struct InvalidEntryPoint(pub PathBuf);
 impl RaisableErr for InvalidEntryPoint {
+    fn _id(&self) -> ErrorId {
+        ErrorId::InvalidEntryPoint
+    }
     fn _kind(&self) -> ErrorType {
         ErrorType::Stop
     }
@@ -17,6 +20,9 @@ impl RaisableErr for InvalidEntryPoint {
 
 pub struct WeirdExtension(pub PathBuf);
 impl RaisableErr for WeirdExtension {
+    fn _id(&self) -> ErrorId {
+        ErrorId::WeirdExtension
+    }
     fn _kind(&self) -> ErrorType {
         ErrorType::Warn
     }
@@ -24,6 +24,10 @@ pub mod slice;
 pub struct LangModule {
     path: PathBuf,
     code: String,
+    /// The byte offset of each character of the code, followed by the length of the code
+    offsets: Vec<usize>,
+    /// The byte offset of the start of each line
+    lines: Vec<usize>,
 }
 
 impl LangModule {
@@ -33,18 +37,32 @@ impl LangModule {
         }
 
         if path.extension() != Some(OsStr::new("fly")) {
-            WeirdExtension(path.clone()).print();
+            WeirdExtension(path.clone()).controlled_raise();
         }
 
-        Ok(Self {
-            code: read_to_string(&path).expect("The file path is invalid."),
-            path,
-        })
+        let code = read_to_string(&path).expect("The file path is invalid.");
+        Ok(Self::new_from_code(path, code))
     }
     pub fn new_from_raw(script: String, label: &str) -> Self {
+        Self::new_from_code(PathBuf::from(format!("<{}>", label)), script)
+    }
+
+    /// Get a module from a code which is already read
+    pub fn new_from_code(path: PathBuf, code: String) -> Self {
+        let offsets = code
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([code.len()])
+            .collect();
+        let lines = std::iter::once(0)
+            .chain(code.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
         Self {
-            code: script,
-            path: PathBuf::from(format!("<{}>", label)),
+            path,
+            code,
+            offsets,
+            lines,
         }
     }
 
@@ -66,6 +84,29 @@ impl LangModule {
     pub fn tail_range(&self) -> Range<usize> {
         self.code.len()..self.code.len()
     }
+
+    /// Get the number of characters of the code
+    pub fn char_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Get the byte offset of the character at the given index.
+    /// The index following the last character gives the length of the code.
+    pub fn offset(&self, index: usize) -> usize {
+        self.offsets[index]
+    }
+    /// Get the index of the character containing the byte at the given offset.
+    /// The length of the code gives the index following the last character.
+    pub fn index(&self, offset: usize) -> usize {
+        self.offsets.partition_point(|start| *start <= offset) - 1
+    }
+    /// Get the byte offset of the start of each line
+    pub fn line_starts(&self) -> &[usize] {
+        &self.lines
+    }
+    /// Get the line index (starting at 0) containing the byte at the given offset
+    pub fn line(&self, offset: usize) -> usize {
+        self.lines.partition_point(|start| *start <= offset) - 1
+    }
 }
 impl Display for LangModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -88,7 +129,7 @@ impl ModIter {
         Self {
             module: Rc::clone(module),
             cursor: 0,
-            max: module.code.len(),
+            max: module.char_count(),
         }
     }
 }
@@ -96,8 +137,8 @@ impl From<&LangModuleSlice> for ModIter {
     fn from(value: &LangModuleSlice) -> Self {
         Self {
             module: Rc::clone(value.module()),
-            cursor: value.range().start,
-            max: value.range().end,
+            cursor: value.module().index(value.range().start),
+            max: value.module().index(value.range().end),
         }
     }
 }
@@ -69,7 +69,7 @@ impl LangModuleSlice {
     pub fn start(&self) -> Option<LangModuleChar> {
         if !self.code().is_empty() {
             let mut modchar = LangModuleChar::new(&self.module);
-            modchar.set(self.range.start);
+            modchar.set(self.module.index(self.range.start));
             Some(modchar)
         } else {
             None
@@ -79,7 +79,7 @@ impl LangModuleSlice {
     pub fn end(&self) -> Option<LangModuleChar> {
         if !self.code().is_empty() {
             let mut modchar = LangModuleChar::new(&self.module);
-            modchar.set(self.range.end.saturating_sub(1));
+            modchar.set(self.module.index(self.range.end.saturating_sub(1)));
             Some(modchar)
         } else {
             None
@@ -100,7 +100,7 @@ impl From<&Vec<LangModuleChar>> for LangModuleSlice {
 
         for modchar in value.iter().skip(1) {
             assert!(
-                module == modchar.module(),
+                Rc::ptr_eq(module, modchar.module()) || module == modchar.module(),
                 "Different LangModule in the given array."
             );
 
@@ -109,7 +109,7 @@ impl From<&Vec<LangModuleChar>> for LangModuleSlice {
         }
 
         let mut modslice = LangModuleSlice::new(module);
-        modslice.set(start..end + 1);
+        modslice.set(module.offset(start)..module.offset(end + 1));
         modslice
     }
 }
@@ -127,7 +127,7 @@ impl From<&Vec<LangModuleSlice>> for LangModuleSlice {
 
         for modchar in value.iter().skip(1) {
             assert!(
-                module == modchar.module(),
+                Rc::ptr_eq(module, modchar.module()) || module == modchar.module(),
                 "Different LangModule in the given array."
             );
 
@@ -144,7 +144,7 @@ impl From<&Vec<LangModuleSlice>> for LangModuleSlice {
 impl From<&LangModuleChar> for LangModuleSlice {
     fn from(value: &LangModuleChar) -> Self {
         let mut modslice = LangModuleSlice::new(value.module());
-        modslice.set(value.index()..(value.index() + 1));
+        modslice.set(value.offset()..value.module().offset(value.index() + 1));
         modslice
     }
 }
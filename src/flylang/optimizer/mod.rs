@@ -0,0 +1,386 @@
+use crate::flylang::{
+    interpreter::{Interpreter, value::Value},
+    lexer::tokens::BinaryOperator,
+    module::slice::LangModuleSlice,
+    parser::ast::{
+        Branches, Node,
+        definables::{Definables, variables::VariableEmplacements},
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+        },
+        instructions::{
+            Instructions,
+            breakers::BreakKind,
+            conditionnal::{If, IfFallBack},
+            loops::LoopParameter,
+        },
+    },
+};
+
+/// Simplify the parsed instructions before they are executed (or compiled):
+///
+/// - the operations on constant values are computed
+/// - the conditions whose condition is a constant only keep the executed branch
+/// - the instructions following a breaker are removed
+/// - the constant values whose result is not used are removed
+///
+/// The constant expressions are computed by the interpreter, so the optimized code always behaves as the
+/// original one. An expression that fails to be computed (like `1 / 0`) is kept, to fail at runtime.
+pub struct Optimizer {
+    interpreter: Interpreter,
+    /// The number of breakers met, to know if some instructions contain one
+    breakers: usize,
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Optimizer {
+    pub fn new() -> Self {
+        Self {
+            interpreter: Interpreter::new(),
+            breakers: 0,
+        }
+    }
+
+    /// Get the optimized version of the given instructions
+    pub fn optimize(mut self, branches: &Branches) -> Branches {
+        let mut optimized = branches.clone();
+        self.branches(&mut optimized);
+        optimized
+    }
+
+    fn branches(&mut self, branches: &mut Branches) {
+        let instructions = std::mem::take(branches);
+        let count = instructions.len();
+
+        for (index, instruction) in instructions.into_iter().enumerate() {
+            let stops = matches!(instruction.kind(), Instructions::Break(_));
+            branches.extend(self.instruction(instruction));
+
+            // The following instructions are never executed
+            if stops {
+                break;
+            }
+            // The value of a constant is only used if it is the last instruction
+            if index + 1 < count
+                && let Some(last) = branches.last()
+                && let Instructions::ValueOf(expression) = last.kind()
+                && Self::constant(expression)
+            {
+                branches.pop();
+            }
+        }
+    }
+
+    /// Optimize an instruction, giving the instructions replacing it
+    fn instruction(&mut self, mut instruction: Node) -> Branches {
+        let location = instruction.location().clone();
+
+        match instruction.kind_mut() {
+            Instructions::ValueOf(expression) => self.expression(expression, &location),
+            Instructions::If(condition) => return self.condition(condition.clone(), &location),
+            Instructions::Loop(repetition) => {
+                match &mut repetition.parameter {
+                    LoopParameter::Through(each) => self.node(&mut each.iterable),
+                    LoopParameter::Conditionnaly(condition) => self.node(&mut condition.condition),
+                }
+                self.branches(&mut repetition.process);
+            }
+            Instructions::Break(breaker) => {
+                self.breakers += 1;
+                if let BreakKind::Return(_, Some(value)) = &mut breaker.kind {
+                    self.node(value);
+                }
+            }
+            Instructions::Use(_) => {}
+        }
+
+        vec![instruction]
+    }
+
+    /// Optimize a condition, and only keep its executed branch if it is known
+    fn condition(&mut self, mut condition: If, location: &LangModuleSlice) -> Branches {
+        self.node(&mut condition.condition);
+        let passed = self.truthy(&condition.condition);
+        let empty = || {
+            vec![Node::new(
+                Instructions::ValueOf(Expressions::Literal(ParsedLiterals::Empty)),
+                location,
+            )]
+        };
+
+        if passed == Some(false) {
+            let Some(fallback) = condition.fallback else {
+                return empty();
+            };
+
+            return match fallback.kind().clone() {
+                IfFallBack::If(other) => self.condition(other.kind().clone(), other.location()),
+                // The fallback is always executed, in its own scope
+                IfFallBack::Process(target, process) => {
+                    let always = If {
+                        condition: Node::new(
+                            Expressions::Literal(ParsedLiterals::True),
+                            condition.condition.location(),
+                        ),
+                        process: Box::new(process),
+                        fallback: None,
+                        scope_target: target,
+                    };
+                    self.condition(always, fallback.location())
+                }
+            };
+        }
+
+        let breakers = self.breakers;
+        self.branches(&mut condition.process);
+
+        if passed == Some(true) {
+            // Without breakers, the scope of the condition is useless
+            if self.breakers == breakers {
+                return match condition.process.is_empty() {
+                    true => empty(),
+                    false => *condition.process,
+                };
+            }
+            condition.fallback = None;
+        }
+
+        if let Some(fallback) = &mut condition.fallback {
+            let fallback_location = fallback.location().clone();
+            match fallback.kind_mut() {
+                IfFallBack::Process(_, process) => self.branches(process),
+                IfFallBack::If(other) => {
+                    let optimized = self.condition(other.kind().clone(), other.location());
+                    let single = match optimized.as_slice() {
+                        [node] => match node.kind() {
+                            Instructions::If(other) => {
+                                Some(Node::new(other.clone(), node.location()))
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    };
+                    let replacement = match single {
+                        Some(other) => IfFallBack::If(Box::new(other)),
+                        None => IfFallBack::Process(None, optimized),
+                    };
+                    **fallback = Node::new(replacement, &fallback_location);
+                }
+            }
+        }
+
+        vec![Node::new(Instructions::If(condition), location)]
+    }
+
+    fn node(&mut self, node: &mut Node<Expressions>) {
+        let location = node.location().clone();
+        self.expression(node.kind_mut(), &location);
+    }
+
+    fn call(&mut self, call: &mut Call) {
+        self.node(&mut call.callable);
+        for argument in call.arguments.iter_mut() {
+            self.node(argument);
+        }
+    }
+
+    fn property(&mut self, property: &mut ReadProperty) {
+        self.node(&mut property.from);
+        if let Property::Expression(key) = property.read.kind_mut() {
+            self.node(key);
+        }
+    }
+
+    fn expression(&mut self, expression: &mut Expressions, location: &LangModuleSlice) {
+        match expression {
+            Expressions::Literal(ParsedLiterals::String(items)) => {
+                for item in items.iter_mut() {
+                    if let ParsedStringItem::Expression(inner) = item.kind_mut() {
+                        self.node(inner);
+                    }
+                }
+            }
+            Expressions::Literal(_) => return,
+            Expressions::Defined(definable) => return self.definable(definable),
+            Expressions::Modifed(modified) => {
+                for modifier in modified.modified_by.iter_mut() {
+                    if let Modifier::CallReturn(call) = modifier.kind_mut() {
+                        self.call(call);
+                    }
+                }
+                return self.definable(modified.definable.kind_mut());
+            }
+            Expressions::Read(property) => return self.property(property),
+            Expressions::ReturnOf(call) => return self.call(call),
+            Expressions::Reverse(reverse) => self.node(&mut reverse.expression),
+            Expressions::Operation(operation) => {
+                let operands = match operation {
+                    Operations::Numeric(operation) => &mut operation.operands,
+                    Operations::Binary(operation) => &mut operation.operands,
+                    Operations::Comparative(operation) => &mut operation.operands,
+                };
+                self.node(&mut operands.0);
+                self.node(&mut operands.1);
+
+                // `&` and `?` operators do not need their right operand if the left one is enough
+                if let Operations::Binary(binary) = operation
+                    && let Some(left) = self.truthy(&binary.operands.0)
+                {
+                    match (binary.operator.kind(), left) {
+                        (BinaryOperator::And, false) => {
+                            *expression = Expressions::Literal(ParsedLiterals::False)
+                        }
+                        (BinaryOperator::Or, true) => {
+                            *expression = Expressions::Literal(ParsedLiterals::True)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expressions::Prioritized(inner) => self.node(inner),
+            Expressions::Ternary(ternary) => {
+                self.node(&mut ternary.condition);
+                self.node(&mut ternary.yes);
+                self.node(&mut ternary.no);
+
+                let Some(passed) = self.truthy(&ternary.condition) else {
+                    return;
+                };
+                // The literals are read from their location, so the kept node is not unwrapped
+                let kept = if passed { &ternary.yes } else { &ternary.no };
+                *expression = Expressions::Prioritized(kept.clone());
+            }
+            Expressions::Structure(structure) => {
+                for entry in structure.entries.iter_mut() {
+                    let entry = entry.kind_mut();
+                    self.node(&mut entry.key);
+                    self.node(&mut entry.value);
+                }
+                return;
+            }
+            Expressions::Array(array) => {
+                for item in array.entries.iter_mut() {
+                    self.node(item);
+                }
+                return;
+            }
+            Expressions::Instanciate(instanciation) => {
+                self.node(&mut instanciation.class);
+                for argument in instanciation.arguments.iter_mut() {
+                    self.node(argument);
+                }
+                return;
+            }
+            Expressions::Typed(typed) => return self.node(&mut typed.expression),
+        }
+
+        if let Some(folded) = self.fold(expression, location) {
+            *expression = folded;
+        }
+    }
+
+    fn definable(&mut self, definable: &mut Definables) {
+        match definable {
+            Definables::Variable(variable) => {
+                self.node(&mut variable.value);
+
+                let location = variable.emplacement.location().clone();
+                match variable.emplacement.kind_mut() {
+                    VariableEmplacements::Scope => {}
+                    VariableEmplacements::Property(property) => self.property(property),
+                    VariableEmplacements::Any(expression) => self.expression(expression, &location),
+                }
+            }
+            Definables::Function(function) => self.branches(&mut function.execution),
+            Definables::Class(class) => {
+                if let Some(constructor) = &mut class.constructor {
+                    self.branches(&mut constructor.kind_mut().execution);
+                }
+                for attribute in class.attributes.iter_mut() {
+                    self.node(&mut attribute.kind_mut().item.value);
+                }
+                for method in class.methods.iter_mut() {
+                    self.branches(&mut method.kind_mut().item.execution);
+                }
+            }
+        }
+    }
+
+    /// Whether the expression is a literal value (which does not depend on variables)
+    fn constant(expression: &Expressions) -> bool {
+        match expression {
+            Expressions::Literal(ParsedLiterals::Word) => false,
+            Expressions::Literal(ParsedLiterals::String(items)) => items
+                .iter()
+                .all(|item| matches!(item.kind(), ParsedStringItem::Literal(_))),
+            Expressions::Literal(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Compute an expression whose operands are constants
+    fn fold(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> Option<Expressions> {
+        let operands: Vec<&Expressions> = match expression {
+            Expressions::Literal(ParsedLiterals::String(items)) => items
+                .iter()
+                .filter_map(|item| match item.kind() {
+                    ParsedStringItem::Expression(inner) => Some(inner.kind()),
+                    ParsedStringItem::Literal(_) => None,
+                })
+                .collect(),
+            Expressions::Reverse(reverse) => vec![reverse.expression.kind()],
+            Expressions::Prioritized(inner) => vec![inner.kind()],
+            Expressions::Operation(operation) => {
+                let (left, right) = match operation {
+                    Operations::Numeric(operation) => &operation.operands,
+                    Operations::Binary(operation) => &operation.operands,
+                    Operations::Comparative(operation) => &operation.operands,
+                };
+                vec![left.kind(), right.kind()]
+            }
+            _ => return None,
+        };
+        if !operands.into_iter().all(Self::constant) {
+            return None;
+        }
+
+        let value = self.interpreter.evaluate(expression, location).ok()?;
+        Some(Expressions::Literal(match value {
+            Value::Empty => ParsedLiterals::Empty,
+            Value::Boolean(true) => ParsedLiterals::True,
+            Value::Boolean(false) => ParsedLiterals::False,
+            Value::Number(number) => ParsedLiterals::Computed(number),
+            Value::String(content) => ParsedLiterals::String(vec![Node::new(
+                ParsedStringItem::Literal(content.to_string()),
+                location,
+            )]),
+            _ => return None,
+        }))
+    }
+
+    /// Get the truthiness of a constant expression
+    fn truthy(&mut self, node: &Node<Expressions>) -> Option<bool> {
+        if !Self::constant(node.kind()) {
+            return None;
+        }
+
+        self.interpreter
+            .evaluate(node.kind(), node.location())
+            .ok()
+            .map(|value| value.truthy())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::flylang::{
-    errors::lang_err,
+    errors::{LangResult, lang_err},
     lexer::tokens::{Keywords, Literals, Toggleable, Tokens},
     module::slice::LangModuleSlice,
     parser::{
@@ -22,12 +22,21 @@ use crate::flylang::{
     },
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClassItemVisibility {
     Public,
     Private,
     Protected,
 }
+impl ClassItemVisibility {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Private => "private",
+            Self::Protected => "protected",
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ClassItem<Kind> {
@@ -37,6 +46,51 @@ pub struct ClassItem<Kind> {
     pub modifie_by: Vec<Node<Modifier>>,
 }
 
+/// Extract the visibility and the static state of a class item from its modifiers.
+///
+/// `#(private)`, `#(protected)`, `#(public)` and `#(static)` are not real modifiers : they are removed from the list.
+fn item_properties(
+    modifiers: Vec<Node<Modifier>>,
+) -> LangResult<(ClassItemVisibility, bool, Vec<Node<Modifier>>)> {
+    let mut visibility = None;
+    let mut is_static = false;
+    let mut others = vec![];
+
+    for modifier in modifiers {
+        let keyword = match modifier.kind() {
+            Modifier::DefinedElement => modifier.location().code(),
+            Modifier::CallReturn(_) => "",
+        };
+        let item_visibility = match keyword {
+            "public" => ClassItemVisibility::Public,
+            "private" => ClassItemVisibility::Private,
+            "protected" => ClassItemVisibility::Protected,
+            "static" => {
+                is_static = true;
+                continue;
+            }
+            _ => {
+                others.push(modifier);
+                continue;
+            }
+        };
+
+        if visibility.is_some() {
+            return lang_err!(UnableToParse(
+                modifier.location().clone(),
+                String::from("A class item can only have one visibility")
+            ));
+        }
+        visibility = Some(item_visibility);
+    }
+
+    Ok((
+        visibility.unwrap_or(ClassItemVisibility::Public),
+        is_static,
+        others,
+    ))
+}
+
 #[derive(Debug, Clone)]
 pub struct DefineClass {
     pub name: Node<Word>,
@@ -122,13 +176,10 @@ impl Parsable for DefineClass {
                     match branch[0].kind() {
                         Instructions::ValueOf(Expressions::Defined(Definables::Function(
                             defined,
-                        ))) => {
-                            // constructor
-                            if constructor.is_none() {
-                                constructor =
-                                    Some(Node::new(defined.clone(), branch[0].location()));
-                                continue;
-                            }
+                        ))) if constructor.is_none() && defined.name.is_none() => {
+                            // constructor (named functions are methods)
+                            constructor = Some(Node::new(defined.clone(), branch[0].location()));
+                            continue;
                         }
                         Instructions::ValueOf(Expressions::Literal(ParsedLiterals::Word)) => {
                             // parent
@@ -167,6 +218,8 @@ impl Parsable for DefineClass {
                         }
                     };
 
+                    let (visibility, is_static, modifiers) = item_properties(modifiers)?;
+
                     match defined_element {
                         Definables::Function(method) => {
                             if method.name.is_none() {
@@ -178,8 +231,8 @@ impl Parsable for DefineClass {
 
                             methods.push(Node::new(
                                 ClassItem {
-                                    visibility: ClassItemVisibility::Public,
-                                    is_static: false,
+                                    visibility,
+                                    is_static,
                                     item: method.clone(),
                                     modifie_by: modifiers,
                                 },
@@ -197,8 +250,8 @@ impl Parsable for DefineClass {
 
                             attributes.push(Node::new(
                                 ClassItem {
-                                    visibility: ClassItemVisibility::Public,
-                                    is_static: false,
+                                    visibility,
+                                    is_static,
                                     item: attribute.clone(),
                                     modifie_by: modifiers,
                                 },
@@ -13,16 +13,26 @@ use crate::flylang::{
                 Instructions,
                 breakers::{Break, BreakKind},
             },
+            specials::typing::Typing,
         },
         errors::{Expected, UnableToParse, UnexpectedNode, UnexpectedToken},
         parsable::Parsable,
     },
 };
 
+#[derive(Debug, Clone)]
+pub struct Argument {
+    pub name: Node<Word>,
+    /// The type of the argument (`name -> type`)
+    pub typing: Option<Node<Typing>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct DefineFunction {
     pub name: Option<Node<Word>>,
-    pub arguments: BoxedBranches<Word>,
+    pub arguments: BoxedBranches<Argument>,
+    /// The type of the returned value (`fn name -> type (...)`)
+    pub returns: Option<Node<Typing>>,
     pub scope_target: Option<Node<ScopeTarget>>,
     pub execution: Branches,
 }
@@ -57,9 +67,19 @@ impl Parsable for DefineFunction {
             return lang_err!(UnexpectedToken(token));
         };
 
+        let mut returns = None;
+        if let Some(slice) = parser.analyser.lookup(0, 1)
+            && matches!(slice[0].kind(), Tokens::Typing)
+        {
+            parser.analyser.next(0, 1);
+            returns = Some(Typing::parse(parser, None)?);
+        }
+
         if !parser.analyser.able_to(0, 1) {
             return lang_err!(Expected {
-                after: if let Some(node) = name {
+                after: if let Some(node) = &returns {
+                    node.location().clone()
+                } else if let Some(node) = name {
                     node.location().clone()
                 } else {
                     token.location().clone()
@@ -86,7 +106,10 @@ impl Parsable for DefineFunction {
                 execution = vec![instruction.clone_as(|_, l| {
                     (
                         Instructions::Break(Break {
-                            kind: BreakKind::Return(None, Some(Node::new(expression.clone(), &l))),
+                            kind: BreakKind::Return(
+                                None,
+                                Some(Node::new(expression.clone(), &l).into()),
+                            ),
                             keyword_location: l.clone(),
                         }),
                         l.clone(),
@@ -109,14 +132,26 @@ impl Parsable for DefineFunction {
                 ));
             }
             let node = &nodes[0];
-            if !matches!(
-                node.kind(),
-                Instructions::ValueOf(Expressions::Literal(ParsedLiterals::Word))
-            ) {
-                return lang_err!(UnexpectedNode(node.clone()));
-            }
+            let argument = match node.kind() {
+                Instructions::ValueOf(Expressions::Literal(ParsedLiterals::Word)) => Argument {
+                    name: node.clone_as(|_, l| (Word, l)),
+                    typing: None,
+                },
+                Instructions::ValueOf(Expressions::Typed(typed))
+                    if matches!(
+                        typed.expression.kind(),
+                        Expressions::Literal(ParsedLiterals::Word)
+                    ) =>
+                {
+                    Argument {
+                        name: Node::new(Word, typed.expression.location()),
+                        typing: Some(typed.typing.clone()),
+                    }
+                }
+                _ => return lang_err!(UnexpectedNode(node.clone())),
+            };
 
-            arguments.push(node.clone_as(|_, l| (Word, l)));
+            arguments.push(Node::new(argument, node.location()));
         }
 
         let location =
@@ -127,6 +162,7 @@ impl Parsable for DefineFunction {
                 name,
                 execution,
                 arguments: Box::new(arguments),
+                returns,
                 scope_target: scope_target
                     .map(|target| Node::new(target.kind().clone(), target.location())),
             },
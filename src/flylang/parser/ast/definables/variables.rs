@@ -12,6 +12,7 @@ use crate::flylang::{
                 property::ReadProperty,
             },
             instructions::Instructions,
+            specials::typing::Typing,
         },
         errors::{Expected, UnexpectedNode, UnexpectedToken},
         mods::ParserBehaviors,
@@ -34,6 +35,8 @@ pub struct DefineVariable {
     pub emplacement: Node<VariableEmplacements>,
     pub value: BoxedNode<Expressions>,
     pub readonly: bool,
+    /// The type of the variable (`name -> type: value`)
+    pub typing: Option<Node<Typing>>,
 }
 
 impl Parsable for DefineVariable {
@@ -61,6 +64,16 @@ impl Parsable for DefineVariable {
             return lang_err!(UnexpectedNode(emplacement_instruction));
         };
 
+        // The type annotation is not part of the emplacement
+        let (emplacement_expression, emplacement_location, typing) = match emplacement_expression {
+            Expressions::Typed(typed) => (
+                typed.expression.kind(),
+                typed.expression.location(),
+                Some(typed.typing.clone()),
+            ),
+            expression => (expression, emplacement_instruction.location(), None),
+        };
+
         let any_recursive = parser
             .behaviors
             .contains(&ParserBehaviors::AllowAnyVariableEmplacement(true));
@@ -108,11 +121,7 @@ impl Parsable for DefineVariable {
                 Expressions::Operation(Operations::Numeric(Operation {
                     operator: operator.clone(),
                     operands: (
-                        Node::new(
-                            emplacement_expression.clone(),
-                            emplacement_instruction.location(),
-                        )
-                        .into(),
+                        Node::new(emplacement_expression.clone(), emplacement_location).into(),
                         expression.into(),
                     ),
                 })),
@@ -124,9 +133,10 @@ impl Parsable for DefineVariable {
 
         Ok(Node::new(
             Self {
-                emplacement: Node::new(emplacement, emplacement_instruction.location()),
+                emplacement: Node::new(emplacement, emplacement_location),
                 value: Box::new(value),
                 readonly: matches!(def_kind, VarDefinition::Constant),
+                typing,
             },
             &def_location,
         ))
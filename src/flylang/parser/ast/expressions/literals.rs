@@ -27,6 +27,9 @@ pub enum ParsedLiterals {
     False,
     #[evt(derive(Debug, Clone))]
     Number,
+    /// A number computed before running the code (by the optimizer), so it is not written as is in the code
+    #[evt(derive(Debug, Clone))]
+    Computed(f64),
     #[evt(derive(Debug, Clone))]
     Empty,
     #[evt(derive(Debug, Clone))]
@@ -42,7 +45,10 @@ impl TryInto<Node<ParsedStringItem>> for Token<StringItem> {
                 StringItem::Literal(content) => ParsedStringItem::Literal(content.clone()),
                 StringItem::Expression(expr) => {
                     let mut parser = Parser::new(self.location().module(), expr.as_ref().clone());
-                    let parsed = parser.parse();
+                    let parsed = match parser.try_parse() {
+                        Ok(parsed) => parsed,
+                        Err(diagnostics) => return Err(diagnostics.into_iter().next().unwrap()),
+                    };
 
                     if parsed.len() != 1 {
                         return lang_err!(UnableToParse(
@@ -16,8 +16,10 @@ use crate::flylang::{
                 property::ReadProperty,
                 reverse::{Reverse, ReverseKind},
                 ternary::Ternary,
+                typed::Typed,
             },
             instructions::Instructions,
+            specials::typing::Typing,
         },
         errors::{Expected, UnexpectedNode, UnexpectedToken},
         mods::ParserBehaviors,
@@ -34,6 +36,7 @@ pub mod operations;
 pub mod property;
 pub mod reverse;
 pub mod ternary;
+pub mod typed;
 
 #[derive(Debug, Clone)]
 pub enum Expressions {
@@ -49,6 +52,7 @@ pub enum Expressions {
     Array(Array),
     Modifed(ModifiedDefinable),
     Instanciate(ClassInstanciation),
+    Typed(Typed),
 }
 
 impl Expressions {
@@ -258,6 +262,28 @@ impl Parsable for Expressions {
             Tokens::Accessor => {
                 ReadProperty::parse(parser, previous)?.clone_as(|k, l| (Expressions::Read(k), l))
             }
+            Tokens::Typing => {
+                let Some(previous) = previous else {
+                    return lang_err!(UnexpectedToken(token));
+                };
+                let Instructions::ValueOf(expression) = previous.kind() else {
+                    return lang_err!(UnexpectedNode(previous));
+                };
+
+                let typing = Typing::parse(parser, None)?;
+                let location = LangModuleSlice::from(&vec![
+                    previous.location().clone(),
+                    typing.location().clone(),
+                ]);
+
+                Node::new(
+                    Self::Typed(Typed {
+                        expression: Box::new(Node::new(expression.clone(), previous.location())),
+                        typing,
+                    }),
+                    &location,
+                )
+            }
             _ => return lang_err!(UnexpectedToken(token)),
         };
 
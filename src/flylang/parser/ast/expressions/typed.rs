@@ -0,0 +1,10 @@
+use crate::flylang::parser::ast::{
+    BoxedNode, Node, expressions::Expressions, specials::typing::Typing,
+};
+
+/// An expression annotated with its type (`value -> number`)
+#[derive(Debug, Clone)]
+pub struct Typed {
+    pub expression: BoxedNode<Expressions>,
+    pub typing: Node<Typing>,
+}
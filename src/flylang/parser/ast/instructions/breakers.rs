@@ -2,7 +2,7 @@ use crate::flylang::{
     lexer::tokens::{Keywords, ScopeTarget, Toggleable, Tokens},
     module::slice::LangModuleSlice,
     parser::{
-        ast::{Node, expressions::Expressions},
+        ast::{BoxedNode, Node, expressions::Expressions},
         parsable::Parsable,
     },
 };
@@ -11,7 +11,7 @@ use crate::flylang::{
 pub enum BreakKind {
     Stop(Option<Node<ScopeTarget>>),
     Pass(Option<Node<ScopeTarget>>),
-    Return(Option<Node<ScopeTarget>>, Option<Node<Expressions>>),
+    Return(Option<Node<ScopeTarget>>, Option<BoxedNode<Expressions>>),
 }
 
 #[derive(Debug, Clone)]
@@ -71,7 +71,7 @@ impl Parsable for Break {
                                 | Tokens::EndOfInstruction => None,
                                 _ => {
                                     parser.analyser.next(0, 0);
-                                    Some(Expressions::parse(parser, previous)?)
+                                    Some(Expressions::parse(parser, previous)?.into())
                                 }
                             }
                         } else {
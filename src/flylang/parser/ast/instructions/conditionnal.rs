@@ -16,7 +16,7 @@ use crate::flylang::{
 #[derive(Debug, Clone)]
 pub enum IfFallBack {
     Process(Option<Node<ScopeTarget>>, Branches),
-    If(Node<If>),
+    If(BoxedNode<If>),
 }
 
 #[derive(Debug, Clone)]
@@ -30,7 +30,7 @@ pub struct If {
 #[derive(Debug, Clone)]
 pub enum IfResult {
     Ternary(Ternary),
-    If(If),
+    If(Box<If>),
 }
 
 impl Parsable for If {
@@ -171,12 +171,12 @@ impl Parsable for If {
         ]);
 
         Ok(Node::new(
-            IfResult::If(Self {
+            IfResult::If(Box::new(Self {
                 condition,
                 process: process.into(),
                 fallback,
                 scope_target: scope,
-            }),
+            })),
             &location,
         ))
     }
@@ -220,7 +220,11 @@ impl Parsable for IfFallBack {
                     result.location().clone(),
                 ]);
                 Ok(Node::new(
-                    Self::If(result.clone_as(|_, l| (condition.clone(), l))),
+                    Self::If(
+                        result
+                            .clone_as(|_, l| (condition.as_ref().clone(), l))
+                            .into(),
+                    ),
                     &location,
                 ))
             }
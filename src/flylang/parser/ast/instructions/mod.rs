@@ -48,7 +48,7 @@ impl Parsable for Instructions {
 
                 match result.kind() {
                     IfResult::If(condition) => {
-                        Node::new(Self::If(condition.clone()), result.location())
+                        Node::new(Self::If(condition.as_ref().clone()), result.location())
                     }
                     IfResult::Ternary(ternary) => Node::new(
                         Self::ValueOf(Expressions::Ternary(ternary.clone())),
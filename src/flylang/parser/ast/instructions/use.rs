@@ -1,12 +1,11 @@
-use std::{fs::{self, canonicalize}, path::PathBuf, rc::Rc};
+use std::{fs, path::PathBuf, rc::Rc};
 
-use clap::builder::OsStr;
 use toml::Value;
 
 use crate::{behavior::LangBehavior, flylang::{
-    errors::{LangResult, lang_err}, lexer::{ranges::{IS_FILE_LOCATION_IF_STARTS_WITH, in_ranges}, tokens::{Keywords, Literals, Toggleable, Token, Tokens}}, module::{LangModule, slice::LangModuleSlice}, parser::{
+    cache, errors::{LangResult, lang_err}, lexer::{ranges::{IS_FILE_LOCATION_IF_STARTS_WITH, in_ranges}, tokens::{Keywords, Literals, Toggleable, Token, Tokens}}, module::{LangModule, slice::LangModuleSlice}, parser::{
         Parser, ast::{
-            Node,
+            Branches, Node,
             expressions::{Expressions, literals::{ParsedLiterals, ParsedStringItem, Word}}, instructions::Instructions,
         }, errors::{Expected, UnableToParse, UnexpectedNode, UnexpectedToken}, parsable::Parsable
     }
@@ -40,6 +39,21 @@ pub struct Package {
 }
 
 impl Package {
+    /// Create a package used in the given module
+    pub fn new(
+        source: PackageSource,
+        included: PackageIncludedContent,
+        emplacement: PackageContentEmplacement,
+        used_in: &Rc<LangModule>,
+    ) -> Self {
+        Self {
+            source,
+            included,
+            emplacement,
+            used_in: Rc::clone(used_in),
+        }
+    }
+
     fn parse_getters(parser: &mut crate::flylang::parser::Parser) -> LangResult<PackageIncludedContent> {
         if !(
             parser.analyser.min_len(1)
@@ -298,4 +312,18 @@ impl Package {
 
         found.map(|p| fs::canonicalize(p).expect("The method returned an inexistant path."))
     }
+
+    /// Load the package's main file (see the `path` method) and parse it.
+    /// The instructions are read from the cache of the file when it is valid, and the cache is rebuilt when it is stale.
+    ///
+    /// Returns None if the file does not exist.
+    pub fn load(&self, behaviors: &LangBehavior) -> Option<LangResult<(Rc<LangModule>, Branches)>> {
+        let path = self.path(behaviors)?;
+
+        Some(LangModule::new(path).map(|module| {
+            let module = module.into_rc();
+            let branches = cache::parse(&module, behaviors);
+            (module, branches)
+        }))
+    }
 }
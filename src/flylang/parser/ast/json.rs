@@ -0,0 +1,469 @@
+//! Conversion of the parsed instructions to JSON, the format given to the compilers' add-ons.
+//! The format is described in `docs/cli/addons.md`.
+
+use serde_json::{Map, Value, json};
+
+use crate::flylang::{
+    lexer::tokens::{
+        BinaryOperator, Comparison, Operator, ScopeTarget,
+        representations::number::NumberRepresentation,
+    },
+    module::slice::LangModuleSlice,
+    parser::ast::{
+        Branches, Node,
+        definables::{
+            Definables,
+            class::{ClassItem, DefineClass},
+            functions::DefineFunction,
+            variables::{DefineVariable, VariableEmplacements},
+        },
+        expressions::{
+            Expressions,
+            call::Call,
+            literals::{ParsedLiterals, ParsedStringItem, Word},
+            modified::Modifier,
+            operations::Operations,
+            property::{Property, ReadProperty},
+            reverse::ReverseKind,
+        },
+        instructions::{
+            Instructions,
+            breakers::BreakKind,
+            conditionnal::{If, IfFallBack},
+            loops::LoopParameter,
+            r#use::{PackageContentEmplacement, PackageIncludedContent, PackageSource},
+        },
+        specials::typing::Typing,
+    },
+};
+
+/// The version of the format. It changes each time the format is modified in a non-compatible way.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Convert instructions to a JSON array
+pub fn branches(branches: &Branches) -> Value {
+    Value::Array(branches.iter().map(instruction).collect())
+}
+
+/// Create a JSON object with the given type, location and fields
+fn object(kind: &str, location: &LangModuleSlice, fields: Value) -> Value {
+    let mut object = Map::new();
+    object.insert("type".into(), kind.into());
+    object.insert(
+        "location".into(),
+        json!({"start": location.range().start, "end": location.range().end}),
+    );
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+
+    Value::Object(object)
+}
+
+fn word(word: &Node<Word>) -> Value {
+    word.location().code().into()
+}
+
+fn optional_word(word: &Option<Node<Word>>) -> Value {
+    word.as_ref().map_or(Value::Null, self::word)
+}
+
+fn typing(typing: &Option<Node<Typing>>) -> Value {
+    typing
+        .as_ref()
+        .map_or(Value::Null, |typing| json!(typing.kind().names()))
+}
+
+fn scope(target: &Option<Node<ScopeTarget>>) -> Value {
+    match target.as_ref().map(|target| target.kind()) {
+        None => Value::Null,
+        Some(ScopeTarget::Named(name)) => json!({"name": name}),
+        Some(ScopeTarget::Numbered(skip)) => json!({"skip": skip.get()}),
+    }
+}
+
+fn node(expression: &Node<Expressions>) -> Value {
+    self::expression(expression.kind(), expression.location())
+}
+
+fn nodes<'a>(expressions: impl IntoIterator<Item = &'a Box<Node<Expressions>>>) -> Value {
+    Value::Array(expressions.into_iter().map(|e| node(e)).collect())
+}
+
+fn instruction(instruction: &Node) -> Value {
+    let location = instruction.location();
+
+    match instruction.kind() {
+        Instructions::ValueOf(expression) => self::expression(expression, location),
+        Instructions::If(condition) => self::condition(condition, location),
+        Instructions::Loop(repetition) => {
+            let body = branches(&repetition.process);
+            let scope = scope(&repetition.scope_target);
+
+            match &repetition.parameter {
+                LoopParameter::Through(each) => object(
+                    "Each",
+                    location,
+                    json!({
+                        "iterable": node(&each.iterable),
+                        "item": optional_word(&each.item),
+                        "index": optional_word(&each.index),
+                        "scope": scope,
+                        "body": body,
+                    }),
+                ),
+                LoopParameter::Conditionnaly(condition) => object(
+                    "While",
+                    location,
+                    json!({
+                        "condition": node(&condition.condition),
+                        "iteration": optional_word(&condition.iteration_number),
+                        "scope": scope,
+                        "body": body,
+                    }),
+                ),
+            }
+        }
+        Instructions::Break(breaker) => {
+            let (kind, target, value) = match &breaker.kind {
+                BreakKind::Stop(target) => ("Stop", target, Value::Null),
+                BreakKind::Pass(target) => ("Pass", target, Value::Null),
+                BreakKind::Return(target, value) => (
+                    "Return",
+                    target,
+                    value.as_ref().map_or(Value::Null, |v| node(v)),
+                ),
+            };
+
+            object(
+                kind,
+                location,
+                json!({"scope": scope(target), "value": value}),
+            )
+        }
+        Instructions::Use(package) => {
+            let source = match &package.source {
+                PackageSource::Package(name) => json!({"package": name}),
+                PackageSource::File(path) => json!({"file": path}),
+            };
+            let names = match &package.included {
+                PackageIncludedContent::All => Value::Null,
+                PackageIncludedContent::Only(names) => names.iter().map(word).collect(),
+            };
+            let into = match &package.emplacement {
+                PackageContentEmplacement::Global => Value::Null,
+                PackageContentEmplacement::Variable(variable) => word(variable),
+            };
+
+            object(
+                "Use",
+                location,
+                json!({"source": source, "names": names, "into": into}),
+            )
+        }
+    }
+}
+
+fn condition(condition: &If, location: &LangModuleSlice) -> Value {
+    let fallback = match condition.fallback.as_ref().map(|fallback| fallback.kind()) {
+        None => Value::Null,
+        Some(IfFallBack::Process(target, process)) => object(
+            "Else",
+            location,
+            json!({"scope": scope(target), "body": branches(process)}),
+        ),
+        Some(IfFallBack::If(other)) => self::condition(other.kind(), other.location()),
+    };
+
+    object(
+        "If",
+        location,
+        json!({
+            "condition": node(&condition.condition),
+            "scope": scope(&condition.scope_target),
+            "body": branches(&condition.process),
+            "else": fallback,
+        }),
+    )
+}
+
+fn expression(expression: &Expressions, location: &LangModuleSlice) -> Value {
+    match expression {
+        Expressions::Literal(literal) => self::literal(literal, location),
+        Expressions::Defined(definable) => self::definable(definable, location, &[]),
+        Expressions::Modifed(modified) => self::definable(
+            modified.definable.kind(),
+            modified.definable.location(),
+            &modified.modified_by,
+        ),
+        Expressions::Read(property) => self::property(property, location),
+        Expressions::ReturnOf(call) => self::call(call, location),
+        Expressions::Reverse(reverse) => object(
+            match reverse.kind {
+                ReverseKind::Boolean => "Not",
+                ReverseKind::Sign => "Negate",
+            },
+            location,
+            json!({"expression": node(&reverse.expression)}),
+        ),
+        Expressions::Operation(operation) => {
+            let (operator, (left, right)) = match operation {
+                Operations::Numeric(operation) => (
+                    match operation.operator.kind() {
+                        Operator::Add => "add",
+                        Operator::Substract => "substract",
+                        Operator::Multiply => "multiply",
+                        Operator::Divide => "divide",
+                        Operator::Power => "power",
+                        Operator::Modulo => "modulo",
+                        Operator::EuclidianDivision => "euclidian_division",
+                    },
+                    &operation.operands,
+                ),
+                Operations::Binary(operation) => (
+                    match operation.operator.kind() {
+                        BinaryOperator::And => "and",
+                        BinaryOperator::Or => "or",
+                        BinaryOperator::Xor => "xor",
+                    },
+                    &operation.operands,
+                ),
+                Operations::Comparative(operation) => (
+                    match operation.operator.kind() {
+                        Comparison::Equal => "equal",
+                        Comparison::Less(true) => "less",
+                        Comparison::Less(false) => "less_or_equal",
+                        Comparison::Greater(true) => "greater",
+                        Comparison::Greater(false) => "greater_or_equal",
+                    },
+                    &operation.operands,
+                ),
+            };
+
+            object(
+                "Operation",
+                location,
+                json!({"operator": operator, "left": node(left), "right": node(right)}),
+            )
+        }
+        // Priorities are given by the tree itself
+        Expressions::Prioritized(inner) => node(inner),
+        Expressions::Ternary(ternary) => object(
+            "Ternary",
+            location,
+            json!({
+                "condition": node(&ternary.condition),
+                "yes": node(&ternary.yes),
+                "no": node(&ternary.no),
+            }),
+        ),
+        Expressions::Structure(structure) => {
+            let entries: Vec<Value> = structure
+                .entries
+                .iter()
+                .map(|entry| {
+                    let entry = entry.kind();
+                    // Words are the name of the key (and not the value of a variable)
+                    let key = match entry.key.kind() {
+                        Expressions::Literal(ParsedLiterals::Word) => object(
+                            "String",
+                            entry.key.location(),
+                            json!({"parts": [entry.key.location().code()]}),
+                        ),
+                        _ => node(&entry.key),
+                    };
+                    json!({"key": key, "value": node(&entry.value)})
+                })
+                .collect();
+
+            object("Structure", location, json!({"entries": entries}))
+        }
+        Expressions::Array(array) => {
+            object("Array", location, json!({"items": nodes(&array.entries)}))
+        }
+        Expressions::Instanciate(instanciation) => object(
+            "New",
+            location,
+            json!({
+                "class": node(&instanciation.class),
+                "arguments": nodes(&instanciation.arguments),
+            }),
+        ),
+        Expressions::Typed(typed) => object(
+            "Typed",
+            location,
+            json!({
+                "expression": node(&typed.expression),
+                "typing": typed.typing.kind().names(),
+            }),
+        ),
+    }
+}
+
+fn literal(literal: &ParsedLiterals, location: &LangModuleSlice) -> Value {
+    match literal {
+        ParsedLiterals::Word => object("Word", location, json!({"name": location.code()})),
+        ParsedLiterals::True => object("Boolean", location, json!({"value": true})),
+        ParsedLiterals::False => object("Boolean", location, json!({"value": false})),
+        ParsedLiterals::Empty => object("Empty", location, Value::Null),
+        ParsedLiterals::Number => {
+            let number: f64 = NumberRepresentation::from(location).into();
+            object("Number", location, json!({"value": number}))
+        }
+        ParsedLiterals::Computed(number) => object("Number", location, json!({"value": number})),
+        ParsedLiterals::String(items) => {
+            let parts: Vec<Value> = items
+                .iter()
+                .map(|item| match item.kind() {
+                    ParsedStringItem::Literal(content) => content.as_str().into(),
+                    ParsedStringItem::Expression(expression) => node(expression),
+                })
+                .collect();
+
+            object("String", location, json!({"parts": parts}))
+        }
+    }
+}
+
+fn call(call: &Call, location: &LangModuleSlice) -> Value {
+    object(
+        "Call",
+        location,
+        json!({"callee": node(&call.callable), "arguments": nodes(&call.arguments)}),
+    )
+}
+
+fn property(property: &ReadProperty, location: &LangModuleSlice) -> Value {
+    let read = property.read.location();
+    let key = match property.read.kind() {
+        Property::Key => object("String", read, json!({"parts": [read.code()]})),
+        Property::Index => {
+            let index: f64 = NumberRepresentation::from(read).into();
+            object("Number", read, json!({"value": index}))
+        }
+        Property::Expression(expression) => node(expression),
+    };
+
+    object(
+        "Read",
+        location,
+        json!({"from": node(&property.from), "property": key}),
+    )
+}
+
+fn modifiers(modifiers: &[Node<Modifier>]) -> Value {
+    modifiers
+        .iter()
+        .map(|modifier| match modifier.kind() {
+            Modifier::DefinedElement => object(
+                "Word",
+                modifier.location(),
+                json!({"name": modifier.location().code()}),
+            ),
+            Modifier::CallReturn(call) => self::call(call, modifier.location()),
+        })
+        .collect()
+}
+
+fn definable(definable: &Definables, location: &LangModuleSlice, by: &[Node<Modifier>]) -> Value {
+    let mut value = match definable {
+        Definables::Variable(variable) => self::variable(variable, location),
+        Definables::Function(function) => self::function(function, location),
+        Definables::Class(class) => self::class(class, location),
+    };
+
+    value["modifiers"] = modifiers(by);
+    value
+}
+
+fn variable(variable: &DefineVariable, location: &LangModuleSlice) -> Value {
+    let emplacement = variable.emplacement.location();
+    let target = match variable.emplacement.kind() {
+        VariableEmplacements::Scope => {
+            object("Word", emplacement, json!({"name": emplacement.code()}))
+        }
+        VariableEmplacements::Property(property) => self::property(property, emplacement),
+        VariableEmplacements::Any(expression) => self::expression(expression, emplacement),
+    };
+
+    object(
+        "Variable",
+        location,
+        json!({
+            "target": target,
+            "value": node(&variable.value),
+            "constant": variable.readonly,
+            "typing": typing(&variable.typing),
+        }),
+    )
+}
+
+fn function(function: &DefineFunction, location: &LangModuleSlice) -> Value {
+    let arguments: Vec<Value> = function
+        .arguments
+        .iter()
+        .map(|argument| {
+            let argument = argument.kind();
+            json!({"name": word(&argument.name), "typing": typing(&argument.typing)})
+        })
+        .collect();
+
+    object(
+        "Function",
+        location,
+        json!({
+            "name": optional_word(&function.name),
+            "arguments": arguments,
+            "returns": typing(&function.returns),
+            "scope": scope(&function.scope_target),
+            "body": branches(&function.execution),
+        }),
+    )
+}
+
+fn member<K>(item: &ClassItem<K>, definition: Value) -> Value {
+    json!({
+        "visibility": item.visibility.name(),
+        "static": item.is_static,
+        "modifiers": modifiers(&item.modifie_by),
+        "definition": definition,
+    })
+}
+
+fn class(class: &DefineClass, location: &LangModuleSlice) -> Value {
+    let attributes: Vec<Value> = class
+        .attributes
+        .iter()
+        .map(|attribute| {
+            member(
+                attribute.kind(),
+                variable(&attribute.kind().item, attribute.location()),
+            )
+        })
+        .collect();
+    let methods: Vec<Value> = class
+        .methods
+        .iter()
+        .map(|method| {
+            member(
+                method.kind(),
+                function(&method.kind().item, method.location()),
+            )
+        })
+        .collect();
+
+    object(
+        "Class",
+        location,
+        json!({
+            "name": word(&class.name),
+            "parents": class.parents.iter().map(word).collect::<Vec<Value>>(),
+            "constructor": class
+                .constructor
+                .as_ref()
+                .map_or(Value::Null, |constructor| function(constructor.kind(), constructor.location())),
+            "attributes": attributes,
+            "methods": methods,
+        }),
+    )
+}
@@ -3,6 +3,7 @@ use crate::flylang::{module::slice::LangModuleSlice, parser::ast::instructions::
 pub mod definables;
 pub mod expressions;
 pub mod instructions;
+pub mod json;
 pub mod specials;
 
 #[derive(Debug, Clone)]
@@ -25,6 +26,9 @@ impl<K> Node<K> {
     pub fn kind(&self) -> &K {
         &self.kind
     }
+    pub fn kind_mut(&mut self) -> &mut K {
+        &mut self.kind
+    }
     pub fn location(&self) -> &LangModuleSlice {
         &self.location
     }
@@ -1 +1,2 @@
-pub mod scope;
\ No newline at end of file
+pub mod scope;
+pub mod typing;
@@ -0,0 +1,79 @@
+use crate::flylang::{
+    errors::lang_err,
+    lexer::tokens::{BinaryOperator, Literals, Tokens},
+    module::slice::LangModuleSlice,
+    parser::{
+        ast::{Node, expressions::literals::Word},
+        errors::Expected,
+        parsable::Parsable,
+    },
+};
+
+/// A type annotation, following the `->` arrow.
+///
+/// Multiple types can be accepted by separating them with `?` (`-> number ? empty`).
+#[derive(Debug, Clone)]
+pub struct Typing {
+    /// The names of the accepted types
+    pub types: Vec<Node<Word>>,
+}
+
+impl Typing {
+    /// The names of the accepted types
+    pub fn names(&self) -> Vec<&str> {
+        self.types
+            .iter()
+            .map(|name| name.location().code())
+            .collect()
+    }
+}
+
+impl Parsable for Typing {
+    type ResultKind = Self;
+
+    fn parse(
+        parser: &mut crate::flylang::parser::Parser,
+        _: Option<Node>,
+    ) -> crate::flylang::errors::LangResult<Node<Self::ResultKind>> {
+        assert!(
+            parser.analyser.min_len(1) && matches!(parser.analyser.get()[0].kind(), Tokens::Typing)
+        );
+
+        let arrow = parser.analyser.get()[0].clone();
+
+        let mut types = vec![];
+        loop {
+            let Some(slice) = parser.analyser.lookup(0, 1) else {
+                return lang_err!(Expected {
+                    after: parser.analyser_slice(),
+                    expected: Some(String::from("a type")),
+                    but_found: None
+                });
+            };
+            if !matches!(slice[0].kind(), Tokens::Literal(Literals::Word)) {
+                return lang_err!(Expected {
+                    after: parser.analyser_slice(),
+                    expected: Some(String::from("a type")),
+                    but_found: Some(slice[0].location().code().to_string())
+                });
+            }
+            types.push(Node::new(Word, slice[0].location()));
+            parser.analyser.increase(1);
+
+            // Another accepted type
+            let union = parser.analyser.lookup(0, 2).is_some_and(|slice| {
+                matches!(slice[0].kind(), Tokens::BinaryOperator(BinaryOperator::Or))
+                    && matches!(slice[1].kind(), Tokens::Literal(Literals::Word))
+            });
+            if !union {
+                break;
+            }
+            parser.analyser.increase(1);
+        }
+
+        let location =
+            LangModuleSlice::from(&vec![arrow.location().clone(), parser.analyser_slice()]);
+
+        Ok(Node::new(Self { types }, &location))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::flylang::{
-    errors::{ErrorType, RaisableErr},
+    errors::{ErrorType, RaisableErr, codes::ErrorId},
     lexer::tokens::Token,
     module::slice::LangModuleSlice,
     parser::ast::Node,
@@ -7,6 +7,9 @@ use crate::flylang::{
 
 pub struct UnexpectedNode(pub Node);
 impl RaisableErr for UnexpectedNode {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnexpectedNode
+    }
     fn _code(&self) -> i32 {
         1
     }
@@ -14,12 +17,18 @@ impl RaisableErr for UnexpectedNode {
         ErrorType::Stop
     }
     fn _message(&self) -> String {
-        format!("Unexpected {:?}.\n{:#}", self.0.kind(), self.0.location())
+        format!("Unexpected {:?}.", self.0.kind())
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.location().clone())
     }
 }
 
 pub struct UnexpectedToken(pub Token);
 impl RaisableErr for UnexpectedToken {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnexpectedToken
+    }
     fn _code(&self) -> i32 {
         2
     }
@@ -27,11 +36,10 @@ impl RaisableErr for UnexpectedToken {
         ErrorType::Stop
     }
     fn _message(&self) -> String {
-        format!(
-            "Token \"{:?}\" was unexpected.\n{:#}",
-            self.0.kind(),
-            self.0.location()
-        )
+        format!("Token \"{:?}\" was unexpected.", self.0.kind())
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.location().clone())
     }
 }
 
@@ -41,6 +49,9 @@ pub struct Expected {
     pub but_found: Option<String>,
 }
 impl RaisableErr for Expected {
+    fn _id(&self) -> ErrorId {
+        ErrorId::Expected
+    }
     fn _code(&self) -> i32 {
         3
     }
@@ -49,17 +60,22 @@ impl RaisableErr for Expected {
     }
     fn _message(&self) -> String {
         format!(
-            "Expected {} after \"{}\" but found {}.\n{:#}",
+            "Expected {} after \"{}\" but found {}.",
             self.expected.clone().unwrap_or("expression".into()),
             self.after.code(),
-            self.but_found.clone().unwrap_or("nothing".into()),
-            self.after
+            self.but_found.clone().unwrap_or("nothing".into())
         )
     }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.after.clone())
+    }
 }
 
 pub struct EmptyScope(pub LangModuleSlice);
 impl RaisableErr for EmptyScope {
+    fn _id(&self) -> ErrorId {
+        ErrorId::EmptyScope
+    }
     fn _code(&self) -> i32 {
         1
     }
@@ -67,15 +83,21 @@ impl RaisableErr for EmptyScope {
         ErrorType::Warn
     }
     fn _message(&self) -> String {
-        format!(
-            "Empty scope detected. Please remove it (in could break at future).\n{:#}",
-            self.0
-        )
+        String::from("Empty scope detected.")
+    }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
+    fn _help(&self) -> Option<String> {
+        Some(String::from("remove it (it could break in the future)"))
     }
 }
 
 pub struct UnableToParse(pub LangModuleSlice, pub String);
 impl RaisableErr for UnableToParse {
+    fn _id(&self) -> ErrorId {
+        ErrorId::UnableToParse
+    }
     fn _code(&self) -> i32 {
         4
     }
@@ -84,10 +106,12 @@ impl RaisableErr for UnableToParse {
     }
     fn _message(&self) -> String {
         format!(
-            "Unable to parse the expression \"{}\". {}.\n{:#}",
+            "Unable to parse the expression \"{}\". {}.",
             self.0.code(),
-            self.1,
-            self.0
+            self.1
         )
     }
+    fn _location(&self) -> Option<LangModuleSlice> {
+        Some(self.0.clone())
+    }
 }
@@ -1,7 +1,7 @@
-use std::{collections::HashSet, mem::take, rc::Rc, vec};
+use std::{collections::HashSet, mem::take, ops::Range, rc::Rc, vec};
 
 use crate::flylang::{
-    errors::{LangResult, lang_err},
+    errors::{Diagnostics, LangResult, RaisableErr, lang_err},
     lexer::{
         Lexer,
         tokens::{ScopeTarget, Toggleable, Token, Tokens},
@@ -27,6 +27,8 @@ pub struct Parser {
     analyser: Analyser<Token<Tokens>>,
     parsed: Branches,
     behaviors: HashSet<ParserBehaviors>,
+    /// The errors found in the code
+    diagnostics: Diagnostics,
 }
 // ? only used in the `scope` method
 type ScopeTokenMatcher = Box<dyn Fn(&Parser, &Token) -> bool>;
@@ -40,6 +42,7 @@ impl Parser {
             analyser: Analyser::new(stream),
             parsed: vec![],
             behaviors: HashSet::new(),
+            diagnostics: Diagnostics::new(),
         }
     }
     pub fn module(&self) -> &Rc<LangModule> {
@@ -62,6 +65,59 @@ impl Parser {
         LangModuleSlice::from(&slices)
     }
 
+    /// Collect an error found in the code, unless an error has already been collected in the code of its instruction
+    /// (given by the range of the tokens `start..end`) : it is most likely caused by it.
+    fn report(&mut self, error: Box<dyn RaisableErr>, tokens: Range<usize>) {
+        let stream = self.analyser.stream();
+        let first = stream[tokens.start].location();
+        let start = first.range().start;
+        let end = stream
+            .get(tokens.end)
+            .map_or(self.module.code().len(), |token| {
+                token.location().range().start
+            });
+
+        let caused = self.diagnostics.errors().iter().any(|collected| {
+            collected._location().is_some_and(|location| {
+                Rc::ptr_eq(location.module(), first.module())
+                    && (start..end.max(start + 1)).contains(&location.range().start)
+            })
+        });
+        if !caused {
+            self.diagnostics.push(error);
+        }
+    }
+
+    /// Skip the tokens of an instruction which cannot be parsed (starting at the `start` token), up to the end of the
+    /// instruction (`;`), of the argument (`,`) or of the scope containing it (`)`).
+    /// The analyser is placed before this token, so the next instructions can still be parsed.
+    fn recover(&mut self, start: usize) {
+        let stream = self.analyser.stream();
+        let mut depth = 0usize;
+        let mut index = start;
+
+        while index < stream.len() {
+            match stream[index].kind() {
+                Tokens::Block(Toggleable::Openning) | Tokens::Object(Toggleable::Openning) => {
+                    depth += 1
+                }
+                Tokens::Block(Toggleable::Closing) | Tokens::Object(Toggleable::Closing) => {
+                    if depth == 0 && index > start {
+                        break;
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                Tokens::EndOfInstruction | Tokens::ArgSeparator if depth == 0 && index > start => {
+                    break;
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+
+        self.analyser.set(index..index);
+    }
+
     /// Parse the next tokens as a scope.
     ///
     /// # Panics
@@ -218,7 +274,15 @@ impl Parser {
             };
 
             if !matches!(token.kind(), Tokens::EndOfInstruction) {
-                analysing.push(Instructions::parse(self, None)?);
+                let start = self.analyser.range().start;
+                match Instructions::parse(self, None) {
+                    Ok(instruction) => analysing.push(instruction),
+                    Err(error) => {
+                        self.recover(start);
+                        self.report(error, start..self.analyser.range().start);
+                        continue;
+                    }
+                }
             }
 
             self.analyser.next(0, 0);
@@ -229,17 +293,41 @@ impl Parser {
         Ok(result)
     }
 
-    /// Execute the parser and return the vector of instructions
-    pub fn parse(&mut self) -> &Branches {
+    /// Parse every instruction. The errors found are collected, the invalid instructions being skipped.
+    fn process(&mut self) {
         let branches = self.branches(
             |state, _| state.analyser.process_finished(),
             |_, _| false,
             None,
         );
-        self.parsed = branches.unwrap_or_else(|e| e.raise()).pop().unwrap();
+        match branches {
+            Ok(mut branches) => self.parsed = branches.pop().unwrap(),
+            Err(error) => self.diagnostics.push(error),
+        }
+    }
+
+    /// Execute the parser and return the vector of instructions.
+    /// The errors found (by the lexer and the parser) are raised.
+    pub fn parse(&mut self) -> &Branches {
+        self.process();
+        take(&mut self.diagnostics).raise();
 
         &self.parsed
     }
+    /// Execute the parser and return the vector of instructions, or every error found (by the lexer and the parser)
+    /// if one of them stops the process. The errors which do not stop it stay available with `diagnostics`.
+    pub fn try_parse(&mut self) -> Result<&Branches, Diagnostics> {
+        self.process();
+
+        match self.diagnostics.has_errors() {
+            true => Err(take(&mut self.diagnostics)),
+            false => Ok(&self.parsed),
+        }
+    }
+    /// Get the errors found in the code (by the lexer and the parser)
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
     /// Clear the parsed instructions and rebuild it
     pub fn reparse(&mut self) -> &Branches {
         self.parsed = vec![];
@@ -251,7 +339,9 @@ impl Parser {
 
 impl From<&mut Lexer> for Parser {
     fn from(value: &mut Lexer) -> Self {
-        let stream = value.lexify().to_vec();
-        Self::new(value.module(), stream)
+        let stream = value.tokenize().to_vec();
+        let mut parser = Self::new(value.module(), stream);
+        parser.diagnostics = value.take_diagnostics();
+        parser
     }
 }
@@ -0,0 +1,107 @@
+use std::{
+    io::{Write, stderr, stdout},
+    rc::Rc,
+};
+
+use crate::flylang::{
+    errors::{LangResult, lang_err},
+    interpreter::errors::{AssertionFailed, UnexpectedType},
+    module::slice::LangModuleSlice,
+    runtime::{
+        Engine,
+        value::{Fields, NativeCallback, NativeFunction, Value},
+    },
+};
+
+fn native<E: Engine>(name: &'static str, callback: NativeCallback<E>) -> (&'static str, Value<E>) {
+    (
+        name,
+        Value::Native(Rc::new(NativeFunction { name, callback })),
+    )
+}
+
+/// Elements available in every scripts without any importation
+pub fn globals<E: Engine>() -> Vec<(&'static str, Value<E>)> {
+    let std = [
+        native("out", out),
+        native("err", err),
+        native("len", len),
+        native("type", type_of),
+    ]
+    .into_iter()
+    .map(|(name, value)| (Value::new_string(name), value))
+    .collect::<Fields<_>>();
+
+    vec![("std", Value::new_structure(std)), native("assert", assert)]
+}
+
+fn display<E: Engine>(arguments: &[Value<E>]) -> String {
+    let displayed: Vec<String> = arguments.iter().map(|v| v.to_string()).collect();
+    displayed.join(" ")
+}
+
+/// Print the given values (separated by a space) to the standard output
+fn out<E: Engine>(
+    _: &mut E,
+    arguments: Vec<Value<E>>,
+    _: &LangModuleSlice,
+) -> LangResult<Value<E>> {
+    let _ = writeln!(stdout(), "{}", display(&arguments));
+    Ok(Value::Empty)
+}
+
+/// Print the given values (separated by a space) to the standard error output
+fn err<E: Engine>(
+    _: &mut E,
+    arguments: Vec<Value<E>>,
+    _: &LangModuleSlice,
+) -> LangResult<Value<E>> {
+    let _ = writeln!(stderr(), "{}", display(&arguments));
+    Ok(Value::Empty)
+}
+
+/// Get the number of items in an array, a structure or a string
+fn len<E: Engine>(
+    _: &mut E,
+    arguments: Vec<Value<E>>,
+    location: &LangModuleSlice,
+) -> LangResult<Value<E>> {
+    let length = match arguments.first() {
+        Some(Value::Array(items)) => items.borrow().len(),
+        Some(Value::Structure(fields)) => fields.borrow().len(),
+        Some(Value::String(s)) => s.chars().count(),
+        other => {
+            return lang_err!(UnexpectedType {
+                location: location.clone(),
+                expected: "array, structure or string",
+                found: other.unwrap_or(&Value::Empty).type_name(),
+            });
+        }
+    };
+
+    Ok(Value::Number(length as f64))
+}
+
+/// Get the type's name of the given value
+fn type_of<E: Engine>(
+    _: &mut E,
+    arguments: Vec<Value<E>>,
+    _: &LangModuleSlice,
+) -> LangResult<Value<E>> {
+    Ok(Value::new_string(
+        arguments.first().unwrap_or(&Value::Empty).type_name(),
+    ))
+}
+
+/// Raise an error if one of the given values is not truthy
+fn assert<E: Engine>(
+    _: &mut E,
+    arguments: Vec<Value<E>>,
+    location: &LangModuleSlice,
+) -> LangResult<Value<E>> {
+    if arguments.iter().all(|v| v.truthy()) {
+        Ok(Value::Empty)
+    } else {
+        lang_err!(AssertionFailed(location.clone()))
+    }
+}
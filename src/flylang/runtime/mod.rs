@@ -0,0 +1,22 @@
+//! The runtime model shared by the engines executing the scripts : the interpreter and the virtual machine.
+//!
+//! Both engines use the same values (see `value::Value`), following the same rules, and the same built-in
+//! elements (see `builtins::globals`). Only the functions and the classes defined by the scripts are
+//! represented by each engine its own way.
+
+use std::fmt::Debug;
+
+pub mod builtins;
+pub mod value;
+
+/// An engine executing the scripts
+pub trait Engine: Debug + Sized {
+    /// A function defined in a script
+    type Function: Debug;
+    /// A class defined in a script
+    type Class: Debug;
+
+    /// Get the name of a function (if it is not anonymous)
+    fn function_name(function: &Self::Function) -> Option<&str>;
+    fn class_name(class: &Self::Class) -> &str;
+}
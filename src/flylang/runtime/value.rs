@@ -0,0 +1,318 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+use crate::flylang::{errors::LangResult, module::slice::LangModuleSlice, runtime::Engine};
+
+pub type NativeCallback<E> = fn(&mut E, Vec<Value<E>>, &LangModuleSlice) -> LangResult<Value<E>>;
+
+/// A function implemented by the engine itself.
+pub struct NativeFunction<E: Engine> {
+    pub name: &'static str,
+    pub callback: NativeCallback<E>,
+}
+impl<E: Engine> Debug for NativeFunction<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<native {}>", self.name)
+    }
+}
+
+/// A method bound to the instance it has been read from (or to its class for static methods)
+#[derive(Debug)]
+pub struct Method<E: Engine> {
+    /// The instance the method has been read from (None for static methods)
+    pub receiver: Option<Rc<RefCell<Instance<E>>>>,
+    /// The class defining the method
+    pub class: Rc<E::Class>,
+    pub function: Rc<E::Function>,
+}
+
+/// Gives access to the constructor and the methods of the classes following `class`
+/// in the method resolution order of the receiver's class.
+#[derive(Debug)]
+pub struct Super<E: Engine> {
+    pub receiver: Rc<RefCell<Instance<E>>>,
+    pub class: Rc<E::Class>,
+}
+
+/// A function or a class modified by a modifier (`#(...)`).
+///
+/// Calling it (or instanciating it) calls the modifier with the target and the given arguments.
+#[derive(Debug)]
+pub struct Modified<E: Engine> {
+    pub modifier: Value<E>,
+    pub target: Value<E>,
+}
+
+/// An object created from a class (using `new`)
+#[derive(Debug)]
+pub struct Instance<E: Engine> {
+    pub class: Rc<E::Class>,
+    pub fields: Fields<Value<E>>,
+}
+
+/// Ordered key/value entries of a structure (or of an instance)
+#[derive(Debug, Clone)]
+pub struct Fields<V> {
+    entries: Vec<(V, V)>,
+}
+impl<V> Default for Fields<V> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+impl<V: PartialEq> Fields<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get(&self, key: &V) -> Option<&V> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+    /// Set the value of the given key. The key is created (at the end) if it does not exist yet.
+    pub fn set(&mut self, key: V, value: V) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+    pub fn contains(&self, key: &V) -> bool {
+        self.get(key).is_some()
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &(V, V)> {
+        self.entries.iter()
+    }
+}
+impl<V: PartialEq> FromIterator<(V, V)> for Fields<V> {
+    fn from_iter<T: IntoIterator<Item = (V, V)>>(iter: T) -> Self {
+        let mut fields = Self::new();
+        for (key, value) in iter {
+            fields.set(key, value);
+        }
+        fields
+    }
+}
+
+/// A runtime value.
+///
+/// # Rules
+/// - Equality: primitives (empty, booleans, numbers and strings), arrays and structures are compared by their content.
+///   Functions, classes and instances are compared by reference (modifiers are ignored).
+/// - Truthiness: `()`, `false`, `0`, `""`, `{}` and `{:}` are falsy. Everything else is truthy.
+/// - Arrays, structures and instances are shared references : modifying them through a variable modifies them everywhere.
+#[derive(Debug)]
+pub enum Value<E: Engine> {
+    Empty,
+    Boolean(bool),
+    Number(f64),
+    String(Rc<str>),
+    Array(Rc<RefCell<Vec<Value<E>>>>),
+    Structure(Rc<RefCell<Fields<Value<E>>>>),
+    Function(Rc<E::Function>),
+    Native(Rc<NativeFunction<E>>),
+    Class(Rc<E::Class>),
+    Instance(Rc<RefCell<Instance<E>>>),
+    Modified(Rc<Modified<E>>),
+    Method(Rc<Method<E>>),
+    Super(Rc<Super<E>>),
+    /// The value of a variable of the virtual machine that is not assigned yet
+    /// (reading it raises an error, so the code never gets it)
+    Unbound,
+}
+
+// Derived, it would require the engine to be cloneable
+impl<E: Engine> Clone for Value<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Empty => Self::Empty,
+            Self::Boolean(b) => Self::Boolean(*b),
+            Self::Number(n) => Self::Number(*n),
+            Self::String(s) => Self::String(Rc::clone(s)),
+            Self::Array(items) => Self::Array(Rc::clone(items)),
+            Self::Structure(fields) => Self::Structure(Rc::clone(fields)),
+            Self::Function(function) => Self::Function(Rc::clone(function)),
+            Self::Native(native) => Self::Native(Rc::clone(native)),
+            Self::Class(class) => Self::Class(Rc::clone(class)),
+            Self::Instance(instance) => Self::Instance(Rc::clone(instance)),
+            Self::Modified(modified) => Self::Modified(Rc::clone(modified)),
+            Self::Method(method) => Self::Method(Rc::clone(method)),
+            Self::Super(parent) => Self::Super(Rc::clone(parent)),
+            Self::Unbound => Self::Unbound,
+        }
+    }
+}
+
+impl<E: Engine> Value<E> {
+    pub fn new_string(content: &str) -> Self {
+        Self::String(Rc::from(content))
+    }
+    pub fn new_array(items: Vec<Value<E>>) -> Self {
+        Self::Array(Rc::new(RefCell::new(items)))
+    }
+    pub fn new_structure(fields: Fields<Value<E>>) -> Self {
+        Self::Structure(Rc::new(RefCell::new(fields)))
+    }
+
+    /// The name of the value's type, as displayed to the user
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Boolean(_) => "boolean",
+            Self::Number(_) => "number",
+            Self::String(_) => "string",
+            Self::Array(_) => "array",
+            Self::Structure(_) => "structure",
+            Self::Function(_) | Self::Native(_) | Self::Method(_) => "function",
+            Self::Class(_) => "class",
+            Self::Instance(_) => "instance",
+            Self::Modified(modified) => modified.target.type_name(),
+            Self::Super(_) => "super",
+            Self::Unbound => "unbound",
+        }
+    }
+
+    /// Returns if the value is considered as `true` in a condition
+    pub fn truthy(&self) -> bool {
+        match self {
+            Self::Empty | Self::Unbound => false,
+            Self::Boolean(b) => *b,
+            Self::Number(n) => *n != 0f64 && !n.is_nan(),
+            Self::String(s) => !s.is_empty(),
+            Self::Array(items) => !items.borrow().is_empty(),
+            Self::Structure(fields) => !fields.borrow().is_empty(),
+            Self::Function(_)
+            | Self::Native(_)
+            | Self::Class(_)
+            | Self::Instance(_)
+            | Self::Modified(_)
+            | Self::Method(_)
+            | Self::Super(_) => true,
+        }
+    }
+
+    /// Get the value without its modifiers
+    pub fn unmodified(&self) -> &Self {
+        match self {
+            Self::Modified(modified) => modified.target.unmodified(),
+            other => other,
+        }
+    }
+
+    /// Same as the `Display` implementation, but strings are quoted.
+    /// This is used to display values inside arrays and structures.
+    pub fn repr(&self) -> String {
+        match self {
+            Self::String(s) => format!("{:?}", s),
+            other => other.to_string(),
+        }
+    }
+    /// Same as `repr`, but strings that are valid words are not quoted.
+    /// This is used to display the keys of structures.
+    fn key_repr(&self) -> String {
+        match self {
+            Self::String(s)
+                if s.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                    && s.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                s.to_string()
+            }
+            other => other.repr(),
+        }
+    }
+}
+
+impl<E: Engine> PartialEq for Value<E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Empty, Self::Empty) => true,
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::Number(a), Self::Number(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Array(a), Self::Array(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+            (Self::Structure(a), Self::Structure(b)) => {
+                Rc::ptr_eq(a, b) || {
+                    let (a, b) = (a.borrow(), b.borrow());
+                    a.len() == b.len() && a.iter().all(|(k, v)| b.get(k) == Some(v))
+                }
+            }
+            (Self::Function(a), Self::Function(b)) => Rc::ptr_eq(a, b),
+            (Self::Native(a), Self::Native(b)) => Rc::ptr_eq(a, b),
+            (Self::Class(a), Self::Class(b)) => Rc::ptr_eq(a, b),
+            (Self::Instance(a), Self::Instance(b)) => Rc::ptr_eq(a, b),
+            (Self::Method(a), Self::Method(b)) => {
+                let same_receiver = match (&a.receiver, &b.receiver) {
+                    (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+                    (None, None) => true,
+                    _ => false,
+                };
+                same_receiver && Rc::ptr_eq(&a.function, &b.function)
+            }
+            (Self::Super(a), Self::Super(b)) => {
+                Rc::ptr_eq(&a.receiver, &b.receiver) && Rc::ptr_eq(&a.class, &b.class)
+            }
+            // A modified element is the same as the element it modifies
+            (Self::Modified(a), b) => a.target == *b,
+            (a, Self::Modified(b)) => *a == b.target,
+            _ => false,
+        }
+    }
+}
+
+impl<E: Engine> Display for Value<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "()"),
+            Self::Boolean(b) => write!(f, "{}", b),
+            Self::Number(n) => {
+                if n.fract() == 0f64 && n.abs() < 1e15 {
+                    write!(f, "{}", *n as i64)
+                } else {
+                    write!(f, "{}", n)
+                }
+            }
+            Self::String(s) => write!(f, "{}", s),
+            Self::Array(items) => {
+                let items: Vec<String> = items.borrow().iter().map(|v| v.repr()).collect();
+                write!(f, "{{{}}}", items.join(", "))
+            }
+            Self::Structure(fields) => {
+                let fields = fields.borrow();
+                if fields.is_empty() {
+                    return write!(f, "{{:}}");
+                }
+
+                let entries: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k.key_repr(), v.repr()))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Self::Function(function) => write!(
+                f,
+                "<fn {}>",
+                E::function_name(function).unwrap_or("anonymous")
+            ),
+            Self::Native(native) => write!(f, "<fn {}>", native.name),
+            Self::Class(class) => write!(f, "<cs {}>", E::class_name(class)),
+            Self::Instance(instance) => {
+                write!(f, "<{} instance>", E::class_name(&instance.borrow().class))
+            }
+            Self::Modified(modified) => write!(f, "{}", modified.target),
+            Self::Method(method) => write!(
+                f,
+                "<fn {}.{}>",
+                E::class_name(&method.class),
+                E::function_name(&method.function).unwrap_or("anonymous")
+            ),
+            Self::Super(_) => write!(f, "<super>"),
+            Self::Unbound => write!(f, "<unbound>"),
+        }
+    }
+}
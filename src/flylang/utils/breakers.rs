@@ -0,0 +1,84 @@
+use crate::flylang::{
+    lexer::tokens::ScopeTarget,
+    parser::ast::instructions::breakers::{Break, BreakKind},
+};
+
+/// The kind of a scope that breakers can target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Function,
+    Loop,
+    Condition,
+}
+impl ScopeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Loop => "loop",
+            Self::Condition => "condition",
+        }
+    }
+}
+
+/// A scope that breakers can target
+pub trait BreakableScope {
+    fn kind(&self) -> ScopeKind;
+    /// The name given to the scope by its target (`@name`)
+    fn name(&self) -> Option<&str>;
+}
+
+/// The reason why a breaker stops at no scope
+pub enum Unresolved {
+    /// The breaker has no target, and its function has no scope of the kind it expects
+    Uncaught { expected: Option<ScopeKind> },
+    /// The scope named by the target (as written) is not of the kind the breaker expects
+    Invalid {
+        target: String,
+        expected: Option<ScopeKind>,
+        found: ScopeKind,
+    },
+    /// No scope has the name of the target (as written), or there are not enough scopes to skip
+    Unknown { target: String },
+}
+
+/// Find the position of the scope a breaker stops at, in the scopes containing it (the innermost one last).
+/// The analyser, the interpreter, the virtual machine and the compilers resolve the breakers with it.
+pub fn resolve<S: BreakableScope>(scopes: &[S], breaker: &Break) -> Result<usize, Unresolved> {
+    let (expected, target) = match &breaker.kind {
+        BreakKind::Stop(target) => (Some(ScopeKind::Loop), target),
+        BreakKind::Pass(target) => (None, target),
+        BreakKind::Return(target, _) => (Some(ScopeKind::Function), target),
+    };
+    let accepts = |scope: &S| expected.is_none_or(|kind| scope.kind() == kind);
+    let mut scopes = scopes.iter().enumerate().rev();
+
+    let Some(target) = target else {
+        // Without target, a breaker cannot go outside of the current function
+        return match scopes.find(|(_, scope)| accepts(scope) || scope.kind() == ScopeKind::Function)
+        {
+            Some((position, scope)) if accepts(scope) => Ok(position),
+            _ => Err(Unresolved::Uncaught { expected }),
+        };
+    };
+
+    let written = || target.location().code().to_string();
+    match target.kind() {
+        ScopeTarget::Named(name) => {
+            match scopes.find(|(_, scope)| scope.name() == Some(name.as_str())) {
+                Some((position, scope)) if accepts(scope) => Ok(position),
+                Some((_, scope)) => Err(Unresolved::Invalid {
+                    target: written(),
+                    expected,
+                    found: scope.kind(),
+                }),
+                None => Err(Unresolved::Unknown { target: written() }),
+            }
+        }
+        // The first scope is the current one, so "@<" skips it
+        ScopeTarget::Numbered(skip) => scopes
+            .filter(|(_, scope)| accepts(scope))
+            .nth(skip.get())
+            .map(|(position, _)| position)
+            .ok_or_else(|| Unresolved::Unknown { target: written() }),
+    }
+}
@@ -0,0 +1,40 @@
+/// Compute the ancestors of a class from its parents, using the C3 linearization. `mro` gives the method
+/// resolution order of a parent (starting with the parent itself), and `same` tells if two handles are the same
+/// class.
+///
+/// Returns None if no consistent order exists (ex: `cs C(A, B)` where `B` inherits from `A`), or if the order of a
+/// parent is not known.
+pub fn linearize<T: Clone>(
+    parents: &[T],
+    mro: impl FnMut(&T) -> Option<Vec<T>>,
+    same: impl Fn(&T, &T) -> bool,
+) -> Option<Vec<T>> {
+    let mut sequences: Vec<Vec<T>> = parents.iter().map(mro).collect::<Option<_>>()?;
+    sequences.push(parents.to_vec());
+
+    let mut ancestors = vec![];
+    loop {
+        sequences.retain(|sequence| !sequence.is_empty());
+        if sequences.is_empty() {
+            return Some(ancestors);
+        }
+
+        // The next ancestor is the first head that is not in the tail of another sequence
+        let head = sequences
+            .iter()
+            .map(|sequence| &sequence[0])
+            .find(|head| {
+                !sequences
+                    .iter()
+                    .any(|sequence| sequence[1..].iter().any(|class| same(class, head)))
+            })?
+            .clone();
+
+        for sequence in sequences.iter_mut() {
+            if same(&sequence[0], &head) {
+                sequence.remove(0);
+            }
+        }
+        ancestors.push(head);
+    }
+}
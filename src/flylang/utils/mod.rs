@@ -1,2 +1,4 @@
 pub mod analyser;
+pub mod breakers;
+pub mod linearization;
 pub mod scoper;
@@ -0,0 +1,230 @@
+use std::rc::Rc;
+
+use crate::flylang::{
+    module::slice::LangModuleSlice, parser::ast::definables::class::ClassItemVisibility,
+    vm::value::Value,
+};
+
+/// The position of an instruction in the code of a function
+pub type Address = u32;
+
+/// Where a variable of a function lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    /// On the stack, during the call
+    Local(u16),
+    /// In the environment of the call, shared with the functions defined inside it
+    Cell(u16),
+}
+
+/// What a breaker going out of its function does once it reaches its scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breaker {
+    Stop,
+    Pass,
+    Return,
+}
+
+/// An instruction of the virtual machine.
+///
+/// Instructions take their operands from the top of the stack, and push their result on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    /// Push a constant of the program
+    Constant(u32),
+    Empty,
+    True,
+    False,
+    /// Push a built-in element (see `builtins::globals`)
+    Builtin(u16),
+    Pop,
+
+    /// Push a variable living on the stack
+    Local(u16),
+    /// Set a variable living on the stack (the value stays on the stack)
+    SetLocal(u16),
+    /// Push a variable of the environment `depth` functions above the current one
+    Cell {
+        depth: u16,
+        index: u16,
+    },
+    /// Set a variable of an environment (the value stays on the stack)
+    SetCell {
+        depth: u16,
+        index: u16,
+    },
+    /// Add 1 to a number living on the stack (the iteration number of a `while` loop)
+    Increment(u16),
+    /// Raise an error : the variable read at this location is not defined
+    Undefined,
+    /// Raise an error : the constant declared at the given location (see `Program::declarations`) is
+    /// reassigned
+    Reassign(u32),
+
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Modulo,
+    Quotient,
+    Equal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Not,
+    Negate,
+    /// Replace a value by its truthiness
+    Truthy,
+    /// Display the given number of values one after the other (string interpolation)
+    Concat(u32),
+
+    Jump(Address),
+    /// Pop a value, and jump if it is truthy
+    JumpIf(Address),
+    /// Pop a value, and jump if it is falsy
+    JumpUnless(Address),
+
+    /// Create an array with the given number of items
+    Array(u32),
+    /// Create a structure with the given number of key/value pairs
+    Structure(u32),
+    /// Read a property : `[from, key]`
+    Get,
+    /// Write a property : `[value, target, key]`. The value stays on the stack.
+    Put,
+
+    /// Create a function from a prototype of the program
+    Function(u32),
+    /// Create a class from its description (see `ClassPrototype` for its operands)
+    Class(u32),
+    /// Wrap a function or a class with modifiers : `[target, modifiers...]`
+    Modify(u16),
+    /// Call a function with the given number of arguments : `[callable, arguments...]`
+    Call(u16),
+    /// Create an instance of a class with the given number of arguments : `[class, arguments...]`
+    New(u16),
+    /// Leave the current function with the value on the top of the stack
+    Return,
+
+    /// Push the variables defined by a module (see `Exports`), executing its function the first time
+    Use(u32),
+    /// Create a structure of the variables of the current function that are assigned (the value of a
+    /// module)
+    Exports,
+    /// Read a variable of a module : `[exports, name]`. The variables the module did not assign are
+    /// pushed unbound.
+    Import,
+
+    /// Start an `each` loop, going through the value on the top of the stack
+    Iterate,
+    /// Push the index and the item of the next iteration of the current `each` loop, or jump when it is over
+    Next(Address),
+    /// End the given number of `each` loops
+    Release(u16),
+
+    /// Let the breakers of other functions reach a scope (see `Prototype::catches`)
+    Handle(u32),
+    /// Remove the given number of handlers
+    Unhandle(u16),
+    /// Leave the function to reach a scope handled by a caller, with the value on the top of the stack
+    Throw {
+        scope: u32,
+        breaker: Breaker,
+    },
+}
+
+/// A scope of a function that breakers reach from other functions
+#[derive(Debug, Clone)]
+pub struct Catch {
+    /// The number of the scope in the program
+    pub scope: u32,
+    /// Where the execution continues after a `stop` (or a `pass` leaving a condition)
+    pub stop: Address,
+    /// Where the execution continues after a `pass` going to the next iteration of a loop
+    pub pass: Address,
+    /// If the scope is the body of the function : the breakers reaching it leave the call
+    pub returns: bool,
+}
+
+/// The compiled code of a function
+#[derive(Debug)]
+pub struct Prototype {
+    pub name: Option<String>,
+    /// The arguments are the first variables living on the stack
+    pub arguments: u16,
+    /// If `self` and `super` are bound (after the arguments)
+    pub method: bool,
+    /// The number of variables living on the stack
+    pub locals: u16,
+    /// The number of variables living in the environment
+    pub cells: u16,
+    /// The arguments (and receivers) moved from the stack to the environment when the function is called
+    pub moved: Vec<(u16, u16)>,
+    /// If the calls create an environment, for the functions defined inside them
+    pub environment: bool,
+    pub code: Vec<Instruction>,
+    /// The location of the code : each location starts at an address, and lasts until the next one
+    pub locations: Vec<(Address, LangModuleSlice)>,
+    pub catches: Vec<Catch>,
+    /// The names of the variables (for the disassembler)
+    pub variables: Vec<(String, Slot)>,
+}
+impl Prototype {
+    /// Get the location of the code of an instruction
+    pub fn location(&self, address: usize) -> Option<&LangModuleSlice> {
+        let position = self
+            .locations
+            .partition_point(|(start, _)| *start as usize <= address);
+        position
+            .checked_sub(1)
+            .map(|position| &self.locations[position].1)
+    }
+
+    /// Get the name of a variable (for the disassembler)
+    pub fn variable(&self, slot: Slot) -> Option<&str> {
+        self.variables
+            .iter()
+            .find(|(_, variable)| *variable == slot)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// A member declared by a class
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub visibility: ClassItemVisibility,
+    pub is_static: bool,
+}
+
+/// The description of a class.
+///
+/// Its `Class` instruction takes as operands :
+/// - its parents,
+/// - the function initialising the attributes of its instances (returning them in an array), if it has one,
+/// - its constructor, if it has one,
+/// - each method, followed by its modifiers,
+/// - the value of each static attribute.
+#[derive(Debug)]
+pub struct ClassPrototype {
+    pub name: LangModuleSlice,
+    pub parents: Vec<LangModuleSlice>,
+    pub attributes: Vec<Member>,
+    /// The methods, with their number of modifiers
+    pub methods: Vec<(Member, u16)>,
+    pub initializer: bool,
+    pub constructor: bool,
+}
+
+/// A compiled module
+#[derive(Debug)]
+pub struct Program {
+    pub constants: Vec<Value>,
+    /// The functions of the program. The first one is the code of the module.
+    pub functions: Vec<Rc<Prototype>>,
+    pub classes: Vec<Rc<ClassPrototype>>,
+    /// Where the reassigned constants are declared (`None` for the built-in elements)
+    pub declarations: Vec<Option<LangModuleSlice>>,
+}
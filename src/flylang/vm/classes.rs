@@ -0,0 +1,282 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::flylang::{
+    interpreter::errors::{
+        InaccessibleMember, InvalidInheritance, TooManyArguments, UnexpectedType,
+    },
+    parser::ast::definables::class::ClassItemVisibility,
+    utils::linearization::linearize,
+    vm::{
+        Execution, Interruption, VirtualMachine,
+        bytecode::Program,
+        interrupt,
+        value::{Class, ClassMethod, Closure, Fields, Instance, Method, Super, Value},
+    },
+};
+
+impl VirtualMachine {
+    /// Create a class from its description and its operands (see `ClassPrototype`)
+    pub(super) fn class(&mut self, program: &Program, index: usize) -> Execution<Value> {
+        let prototype = Rc::clone(&program.classes[index]);
+        let statics: Vec<_> = prototype
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.is_static)
+            .collect();
+        let count = prototype.parents.len()
+            + usize::from(prototype.initializer)
+            + usize::from(prototype.constructor)
+            + prototype
+                .methods
+                .iter()
+                .map(|(_, modifiers)| 1 + *modifiers as usize)
+                .sum::<usize>()
+            + statics.len();
+        let mut operands = self.stack.split_off(self.stack.len() - count).into_iter();
+
+        let mut parents = vec![];
+        for location in &prototype.parents {
+            match operands.next().unwrap().unmodified() {
+                Value::Class(parent) => parents.push(Rc::clone(parent)),
+                other => {
+                    return interrupt!(UnexpectedType {
+                        location: location.clone(),
+                        expected: "class",
+                        found: other.type_name(),
+                    });
+                }
+            }
+        }
+
+        let Some(ancestors) = linearize(&parents, |parent| Some(parent.mro()), Rc::ptr_eq) else {
+            return interrupt!(InvalidInheritance(prototype.name.clone()));
+        };
+
+        let function = |operands: &mut std::vec::IntoIter<Value>| -> Rc<Closure> {
+            match operands.next() {
+                Some(Value::Function(closure)) => closure,
+                _ => unreachable!("the functions of a class are created with it"),
+            }
+        };
+        let initializer = prototype.initializer.then(|| function(&mut operands));
+        let constructor = prototype.constructor.then(|| function(&mut operands));
+        let mut methods = vec![];
+        for (_, modifiers) in &prototype.methods {
+            methods.push(ClassMethod {
+                function: function(&mut operands),
+                modifiers: operands.by_ref().take(*modifiers as usize).collect(),
+            });
+        }
+
+        // Static attributes are initialised once, when the class is defined
+        let mut fields = Fields::new();
+        for (attribute, value) in statics.into_iter().zip(operands) {
+            fields.set(Value::new_string(&attribute.name), value);
+        }
+
+        Ok(Value::Class(Rc::new(Class {
+            prototype,
+            parents,
+            ancestors,
+            initializer,
+            constructor,
+            methods,
+            statics: RefCell::new(fields),
+        })))
+    }
+
+    /// Create an instance of the given class.
+    ///
+    /// The attributes are initialised from the farthest ancestor to the class itself (so a class can override
+    /// the attributes of its parents), then the first constructor found in the method resolution order is called.
+    pub(super) fn instanciate(
+        &mut self,
+        program: &Program,
+        class: Value,
+        arguments: Vec<Value>,
+    ) -> Execution<Value> {
+        let class = match class {
+            Value::Class(class) => class,
+            // The modifier creates the instance itself
+            Value::Modified(modified)
+                if matches!(modified.target.unmodified(), Value::Class(_)) =>
+            {
+                return self.invoke(
+                    program,
+                    modified.modifier.clone(),
+                    vec![modified.target.clone(), Value::new_array(arguments)],
+                );
+            }
+            other => {
+                return interrupt!(UnexpectedType {
+                    location: self.location(),
+                    expected: "class",
+                    found: other.type_name(),
+                });
+            }
+        };
+
+        let instance = Rc::new(RefCell::new(Instance {
+            class: Rc::clone(&class),
+            fields: Fields::new(),
+        }));
+
+        let mro = class.mro();
+        for owner in mro.iter().rev() {
+            let Some(initializer) = &owner.initializer else {
+                continue;
+            };
+            // Attributes are evaluated in the environment of their class
+            let Value::Array(values) =
+                self.invoke(program, Value::Function(Rc::clone(initializer)), vec![])?
+            else {
+                unreachable!("the attributes are returned in an array");
+            };
+
+            let attributes = owner.prototype.attributes.iter();
+            let names = attributes.filter(|attribute| !attribute.is_static);
+            for (attribute, value) in names.zip(values.borrow().iter()) {
+                instance
+                    .borrow_mut()
+                    .fields
+                    .set(Value::new_string(&attribute.name), value.clone());
+            }
+        }
+
+        self.construct(program, &instance, &mro, arguments)?;
+        Ok(Value::Instance(instance))
+    }
+
+    /// Call the first constructor defined by the given classes
+    pub(super) fn construct(
+        &mut self,
+        program: &Program,
+        receiver: &Rc<RefCell<Instance>>,
+        classes: &[Rc<Class>],
+        arguments: Vec<Value>,
+    ) -> Execution {
+        let owner = classes.iter().find(|class| class.constructor.is_some());
+
+        match owner {
+            Some(owner) => {
+                let constructor = Value::Method(Rc::new(Method {
+                    receiver: Some(Rc::clone(receiver)),
+                    class: Rc::clone(owner),
+                    function: Rc::clone(owner.constructor.as_ref().unwrap()),
+                }));
+                self.invoke(program, constructor, arguments)?;
+                Ok(())
+            }
+            // Without constructor, no argument can be given
+            None if arguments.is_empty() => Ok(()),
+            None => interrupt!(TooManyArguments {
+                location: self.location(),
+                expected: 0,
+                found: arguments.len(),
+            }),
+        }
+    }
+
+    /// The classes following the one of `super` in the method resolution order of the receiver's class
+    pub(super) fn next_classes(parent: &Super) -> Vec<Rc<Class>> {
+        let mro = parent.receiver.borrow().class.mro();
+        let position = mro
+            .iter()
+            .position(|class| Rc::ptr_eq(class, &parent.class))
+            .map_or(mro.len(), |position| position + 1);
+
+        mro[position..].to_vec()
+    }
+
+    /// Find a method in the given classes, and bind it to the receiver (if the method is not static)
+    pub(super) fn bound_method(
+        receiver: Option<&Rc<RefCell<Instance>>>,
+        classes: &[Rc<Class>],
+        key: &Value,
+    ) -> Option<Value> {
+        let Value::String(name) = key else {
+            return None;
+        };
+
+        classes.iter().find_map(|class| {
+            let method = class.method(name, receiver.is_none())?;
+
+            Some(Self::modify(
+                Value::Method(Rc::new(Method {
+                    receiver: receiver.map(Rc::clone),
+                    class: Rc::clone(class),
+                    function: Rc::clone(&method.function),
+                })),
+                method.modifiers.iter().cloned(),
+            ))
+        })
+    }
+
+    /// Verify that the current code can access a member declared by one of the given classes :
+    ///
+    /// - private members can only be accessed from the methods of the class declaring them,
+    /// - protected members can also be accessed from the methods of its child classes.
+    ///
+    /// Returns the class declaring the member, if any.
+    pub(super) fn check_access(
+        &self,
+        classes: &[Rc<Class>],
+        key: &Value,
+        is_static: bool,
+    ) -> Execution<Option<Rc<Class>>> {
+        let Value::String(name) = key else {
+            return Ok(None);
+        };
+        let Some((owner, visibility)) = classes.iter().find_map(|class| {
+            let visibility = class.declaration(name, is_static)?;
+            Some((Rc::clone(class), visibility))
+        }) else {
+            return Ok(None);
+        };
+
+        let current = self.frames.last().and_then(|frame| frame.class.as_ref());
+        let allowed = match visibility {
+            ClassItemVisibility::Public => true,
+            ClassItemVisibility::Private => current.is_some_and(|class| Rc::ptr_eq(class, &owner)),
+            ClassItemVisibility::Protected => current.is_some_and(|class| {
+                class
+                    .mro()
+                    .iter()
+                    .any(|ancestor| Rc::ptr_eq(ancestor, &owner))
+            }),
+        };
+
+        if !allowed {
+            return interrupt!(InaccessibleMember {
+                location: self.location(),
+                member: name.to_string(),
+                class: owner.name().to_string(),
+                visibility: visibility.name(),
+            });
+        }
+
+        Ok(Some(owner))
+    }
+
+    /// Read a static member of a class (or of its parents)
+    pub(super) fn read_static(&self, class: &Rc<Class>, key: &Value) -> Execution<Option<Value>> {
+        let mro = class.mro();
+        self.check_access(&mro, key, true)?;
+
+        let attribute = mro
+            .iter()
+            .find_map(|class| class.statics.borrow().get(key).cloned());
+        Ok(attribute.or_else(|| Self::bound_method(None, &mro, key)))
+    }
+
+    /// Write a static attribute of a class.
+    /// If the attribute is declared by one of its parents, it is modified on the parent.
+    pub(super) fn write_static(&self, class: &Rc<Class>, key: Value, value: Value) -> Execution {
+        let owner = self
+            .check_access(&class.mro(), &key, true)?
+            .unwrap_or_else(|| Rc::clone(class));
+
+        owner.statics.borrow_mut().set(key, value);
+        Ok(())
+    }
+}
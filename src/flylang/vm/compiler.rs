@@ -0,0 +1,1347 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    path::PathBuf,
+    rc::Rc,
+};
+
+use crate::{
+    behavior::LangBehavior,
+    flylang::{
+        analyser::{
+            Analyser,
+            symbols::{SymbolKind, SymbolTable},
+        },
+        errors::{LangResult, lang_err},
+        interpreter::errors::{
+            CircularUse, InvalidScopeTarget, PackageNotFound, UncaughtBreaker, UnexpectedType,
+            UnknownScope,
+        },
+        lexer::tokens::{
+            BinaryOperator, Comparison, Operator, ScopeTarget,
+            representations::number::NumberRepresentation,
+        },
+        module::slice::LangModuleSlice,
+        parser::ast::{
+            Branches, Node,
+            definables::{
+                Definables,
+                class::DefineClass,
+                functions::DefineFunction,
+                variables::{DefineVariable, VariableEmplacements},
+            },
+            expressions::{
+                Expressions,
+                call::Call,
+                literals::{ParsedLiterals, ParsedStringItem},
+                modified::Modifier,
+                operations::Operations,
+                property::Property,
+                reverse::ReverseKind,
+            },
+            instructions::{
+                Instructions,
+                breakers::{Break, BreakKind},
+                conditionnal::{If, IfFallBack},
+                loops::{Loop, LoopParameter},
+                r#use::{
+                    Package, PackageContentEmplacement, PackageIncludedContent, PackageSource,
+                },
+            },
+        },
+        runtime::builtins,
+        utils::breakers::{self, BreakableScope, ScopeKind, Unresolved},
+        vm::{
+            VirtualMachine,
+            bytecode::{
+                Address, Breaker, Catch, ClassPrototype, Instruction, Member, Program, Prototype,
+                Slot,
+            },
+            value::Value,
+        },
+    },
+};
+
+/// What a compilation finds about the code, that the next compilation needs before reaching it
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Layout {
+    /// The variables of each function (numbered in the order they are compiled)
+    variables: Vec<Vec<String>>,
+    /// The variables of each function that the functions defined inside it use
+    captured: Vec<HashSet<String>>,
+    /// If each function defines other functions
+    environments: Vec<bool>,
+    /// The scopes (numbered in the order they are entered) that breakers reach from another function
+    raised: HashSet<usize>,
+}
+
+/// A function being compiled
+struct Frame {
+    /// The number of the function in the program
+    id: usize,
+    name: Option<String>,
+    arguments: u16,
+    method: bool,
+    /// The variables of the function : the ones found by the previous compilation, then the new ones
+    variables: Vec<String>,
+    slots: HashMap<String, Slot>,
+    locals: u16,
+    cells: u16,
+    moved: Vec<(u16, u16)>,
+    /// The variables used by the functions defined inside, according to the previous compilation
+    captured: HashSet<String>,
+    /// If the calls have an environment, according to the previous compilation
+    environment: bool,
+    /// If the function defines other functions
+    closures: bool,
+    code: Vec<Instruction>,
+    locations: Vec<(Address, LangModuleSlice)>,
+    catches: Vec<Catch>,
+}
+
+/// A scope containing the code being compiled, that breakers can target
+struct Scope {
+    kind: ScopeKind,
+    name: Option<String>,
+    id: usize,
+    /// The position of the function containing the scope, in the frames
+    frame: usize,
+    /// If breakers reach the scope from another function : it pushes a handler
+    raised: bool,
+    /// The catch of the handler, in the catches of the function
+    catch: Option<usize>,
+    /// If the scope is the body of an `each` loop, using an iterator
+    iterates: bool,
+    /// Where the next iteration of a loop starts
+    head: Address,
+    /// The jumps leaving the scope, patched once its end is known
+    exits: Vec<usize>,
+}
+impl BreakableScope for Scope {
+    fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// Compiles the instructions of a module to the bytecode of the virtual machine.
+///
+/// The code is compiled until its layout does not change : a compilation finds the variables used by
+/// the functions defined inside their function (they live in an environment) and the scopes breakers
+/// reach from another function, which the code needs before reaching them.
+pub struct Compiler<'a> {
+    /// The symbols of the module being compiled
+    symbols: Rc<SymbolTable>,
+    /// The behaviors used to find and to load the packages
+    behavior: &'a LangBehavior,
+    /// The layout found by the previous compilation
+    known: Layout,
+    layout: Layout,
+    builtins: Vec<&'static str>,
+    constants: Vec<Value>,
+    /// The position of the constant strings and numbers, to reuse them
+    strings: HashMap<String, u32>,
+    numbers: HashMap<u64, u32>,
+    functions: Vec<Option<Rc<Prototype>>>,
+    classes: Vec<Rc<ClassPrototype>>,
+    declarations: Vec<Option<LangModuleSlice>>,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    /// The number of scopes entered
+    entered: usize,
+    /// The function of each module already used (see `package`), by its path
+    modules: HashMap<PathBuf, u32>,
+    /// The modules being compiled, from the first one used
+    loading: Vec<PathBuf>,
+}
+
+impl<'a> Compiler<'a> {
+    /// Compile the instructions of a module
+    pub fn compile(branches: &Branches) -> LangResult<Program> {
+        Compiler::compile_with_behavior(branches, &LangBehavior::default())
+    }
+
+    /// Compile the instructions of a module, finding and loading the packages it uses with the given
+    /// behaviors
+    pub fn compile_with_behavior(
+        branches: &Branches,
+        behavior: &LangBehavior,
+    ) -> LangResult<Program> {
+        let symbols = Rc::new(Analyser::new().analyse(branches).symbols);
+
+        let mut known = Layout::default();
+        loop {
+            let (program, layout) =
+                Compiler::new(Rc::clone(&symbols), behavior, known.clone()).module(branches)?;
+            if layout == known {
+                return Ok(program);
+            }
+            known = layout;
+        }
+    }
+
+    fn new(symbols: Rc<SymbolTable>, behavior: &'a LangBehavior, known: Layout) -> Self {
+        Self {
+            symbols,
+            behavior,
+            known,
+            layout: Layout::default(),
+            builtins: builtins::globals::<VirtualMachine>()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+            constants: vec![],
+            strings: HashMap::new(),
+            numbers: HashMap::new(),
+            functions: vec![],
+            classes: vec![],
+            declarations: vec![],
+            frames: vec![],
+            scopes: vec![],
+            entered: 0,
+            modules: HashMap::new(),
+            loading: vec![],
+        }
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    /// The address of the next instruction of the current function
+    fn here(&self) -> Address {
+        self.frames.last().unwrap().code.len() as Address
+    }
+
+    fn emit(&mut self, instruction: Instruction, location: &LangModuleSlice) -> usize {
+        let frame = self.frame();
+        let address = frame.code.len();
+        if frame
+            .locations
+            .last()
+            .is_none_or(|(_, last)| last.range() != location.range())
+        {
+            frame.locations.push((address as Address, location.clone()));
+        }
+
+        frame.code.push(instruction);
+        address
+    }
+
+    /// Set the address a jump goes to
+    fn patch(&mut self, position: usize, target: Address) {
+        match &mut self.frame().code[position] {
+            Instruction::Jump(address)
+            | Instruction::JumpIf(address)
+            | Instruction::JumpUnless(address)
+            | Instruction::Next(address) => *address = target,
+            _ => unreachable!("only jumps are patched"),
+        }
+    }
+
+    /// Add a constant to the program, and push it
+    fn constant(&mut self, value: Value, location: &LangModuleSlice) {
+        let known = match &value {
+            Value::String(s) => self.strings.get(s.as_ref()),
+            Value::Number(n) => self.numbers.get(&n.to_bits()),
+            _ => None,
+        };
+        let index = match known {
+            Some(index) => *index,
+            None => {
+                let index = self.constants.len() as u32;
+                match &value {
+                    Value::String(s) => {
+                        self.strings.insert(s.to_string(), index);
+                    }
+                    Value::Number(n) => {
+                        self.numbers.insert(n.to_bits(), index);
+                    }
+                    _ => {}
+                }
+                self.constants.push(value);
+                index
+            }
+        };
+
+        self.emit(Instruction::Constant(index), location);
+    }
+
+    /// Compile a whole module, returning the program and its layout
+    fn module(mut self, branches: &Branches) -> LangResult<(Program, Layout)> {
+        self.define(None, &[], false, |this| {
+            for (position, instruction) in branches.iter().enumerate() {
+                match instruction.kind() {
+                    // The value of the last instruction is the result of the module
+                    Instructions::ValueOf(expression) if position + 1 == branches.len() => {
+                        this.expression(expression, instruction.location())?;
+                        this.emit(Instruction::Return, instruction.location());
+                    }
+                    _ => this.instruction(instruction)?,
+                }
+            }
+            Ok(())
+        })?;
+
+        let program = Program {
+            constants: self.constants,
+            functions: self.functions.into_iter().map(Option::unwrap).collect(),
+            classes: self.classes,
+            declarations: self.declarations,
+        };
+        Ok((program, self.layout))
+    }
+
+    /// Compile a function, with its own frame, returning its number in the program.
+    /// `arguments` are the names of its arguments, and `body` compiles its instructions. The variables of
+    /// methods include `self` and `super`.
+    fn define(
+        &mut self,
+        name: Option<String>,
+        arguments: &[String],
+        method: bool,
+        body: impl FnOnce(&mut Self) -> LangResult<()>,
+    ) -> LangResult<u32> {
+        let id = self.functions.len();
+        self.functions.push(None);
+        self.layout.variables.push(vec![]);
+        self.layout.captured.push(HashSet::new());
+        self.layout.environments.push(false);
+
+        let mut frame = Frame {
+            id,
+            name,
+            arguments: arguments.len() as u16,
+            method,
+            variables: self.known.variables.get(id).cloned().unwrap_or_default(),
+            slots: HashMap::new(),
+            locals: 0,
+            cells: 0,
+            moved: vec![],
+            captured: self.known.captured.get(id).cloned().unwrap_or_default(),
+            environment: self.known.environments.get(id).copied().unwrap_or(false),
+            closures: false,
+            code: vec![],
+            locations: vec![],
+            catches: vec![],
+        };
+
+        // The arguments (then the receivers) are the first variables on the stack
+        let mut bound: Vec<&str> = arguments.iter().map(String::as_str).collect();
+        if method {
+            bound.extend(["self", "super"]);
+        }
+        for (position, name) in bound.into_iter().enumerate() {
+            let local = position as u16;
+            frame.locals += 1;
+            let slot = match frame.captured.contains(name) {
+                true => {
+                    frame.moved.push((local, frame.cells));
+                    frame.cells += 1;
+                    Slot::Cell(frame.cells - 1)
+                }
+                false => Slot::Local(local),
+            };
+            frame.slots.insert(name.to_string(), slot);
+            if !frame.variables.iter().any(|variable| variable == name) {
+                frame.variables.push(name.to_string());
+            }
+        }
+
+        self.frames.push(frame);
+        let body = body(self);
+        if body.is_ok() {
+            self.frame().code.push(Instruction::Empty);
+            self.frame().code.push(Instruction::Return);
+        }
+        let frame = self.frames.pop().unwrap();
+        body?;
+
+        let mut variables: Vec<(String, Slot)> = frame.slots.into_iter().collect();
+        variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.layout.variables[id] = frame.variables;
+        self.layout.environments[id] = frame.closures;
+
+        self.functions[id] = Some(Rc::new(Prototype {
+            name: frame.name,
+            arguments: frame.arguments,
+            method: frame.method,
+            locals: frame.locals,
+            cells: frame.cells,
+            moved: frame.moved,
+            environment: frame.closures,
+            code: frame.code,
+            locations: frame.locations,
+            catches: frame.catches,
+            variables,
+        }));
+        Ok(id as u32)
+    }
+
+    /// Push a function defined inside the current one
+    fn closure(&mut self, function: u32, location: &LangModuleSlice) {
+        self.frame().closures = true;
+        self.emit(Instruction::Function(function), location);
+    }
+
+    /// Get the slot of a variable of a function, giving it one if it has none yet
+    fn slot(&mut self, position: usize, name: &str) -> Slot {
+        let frame = &mut self.frames[position];
+        if let Some(slot) = frame.slots.get(name) {
+            return *slot;
+        }
+
+        let slot = match frame.captured.contains(name) {
+            true => {
+                frame.cells += 1;
+                Slot::Cell(frame.cells - 1)
+            }
+            false => {
+                frame.locals += 1;
+                Slot::Local(frame.locals - 1)
+            }
+        };
+        frame.slots.insert(name.to_string(), slot);
+        if !frame.variables.iter().any(|variable| variable == name) {
+            frame.variables.push(name.to_string());
+        }
+        slot
+    }
+
+    /// Get a variable on the stack without name (used by the generated code)
+    fn hidden(&mut self) -> u16 {
+        self.frame().locals += 1;
+        self.frame().locals - 1
+    }
+
+    /// Find the function having a variable, from the current one to the outer ones
+    fn owner(&self, name: &str) -> Option<usize> {
+        self.frames
+            .iter()
+            .rposition(|frame| frame.variables.iter().any(|variable| variable == name))
+    }
+
+    /// Get the instructions reading and writing a variable of the given function
+    fn access(&mut self, owner: usize, name: &str) -> (Instruction, Instruction) {
+        let current = self.frames.len() - 1;
+        let slot = self.slot(owner, name);
+        if owner == current {
+            return match slot {
+                Slot::Local(index) => (Instruction::Local(index), Instruction::SetLocal(index)),
+                Slot::Cell(index) => (
+                    Instruction::Cell { depth: 0, index },
+                    Instruction::SetCell { depth: 0, index },
+                ),
+            };
+        }
+
+        // The variable of an enclosing function lives in its environment
+        self.layout.captured[self.frames[owner].id].insert(name.to_string());
+        let index = match slot {
+            Slot::Cell(index) => index,
+            // Found by the next compilation
+            Slot::Local(_) => 0,
+        };
+        // Functions without environment use the one they have been defined in
+        let depth = (current - owner - usize::from(!self.frames[current].environment)) as u16;
+        (
+            Instruction::Cell { depth, index },
+            Instruction::SetCell { depth, index },
+        )
+    }
+
+    /// Push the value of a name : a variable or a built-in element
+    fn word(&mut self, location: &LangModuleSlice) {
+        let name = location.code();
+        let instruction = match self.owner(name) {
+            Some(owner) => self.access(owner, name).0,
+            None => match self.builtins.iter().position(|builtin| *builtin == name) {
+                Some(index) => Instruction::Builtin(index as u16),
+                None => Instruction::Undefined,
+            },
+        };
+
+        self.emit(instruction, location);
+    }
+
+    /// Set a variable to the value on the top of the stack (the value stays on the stack).
+    /// The variable belongs to the current function if the assignment declares it (according to the
+    /// analyser), or if no enclosing function has it.
+    fn assign(&mut self, location: &LangModuleSlice) {
+        let name = location.code();
+
+        // Constants, receivers and built-in elements cannot be reassigned
+        if let Some(symbol) = self.symbols.resolve(location)
+            && matches!(
+                symbol.kind,
+                SymbolKind::Constant | SymbolKind::Receiver | SymbolKind::Builtin
+            )
+            && symbol
+                .declaration
+                .as_ref()
+                .is_none_or(|declaration| declaration.range() != location.range())
+        {
+            self.declarations.push(symbol.declaration.clone());
+            let index = self.declarations.len() as u32 - 1;
+            self.emit(Instruction::Reassign(index), location);
+            return;
+        }
+
+        let current = self.frames.len() - 1;
+        let owner = match self.owner(name) {
+            Some(owner) if !self.declares(location) => owner,
+            _ => current,
+        };
+        let instruction = self.access(owner, name).1;
+        self.emit(instruction, location);
+    }
+
+    /// Returns if the variable is declared at this location
+    fn declares(&self, location: &LangModuleSlice) -> bool {
+        self.symbols.resolve(location).is_some_and(|symbol| {
+            matches!(
+                symbol.kind,
+                SymbolKind::Variable
+                    | SymbolKind::Constant
+                    | SymbolKind::Function
+                    | SymbolKind::Class
+                    | SymbolKind::Binding
+            ) && symbol
+                .declaration
+                .as_ref()
+                .is_some_and(|declaration| declaration.range() == location.range())
+        })
+    }
+
+    fn block(&mut self, branches: &Branches) -> LangResult<()> {
+        for instruction in branches {
+            self.instruction(instruction)?;
+        }
+
+        Ok(())
+    }
+
+    fn instruction(&mut self, instruction: &Node) -> LangResult<()> {
+        let location = instruction.location();
+        match instruction.kind() {
+            Instructions::ValueOf(expression) => {
+                self.expression(expression, location)?;
+                self.emit(Instruction::Pop, location);
+            }
+            Instructions::If(condition) => self.condition(condition)?,
+            Instructions::Loop(repetition) => self.repeat(repetition, location)?,
+            Instructions::Break(breaker) => self.breaker(breaker)?,
+            Instructions::Use(package) => self.import(package, location)?,
+        }
+
+        Ok(())
+    }
+
+    /// Compile a `use` instruction : bind the variables defined by the module of the package
+    fn import(&mut self, package: &Package, location: &LangModuleSlice) -> LangResult<()> {
+        let module = self.package(package, location)?;
+
+        match (&package.included, &package.emplacement) {
+            (_, PackageContentEmplacement::Variable(variable)) => {
+                self.emit(Instruction::Use(module), location);
+                self.assign(variable.location());
+                self.emit(Instruction::Pop, location);
+            }
+            (PackageIncludedContent::Only(names), PackageContentEmplacement::Global) => {
+                for name in names {
+                    let name = name.location();
+                    self.emit(Instruction::Use(module), name);
+                    self.constant(Value::new_string(name.code()), name);
+                    self.emit(Instruction::Get, name);
+                    self.assign(name);
+                    self.emit(Instruction::Pop, name);
+                }
+            }
+            // The variables of the module are known once it is compiled
+            (PackageIncludedContent::All, PackageContentEmplacement::Global) => {
+                let names: Vec<String> = self.functions[module as usize]
+                    .as_ref()
+                    .unwrap()
+                    .variables
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                for name in names {
+                    self.emit(Instruction::Use(module), location);
+                    self.constant(Value::new_string(&name), location);
+                    self.emit(Instruction::Import, location);
+                    let owner = self.owner(&name).unwrap_or(self.frames.len() - 1);
+                    let instruction = self.access(owner, &name).1;
+                    self.emit(instruction, location);
+                    self.emit(Instruction::Pop, location);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Compile the module of a package the first time it is used, returning its function in the program.
+    /// The module does not see the variables nor the scopes of the code using it : its function returns
+    /// the variables it defines.
+    fn package(&mut self, package: &Package, location: &LangModuleSlice) -> LangResult<u32> {
+        let not_found = || -> LangResult<u32> {
+            let (PackageSource::File(package) | PackageSource::Package(package)) = &package.source;
+            lang_err!(PackageNotFound {
+                location: location.clone(),
+                package: package.clone(),
+            })
+        };
+
+        let Some(path) = package.path(self.behavior) else {
+            return not_found();
+        };
+        if let Some(module) = self.modules.get(&path) {
+            return Ok(*module);
+        }
+        if self.loading.contains(&path) {
+            return lang_err!(CircularUse {
+                location: location.clone(),
+                path,
+            });
+        }
+        let Some(loaded) = package.load(self.behavior) else {
+            return not_found();
+        };
+        let (_, branches) = loaded?;
+
+        let analysis = Analyser::new().analyse(&branches);
+        let symbols = mem::replace(&mut self.symbols, Rc::new(analysis.symbols));
+        let frames = mem::take(&mut self.frames);
+        let scopes = mem::take(&mut self.scopes);
+        self.loading.push(path.clone());
+        let name = path.to_string_lossy().to_string();
+        let module = self.define(Some(name), &[], false, |this| {
+            this.block(&branches)?;
+            this.emit(Instruction::Exports, location);
+            this.emit(Instruction::Return, location);
+            Ok(())
+        });
+        self.loading.pop();
+        self.scopes = scopes;
+        self.frames = frames;
+        self.symbols = symbols;
+
+        let module = module?;
+        self.modules.insert(path, module);
+        Ok(module)
+    }
+
+    /// Enter a scope, that breakers can target. When breakers reach it from another function, it pushes
+    /// a handler.
+    fn enter(
+        &mut self,
+        kind: ScopeKind,
+        target: &Option<Node<ScopeTarget>>,
+        iterates: bool,
+        location: &LangModuleSlice,
+    ) {
+        let name = match target.as_ref().map(|target| target.kind()) {
+            Some(ScopeTarget::Named(name)) => Some(name.clone()),
+            _ => None,
+        };
+        let id = self.entered;
+        self.entered += 1;
+
+        let raised = self.known.raised.contains(&id);
+        let catch = raised.then(|| {
+            let catches = &mut self.frame().catches;
+            catches.push(Catch {
+                scope: id as u32,
+                stop: 0,
+                pass: 0,
+                returns: kind == ScopeKind::Function,
+            });
+            catches.len() - 1
+        });
+        if let Some(catch) = catch {
+            self.emit(Instruction::Handle(catch as u32), location);
+        }
+
+        let head = self.here();
+        self.scopes.push(Scope {
+            kind,
+            name,
+            id,
+            frame: self.frames.len() - 1,
+            raised,
+            catch,
+            iterates,
+            head,
+            exits: vec![],
+        });
+    }
+
+    /// Set where the next iteration of the current loop starts
+    fn head(&mut self) {
+        let head = self.here();
+        self.scopes.last_mut().unwrap().head = head;
+    }
+
+    /// Leave the current scope : its breakers jump here, where its handler is removed
+    fn leave(&mut self, location: &LangModuleSlice) {
+        let scope = self.scopes.pop().unwrap();
+        let end = self.here();
+        for exit in scope.exits {
+            self.patch(exit, end);
+        }
+
+        if let Some(catch) = scope.catch {
+            self.emit(Instruction::Unhandle(1), location);
+            let catch = &mut self.frame().catches[catch];
+            catch.stop = end;
+            catch.pass = match scope.kind {
+                ScopeKind::Loop => scope.head,
+                _ => end,
+            };
+        }
+    }
+
+    fn condition(&mut self, condition: &If) -> LangResult<()> {
+        let mut ends = vec![];
+        self.branch(condition, &mut ends)?;
+
+        let end = self.here();
+        for jump in ends {
+            self.patch(jump, end);
+        }
+        Ok(())
+    }
+
+    /// Compile a condition and its fallbacks. `ends` are the jumps to the end of the whole chain.
+    fn branch(&mut self, condition: &If, ends: &mut Vec<usize>) -> LangResult<()> {
+        let location = condition.condition.location();
+        self.node(&condition.condition)?;
+        let skip = self.emit(Instruction::JumpUnless(0), location);
+
+        self.enter(
+            ScopeKind::Condition,
+            &condition.scope_target,
+            false,
+            location,
+        );
+        self.block(&condition.process)?;
+        self.leave(location);
+
+        let Some(fallback) = &condition.fallback else {
+            let end = self.here();
+            self.patch(skip, end);
+            return Ok(());
+        };
+        ends.push(self.emit(Instruction::Jump(0), location));
+        let otherwise = self.here();
+        self.patch(skip, otherwise);
+
+        match fallback.kind() {
+            IfFallBack::Process(target, process) => {
+                self.enter(ScopeKind::Condition, target, false, fallback.location());
+                self.block(process)?;
+                self.leave(fallback.location());
+            }
+            IfFallBack::If(other) => self.branch(other.kind(), ends)?,
+        }
+        Ok(())
+    }
+
+    fn repeat(&mut self, repetition: &Loop, location: &LangModuleSlice) -> LangResult<()> {
+        let (exit, iterates) = match &repetition.parameter {
+            LoopParameter::Through(each) => {
+                self.node(&each.iterable)?;
+                self.emit(Instruction::Iterate, each.iterable.location());
+                self.enter(ScopeKind::Loop, &repetition.scope_target, true, location);
+                self.head();
+
+                let exit = self.emit(Instruction::Next(0), location);
+                // The item is bound before the index
+                for word in [&each.item, &each.index] {
+                    match word {
+                        Some(word) => {
+                            self.assign(word.location());
+                            self.emit(Instruction::Pop, word.location());
+                        }
+                        None => {
+                            self.emit(Instruction::Pop, location);
+                        }
+                    }
+                }
+                (exit, true)
+            }
+            LoopParameter::Conditionnaly(condition) => {
+                // The iteration number is counted in a hidden variable
+                let counter = condition.iteration_number.as_ref().map(|_| {
+                    let counter = self.hidden();
+                    self.constant(Value::Number(0f64), location);
+                    self.emit(Instruction::SetLocal(counter), location);
+                    self.emit(Instruction::Pop, location);
+                    counter
+                });
+                self.enter(ScopeKind::Loop, &repetition.scope_target, false, location);
+                self.head();
+
+                self.node(&condition.condition)?;
+                let exit = self.emit(Instruction::JumpUnless(0), condition.condition.location());
+                if let (Some(counter), Some(word)) = (counter, &condition.iteration_number) {
+                    self.emit(Instruction::Local(counter), word.location());
+                    self.assign(word.location());
+                    self.emit(Instruction::Pop, word.location());
+                    self.emit(Instruction::Increment(counter), word.location());
+                }
+                (exit, false)
+            }
+        };
+        self.scopes.last_mut().unwrap().exits.push(exit);
+
+        self.block(&repetition.process)?;
+        let head = self.scopes.last().unwrap().head;
+        self.emit(Instruction::Jump(head), location);
+        self.leave(location);
+        if iterates {
+            self.emit(Instruction::Release(1), location);
+        }
+        Ok(())
+    }
+
+    /// Find the scope a breaker stops at, the way the interpreter resolves it (in the code containing it)
+    fn resolve(&self, breaker: &Break) -> LangResult<usize> {
+        let location = breaker.keyword_location.clone();
+        match breakers::resolve(&self.scopes, breaker) {
+            Ok(position) => Ok(position),
+            Err(Unresolved::Uncaught { .. }) => lang_err!(UncaughtBreaker(location)),
+            Err(Unresolved::Invalid {
+                target,
+                expected,
+                found,
+            }) => lang_err!(InvalidScopeTarget {
+                location,
+                target,
+                expected: expected.map_or("", |kind| kind.name()),
+                found: found.name(),
+            }),
+            Err(Unresolved::Unknown { target }) => lang_err!(UnknownScope { location, target }),
+        }
+    }
+
+    fn breaker(&mut self, breaker: &Break) -> LangResult<()> {
+        let location = &breaker.keyword_location;
+        let position = self.resolve(breaker)?;
+        let (kind, value) = match &breaker.kind {
+            BreakKind::Stop(_) => (Breaker::Stop, None),
+            BreakKind::Pass(_) => (Breaker::Pass, None),
+            BreakKind::Return(_, value) => (Breaker::Return, value.as_deref()),
+        };
+        let scope = &self.scopes[position];
+        let (scope_kind, id, head) = (scope.kind, scope.id, scope.head);
+
+        // Going out of the function, the breaker reaches the handler of its scope
+        if scope.frame != self.frames.len() - 1 {
+            self.layout.raised.insert(id);
+            self.value_or_empty(value, location)?;
+            self.emit(
+                Instruction::Throw {
+                    scope: id as u32,
+                    breaker: kind,
+                },
+                location,
+            );
+            return Ok(());
+        }
+
+        if scope_kind == ScopeKind::Function {
+            self.value_or_empty(value, location)?;
+            self.emit(Instruction::Return, location);
+            return Ok(());
+        }
+
+        // The handlers and the iterators of the scopes the breaker leaves are removed
+        let inner = &self.scopes[position + 1..];
+        let handlers = inner.iter().filter(|scope| scope.raised).count() as u16;
+        let iterators = inner.iter().filter(|scope| scope.iterates).count() as u16;
+        if handlers > 0 {
+            self.emit(Instruction::Unhandle(handlers), location);
+        }
+        if iterators > 0 {
+            self.emit(Instruction::Release(iterators), location);
+        }
+
+        match (scope_kind, kind) {
+            (ScopeKind::Loop, Breaker::Pass) => {
+                self.emit(Instruction::Jump(head), location);
+            }
+            _ => {
+                let jump = self.emit(Instruction::Jump(0), location);
+                self.scopes[position].exits.push(jump);
+            }
+        }
+        Ok(())
+    }
+
+    /// Push the value of an optional expression (empty without expression)
+    fn value_or_empty(
+        &mut self,
+        value: Option<&Node<Expressions>>,
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        match value {
+            Some(value) => self.node(value),
+            None => {
+                self.emit(Instruction::Empty, location);
+                Ok(())
+            }
+        }
+    }
+
+    fn node(&mut self, node: &Node<Expressions>) -> LangResult<()> {
+        self.expression(node.kind(), node.location())
+    }
+
+    /// Compile an expression, pushing its value
+    fn expression(
+        &mut self,
+        expression: &Expressions,
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        match expression {
+            Expressions::Literal(literal) => self.literal(literal, location)?,
+            Expressions::Prioritized(inner) => self.node(inner)?,
+            // Types are verified by the checker
+            Expressions::Typed(typed) => self.node(&typed.expression)?,
+            Expressions::Operation(operation) => self.operation(operation, location)?,
+            Expressions::Reverse(reverse) => {
+                self.node(&reverse.expression)?;
+                self.emit(
+                    match reverse.kind {
+                        ReverseKind::Boolean => Instruction::Not,
+                        ReverseKind::Sign => Instruction::Negate,
+                    },
+                    location,
+                );
+            }
+            Expressions::Ternary(ternary) => {
+                self.node(&ternary.condition)?;
+                let otherwise = self.emit(Instruction::JumpUnless(0), location);
+                self.node(&ternary.yes)?;
+                let end = self.emit(Instruction::Jump(0), location);
+                let here = self.here();
+                self.patch(otherwise, here);
+                self.node(&ternary.no)?;
+                let here = self.here();
+                self.patch(end, here);
+            }
+            Expressions::ReturnOf(call) => self.call(call)?,
+            Expressions::Defined(definable) => self.definition(definable, &[], location)?,
+            Expressions::Modifed(modified) => self.definition(
+                modified.definable.kind(),
+                &modified.modified_by,
+                modified.definable.location(),
+            )?,
+            Expressions::Read(property) => {
+                self.node(&property.from)?;
+                self.key(&property.read)?;
+                self.emit(Instruction::Get, location);
+            }
+            Expressions::Structure(structure) => {
+                for entry in &structure.entries {
+                    let entry = entry.kind();
+                    match entry.key.kind() {
+                        // Words are the name of the key (and not the value of a variable)
+                        Expressions::Literal(ParsedLiterals::Word) => self.constant(
+                            Value::new_string(entry.key.location().code()),
+                            entry.key.location(),
+                        ),
+                        _ => self.node(&entry.key)?,
+                    }
+                    self.node(&entry.value)?;
+                }
+                self.emit(
+                    Instruction::Structure(structure.entries.len() as u32),
+                    location,
+                );
+            }
+            Expressions::Array(array) => {
+                for item in &array.entries {
+                    self.node(item)?;
+                }
+                self.emit(Instruction::Array(array.entries.len() as u32), location);
+            }
+            Expressions::Instanciate(instanciation) => {
+                self.node(&instanciation.class)?;
+                for argument in &instanciation.arguments {
+                    self.node(argument)?;
+                }
+                self.emit(
+                    Instruction::New(instanciation.arguments.len() as u16),
+                    location,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &ParsedLiterals, location: &LangModuleSlice) -> LangResult<()> {
+        match literal {
+            ParsedLiterals::Word => self.word(location),
+            ParsedLiterals::True => {
+                self.emit(Instruction::True, location);
+            }
+            ParsedLiterals::False => {
+                self.emit(Instruction::False, location);
+            }
+            ParsedLiterals::Empty => {
+                self.emit(Instruction::Empty, location);
+            }
+            ParsedLiterals::Number => self.constant(
+                Value::Number(NumberRepresentation::from(location).into()),
+                location,
+            ),
+            ParsedLiterals::Computed(value) => self.constant(Value::Number(*value), location),
+            ParsedLiterals::String(parts) => {
+                for part in parts {
+                    match part.kind() {
+                        ParsedStringItem::Literal(content) => {
+                            self.constant(Value::new_string(content), part.location())
+                        }
+                        ParsedStringItem::Expression(expression) => self.node(expression)?,
+                    }
+                }
+                match parts.len() {
+                    0 => self.constant(Value::new_string(""), location),
+                    1 if matches!(parts[0].kind(), ParsedStringItem::Literal(_)) => {}
+                    count => {
+                        self.emit(Instruction::Concat(count as u32), location);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Push the key read by a property accessor (`.<key>`)
+    fn key(&mut self, property: &Node<Property>) -> LangResult<()> {
+        let location = property.location();
+        match property.kind() {
+            Property::Key => self.constant(Value::new_string(location.code()), location),
+            Property::Index => self.constant(
+                Value::Number(NumberRepresentation::from(location).into()),
+                location,
+            ),
+            Property::Expression(expression) => self.node(expression)?,
+        }
+
+        Ok(())
+    }
+
+    fn call(&mut self, call: &Call) -> LangResult<()> {
+        self.node(&call.callable)?;
+        for argument in &call.arguments {
+            self.node(argument)?;
+        }
+
+        self.emit(
+            Instruction::Call(call.arguments.len() as u16),
+            call.callable.location(),
+        );
+        Ok(())
+    }
+
+    fn operation(&mut self, operation: &Operations, location: &LangModuleSlice) -> LangResult<()> {
+        let (instruction, (left, right)) = match operation {
+            Operations::Numeric(numeric) => (
+                match numeric.operator.kind() {
+                    Operator::Add => Instruction::Add,
+                    Operator::Substract => Instruction::Subtract,
+                    Operator::Multiply => Instruction::Multiply,
+                    Operator::Divide => Instruction::Divide,
+                    Operator::Modulo => Instruction::Modulo,
+                    Operator::EuclidianDivision => Instruction::Quotient,
+                    Operator::Power => Instruction::Power,
+                },
+                &numeric.operands,
+            ),
+            Operations::Comparative(comparison) => (
+                match comparison.operator.kind() {
+                    Comparison::Equal => Instruction::Equal,
+                    Comparison::Less(true) => Instruction::Less,
+                    Comparison::Less(false) => Instruction::LessEqual,
+                    Comparison::Greater(true) => Instruction::Greater,
+                    Comparison::Greater(false) => Instruction::GreaterEqual,
+                },
+                &comparison.operands,
+            ),
+            Operations::Binary(binary) => {
+                let (left, right) = &binary.operands;
+                self.node(left)?;
+
+                // `&` and `?` operators are short-circuiting
+                let (skip, shortcut) = match binary.operator.kind() {
+                    BinaryOperator::And => (Instruction::JumpUnless(0), Instruction::False),
+                    BinaryOperator::Or => (Instruction::JumpIf(0), Instruction::True),
+                    BinaryOperator::Xor => {
+                        self.emit(Instruction::Truthy, location);
+                        self.node(right)?;
+                        self.emit(Instruction::Truthy, location);
+                        self.emit(Instruction::Equal, location);
+                        self.emit(Instruction::Not, location);
+                        return Ok(());
+                    }
+                };
+                let skip = self.emit(skip, location);
+                self.node(right)?;
+                self.emit(Instruction::Truthy, location);
+                let end = self.emit(Instruction::Jump(0), location);
+                let here = self.here();
+                self.patch(skip, here);
+                self.emit(shortcut, location);
+                let here = self.here();
+                self.patch(end, here);
+                return Ok(());
+            }
+        };
+
+        self.node(left)?;
+        self.node(right)?;
+        self.emit(instruction, location);
+        Ok(())
+    }
+
+    /// Evaluate modifiers, keeping them in hidden variables
+    fn modifiers(&mut self, modifiers: &[Node<Modifier>]) -> LangResult<Vec<u16>> {
+        let mut hidden = vec![];
+        for modifier in modifiers {
+            self.modifier(modifier)?;
+            let local = self.hidden();
+            self.emit(Instruction::SetLocal(local), modifier.location());
+            self.emit(Instruction::Pop, modifier.location());
+            hidden.push(local);
+        }
+
+        Ok(hidden)
+    }
+
+    /// Push the function used as a modifier
+    fn modifier(&mut self, modifier: &Node<Modifier>) -> LangResult<()> {
+        match modifier.kind() {
+            Modifier::DefinedElement => self.word(modifier.location()),
+            // The modifier is the function returned by the call
+            Modifier::CallReturn(call) => self.call(call)?,
+        }
+
+        Ok(())
+    }
+
+    /// Wrap the function or the class on the top of the stack with its modifiers
+    fn modify(&mut self, modifiers: &[u16], location: &LangModuleSlice) {
+        if modifiers.is_empty() {
+            return;
+        }
+
+        for modifier in modifiers {
+            self.emit(Instruction::Local(*modifier), location);
+        }
+        self.emit(Instruction::Modify(modifiers.len() as u16), location);
+    }
+
+    /// Compile a definition, pushing the defined value
+    fn definition(
+        &mut self,
+        definable: &Definables,
+        modifiers: &[Node<Modifier>],
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        let modifiers = self.modifiers(modifiers)?;
+
+        match definable {
+            Definables::Variable(variable) => {
+                self.value(variable, &modifiers, location)?;
+
+                let emplacement = variable.emplacement.location();
+                match variable.emplacement.kind() {
+                    VariableEmplacements::Scope => self.assign(emplacement),
+                    VariableEmplacements::Property(property) => {
+                        self.node(&property.from)?;
+                        self.key(&property.read)?;
+                        self.emit(Instruction::Put, emplacement);
+                    }
+                    VariableEmplacements::Any(_) => {
+                        return lang_err!(UnexpectedType {
+                            location: emplacement.clone(),
+                            expected: "variable or property",
+                            found: "expression",
+                        });
+                    }
+                }
+            }
+            Definables::Function(function) => {
+                let index = self.function(function, location, false)?;
+                self.closure(index, location);
+                self.modify(&modifiers, location);
+                if let Some(name) = &function.name {
+                    self.assign(name.location());
+                }
+            }
+            Definables::Class(class) => {
+                self.class(class, location)?;
+                self.modify(&modifiers, location);
+                self.assign(class.name.location());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Push the value of a variable, with its modifiers (kept in the given hidden variables) applied.
+    /// Variables' modifiers are called with the variable's name, and return its value.
+    fn value(
+        &mut self,
+        variable: &DefineVariable,
+        modifiers: &[u16],
+        location: &LangModuleSlice,
+    ) -> LangResult<()> {
+        self.node(&variable.value)?;
+        if modifiers.is_empty() {
+            return Ok(());
+        }
+
+        let value = self.hidden();
+        let name = Value::new_string(variable.emplacement.location().code());
+        for modifier in modifiers {
+            self.emit(Instruction::SetLocal(value), location);
+            self.emit(Instruction::Pop, location);
+            self.emit(Instruction::Local(*modifier), location);
+            self.constant(name.clone(), location);
+            self.emit(
+                match variable.readonly {
+                    true => Instruction::True,
+                    false => Instruction::False,
+                },
+                location,
+            );
+            self.emit(Instruction::Local(value), location);
+            self.emit(Instruction::Array(2), location);
+            self.emit(Instruction::Call(2), location);
+        }
+        Ok(())
+    }
+
+    /// Compile a function, returning its number in the program
+    fn function(
+        &mut self,
+        function: &DefineFunction,
+        location: &LangModuleSlice,
+        method: bool,
+    ) -> LangResult<u32> {
+        let name = function
+            .name
+            .as_ref()
+            .map(|name| name.location().code().to_string());
+        let arguments: Vec<String> = function
+            .arguments
+            .iter()
+            .map(|argument| argument.kind().name.location().code().to_string())
+            .collect();
+        self.define(name, &arguments, method, |this| {
+            this.enter(ScopeKind::Function, &function.scope_target, false, location);
+            this.block(&function.execution)?;
+            this.leave(location);
+            Ok(())
+        })
+    }
+
+    /// Compile the creation of a class, pushing it
+    fn class(&mut self, class: &DefineClass, location: &LangModuleSlice) -> LangResult<()> {
+        for parent in &class.parents {
+            self.word(parent.location());
+        }
+
+        let member = |name: &str, visibility, is_static| Member {
+            name: name.to_string(),
+            visibility,
+            is_static,
+        };
+        let mut attributes = vec![];
+        for attribute in &class.attributes {
+            let item = attribute.kind();
+            attributes.push(member(
+                item.item.emplacement.location().code(),
+                item.visibility,
+                item.is_static,
+            ));
+        }
+
+        // The attributes of the instances are returned by a function defined with the class
+        let initialised: Vec<_> = class
+            .attributes
+            .iter()
+            .filter(|attribute| !attribute.kind().is_static)
+            .collect();
+        if !initialised.is_empty() {
+            let function = self.define(None, &[], false, |this| {
+                for attribute in &initialised {
+                    let item = attribute.kind();
+                    let modifiers = this.modifiers(&item.modifie_by)?;
+                    this.value(&item.item, &modifiers, attribute.location())?;
+                }
+                this.emit(Instruction::Array(initialised.len() as u32), location);
+                this.emit(Instruction::Return, location);
+                Ok(())
+            })?;
+            self.closure(function, location);
+        }
+
+        if let Some(constructor) = &class.constructor {
+            let function = self.function(constructor.kind(), constructor.location(), true)?;
+            self.closure(function, constructor.location());
+        }
+
+        let mut methods = vec![];
+        for method in &class.methods {
+            let item = method.kind();
+            // Static methods are not bound to an instance
+            let function = self.function(&item.item, method.location(), !item.is_static)?;
+            self.closure(function, method.location());
+            for modifier in &item.modifie_by {
+                self.modifier(modifier)?;
+            }
+
+            let name = item
+                .item
+                .name
+                .as_ref()
+                .map_or("", |name| name.location().code());
+            methods.push((
+                member(name, item.visibility, item.is_static),
+                item.modifie_by.len() as u16,
+            ));
+        }
+
+        // Static attributes are initialised once, when the class is defined
+        for attribute in class.attributes.iter().filter(|item| item.kind().is_static) {
+            let item = attribute.kind();
+            let modifiers = self.modifiers(&item.modifie_by)?;
+            self.value(&item.item, &modifiers, attribute.location())?;
+        }
+
+        self.classes.push(Rc::new(ClassPrototype {
+            name: class.name.location().clone(),
+            parents: class
+                .parents
+                .iter()
+                .map(|parent| parent.location().clone())
+                .collect(),
+            attributes,
+            methods,
+            initializer: !initialised.is_empty(),
+            constructor: class.constructor.is_some(),
+        }));
+        let index = self.classes.len() as u32 - 1;
+        self.emit(Instruction::Class(index), location);
+        Ok(())
+    }
+}
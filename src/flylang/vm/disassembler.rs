@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+use crate::flylang::{
+    runtime::builtins,
+    vm::{
+        VirtualMachine,
+        bytecode::{Instruction, Program, Prototype, Slot},
+    },
+};
+
+/// The position (`line:column`) of the code of an instruction
+fn position(prototype: &Prototype, address: usize) -> String {
+    match prototype
+        .location(address)
+        .and_then(|location| location.start())
+    {
+        Some(start) => format!("{}:{}", start.line() + 1, start.line_index() + 1),
+        None => String::new(),
+    }
+}
+
+/// A comment describing the operand of an instruction (the constant or the variable it uses)
+fn comment(program: &Program, prototype: &Prototype, instruction: &Instruction) -> Option<String> {
+    let variable = |slot| prototype.variable(slot).map(str::to_string);
+
+    match instruction {
+        Instruction::Constant(index) => Some(program.constants[*index as usize].repr()),
+        Instruction::Builtin(index) => builtins::globals::<VirtualMachine>()
+            .get(*index as usize)
+            .map(|(name, _)| name.to_string()),
+        Instruction::Local(index) | Instruction::SetLocal(index) => variable(Slot::Local(*index)),
+        Instruction::Cell { depth: 0, index } | Instruction::SetCell { depth: 0, index } => {
+            variable(Slot::Cell(*index))
+        }
+        Instruction::Function(index) => Some(format!(
+            "<fn {}>",
+            program.functions[*index as usize]
+                .name
+                .as_deref()
+                .unwrap_or("anonymous")
+        )),
+        Instruction::Use(index) => Some(format!(
+            "<module {}>",
+            program.functions[*index as usize]
+                .name
+                .as_deref()
+                .unwrap_or_default()
+        )),
+        Instruction::Class(index) => Some(format!(
+            "<cs {}>",
+            program.classes[*index as usize].name.code()
+        )),
+        _ => None,
+    }
+}
+
+/// Write the compiled functions of a program in a readable form.
+///
+/// Each instruction is written on its own line, after its address and the position of its code.
+pub fn disassemble(program: &Program) -> String {
+    let mut output = String::new();
+
+    for (index, prototype) in program.functions.iter().enumerate() {
+        let name = match (index, &prototype.name) {
+            (0, _) => String::from("<module>"),
+            (_, Some(name)) => name.clone(),
+            (_, None) => String::from("anonymous"),
+        };
+        let _ = writeln!(
+            output,
+            "fn #{} {} ({} arguments, {} locals, {} cells)",
+            index, name, prototype.arguments, prototype.locals, prototype.cells
+        );
+
+        for (address, instruction) in prototype.code.iter().enumerate() {
+            let line = format!(
+                "{:>5} {:>8}  {:?}",
+                address,
+                position(prototype, address),
+                instruction
+            );
+            let _ = match comment(program, prototype, instruction) {
+                Some(comment) => writeln!(output, "{:<48} ; {}", line, comment),
+                None => writeln!(output, "{}", line),
+            };
+        }
+        output.push('\n');
+    }
+
+    output
+}
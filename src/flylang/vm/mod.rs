@@ -0,0 +1,886 @@
+//! A stack virtual machine executing flylang compiled to bytecode.
+//!
+//! - The compiler (`compiler::Compiler`) translates each function to a `Prototype` : a list of instructions
+//!   taking their operands from the stack. The code of the module is the first function of the program.
+//! - The variables of a call live on the stack, after its arguments. The variables used by the functions
+//!   defined inside it live in an environment, that the functions keep.
+//! - Breakers leave the scopes of their function with jumps. Breakers going out of their function reach a
+//!   handler pushed by the scope they target, which restores the machine as it was when it was pushed.
+//!   Like the compilers, breakers are resolved in the code containing them (and not in the calls being
+//!   executed).
+//!
+//! The values (see `runtime::value::Value`) are the ones of the interpreter, and the errors follow its ones.
+
+use std::{cell::RefCell, collections::HashMap, mem, rc::Rc};
+
+use crate::flylang::{
+    errors::{LangResult, RaisableErr},
+    interpreter::errors::{
+        ConstantReassignment, DivisionByZero, IndexOutOfRange, InvalidOperation, InvalidProperty,
+        NotCallable, TooManyArguments, UncaughtBreaker, UndefinedProperty, UndefinedVariable,
+        UnexpectedType,
+    },
+    module::slice::LangModuleSlice,
+    runtime::builtins,
+    vm::{
+        bytecode::{Breaker, Instruction, Program, Slot},
+        value::{Class, Closure, Environment, Fields, Instance, Modified, Super, Value},
+    },
+};
+
+pub mod bytecode;
+mod classes;
+pub mod compiler;
+pub mod disassembler;
+pub mod value;
+
+/// The reason why the execution of the current function has been interrupted
+enum Interruption {
+    Error(Box<dyn RaisableErr>),
+    /// A breaker going to the scope of the given handler
+    Throw {
+        handler: usize,
+        breaker: Breaker,
+        value: Value,
+    },
+}
+impl From<Box<dyn RaisableErr>> for Interruption {
+    fn from(value: Box<dyn RaisableErr>) -> Self {
+        Self::Error(value)
+    }
+}
+
+type Execution<T = ()> = Result<T, Interruption>;
+
+macro_rules! interrupt {
+    ($e:expr) => {
+        Err(Interruption::Error(Box::new($e)))
+    };
+}
+pub(crate) use interrupt;
+
+/// A call being executed
+#[derive(Debug)]
+struct Frame {
+    closure: Rc<Closure>,
+    /// The address of the next instruction
+    ip: usize,
+    /// The position of the first variable of the call on the stack
+    base: usize,
+    environment: Option<Rc<Environment>>,
+    /// The class whose members the code can access
+    class: Option<Rc<Class>>,
+    /// The number of iterators and handlers when the call started
+    iterators: usize,
+    handlers: usize,
+}
+
+/// The remaining iterations of an `each` loop
+#[derive(Debug)]
+enum Iterator {
+    /// Arrays, structures and strings are copied, so modifying them inside the loop does not change the
+    /// iterations
+    Entries(std::vec::IntoIter<(Value, Value)>),
+    /// Numbers give the numbers from 0 (included) to themselves (excluded)
+    Range { next: usize, end: f64 },
+}
+impl std::iter::Iterator for Iterator {
+    type Item = (Value, Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Entries(entries) => entries.next(),
+            Self::Range { next, end } => {
+                let number = *next as f64;
+                if number >= *end {
+                    return None;
+                }
+                *next += 1;
+                Some((Value::Number(number), Value::Number(number)))
+            }
+        }
+    }
+}
+
+/// A scope that breakers of other functions can reach
+#[derive(Debug)]
+struct Handler {
+    /// The number of the scope in the program
+    scope: u32,
+    /// The catch of the scope, in the function of the frame
+    catch: usize,
+    frame: usize,
+    /// The size of the stack and the number of iterators when the scope started
+    stack: usize,
+    iterators: usize,
+}
+
+/// Stack virtual machine executing compiled programs
+#[derive(Debug)]
+pub struct VirtualMachine {
+    stack: Vec<Value>,
+    frames: Vec<Frame>,
+    iterators: Vec<Iterator>,
+    handlers: Vec<Handler>,
+    /// The built-in elements (see `builtins::globals`)
+    builtins: Vec<Value>,
+    /// The value of each module already used, by its function in the program
+    modules: HashMap<u32, Value>,
+}
+
+impl Default for VirtualMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VirtualMachine {
+    pub fn new() -> Self {
+        Self {
+            stack: vec![],
+            frames: vec![],
+            iterators: vec![],
+            handlers: vec![],
+            builtins: builtins::globals()
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect(),
+            modules: HashMap::new(),
+        }
+    }
+
+    /// Execute a program, returning the value of the last instruction of its module
+    pub fn run(&mut self, program: &Program) -> LangResult<Value> {
+        self.stack.clear();
+        self.frames.clear();
+        self.iterators.clear();
+        self.handlers.clear();
+        self.modules.clear();
+
+        let module = Rc::new(Closure {
+            prototype: Rc::clone(&program.functions[0]),
+            environment: None,
+            class: None,
+        });
+        let execution = self
+            .invoke(program, Value::Function(module), vec![])
+            .map_err(|interruption| match interruption {
+                Interruption::Error(error) => error,
+                // The breakers always reach a handler (verified by the compiler)
+                Interruption::Throw { .. } => unreachable!(),
+            });
+
+        // The values of an interrupted execution are not needed anymore
+        self.stack.clear();
+        self.frames.clear();
+        self.iterators.clear();
+        self.handlers.clear();
+        execution
+    }
+
+    /// The location of the instruction being executed
+    fn location(&self) -> LangModuleSlice {
+        let frame = self.frames.last().unwrap();
+        frame
+            .closure
+            .prototype
+            .location(frame.ip.saturating_sub(1))
+            .cloned()
+            .expect("the instructions raising errors have a location")
+    }
+
+    /// Push the value of a variable, which must be assigned
+    fn push_bound(&mut self, value: Value) -> Execution {
+        if let Value::Unbound = value {
+            return interrupt!(UndefinedVariable(self.location()));
+        }
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Get the environment `depth` functions above the current one
+    fn environment(&self, depth: u16) -> &Environment {
+        let mut environment = self.frames.last().unwrap().environment.as_deref().unwrap();
+        for _ in 0..depth {
+            environment = environment.outer.as_deref().unwrap();
+        }
+        environment
+    }
+
+    /// Call a value with the given arguments, and execute it until it returns
+    fn invoke(
+        &mut self,
+        program: &Program,
+        callable: Value,
+        arguments: Vec<Value>,
+    ) -> Execution<Value> {
+        let count = arguments.len();
+        self.stack.push(callable);
+        self.stack.extend(arguments);
+
+        match self.call(program, count)? {
+            true => self.execute(program, self.frames.len() - 1),
+            false => Ok(self.stack.pop().unwrap()),
+        }
+    }
+
+    /// Execute the code until the call of the frame at `floor` returns
+    fn execute(&mut self, program: &Program, floor: usize) -> Execution<Value> {
+        loop {
+            let result = match self.step(program, floor) {
+                Err(Interruption::Throw {
+                    handler,
+                    breaker,
+                    value,
+                }) => self.catch(handler, breaker, value, floor),
+                other => other,
+            };
+
+            if let Some(value) = result? {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Execute the instructions of the current call, until it calls or returns.
+    /// Returns the returned value when the call of the frame at `floor` returns.
+    fn step(&mut self, program: &Program, floor: usize) -> Execution<Option<Value>> {
+        let top = self.frames.len() - 1;
+        let prototype = Rc::clone(&self.frames[top].closure.prototype);
+        let base = self.frames[top].base;
+
+        loop {
+            let frame = &mut self.frames[top];
+            let instruction = prototype.code[frame.ip];
+            frame.ip += 1;
+
+            match instruction {
+                Instruction::Constant(index) => {
+                    self.stack.push(program.constants[index as usize].clone())
+                }
+                Instruction::Empty => self.stack.push(Value::Empty),
+                Instruction::True => self.stack.push(Value::Boolean(true)),
+                Instruction::False => self.stack.push(Value::Boolean(false)),
+                Instruction::Builtin(index) => {
+                    self.stack.push(self.builtins[index as usize].clone())
+                }
+                Instruction::Pop => {
+                    self.stack.pop();
+                }
+
+                Instruction::Local(index) => {
+                    let value = self.stack[base + index as usize].clone();
+                    self.push_bound(value)?;
+                }
+                Instruction::SetLocal(index) => {
+                    self.stack[base + index as usize] = self.stack.last().unwrap().clone()
+                }
+                Instruction::Cell { depth, index } => {
+                    let value = self.environment(depth).cells.borrow()[index as usize].clone();
+                    self.push_bound(value)?;
+                }
+                Instruction::SetCell { depth, index } => {
+                    let value = self.stack.last().unwrap().clone();
+                    self.environment(depth).cells.borrow_mut()[index as usize] = value;
+                }
+                Instruction::Increment(index) => {
+                    if let Value::Number(n) = &mut self.stack[base + index as usize] {
+                        *n += 1f64;
+                    }
+                }
+                Instruction::Undefined => {
+                    return interrupt!(UndefinedVariable(self.location()));
+                }
+                Instruction::Reassign(index) => {
+                    return interrupt!(ConstantReassignment {
+                        location: self.location(),
+                        defined_at: program.declarations[index as usize].clone(),
+                    });
+                }
+
+                Instruction::Add
+                | Instruction::Subtract
+                | Instruction::Multiply
+                | Instruction::Divide
+                | Instruction::Power
+                | Instruction::Modulo
+                | Instruction::Quotient
+                | Instruction::Less
+                | Instruction::LessEqual
+                | Instruction::Greater
+                | Instruction::GreaterEqual => {
+                    let right = self.stack.pop().unwrap();
+                    let left = self.stack.pop().unwrap();
+                    let value = self.operation(instruction, left, right)?;
+                    self.stack.push(value);
+                }
+                Instruction::Equal => {
+                    let right = self.stack.pop().unwrap();
+                    let left = self.stack.pop().unwrap();
+                    self.stack.push(Value::Boolean(left == right));
+                }
+                Instruction::Not => {
+                    let value = self.stack.pop().unwrap();
+                    self.stack.push(Value::Boolean(!value.truthy()));
+                }
+                Instruction::Truthy => {
+                    let value = self.stack.pop().unwrap();
+                    self.stack.push(Value::Boolean(value.truthy()));
+                }
+                Instruction::Negate => match self.stack.pop().unwrap() {
+                    Value::Number(n) => self.stack.push(Value::Number(-n)),
+                    value => {
+                        return interrupt!(InvalidOperation {
+                            location: self.location(),
+                            operator: String::from("-"),
+                            operands: vec![value.type_name()],
+                        });
+                    }
+                },
+                Instruction::Concat(count) => {
+                    let parts = self.stack.split_off(self.stack.len() - count as usize);
+                    let content: String = parts.iter().map(|part| part.to_string()).collect();
+                    self.stack.push(Value::new_string(&content));
+                }
+
+                Instruction::Jump(address) => self.frames[top].ip = address as usize,
+                Instruction::JumpIf(address) => {
+                    if self.stack.pop().unwrap().truthy() {
+                        self.frames[top].ip = address as usize;
+                    }
+                }
+                Instruction::JumpUnless(address) => {
+                    if !self.stack.pop().unwrap().truthy() {
+                        self.frames[top].ip = address as usize;
+                    }
+                }
+
+                Instruction::Array(count) => {
+                    let items = self.stack.split_off(self.stack.len() - count as usize);
+                    self.stack.push(Value::new_array(items));
+                }
+                Instruction::Structure(count) => {
+                    let entries = self.stack.split_off(self.stack.len() - 2 * count as usize);
+                    let mut entries = entries.into_iter();
+                    let mut fields = Fields::new();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        fields.set(key, value);
+                    }
+                    self.stack.push(Value::new_structure(fields));
+                }
+                Instruction::Get => {
+                    let key = self.stack.pop().unwrap();
+                    let from = self.stack.pop().unwrap();
+                    let value = self.read_property(&from, &key)?;
+                    self.stack.push(value);
+                }
+                Instruction::Put => {
+                    let key = self.stack.pop().unwrap();
+                    let target = self.stack.pop().unwrap();
+                    let value = self.stack.last().unwrap().clone();
+                    self.write_property(&target, key, value)?;
+                }
+
+                Instruction::Function(index) => {
+                    let frame = &self.frames[top];
+                    let closure = Closure {
+                        prototype: Rc::clone(&program.functions[index as usize]),
+                        environment: frame.environment.clone(),
+                        class: frame.class.clone(),
+                    };
+                    self.stack.push(Value::Function(Rc::new(closure)));
+                }
+                Instruction::Class(index) => {
+                    let class = self.class(program, index as usize)?;
+                    self.stack.push(class);
+                }
+                Instruction::Modify(count) => {
+                    let modifiers = self.stack.split_off(self.stack.len() - count as usize);
+                    let target = self.stack.pop().unwrap();
+                    self.stack.push(Self::modify(target, modifiers));
+                }
+                Instruction::Call(count) => {
+                    if self.call(program, count as usize)? {
+                        return Ok(None);
+                    }
+                }
+                Instruction::New(count) => {
+                    let arguments = self.stack.split_off(self.stack.len() - count as usize);
+                    let class = self.stack.pop().unwrap();
+                    let instance = self.instanciate(program, class, arguments)?;
+                    self.stack.push(instance);
+                }
+                Instruction::Return => {
+                    let value = self.stack.pop().unwrap();
+                    return Ok(self.leave(value, floor));
+                }
+
+                Instruction::Use(index) => {
+                    let exports = match self.modules.get(&index) {
+                        Some(exports) => exports.clone(),
+                        None => {
+                            let module = Rc::new(Closure {
+                                prototype: Rc::clone(&program.functions[index as usize]),
+                                environment: None,
+                                class: None,
+                            });
+                            let exports = self.invoke(program, Value::Function(module), vec![])?;
+                            self.modules.insert(index, exports.clone());
+                            exports
+                        }
+                    };
+                    self.stack.push(exports);
+                }
+                Instruction::Exports => {
+                    let fields = prototype
+                        .variables
+                        .iter()
+                        .filter_map(|(name, slot)| {
+                            let value = match slot {
+                                Slot::Local(index) => self.stack[base + *index as usize].clone(),
+                                Slot::Cell(index) => {
+                                    self.environment(0).cells.borrow()[*index as usize].clone()
+                                }
+                            };
+                            (!matches!(value, Value::Unbound))
+                                .then(|| (Value::new_string(name), value))
+                        })
+                        .collect::<Fields>();
+                    self.stack.push(Value::new_structure(fields));
+                }
+                Instruction::Import => {
+                    let name = self.stack.pop().unwrap();
+                    let exports = self.stack.pop().unwrap();
+                    let Value::Structure(fields) = exports else {
+                        unreachable!("the value of a module is a structure");
+                    };
+                    let value = fields.borrow().get(&name).cloned();
+                    self.stack.push(value.unwrap_or(Value::Unbound));
+                }
+
+                Instruction::Iterate => {
+                    let iterable = self.stack.pop().unwrap();
+                    let iterator = self.iterator(iterable)?;
+                    self.iterators.push(iterator);
+                }
+                Instruction::Next(address) => match self.iterators.last_mut().unwrap().next() {
+                    Some((index, item)) => {
+                        self.stack.push(index);
+                        self.stack.push(item);
+                    }
+                    None => self.frames[top].ip = address as usize,
+                },
+                Instruction::Release(count) => {
+                    let length = self.iterators.len() - count as usize;
+                    self.iterators.truncate(length);
+                }
+
+                Instruction::Handle(catch) => self.handlers.push(Handler {
+                    scope: prototype.catches[catch as usize].scope,
+                    catch: catch as usize,
+                    frame: top,
+                    stack: self.stack.len(),
+                    iterators: self.iterators.len(),
+                }),
+                Instruction::Unhandle(count) => {
+                    let length = self.handlers.len() - count as usize;
+                    self.handlers.truncate(length);
+                }
+                Instruction::Throw { scope, breaker } => {
+                    let value = self.stack.pop().unwrap();
+                    return match self
+                        .handlers
+                        .iter()
+                        .rposition(|handler| handler.scope == scope)
+                    {
+                        Some(handler) => Err(Interruption::Throw {
+                            handler,
+                            breaker,
+                            value,
+                        }),
+                        // The function has been called outside of its scope
+                        None => interrupt!(UncaughtBreaker(self.location())),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Leave the current call, returning the value if it is the call of the frame at `floor`
+    fn leave(&mut self, value: Value, floor: usize) -> Option<Value> {
+        let frame = self.frames.pop().unwrap();
+        // The called value is below the variables of the call
+        self.stack.truncate(frame.base - 1);
+        self.iterators.truncate(frame.iterators);
+        self.handlers.truncate(frame.handlers);
+
+        if self.frames.len() == floor {
+            return Some(value);
+        }
+        self.stack.push(value);
+        None
+    }
+
+    /// Continue the execution at the scope of a handler, reached by a breaker
+    fn catch(
+        &mut self,
+        handler: usize,
+        breaker: Breaker,
+        value: Value,
+        floor: usize,
+    ) -> Execution<Option<Value>> {
+        let Handler {
+            catch,
+            frame,
+            stack,
+            iterators,
+            ..
+        } = self.handlers[handler];
+        // The scope belongs to a call outside of this execution
+        if frame < floor {
+            return Err(Interruption::Throw {
+                handler,
+                breaker,
+                value,
+            });
+        }
+
+        self.frames.truncate(frame + 1);
+        self.stack.truncate(stack);
+        self.iterators.truncate(iterators);
+        self.handlers.truncate(handler + 1);
+
+        let catch = &self.frames[frame].closure.prototype.catches[catch];
+        if catch.returns {
+            let value = match breaker {
+                Breaker::Return => value,
+                _ => Value::Empty,
+            };
+            return Ok(self.leave(value, floor));
+        }
+
+        self.frames[frame].ip = match breaker {
+            Breaker::Pass => catch.pass,
+            _ => catch.stop,
+        } as usize;
+        Ok(None)
+    }
+
+    /// Call the value below the given number of arguments on the stack.
+    /// Returns if a call of a function has started (else, the result replaced the value and its arguments).
+    fn call(&mut self, program: &Program, count: usize) -> Execution<bool> {
+        let position = self.stack.len() - count - 1;
+
+        match self.stack[position].clone() {
+            Value::Function(closure) => self.enter(closure, count, None, None).map(|_| true),
+            Value::Method(method) => self
+                .enter(
+                    Rc::clone(&method.function),
+                    count,
+                    Some(Rc::clone(&method.class)),
+                    method.receiver.clone(),
+                )
+                .map(|_| true),
+            Value::Native(native) => {
+                let arguments = self.stack.split_off(position + 1);
+                self.stack.pop();
+                let location = self.location();
+                let value = (native.callback)(self, arguments, &location)?;
+                self.stack.push(value);
+                Ok(false)
+            }
+            Value::Modified(modified) => {
+                let arguments = self.stack.split_off(position + 1);
+                self.stack[position] = modified.modifier.clone();
+                self.stack.push(modified.target.clone());
+                self.stack.push(Value::new_array(arguments));
+                self.call(program, 2)
+            }
+            // Calling `super` calls the constructor of the parents
+            Value::Super(parent) => {
+                let arguments = self.stack.split_off(position + 1);
+                self.stack.pop();
+                self.construct(
+                    program,
+                    &parent.receiver,
+                    &Self::next_classes(&parent),
+                    arguments,
+                )?;
+                self.stack.push(Value::Empty);
+                Ok(false)
+            }
+            other => interrupt!(NotCallable(self.location(), other.type_name())),
+        }
+    }
+
+    /// Start the call of a function, whose arguments are on the top of the stack
+    fn enter(
+        &mut self,
+        closure: Rc<Closure>,
+        count: usize,
+        class: Option<Rc<Class>>,
+        receiver: Option<Rc<RefCell<Instance>>>,
+    ) -> Execution {
+        let prototype = Rc::clone(&closure.prototype);
+        let expected = prototype.arguments as usize;
+        if count > expected {
+            return interrupt!(TooManyArguments {
+                location: self.location(),
+                expected,
+                found: count,
+            });
+        }
+
+        // The missing arguments are empty, and the other variables are not assigned yet
+        let base = self.stack.len() - count;
+        self.stack.resize(base + expected, Value::Empty);
+        self.stack
+            .resize(base + prototype.locals as usize, Value::Unbound);
+        if let (true, Some(instance), Some(class)) = (prototype.method, &receiver, &class) {
+            self.stack[base + expected] = Value::Instance(Rc::clone(instance));
+            self.stack[base + expected + 1] = Value::Super(Rc::new(Super {
+                receiver: Rc::clone(instance),
+                class: Rc::clone(class),
+            }));
+        }
+
+        let environment = match prototype.environment {
+            true => {
+                let mut cells = vec![Value::Unbound; prototype.cells as usize];
+                for (local, cell) in &prototype.moved {
+                    cells[*cell as usize] =
+                        mem::replace(&mut self.stack[base + *local as usize], Value::Empty);
+                }
+                Some(Rc::new(Environment {
+                    cells: RefCell::new(cells),
+                    outer: closure.environment.clone(),
+                }))
+            }
+            false => closure.environment.clone(),
+        };
+
+        self.frames.push(Frame {
+            class: class.or_else(|| closure.class.clone()),
+            closure,
+            ip: 0,
+            base,
+            environment,
+            iterators: self.iterators.len(),
+            handlers: self.handlers.len(),
+        });
+        Ok(())
+    }
+
+    /// Wrap a function or a class with its modifiers.
+    /// The first modifier is applied first, so it is the most inner one.
+    fn modify(target: Value, modifiers: impl IntoIterator<Item = Value>) -> Value {
+        modifiers.into_iter().fold(target, |target, modifier| {
+            Value::Modified(Rc::new(Modified { modifier, target }))
+        })
+    }
+
+    fn operation(&self, instruction: Instruction, left: Value, right: Value) -> Execution<Value> {
+        let operator = match instruction {
+            Instruction::Add => "+",
+            Instruction::Subtract => "-",
+            Instruction::Multiply => "*",
+            Instruction::Divide => "/",
+            Instruction::Power => "**",
+            Instruction::Modulo => "%",
+            Instruction::Quotient => "//",
+            Instruction::Less => "<",
+            Instruction::LessEqual => "<=",
+            Instruction::Greater => ">",
+            Instruction::GreaterEqual => ">=",
+            _ => unreachable!(),
+        };
+
+        let ordering = match (&left, &right) {
+            (Value::Number(a), Value::Number(b)) => {
+                if matches!(
+                    instruction,
+                    Instruction::Divide | Instruction::Quotient | Instruction::Modulo
+                ) && *b == 0f64
+                {
+                    return interrupt!(DivisionByZero(self.location()));
+                }
+
+                let (a, b) = (*a, *b);
+                match instruction {
+                    Instruction::Add => return Ok(Value::Number(a + b)),
+                    Instruction::Subtract => return Ok(Value::Number(a - b)),
+                    Instruction::Multiply => return Ok(Value::Number(a * b)),
+                    Instruction::Divide => return Ok(Value::Number(a / b)),
+                    Instruction::Power => return Ok(Value::Number(a.powf(b))),
+                    Instruction::Modulo => return Ok(Value::Number(a % b)),
+                    Instruction::Quotient => return Ok(Value::Number(a.div_euclid(b))),
+                    _ => a.partial_cmp(&b),
+                }
+            }
+            (Value::String(_), _) | (_, Value::String(_))
+                if matches!(instruction, Instruction::Add) =>
+            {
+                return Ok(Value::new_string(&format!("{}{}", left, right)));
+            }
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            _ => None,
+        };
+
+        match (instruction, ordering) {
+            (Instruction::Less, Some(ordering)) => Ok(Value::Boolean(ordering.is_lt())),
+            (Instruction::LessEqual, Some(ordering)) => Ok(Value::Boolean(ordering.is_le())),
+            (Instruction::Greater, Some(ordering)) => Ok(Value::Boolean(ordering.is_gt())),
+            (Instruction::GreaterEqual, Some(ordering)) => Ok(Value::Boolean(ordering.is_ge())),
+            _ => interrupt!(InvalidOperation {
+                location: self.location(),
+                operator: operator.to_string(),
+                operands: vec![left.type_name(), right.type_name()],
+            }),
+        }
+    }
+
+    /// Convert the key to a valid index of a sequence of the given length.
+    /// Negative indexes start from the end of the sequence.
+    fn sequence_index(&self, key: &Value, length: usize) -> Execution<usize> {
+        let Value::Number(index) = key else {
+            return interrupt!(UnexpectedType {
+                location: self.location(),
+                expected: "number",
+                found: key.type_name(),
+            });
+        };
+
+        let position = if *index < 0f64 {
+            length as f64 + index
+        } else {
+            *index
+        };
+        if position.fract() != 0f64 || position < 0f64 || position >= length as f64 {
+            return interrupt!(IndexOutOfRange {
+                location: self.location(),
+                index: *index,
+                length,
+            });
+        }
+
+        Ok(position as usize)
+    }
+
+    fn read_property(&self, from: &Value, key: &Value) -> Execution<Value> {
+        match from {
+            Value::Structure(fields) => fields.borrow().get(key).cloned(),
+            Value::Instance(instance) => {
+                let mro = instance.borrow().class.mro();
+                self.check_access(&mro, key, false)?;
+
+                let field = instance.borrow().fields.get(key).cloned();
+                // Fields hide the methods of the same name
+                field.or_else(|| Self::bound_method(Some(instance), &mro, key))
+            }
+            Value::Super(parent) => {
+                let classes = Self::next_classes(parent);
+                self.check_access(&classes, key, false)?;
+                Self::bound_method(Some(&parent.receiver), &classes, key)
+            }
+            Value::Class(class) => self.read_static(class, key)?,
+            // Static members can be read through a modified class
+            Value::Modified(modified) => return self.read_property(&modified.target, key),
+            Value::Array(items) => {
+                let items = items.borrow();
+                let index = self.sequence_index(key, items.len())?;
+                Some(items[index].clone())
+            }
+            Value::String(s) => {
+                let index = self.sequence_index(key, s.chars().count())?;
+                s.chars()
+                    .nth(index)
+                    .map(|c| Value::new_string(&c.to_string()))
+            }
+            other => {
+                return interrupt!(InvalidProperty {
+                    location: self.location(),
+                    key: key.repr(),
+                    of: other.type_name(),
+                });
+            }
+        }
+        .map_or_else(
+            || interrupt!(UndefinedProperty(self.location(), key.repr())),
+            Ok,
+        )
+    }
+
+    fn write_property(&self, target: &Value, key: Value, value: Value) -> Execution {
+        match target {
+            Value::Structure(fields) => fields.borrow_mut().set(key, value),
+            Value::Instance(instance) => {
+                let mro = instance.borrow().class.mro();
+                self.check_access(&mro, &key, false)?;
+                instance.borrow_mut().fields.set(key, value)
+            }
+            Value::Class(class) => self.write_static(class, key, value)?,
+            Value::Modified(modified) => return self.write_property(&modified.target, key, value),
+            Value::Array(items) => {
+                let mut items = items.borrow_mut();
+                // Writing right after the last item appends it
+                if key == Value::Number(items.len() as f64) {
+                    items.push(value);
+                } else {
+                    let index = self.sequence_index(&key, items.len())?;
+                    items[index] = value;
+                }
+            }
+            other => {
+                return interrupt!(InvalidProperty {
+                    location: self.location(),
+                    key: key.repr(),
+                    of: other.type_name(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the iterator of an `each` loop going through a value
+    fn iterator(&self, iterable: Value) -> Execution<Iterator> {
+        let position = |(index, item): (usize, Value)| (Value::Number(index as f64), item);
+
+        Ok(match iterable {
+            Value::Array(items) => Iterator::Entries(
+                items
+                    .borrow()
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .map(position)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Value::Structure(fields) => Iterator::Entries(
+                fields
+                    .borrow()
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Value::String(s) => Iterator::Entries(
+                s.chars()
+                    .map(|c| Value::new_string(&c.to_string()))
+                    .enumerate()
+                    .map(position)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Value::Number(end) => Iterator::Range { next: 0, end },
+            other => {
+                return interrupt!(UnexpectedType {
+                    location: self.location(),
+                    expected: "array, structure, string or number",
+                    found: other.type_name(),
+                });
+            }
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+use crate::flylang::{
+    parser::ast::definables::class::ClassItemVisibility,
+    runtime::{Engine, value},
+    vm::{
+        VirtualMachine,
+        bytecode::{ClassPrototype, Prototype},
+    },
+};
+
+/// The values of the virtual machine (see `runtime::value::Value`)
+pub type Value = value::Value<VirtualMachine>;
+pub type Fields = value::Fields<Value>;
+pub type NativeCallback = value::NativeCallback<VirtualMachine>;
+pub type NativeFunction = value::NativeFunction<VirtualMachine>;
+pub type Method = value::Method<VirtualMachine>;
+pub type Super = value::Super<VirtualMachine>;
+pub type Modified = value::Modified<VirtualMachine>;
+pub type Instance = value::Instance<VirtualMachine>;
+
+impl Engine for VirtualMachine {
+    type Function = Closure;
+    type Class = Class;
+
+    fn function_name(function: &Closure) -> Option<&str> {
+        function.prototype.name.as_deref()
+    }
+    fn class_name(class: &Class) -> &str {
+        class.name()
+    }
+}
+
+/// The variables of a call that the functions defined inside it use
+pub struct Environment {
+    pub cells: RefCell<Vec<Value>>,
+    /// The environment of the call the function has been defined in
+    pub outer: Option<Rc<Environment>>,
+}
+impl Debug for Environment {
+    // Values can reference their own environment (closures), so only the number of variables is displayed.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Environment")
+            .field("cells", &self.cells.borrow().len())
+            .field("outer", &self.outer)
+            .finish()
+    }
+}
+
+/// A function defined in a flylang script
+#[derive(Debug)]
+pub struct Closure {
+    pub prototype: Rc<Prototype>,
+    /// The environment of the call the function has been defined in
+    pub environment: Option<Rc<Environment>>,
+    /// The class whose method has defined the function
+    pub class: Option<Rc<Class>>,
+}
+
+/// A method of a class
+#[derive(Debug)]
+pub struct ClassMethod {
+    pub function: Rc<Closure>,
+    /// The modifiers of the method, applied each time the method is read
+    pub modifiers: Vec<Value>,
+}
+
+/// A class created by a script
+#[derive(Debug)]
+pub struct Class {
+    pub prototype: Rc<ClassPrototype>,
+    pub parents: Vec<Rc<Class>>,
+    /// The ancestors of the class, in the order their methods are resolved (C3 linearization)
+    pub ancestors: Vec<Rc<Class>>,
+    /// Returns the attributes of a new instance, in the order of their declaration
+    pub initializer: Option<Rc<Closure>>,
+    pub constructor: Option<Rc<Closure>>,
+    /// The methods, in the order of `prototype.methods`
+    pub methods: Vec<ClassMethod>,
+    /// The values of the static attributes of the class
+    pub statics: RefCell<Fields>,
+}
+impl Class {
+    pub fn name(&self) -> &str {
+        self.prototype.name.code()
+    }
+
+    /// The method resolution order : the class itself, followed by its ancestors
+    pub fn mro(self: &Rc<Self>) -> Vec<Rc<Class>> {
+        let mut mro = vec![Rc::clone(self)];
+        mro.extend(self.ancestors.iter().cloned());
+        mro
+    }
+
+    /// Get a method defined by this class (and not by its parents)
+    pub fn method(&self, name: &str, is_static: bool) -> Option<&ClassMethod> {
+        let position = self
+            .prototype
+            .methods
+            .iter()
+            .position(|(member, _)| member.name == name && member.is_static == is_static)?;
+        Some(&self.methods[position])
+    }
+
+    /// Get the visibility of an attribute or a method declared by this class (and not by its parents)
+    pub fn declaration(&self, name: &str, is_static: bool) -> Option<ClassItemVisibility> {
+        let prototype = &self.prototype;
+        prototype
+            .attributes
+            .iter()
+            .chain(prototype.methods.iter().map(|(member, _)| member))
+            .find(|member| member.name == name && member.is_static == is_static)
+            .map(|member| member.visibility)
+    }
+}
@@ -1,6 +1,14 @@
 use std::path::Path;
-use crate::{behavior::LangBehavior, cli::LangCLI};
+use crate::{
+    behavior::LangBehavior,
+    cli::{LangCLI, MessageFormat},
+    flylang::errors::{
+        diagnostic,
+        levels::{self, Levels},
+    },
+};
 
+pub mod addons;
 pub mod behavior;
 pub mod cli;
 pub mod flylang;
@@ -15,11 +23,16 @@ pub struct LangRunner {
 impl LangRunner {
     pub fn create() -> Self {
         let cli = LangCLI::parse();
-        let base_behavior_file = cli.behavior_file.unwrap_or("./flylang.toml".into());
+        diagnostic::set_colored(cli.color.enabled());
+        diagnostic::set_json(cli.message_format == MessageFormat::Json);
+        let base_behavior_file = cli.behavior_file.clone().unwrap_or("./flylang.toml".into());
 
-        Self {
-            cli: LangCLI::parse(),
-            behavior: LangBehavior::new_parsed(Path::new(&base_behavior_file)),
-        }
+        // The warnings of the behavior files are denied too
+        let levels = Levels::new().deny_warnings(cli.deny_warnings);
+        levels::set_levels(levels.clone());
+        let behavior = LangBehavior::new_parsed(Path::new(&base_behavior_file));
+        levels::set_levels(levels.configured(&behavior, Path::new(&base_behavior_file)));
+
+        Self { cli, behavior }
     }
 }
@@ -1,27 +1,198 @@
-use std::path::PathBuf;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
-use flylang::{LangRunner, flylang::parser::ast::instructions::Instructions};
+use toml::Value;
+
+use flylang::{
+    LangRunner,
+    addons::Addons,
+    behavior::LangBehavior,
+    cli::{
+        errors::{NotACompiler, UnknownParser},
+        parser::{INTERPRETER, VIRTUAL_MACHINE},
+    },
+    flylang::{
+        FlyLang,
+        analyser::Analyser,
+        cache,
+        checker::Checker,
+        compilers,
+        errors::RaisableErr,
+        formatter::{
+            self, Formatter,
+            errors::{FormattedNotWritten, NotFormatted},
+        },
+        interpreter::Interpreter,
+        module::LangModule,
+        optimizer::Optimizer,
+        parser::ast::Branches,
+        vm::{VirtualMachine, compiler::Compiler, disassembler::disassemble},
+    },
+};
+
+/// Parse a file (or read its cache) and verify its code.
+/// Returns its module and its optimized instructions.
+fn load(file: &str, behavior: &LangBehavior) -> (Rc<LangModule>, Branches) {
+    let module = FlyLang::module(PathBuf::from(file)).into_rc();
+    let nodes = &cache::parse(&module, behavior);
+
+    // Every name and scope error is shown before stopping
+    let analysis = Analyser::new().analyse(nodes);
+    for error in &analysis.errors {
+        error.print();
+    }
+    if let Some(error) = analysis.errors.first() {
+        error.kill();
+    }
+
+    // Every type error is shown before stopping
+    let errors = Checker::new().check(nodes);
+    for error in &errors {
+        error.print();
+    }
+    if let Some(error) = errors.first() {
+        error.kill();
+    }
+
+    let nodes = Optimizer::new().optimize(nodes);
+    (module, nodes)
+}
+
+/// The name of the parser to use : the given one, or the `language.compiler` behavior
+fn selected(parser: &Option<String>, behavior: &LangBehavior) -> Option<String> {
+    parser
+        .clone()
+        .or_else(|| match behavior.get("language.compiler") {
+            Some(Value::String(compiler)) => Some(compiler),
+            _ => None,
+        })
+}
 
 fn main() {
     flylang::utils::env::extend_env();
     let runner = LangRunner::create();
 
     match &runner.cli.command {
-        flylang::cli::LangCommands::Exec { entrypoint, parser } => {
+        flylang::cli::LangCommands::Exec {
+            entrypoint,
+            parser,
+            disassemble: disassembled,
+        } => {
+            let file = entrypoint.clone().expect("Default entry point not set.");
+            let (module, nodes) = load(&file, &runner.behavior);
+
+            let status = match selected(parser, &runner.behavior).as_deref() {
+                None | Some(INTERPRETER) => {
+                    Interpreter::with_behavior(&runner.behavior)
+                        .run(&nodes)
+                        .unwrap_or_else(|e| e.raise());
+                    return;
+                }
+                Some(VIRTUAL_MACHINE) => {
+                    let program = Compiler::compile_with_behavior(&nodes, &runner.behavior)
+                        .unwrap_or_else(|e| e.raise());
+                    if *disassembled {
+                        print!("{}", disassemble(&program));
+                    }
+                    VirtualMachine::new()
+                        .run(&program)
+                        .unwrap_or_else(|e| e.raise());
+                    return;
+                }
+                Some(name) => match compilers::find(name) {
+                    Some(compiler) => compilers::execute(&*compiler, &module, &nodes),
+                    None => {
+                        let addons =
+                            Addons::from_behavior(&runner.behavior, "language.compilers_folders");
+                        let Some(addon) = addons.find(name) else {
+                            UnknownParser(name.to_string()).raise();
+                        };
+
+                        addon.execute(&module, &nodes)
+                    }
+                },
+            };
+            std::process::exit(status.unwrap_or_else(|e| e.raise()));
+        }
+        flylang::cli::LangCommands::Compile {
+            entrypoint,
+            parser,
+            output,
+        } => {
             let file = entrypoint.clone().expect("Default entry point not set.");
+            let name = match selected(parser, &runner.behavior) {
+                None => NotACompiler(INTERPRETER.to_string()).raise(),
+                Some(name) if name == INTERPRETER || name == VIRTUAL_MACHINE => {
+                    NotACompiler(name).raise()
+                }
+                Some(name) => name,
+            };
+            let (module, nodes) = load(&file, &runner.behavior);
+
+            let written = match compilers::find(&name) {
+                Some(compiler) => compilers::write(&*compiler, &module, &nodes, Path::new(output)),
+                None => {
+                    let addons =
+                        Addons::from_behavior(&runner.behavior, "language.compilers_folders");
+                    let Some(addon) = addons.find(&name) else {
+                        UnknownParser(name).raise();
+                    };
+
+                    addon.write(&module, &nodes, Path::new(output))
+                }
+            };
+            let written = written.unwrap_or_else(|e| e.raise());
+            println!("Compiled <{:?}> to <{:?}>.", file, written);
+        }
+        flylang::cli::LangCommands::Fmt { files, check } => {
+            let mut unformatted = vec![];
 
-            let mut parser = flylang::flylang::FlyLang::parser(PathBuf::from(file));
-            let nodes = parser.parse();
-            dbg!(nodes);
+            for path in files
+                .iter()
+                .flat_map(|file| formatter::sources(Path::new(file)))
+            {
+                let module = FlyLang::module(path.clone()).into_rc();
+                let formatted = match Formatter::new().format(&module) {
+                    Ok(formatted) => formatted,
+                    Err(diagnostics) => {
+                        diagnostics.raise();
+                        continue;
+                    }
+                };
+                if formatted == module.code() {
+                    continue;
+                }
 
-            for node in nodes {
-                if let Instructions::Use(pkg) = node.kind() {
-                    dbg!(pkg, pkg.path(&runner.behavior));
+                if *check {
+                    unformatted.push(path);
+                } else if let Err(error) = fs::write(&path, formatted) {
+                    FormattedNotWritten {
+                        path,
+                        reason: error.to_string(),
+                    }
+                    .raise();
+                } else {
+                    println!("Formatted <{:?}>.", path);
                 }
             }
+
+            if !unformatted.is_empty() {
+                NotFormatted(unformatted).raise();
+            }
+        }
+        flylang::cli::LangCommands::Pkg { action: _ } => todo!(),
+        flylang::cli::LangCommands::Parser { action, directory } => {
+            let installed = match directory {
+                Some(directory) => Addons::new(vec![PathBuf::from(directory)]),
+                None => Addons::from_behavior(&runner.behavior, "language.compilers_folders"),
+            };
+            let sources = Addons::from_behavior(&runner.behavior, "language.compilers_sources");
+
+            action.run(&installed, &sources);
         }
-        flylang::cli::LangCommands::Pkg { action } => todo!(),
-        flylang::cli::LangCommands::Parser { action, directory } => todo!(),
         flylang::cli::LangCommands::Behavior {} => {
             dbg!(&runner.behavior);
             todo!()
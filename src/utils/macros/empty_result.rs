@@ -5,9 +5,11 @@ macro_rules! ok {
 }
 pub(crate) use ok;
 
+#[allow(unused_macros)]
 macro_rules! err {
     () => {
         Err(())
     };
 }
+#[allow(unused_imports)]
 pub(crate) use err;
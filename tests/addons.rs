@@ -0,0 +1,180 @@
+use flylang::{
+    addons::{Addon, Addons},
+    behavior::LangBehavior,
+    flylang::{FlyLang, parser::ast::json},
+};
+
+#[cfg(test)]
+pub mod tests {
+    use std::{fs, path::PathBuf};
+
+    use serde_json::Value;
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-addons");
+
+    fn to_json(script: &str) -> Value {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        json::branches(parser.parse())
+    }
+
+    /// Create a folder of add-ons, each one with the given manifest
+    fn addons_folder(test: &str, manifests: &[(&str, &str)]) -> PathBuf {
+        let folder = std::env::temp_dir()
+            .join(format!("flylang-tests-addons-{}", std::process::id()))
+            .join(test);
+        let _ = fs::remove_dir_all(&folder);
+
+        for (name, manifest) in manifests {
+            fs::create_dir_all(folder.join(name)).unwrap();
+            fs::write(folder.join(name).join(Addon::MANIFEST), manifest).unwrap();
+        }
+        folder
+    }
+
+    #[test]
+    fn json_format() {
+        let branches = to_json(r#"a: {k: 1}; each @outer(a, v, std.out("&(v)!")); if(a, 1, 2)"#);
+        let branches = branches.as_array().unwrap();
+        assert_eq!(branches.len(), 3);
+
+        let variable = &branches[0];
+        assert_eq!(variable["type"], "Variable");
+        assert_eq!(variable["target"]["name"], "a");
+        assert_eq!(variable["location"]["start"], 0);
+        // Words used as keys are strings
+        let entry = &variable["value"]["entries"][0];
+        assert_eq!(entry["key"]["parts"][0], "k");
+        assert_eq!(entry["value"]["value"], 1.0);
+
+        let each = &branches[1];
+        assert_eq!(each["type"], "Each");
+        assert_eq!(each["scope"]["name"], "outer");
+        assert_eq!(each["item"], "v");
+        let call = &each["body"][0];
+        assert_eq!(call["type"], "Call");
+        assert_eq!(call["callee"]["type"], "Read");
+        assert_eq!(call["callee"]["property"]["parts"][0], "out");
+        let parts = &call["arguments"][0]["parts"];
+        assert_eq!(parts[0]["name"], "v");
+        assert_eq!(parts[1], "!");
+
+        assert_eq!(branches[2]["type"], "Ternary");
+    }
+
+    #[test]
+    fn json_definitions() {
+        let branches = to_json(
+            "fn add -> number (a -> number, b, return a + b); cs B(A, fn(x, self.x: x), #(private) x: 0; fn get(return @< self.x))",
+        );
+
+        let function = &branches[0];
+        assert_eq!(function["type"], "Function");
+        assert_eq!(function["name"], "add");
+        assert_eq!(function["returns"][0], "number");
+        assert_eq!(function["arguments"][0]["typing"][0], "number");
+        assert_eq!(function["arguments"][1]["typing"], Value::Null);
+        let value = &function["body"][0]["value"];
+        assert_eq!(value["operator"], "add");
+
+        let class = &branches[1];
+        assert_eq!(class["type"], "Class");
+        assert_eq!(class["parents"][0], "A");
+        assert_eq!(class["constructor"]["arguments"][0]["name"], "x");
+        assert_eq!(class["attributes"][0]["visibility"], "private");
+        assert_eq!(class["attributes"][0]["definition"]["target"]["name"], "x");
+        let method = &class["methods"][0]["definition"];
+        assert_eq!(method["name"], "get");
+        assert_eq!(method["body"][0]["scope"]["skip"], 1);
+    }
+
+    #[test]
+    fn discovery() {
+        let folder = addons_folder(
+            "discovery",
+            &[
+                (
+                    "first",
+                    r#"[addon]
+                    description = "The first add-on"
+                    command = ["cat"]"#,
+                ),
+                (
+                    "second",
+                    r#"[addon]
+                    name = "renamed"
+                    command = ["cat"]"#,
+                ),
+                ("invalid", "[addon]\ncommand = []"),
+                // The names leaving the add-ons folder are not valid
+                ("parent", "[addon]\nname = \"../../x\"\ncommand = [\"cat\"]"),
+                (
+                    "absolute",
+                    "[addon]\nname = \"/tmp/x\"\ncommand = [\"cat\"]",
+                ),
+                ("nested", "[addon]\nname = \"a/b\"\ncommand = [\"cat\"]"),
+                ("current", "[addon]\nname = \".\"\ncommand = [\"cat\"]"),
+            ],
+        );
+        let addons = Addons::new(vec![folder.clone(), folder.join("missing")]);
+
+        let names: Vec<String> = addons.list().into_iter().map(|addon| addon.name).collect();
+        assert_eq!(names, vec!["first", "renamed"]);
+        assert!(addons.find("renamed").is_some());
+        assert!(addons.find("invalid").is_none());
+        assert_eq!(addons.search("FIRST ADD").len(), 1);
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn folders_from_behavior() {
+        let folder = addons_folder("behavior", &[]);
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(
+            folder.join("flylang.toml"),
+            "[language]\ncompilers_folders = [\"addons\", \"/absolute/addons\"]\ncompilers_sources = \"../sources\"\n",
+        )
+        .unwrap();
+        let behavior = LangBehavior::new_parsed(&folder.join("flylang.toml"));
+
+        // The relative folders are relative to the behavior file
+        assert_eq!(
+            Addons::from_behavior(&behavior, "language.compilers_folders").folders(),
+            &[folder.join("addons"), PathBuf::from("/absolute/addons")]
+        );
+        assert_eq!(
+            Addons::from_behavior(&behavior, "language.compilers_sources").folders(),
+            &[folder.join("../sources")]
+        );
+    }
+
+    #[test]
+    fn compilation() {
+        let folder = addons_folder(
+            "compilation",
+            &[
+                ("echo", "[addon]\ncommand = [\"cat\"]"),
+                ("failing", "[addon]\ncommand = [\"false\"]"),
+            ],
+        );
+        let addons = Addons::new(vec![folder.clone()]);
+        let mut parser = FlyLang::anonymous_parser("a: 1", SCRIPTS_LABEL);
+        let branches = parser.parse().clone();
+
+        // The add-on receives the module, and its output is the generated code
+        let echo = addons.find("echo").unwrap();
+        let output = echo.compile(parser.module(), &branches).unwrap();
+        let input: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(input["version"], json::FORMAT_VERSION);
+        assert_eq!(input["module"]["code"], "a: 1");
+        assert_eq!(input["branches"][0]["type"], "Variable");
+
+        let failing = addons.find("failing").unwrap();
+        let error = failing.compile(parser.module(), &branches).unwrap_err();
+        assert_eq!(error._code(), 3);
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
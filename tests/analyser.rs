@@ -0,0 +1,211 @@
+use flylang::flylang::{
+    FlyLang,
+    analyser::{Analyser, Analysis},
+    errors::codes::ErrorId,
+};
+
+#[cfg(test)]
+pub mod tests {
+    use flylang::flylang::analyser::symbols::{NamespaceKind, SymbolKind};
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-analyser");
+
+    fn analyse(script: &str) -> Analysis {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        Analyser::new().analyse(parser.parse())
+    }
+
+    /// Analyse the script and return the codes of the found errors
+    fn errors(script: &str) -> Vec<i32> {
+        analyse(script)
+            .errors
+            .iter()
+            .map(|error| error._code())
+            .collect()
+    }
+
+    /// Get the kind of the symbols named `name`, and the positions referring to each of them
+    fn symbols(analysis: &Analysis, name: &str) -> Vec<(SymbolKind, Vec<usize>)> {
+        let table = &analysis.symbols;
+        (0..table.symbols().len())
+            .filter(|id| table.symbol(*id).name == name)
+            .map(|id| {
+                let positions = table.references(id).iter().map(|r| r.start).collect();
+                (table.symbol(id).kind, positions)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolution() {
+        let analysis = analyse("a: 1; b: a + 1; a: b; c:: a");
+        assert!(analysis.errors.is_empty());
+
+        assert_eq!(
+            symbols(&analysis, "a"),
+            vec![(SymbolKind::Variable, vec![0, 9, 16, 26])]
+        );
+        assert_eq!(
+            symbols(&analysis, "c"),
+            vec![(SymbolKind::Constant, vec![22])]
+        );
+        assert_eq!(symbols(&analysis, "std")[0].0, SymbolKind::Builtin);
+    }
+
+    #[test]
+    fn functions() {
+        // The function is analysed once its module is, and it has its own namespace
+        let analysis = analyse("fn f(x, return x + later); later: 1; x: 2");
+        assert!(analysis.errors.is_empty());
+
+        let x = symbols(&analysis, "x");
+        assert_eq!(x.len(), 2);
+        assert!(x.contains(&(SymbolKind::Argument, vec![5, 15])));
+        assert!(x.contains(&(SymbolKind::Variable, vec![37])));
+        assert_eq!(
+            symbols(&analysis, "later"),
+            vec![(SymbolKind::Variable, vec![19, 27])]
+        );
+        assert_eq!(symbols(&analysis, "f")[0].0, SymbolKind::Function);
+
+        let table = &analysis.symbols;
+        let function = table
+            .namespaces()
+            .iter()
+            .position(|namespace| namespace.kind == NamespaceKind::Function)
+            .unwrap();
+        let names: Vec<&str> = table
+            .declared_in(function)
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["x"]);
+    }
+
+    #[test]
+    fn classes() {
+        let analysis =
+            analyse("cs A(fn(v, self.v: v), v: 0; fn get(return self.v)); cs B(A); new B(1)");
+        assert!(analysis.errors.is_empty());
+
+        assert_eq!(
+            symbols(&analysis, "A"),
+            vec![(SymbolKind::Class, vec![3, 58])]
+        );
+        assert_eq!(symbols(&analysis, "get")[0].0, SymbolKind::Member);
+        assert!(
+            symbols(&analysis, "self")
+                .iter()
+                .all(|(kind, _)| *kind == SymbolKind::Receiver)
+        );
+    }
+
+    #[test]
+    fn loops() {
+        // In a loop, a variable can be read before being defined
+        assert!(errors("i: 0; while(i < 2, if(i, std.out(last)); last: i; i +: 1)").is_empty());
+
+        let analysis = analyse("each({1, 2}, item, index, std.out(item + index))");
+        assert!(analysis.errors.is_empty());
+        assert_eq!(symbols(&analysis, "item")[0].0, SymbolKind::Binding);
+        assert_eq!(symbols(&analysis, "index")[0].0, SymbolKind::Binding);
+    }
+
+    #[test]
+    fn undefined_variables() {
+        assert_eq!(errors("a: b"), vec![1]);
+        assert_eq!(errors("fn f(return missing); f()"), vec![1]);
+        assert_eq!(errors("while(false, std.out(never))"), vec![1]);
+        assert_eq!(errors("cs A(missing)"), vec![1]);
+        // The words used as keys are not variables
+        assert_eq!(errors("{key: value}"), vec![1]);
+    }
+
+    #[test]
+    fn members() {
+        let inaccessible = |script| {
+            analyse(script)
+                .errors
+                .iter()
+                .filter(|error| error._id() == ErrorId::InaccessibleMember)
+                .map(|error| error._location().unwrap().range().start)
+                .collect::<Vec<_>>()
+        };
+
+        // Classes and instances assigned once
+        assert_eq!(
+            inaccessible("cs A(#(private) s: 1); a: new A(); a.s"),
+            vec![37]
+        );
+        assert_eq!(
+            inaccessible("cs A(#(private) s: 1); a: new A(); a.s: 2"),
+            vec![37]
+        );
+        assert_eq!(inaccessible("cs A(#(private) s: 1); (new A()).s"), vec![33]);
+        assert_eq!(inaccessible("cs A(#(private, static) n: 1); A.n"), vec![33]);
+        assert_eq!(
+            inaccessible("cs A(#(protected) s: 1); cs C(fn f(return (new A()).s))"),
+            vec![52]
+        );
+        // From the methods of the class (and of its children for the protected members)
+        assert!(inaccessible("cs A(#(private) s: 1; fn f(return (new A()).s))").is_empty());
+        assert!(
+            inaccessible("cs A(#(private, static) n: 1; #(static) fn f(return A.n))").is_empty()
+        );
+        assert!(inaccessible("cs A(#(protected) s: 1); cs B(A, fn f(return self.s))").is_empty());
+        assert_eq!(
+            inaccessible("cs A(#(private) s: 1); cs B(A, fn f(return super.s))"),
+            vec![49]
+        );
+        assert_eq!(
+            inaccessible("cs P(#(private) s: 1); cs A(P, fn f(return self.s))"),
+            vec![48]
+        );
+
+        // A child class can declare the member again
+        assert!(
+            inaccessible("cs P(#(private) s: 1); cs A(P, fn f(return self.s)); cs B(A, s: 2)")
+                .is_empty()
+        );
+        // The value is not known
+        assert!(inaccessible("cs A(#(private) s: 1); a: new A(); a: 3; a.s").is_empty());
+        assert!(inaccessible("cs A(#(private) s: 1); fn f(a, return a.s)").is_empty());
+        assert!(inaccessible("cs A(#(private) s: 1); cs A(s: 1); a: new A(); a.s").is_empty());
+        assert!(
+            inaccessible("fn m(def, args, return def); #(m) cs A(#(private) s: 1); A.s").is_empty()
+        );
+    }
+
+    #[test]
+    fn imports() {
+        let analysis = analyse(r#"use (a, b) from "lib"; use "lib" in lib; std.out(a, b, lib)"#);
+        assert!(analysis.errors.is_empty());
+        assert_eq!(symbols(&analysis, "a")[0].0, SymbolKind::Import);
+        assert_eq!(symbols(&analysis, "lib")[0].0, SymbolKind::Import);
+
+        // The imported names cannot be known
+        assert!(errors(r#"use "lib"; anything"#).is_empty());
+    }
+
+    #[test]
+    fn breakers() {
+        assert!(errors("fn f(while(true, if(true, stop; pass)); return 1)").is_empty());
+        assert_eq!(errors("return 1"), vec![4]);
+        assert_eq!(errors("stop"), vec![4]);
+        assert_eq!(errors("pass"), vec![4]);
+        // A breaker cannot go outside of the function it is in
+        assert_eq!(errors("while(true, fn f(stop))"), vec![4]);
+    }
+
+    #[test]
+    fn scope_targets() {
+        assert!(errors("while @outer(true, each(3, i, stop @outer))").is_empty());
+        assert!(errors("fn f @f(each(3, i, return @f i))").is_empty());
+        assert!(errors("while(true, while(true, stop @<))").is_empty());
+
+        assert_eq!(errors("while(true, stop @unknown)"), vec![2]);
+        assert_eq!(errors("while(true, stop @<<)"), vec![2]);
+        assert_eq!(errors("fn f(if @c(true, while(true, return @c)))"), vec![3]);
+    }
+}
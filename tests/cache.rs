@@ -0,0 +1,129 @@
+use flylang::{
+    behavior::LangBehavior,
+    flylang::{FlyLang, cache, module::LangModule, parser::ast::instructions::Instructions},
+};
+
+#[cfg(test)]
+pub mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-cache");
+
+    /// Create a project folder with the given files, and its behavior (enabling the cache)
+    pub(crate) fn project(test: &str, files: &[(&str, &str)]) -> (PathBuf, LangBehavior) {
+        let folder = std::env::temp_dir()
+            .join(format!("flylang-tests-cache-{}", std::process::id()))
+            .join(test);
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+
+        for (name, content) in files {
+            fs::write(folder.join(name), content).unwrap();
+        }
+        fs::write(folder.join("flylang.toml"), "[language]\ncache=true\n").unwrap();
+
+        let behavior = LangBehavior::new_parsed(&folder.join("flylang.toml"));
+        (folder, behavior)
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut parser = FlyLang::anonymous_parser(
+            r#"
+            use {a, b} from "./other" in other;
+            x -> number: 1 + (2) - -3 ** 2 % 2 // 1;
+            A:: {key: "a &(x) b", 1: {x, 2}};
+            fn f -> number @scope(a -> number ? empty, b, if(true ~ a >= b, !b; return @scope a) else(return @< b));
+            cs C(A, B, #(private, static) count: 0; #(deco(1)) fn method(stop); fn c(pass));
+            each @outer(A, value, key, while(value ~ x, stop @outer));
+            until(x > 10, iteration, x +: 1);
+            z: new C(1).method();
+            z.(x): y.0; if(x, 1) else if(A.key.0, 2) else (3)
+            "#,
+            SCRIPTS_LABEL,
+        );
+        let module = parser.module().clone();
+        let branches = parser.parse();
+
+        let bytes = cache::encode(&module, branches);
+        let decoded = cache::decode(&module, &bytes).expect("The cache is valid");
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", branches));
+    }
+
+    #[test]
+    fn stale_caches() {
+        let mut parser = FlyLang::anonymous_parser("x: 1; std.out(x)", SCRIPTS_LABEL);
+        let module = parser.module().clone();
+        let bytes = cache::encode(&module, parser.parse());
+
+        // The code of the module changed
+        let other = FlyLang::anonymous_module("x: 2; std.out(x)", SCRIPTS_LABEL).into_rc();
+        assert!(cache::decode(&other, &bytes).is_none());
+
+        // The cache is corrupted
+        assert!(cache::decode(&module, &bytes[..bytes.len() - 1]).is_none());
+        assert!(cache::decode(&module, &[bytes.as_slice(), &[0]].concat()).is_none());
+        assert!(cache::decode(&module, b"FLYC").is_none());
+    }
+
+    #[test]
+    fn cached_modules() {
+        let (folder, behavior) = project("modules", &[("main.fly", "x: 1; std.out(x)")]);
+        let path = folder.join("main.fly");
+
+        let module = LangModule::new(path.clone()).unwrap().into_rc();
+        assert_eq!(cache::location(&module), Some(folder.join("main.flyc")));
+        assert!(cache::read(&module).is_none());
+
+        let parsed = cache::parse(&module, &behavior);
+        let cached = cache::read(&module).expect("The cache has been written");
+        assert_eq!(format!("{:?}", cached), format!("{:?}", parsed));
+
+        // The cache is rebuilt once the module changes
+        fs::write(&path, "x: 2; std.out(x); std.out(x)").unwrap();
+        let module = LangModule::new(path).unwrap().into_rc();
+        assert!(cache::read(&module).is_none());
+        assert_eq!(cache::parse(&module, &behavior).len(), 3);
+        assert_eq!(cache::read(&module).map(|branches| branches.len()), Some(3));
+    }
+
+    #[test]
+    fn disabled_cache() {
+        let (folder, _) = project("disabled", &[("main.fly", "x: 1")]);
+        let module = LangModule::new(folder.join("main.fly")).unwrap().into_rc();
+
+        cache::parse(&module, &LangBehavior::default());
+        assert!(!folder.join("main.flyc").exists());
+
+        // Anonymous modules are never cached
+        let anonymous = FlyLang::anonymous_module("x: 1", SCRIPTS_LABEL).into_rc();
+        assert_eq!(cache::location(&anonymous), None);
+    }
+
+    #[test]
+    fn packages() {
+        let (folder, behavior) = project(
+            "packages",
+            &[
+                ("main.fly", r#"use "./other.fly""#),
+                ("other.fly", "x: 1; y: 2"),
+            ],
+        );
+        let main = LangModule::new(folder.join("main.fly")).unwrap().into_rc();
+        let branches = cache::parse(&main, &behavior);
+        let Instructions::Use(package) = branches[0].kind() else {
+            panic!("Expected a package");
+        };
+
+        let (module, instructions) = package.load(&behavior).unwrap().unwrap();
+        assert_eq!(module.code(), "x: 1; y: 2");
+        assert_eq!(instructions.len(), 2);
+        assert!(folder.join("other.flyc").exists());
+
+        // The package is read from its cache
+        let (_, cached) = package.load(&behavior).unwrap().unwrap();
+        assert_eq!(format!("{:?}", cached), format!("{:?}", instructions));
+    }
+}
@@ -0,0 +1,143 @@
+use flylang::flylang::{FlyLang, checker::Checker};
+
+#[cfg(test)]
+pub mod tests {
+    use flylang::flylang::parser::ast::{
+        definables::Definables, expressions::Expressions, instructions::Instructions,
+    };
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-checker");
+
+    /// Check the script and return the codes of the found errors
+    fn check(script: &str) -> Vec<i32> {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        Checker::new()
+            .check(parser.parse())
+            .iter()
+            .map(|error| error._code())
+            .collect()
+    }
+
+    #[test]
+    fn annotations() {
+        let parsed = FlyLang::anonymous_parser(
+            "count -> number ? empty: 1; fn add -> number (a -> number, b, return a + b)",
+            SCRIPTS_LABEL,
+        )
+        .parse()
+        .to_vec();
+        assert_eq!(parsed.len(), 2);
+
+        let Instructions::ValueOf(Expressions::Defined(Definables::Variable(variable))) =
+            parsed[0].kind()
+        else {
+            panic!("Expected a variable definition");
+        };
+        assert_eq!(variable.emplacement.location().code(), "count");
+        assert_eq!(
+            variable.typing.as_ref().unwrap().kind().names(),
+            vec!["number", "empty"]
+        );
+
+        let Instructions::ValueOf(Expressions::Defined(Definables::Function(function))) =
+            parsed[1].kind()
+        else {
+            panic!("Expected a function definition");
+        };
+        assert_eq!(
+            function.returns.as_ref().unwrap().kind().names(),
+            vec!["number"]
+        );
+        assert_eq!(function.arguments.len(), 2);
+        assert_eq!(function.arguments[0].kind().name.location().code(), "a");
+        assert!(function.arguments[0].kind().typing.is_some());
+        assert!(function.arguments[1].kind().typing.is_none());
+    }
+
+    #[test]
+    fn valid_types() {
+        assert_eq!(
+            check("a -> number: 1; a +: 2; b -> string: \"b\" + a"),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            check(
+                "fn add -> number (a -> number, b -> number, return a + b); c -> number: add(1, 2)"
+            ),
+            Vec::<i32>::new()
+        );
+        assert_eq!(check("a -> number ? empty: (); a: 1"), Vec::<i32>::new());
+        // Classes can be used as types, and instances of child classes are accepted
+        assert_eq!(
+            check("cs A(); cs B(A); fn f(a -> A, return a); f(new B()); b -> A: new B()"),
+            Vec::<i32>::new()
+        );
+        // Untyped values are accepted everywhere
+        assert_eq!(
+            check("fn f(a -> number, a); x: \"a\"; f(x); y -> string: f(1)"),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn mismatches() {
+        assert_eq!(check("a -> number: \"a\""), vec![1]);
+        // The type is kept when the variable is assigned again
+        assert_eq!(check("a -> number: 1; a: true"), vec![1]);
+        assert_eq!(check("fn f(a -> string, a); f(1)"), vec![1]);
+        assert_eq!(check("fn f -> number (return \"a\")"), vec![1]);
+        assert_eq!(check("fn f -> number (a: 1; return); f()"), vec![1]);
+        assert_eq!(check("cs A(); cs B(); a -> A: new B()"), vec![1]);
+        // Every error is reported
+        assert_eq!(check("a -> number: \"a\"; b -> string: 1"), vec![1, 1]);
+    }
+
+    #[test]
+    fn classes() {
+        let account = r#"
+            cs Account(
+                fn(amount -> number, self.balance: amount),
+                balance -> number: 0;
+                fn get -> number (return self.balance);
+            );
+        "#;
+        assert_eq!(
+            check(&format!("{} a -> Account: new Account(1)", account)),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            check(&format!("{} a: new Account(\"1\")", account)),
+            vec![1]
+        );
+        assert_eq!(
+            check(&format!(
+                "{} a -> Account: new Account(1); s -> string: a.get()",
+                account
+            )),
+            vec![1]
+        );
+        assert_eq!(
+            check(&format!(
+                "{} a -> Account: new Account(1); a.balance: true",
+                account
+            )),
+            vec![1]
+        );
+        assert_eq!(check("cs A(value -> string: 1)"), vec![1]);
+    }
+
+    #[test]
+    fn unknown_type() {
+        assert_eq!(check("a -> integer: 1"), vec![2]);
+        assert_eq!(check("fn f(a -> Missing, a)"), vec![2]);
+    }
+
+    #[test]
+    fn invalid_operands() {
+        assert_eq!(check("1 - \"a\""), vec![3]);
+        assert_eq!(check("a -> boolean: true; -a"), vec![3]);
+        assert_eq!(check("\"a\" + 1; x: 1; x - \"a\""), Vec::<i32>::new());
+    }
+}
@@ -0,0 +1,196 @@
+use flylang::flylang::{FlyLang, compilers, interpreter::Interpreter};
+
+#[cfg(test)]
+pub mod tests {
+    use std::{
+        fs,
+        path::Path,
+        process::{Command, Output},
+    };
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-compilers");
+
+    /// The built-in compilers, with a tool executing the code they generate
+    const COMPILERS: [&str; 4] = ["js", "py", "c", "lua"];
+
+    /// Generate the code of a script with the given compiler
+    pub fn compile(compiler: &str, script: &str) -> String {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        let branches = parser.parse().clone();
+        compilers::find(compiler)
+            .unwrap()
+            .compile(parser.module(), &branches)
+            .unwrap_or_else(|e| panic!("{}", e._message()))
+    }
+
+    /// Returns if a tool is installed. When the `FLYLANG_TEST_TOOLS` environment variable is set (as in the CI),
+    /// a missing tool fails the test instead of skipping it.
+    fn installed(tool: &str, flag: &str) -> bool {
+        let installed = Command::new(tool).arg(flag).output().is_ok();
+        assert!(
+            installed || std::env::var_os("FLYLANG_TEST_TOOLS").is_none(),
+            "{} is not installed",
+            tool
+        );
+        installed
+    }
+
+    /// Execute a generated file, with the tool of its compiler.
+    /// Returns `None` when the tool is not installed.
+    fn launch(compiler: &str, file: &Path) -> Option<Output> {
+        match compiler {
+            "js" => installed("node", "--version")
+                .then(|| Command::new("node").arg(file).output().unwrap()),
+            "py" => installed("python3", "--version")
+                .then(|| Command::new("python3").arg(file).output().unwrap()),
+            "lua" => {
+                let lua = std::env::var("LUA").unwrap_or_else(|_| String::from("lua"));
+                installed(&lua, "-v").then(|| Command::new(&lua).arg(file).output().unwrap())
+            }
+            "c" => {
+                let cc = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+                if !installed(&cc, "--version") {
+                    return None;
+                }
+                // The generated code and its runtime library are compiled together
+                let program = file.with_extension(std::env::consts::EXE_EXTENSION);
+                let output = Command::new(&cc)
+                    .args(["-std=c99", "-Wall", "-Werror", "-o"])
+                    .arg(&program)
+                    .arg(file)
+                    .arg(file.with_file_name("flylang.c"))
+                    .arg("-lm")
+                    .output()
+                    .unwrap();
+                assert!(
+                    output.status.success(),
+                    "{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Some(Command::new(program).output().unwrap())
+            }
+            _ => unreachable!("no tool for the {} compiler", compiler),
+        }
+    }
+
+    /// Compile and execute a script with the given compiler.
+    /// Returns `None` when the tool executing the generated code is not installed.
+    pub fn execute(compiler: &str, test: &str, script: &str) -> Option<Output> {
+        let folder = std::env::temp_dir()
+            .join(format!("flylang-tests-{}-{}", compiler, std::process::id()))
+            .join(test);
+        let _ = fs::remove_dir_all(&folder);
+
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        let branches = parser.parse().clone();
+        let file = compilers::write(
+            compilers::find(compiler).unwrap().as_ref(),
+            parser.module(),
+            &branches,
+            &folder,
+        )
+        .unwrap_or_else(|e| panic!("{}", e._message()));
+
+        launch(compiler, &file)
+    }
+
+    /// Compile and execute a script with the given compiler, returning what it printed
+    pub fn run(compiler: &str, test: &str, script: &str) -> Option<String> {
+        let output = execute(compiler, test, script)?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Some(String::from_utf8(output.stdout).unwrap())
+    }
+
+    /// Execute a script with the interpreter, returning what `std.out` would print for the given variables.
+    /// Returns `None` when the script fails.
+    fn interpret(script: &str, variables: &[&str]) -> Option<String> {
+        let mut displayed = Vec::new();
+        for variable in variables {
+            let mut parser =
+                FlyLang::anonymous_parser(&format!("{}; {}", script, variable), SCRIPTS_LABEL);
+            displayed.push(Interpreter::new().run(parser.parse()).ok()?.to_string());
+        }
+        Some(displayed.join(" ") + "\n")
+    }
+
+    /// Check that every compiler prints what the interpreter gives, or fails when it fails
+    fn compare(test: &str, scripts: &[(&str, &[&str])]) {
+        for (index, (script, variables)) in scripts.iter().enumerate() {
+            let expected = interpret(script, variables);
+            let printed = format!("{}; std.out({})", script, variables.join(", "));
+
+            for compiler in COMPILERS {
+                let test = format!("compare_{}_{}", test, index);
+                let Some(output) = execute(compiler, &test, &printed) else {
+                    continue;
+                };
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                match &expected {
+                    Some(expected) => {
+                        assert!(
+                            output.status.success(),
+                            "{} : {} : {}",
+                            compiler,
+                            script,
+                            stderr
+                        );
+                        assert_eq!(
+                            String::from_utf8_lossy(&output.stdout),
+                            *expected,
+                            "{} : {}",
+                            compiler,
+                            script
+                        );
+                    }
+                    None => assert!(!output.status.success(), "{} : {}", compiler, script),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn loop_variables() {
+        compare(
+            "loop_variables",
+            &[
+                ("n: 0; while(n < 3, i, n +: 1)", &["i", "n"]),
+                (r#"each({"a", "b"}, item, index, ())"#, &["item", "index"]),
+                ("t: 0; each({1, 2, 3}, v, k, t +: v)", &["t", "v", "k"]),
+                ("m: 5; while(m > 0, i, m -: 2)", &["i", "m"]),
+            ],
+        );
+    }
+
+    #[test]
+    fn constants() {
+        compare(
+            "constants",
+            &[
+                ("A:: 1; B: A + 1", &["A", "B"]),
+                ("A:: 1; fn f(b: A; return b); c: f()", &["A", "c"]),
+                ("A:: 1; A: 2", &["A"]),
+                ("A:: 1; fn f(A: 2); f()", &["A"]),
+                ("A:: 1; fn A()", &["A"]),
+                ("A:: 1; each({1}, A, ())", &["A"]),
+            ],
+        );
+    }
+
+    #[test]
+    fn non_ascii_strings() {
+        // The strings are made of characters, not of the bytes encoding them
+        compare(
+            "non_ascii_strings",
+            &[(
+                r#"s: "héllo"; n: std.len(s); c: s.1; l: (); each(s, item, index, if(index = 2, l: item))"#,
+                &["n", "c", "l"],
+            )],
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use flylang::flylang::{
+    FlyLang,
+    cst::{Cst, CstNode},
+    lexer::{
+        Lexer,
+        tokens::{Tokens, Trivia},
+    },
+};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-cst");
+
+    fn cst(script: &str) -> Cst {
+        Cst::new(&FlyLang::anonymous_module(script, SCRIPTS_LABEL).into_rc())
+    }
+
+    #[test]
+    fn same_code() {
+        let scripts = [
+            "",
+            "   \n\t",
+            "|| only a comment",
+            "x: 1;;  ; y: 2 ;\n",
+            "|| header\nuse {a, b} from \"./other\" in other; |inline|x -> number   :1+(2)- -3**2%2 // 1;\n",
+            "A::{key:\"a &( x |c| ) b\",1:{x,2}}; y + : - z; z +: 1",
+            "fn f @scope(a, if(true, return @scope a) else(return @< b)); each @-1(x, v, stop @<<)",
+            "cs C(A, #(private, static) count:0; fn c(pass));\r\n  new C().c()  |unclosed comment",
+        ];
+
+        for script in scripts {
+            assert_eq!(cst(script).code(), script);
+        }
+
+        let code = std::fs::read_to_string("tests/scripts/misc.fly").unwrap();
+        assert_eq!(cst(&code).to_string(), code);
+    }
+
+    #[test]
+    fn trivia() {
+        let tree = cst("x: 1 || comment\n;; |block| y");
+        let tokens = tree.tokens();
+        let kinds: Vec<Vec<String>> = tokens
+            .iter()
+            .map(|token| {
+                token
+                    .trivia()
+                    .iter()
+                    .map(|trivia| format!("{:?}", trivia.kind()))
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                vec![],
+                vec![],
+                vec!["Whitespace"],
+                vec!["Whitespace", "LineComment"],
+                vec![
+                    "EndOfInstruction",
+                    "Whitespace",
+                    "BlockComment",
+                    "Whitespace"
+                ],
+            ]
+        );
+        assert_eq!(tokens[3].trivia()[1].location().code(), "|| comment\n");
+        assert!(tree.trailing().is_empty());
+
+        let tree = cst("x  ");
+        assert!(matches!(tree.trailing()[0].kind(), Trivia::Whitespace));
+        assert_eq!(tree.trailing()[0].location().code(), "  ");
+    }
+
+    #[test]
+    fn scopes() {
+        let tree = cst("f(a, {b, (c)}); g");
+        let nodes = tree.nodes();
+        assert_eq!(nodes.len(), 4);
+
+        let CstNode::Scope(call) = &nodes[1] else {
+            panic!("Expected a scope");
+        };
+        assert_eq!(call.opening.location().code(), "(");
+        assert!(call.closing.is_some());
+        assert_eq!(call.content.len(), 3);
+
+        let CstNode::Scope(object) = &call.content[2] else {
+            panic!("Expected a scope");
+        };
+        assert!(matches!(object.opening.kind(), Tokens::Object(_)));
+        assert!(matches!(object.content[2], CstNode::Scope(_)));
+    }
+
+    #[test]
+    fn invalid_code() {
+        let scripts = [
+            "a: $; b: (1}; c: {2)); d: `",
+            "x) + y}",
+            "a: (1, {2, \"&(3",
+            "s: \"a &( $ ) b\"; t: 1 $$ 2",
+            "é: 1 + `;\n",
+        ];
+
+        // The tree is built anyway, without losing any character
+        for script in scripts {
+            let tree = cst(script);
+            assert!(tree.diagnostics().has_errors(), "{}", script);
+            assert_eq!(tree.code(), script);
+        }
+
+        let tree = cst("x: $ 1");
+        let invalid = &tree.tokens()[2].trivia()[1];
+        assert!(matches!(invalid.kind(), Trivia::Invalid));
+        assert_eq!(invalid.location().code(), "$");
+    }
+
+    #[test]
+    fn trivia_are_not_kept_by_default() {
+        let module = FlyLang::anonymous_module("x : 1 || comment\n; y", SCRIPTS_LABEL).into_rc();
+        let mut lexer = Lexer::new(&module);
+
+        assert!(lexer.lexify().iter().all(|token| token.trivia().is_empty()));
+        assert!(lexer.trailing().is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+use std::{path::PathBuf, rc::Rc};
+
+use flylang::{
+    behavior::errors::PathNotFound,
+    flylang::{
+        FlyLang,
+        errors::{
+            ErrorType, RaisableErr,
+            codes::ErrorId,
+            diagnostic::{Label, render, serialize},
+        },
+        interpreter::Interpreter,
+        lexer::errors::{InvalidScopeEnding, UnclosedScope},
+        module::{LangModule, slice::LangModuleSlice},
+        utils::scoper::Scope,
+    },
+};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-diagnostic");
+
+    fn module(script: &str) -> Rc<LangModule> {
+        FlyLang::anonymous_module(script, SCRIPTS_LABEL).into_rc()
+    }
+
+    /// An error on a part of the code, with labels on other parts
+    struct Located(LangModuleSlice, Vec<Label>);
+    impl RaisableErr for Located {
+        fn _id(&self) -> ErrorId {
+            ErrorId::EmptyScope
+        }
+        fn _kind(&self) -> ErrorType {
+            ErrorType::Warn
+        }
+        fn _message(&self) -> String {
+            String::from("Located.")
+        }
+        fn _location(&self) -> Option<LangModuleSlice> {
+            Some(self.0.clone())
+        }
+        fn _labels(&self) -> Vec<Label> {
+            self.1.clone()
+        }
+    }
+
+    #[test]
+    fn carets() {
+        let module = module("x: 1;\ny: x + \"a\";");
+        let error = Located(LangModuleSlice::new_with(&module, 9..16), vec![]);
+
+        assert_eq!(
+            render(&error, false),
+            [
+                "Warning[E0008]: Located.",
+                " --> <tests-diagnostic>:2:4",
+                "  |",
+                "2 | y: x + \"a\";",
+                "  |    ^^^^^^^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let module = module("x: f(1, 2}");
+        let error = InvalidScopeEnding(
+            Rc::new(LangModuleSlice::new_with(&module, 9..10)),
+            Scope::Block(LangModuleSlice::new_with(&module, 4..5)),
+        );
+
+        assert_eq!(
+            render(&error, false),
+            [
+                "Error[E0003]: Unclosed Block. Expected to close it but found \"}\".",
+                " --> <tests-diagnostic>:1:10",
+                "  |",
+                "1 | x: f(1, 2}",
+                "  |          ^",
+                "  |     - this block is opened here",
+                " = help: close it with \")\" before",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn labels_on_other_lines() {
+        let mut parser = FlyLang::anonymous_parser("x :: 1;\ny: 2;\nz: 3;\nx: 4;", SCRIPTS_LABEL);
+        let error = Interpreter::new().run(parser.parse()).unwrap_err();
+
+        assert_eq!(
+            render(&*error, false),
+            [
+                "Error[E0029]: \"x\" is a constant and cannot be reassigned.",
+                " --> <tests-diagnostic>:4:1",
+                "  |",
+                "1 | x :: 1;",
+                "  | - the constant is defined here",
+                "...",
+                "4 | x: 4;",
+                "  | ^",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn notes_and_help() {
+        let module = module("a: (1 +\n  2");
+        let error = UnclosedScope(Scope::Block(LangModuleSlice::new_with(&module, 3..4)));
+
+        assert_eq!(
+            render(&error, false),
+            [
+                "Error[E0004]: Unclosed Block.",
+                " --> <tests-diagnostic>:1:4",
+                "  |",
+                "1 | a: (1 +",
+                "  |    ^",
+                " = note: the end of the code is reached before the block is closed",
+                " = help: close it with \")\"",
+            ]
+            .join("\n")
+        );
+
+        // Without location
+        let error = PathNotFound {
+            path: PathBuf::from("none.toml"),
+        };
+        assert_eq!(
+            render(&error, false),
+            "Warning[E0043]: No file found at <\"none.toml\">\n = help: create the file or fix the path to it"
+        );
+    }
+
+    #[test]
+    fn spanned_lines() {
+        let module = module("f: fn(\n  a;\n  b;\n  c;\n  d\n);\n\nf()");
+        let error = Located(
+            LangModuleSlice::new_with(&module, 3..27),
+            vec![Label::new(
+                &LangModuleSlice::new_with(&module, 30..31),
+                "called here",
+            )],
+        );
+
+        assert_eq!(
+            render(&error, false),
+            [
+                "Warning[E0008]: Located.",
+                " --> <tests-diagnostic>:1:4",
+                "  |",
+                "1 | f: fn(",
+                "  |    ^^^",
+                "2 |   a;",
+                "  | ^^^^",
+                "...",
+                "6 | );",
+                "  | ^",
+                "...",
+                "8 | f()",
+                "  | - called here",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn colors() {
+        let module = module("x");
+        let error = Located(LangModuleSlice::new_with(&module, 0..1), vec![]);
+
+        assert!(!render(&error, false).contains('\x1b'));
+
+        let colored = render(&error, true);
+        assert!(colored.starts_with("\x1b[1;33mWarning[E0008]:\x1b[0m Located."));
+        assert!(colored.contains("\x1b[1;33m^\x1b[0m"));
+    }
+
+    #[test]
+    fn codes() {
+        assert_eq!(ErrorId::UnknownCharacter.to_string(), "E0001");
+        assert_eq!(ErrorId::InvalidScopeEnding.to_string(), "E0003");
+        assert_eq!(ErrorId::NoAddonsFolder.to_string(), "E0052");
+    }
+
+    #[test]
+    fn json() {
+        let module = module("x: 1;\ny: f(1, 2}");
+        let error = InvalidScopeEnding(
+            Rc::new(LangModuleSlice::new_with(&module, 15..16)),
+            Scope::Block(LangModuleSlice::new_with(&module, 10..11)),
+        );
+
+        assert_eq!(
+            serialize(&error),
+            serde_json::json!({
+                "severity": "error",
+                "code": "E0003",
+                "message": "Unclosed Block. Expected to close it but found \"}\".",
+                "location": {
+                    "path": "<tests-diagnostic>",
+                    "start": {"byte": 15, "line": 2, "column": 10},
+                    "end": {"byte": 16, "line": 2, "column": 11},
+                },
+                "labels": [{
+                    "location": {
+                        "path": "<tests-diagnostic>",
+                        "start": {"byte": 10, "line": 2, "column": 5},
+                        "end": {"byte": 11, "line": 2, "column": 6},
+                    },
+                    "text": "this block is opened here",
+                }],
+                "notes": [],
+                "help": "close it with \")\" before",
+            })
+        );
+
+        let error = PathNotFound {
+            path: PathBuf::from("none.toml"),
+        };
+        let serialized = serialize(&error);
+        assert_eq!(serialized["severity"], "warning");
+        assert_eq!(serialized["code"], "E0043");
+        assert!(serialized["location"].is_null());
+    }
+}
@@ -0,0 +1,120 @@
+use flylang::flylang::{
+    FlyLang,
+    formatter::{self, Formatter},
+};
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-formatter");
+
+    fn format(script: &str) -> String {
+        Formatter::new()
+            .format(&FlyLang::anonymous_module(script, SCRIPTS_LABEL).into_rc())
+            .unwrap()
+    }
+
+    /// The text of the tokens of a script, without their spaces
+    fn tokens(script: &str) -> Vec<String> {
+        FlyLang::anonymous_lexer(script, SCRIPTS_LABEL)
+            .lexify()
+            .iter()
+            .map(|token| token.location().code().replace(char::is_whitespace, ""))
+            .collect()
+    }
+
+    #[test]
+    fn spaces() {
+        assert_eq!(
+            format("x -> number   :1+(2)- -3**2%2 // 1;A::{key:\"a &(x)  b\",1:{x,2}}"),
+            "x -> number: 1 + (2) - -3 ** 2 % 2 // 1; A:: {key: \"a &(x)  b\", 1: {x, 2}}\n"
+        );
+        assert_eq!(
+            format("fn f -> number @scope(a,b,if(!a ~ b>=a,return @scope a)else(return @< b))"),
+            "fn f -> number @scope(a, b, if(!a ~ b >= a, return @scope a) else(return @< b))\n"
+        );
+        assert_eq!(
+            format("#( private ) count : 0 ; z:new C( 1 ).method ( ) ; y + : - z;v: x.0.1"),
+            "#(private) count: 0; z: new C(1).method(); y +: -z; v: x.0.1\n"
+        );
+        // A `-` separated from a number stays separated
+        assert_eq!(format("u: (- 3) + (-3) - -.25"), "u: (- 3) + (-3) - -.25\n");
+    }
+
+    #[test]
+    fn comments() {
+        let script = "|| header\n\nx: 1 ;   || trailing   \ny : |inline| 2;;\n|block\n  comment|\n";
+        assert_eq!(
+            format(script),
+            "|| header\n\nx: 1; || trailing\ny: |inline| 2;\n|block\n  comment|\n"
+        );
+    }
+
+    #[test]
+    fn indentation() {
+        let script = "fn g(a,\n        b,\n            c, || trailing\nf(g(\nx\n)),\n{1,\n2}\n)\n\n\n\nstd.out(g)";
+        assert_eq!(
+            format(script),
+            "fn g(a,\n  b,\n  c, || trailing\n  f(g(\n    x\n  )),\n  {1,\n    2}\n)\n\nstd.out(g)\n"
+        );
+    }
+
+    #[test]
+    fn formatted_code_is_kept() {
+        let scripts = [
+            "",
+            "x: 1\n",
+            "each @outer({1, 2}, value, key,\n  while(value ~ x, stop @outer)\n);\nuntil(x > 10, iteration, x +: 1)\n",
+        ];
+        for script in scripts {
+            assert_eq!(format(script), script);
+        }
+
+        let code = fs::read_to_string("tests/scripts/misc.fly").unwrap();
+        let formatted = format(&code);
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn invalid_code_is_not_formatted() {
+        let module = FlyLang::anonymous_module("x:  $ 1;\ny: (2}", SCRIPTS_LABEL).into_rc();
+        let diagnostics = Formatter::new().format(&module).unwrap_err();
+        assert_eq!(diagnostics.errors().len(), 2);
+    }
+
+    #[test]
+    fn same_tokens() {
+        let script = r#"use "./other" (a, b);
+        cs C(A, B, #(private, static) count:0; #(deco(1)) fn method(stop); fn c(pass));
+        each @outer(A, value, key, while(value ~ x, stop @outer)); z: "a".0 ** -x - - 1"#;
+        assert_eq!(tokens(&format(script)), tokens(script));
+    }
+
+    #[test]
+    fn sources() {
+        let folder = std::env::temp_dir()
+            .join(format!("flylang-tests-formatter-{}", std::process::id()))
+            .join("sources");
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(folder.join("sub")).unwrap();
+        for file in ["b.fly", "a.fly", "notes.txt", "sub/c.fly"] {
+            fs::write(folder.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            formatter::sources(&folder),
+            vec![
+                folder.join("a.fly"),
+                folder.join("b.fly"),
+                folder.join("sub").join("c.fly")
+            ]
+        );
+        assert_eq!(
+            formatter::sources(&folder.join("notes.txt")),
+            vec![folder.join("notes.txt")]
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use flylang::flylang::{
+    FlyLang,
+    interpreter::{Interpreter, value::Value},
+};
+
+/// The tests of the language, written once and run against both engines : the interpreter here and the virtual
+/// machine in `tests/vm.rs`. They use the `run` function and the `Value` type of the module expanding them.
+macro_rules! engine_tests {
+    () => {
+        #[test]
+        fn literals() {
+            assert_eq!(run("()"), Value::Empty);
+            assert_eq!(run("true"), Value::Boolean(true));
+            assert_eq!(run("0x10"), Value::Number(16f64));
+            assert_eq!(
+                run(r#""1 + 1 = &(1 + 1)""#),
+                Value::String("1 + 1 = 2".into())
+            );
+        }
+
+        #[test]
+        fn variables() {
+            assert_eq!(run("a: 2; a *: 5; a - 1"), Value::Number(9f64));
+        }
+
+        #[test]
+        fn operations() {
+            assert_eq!(run("1 + 2 * 3"), Value::Number(7f64));
+            assert_eq!(run("7 // 2"), Value::Number(3f64));
+            assert_eq!(run("2 ** 3"), Value::Number(8f64));
+            assert_eq!(run("true & 1 < 2"), Value::Boolean(true));
+            assert_eq!(run("true ~ true"), Value::Boolean(false));
+            assert_eq!(run(r#""a" + 1"#), Value::String("a1".into()));
+        }
+
+        #[test]
+        fn functions() {
+            assert_eq!(run("fn add(a, b, a + b); add(1, 2)"), Value::Number(3f64));
+            assert_eq!(
+                run("fn fact(n, if(n < 2, return 1); return n * fact(n - 1)); fact(5)"),
+                Value::Number(120f64)
+            );
+            assert_eq!(run("fn nothing(a: 1; ()); nothing()"), Value::Empty);
+        }
+
+        #[test]
+        fn typed_code() {
+            assert_eq!(
+                run(
+                    "fn add -> number (a -> number, b -> number, return a + b); x -> number: add(1, 2); x"
+                ),
+                Value::Number(3f64)
+            );
+            assert_eq!(
+                run("cs A(value -> number ? empty: 1); a -> A: new A(); a.value"),
+                Value::Number(1f64)
+            );
+        }
+
+        #[test]
+        fn closures() {
+            assert_eq!(
+                run("fn counter(count: 0; return fn(count +: 1)); next: counter(); next(); next()"),
+                Value::Number(2f64)
+            );
+            // Each call has its own environment
+            assert_eq!(
+                run(
+                    "fn counter(count: 0; return fn(count +: 1)); a: counter(); b: counter(); a(); a(); b()"
+                ),
+                Value::Number(1f64)
+            );
+            // Arguments shadow the variables of the parent environments
+            assert_eq!(run("a: 1; fn f(a, a: 5); f(2); a"), Value::Number(1f64));
+            assert_eq!(run("a: 1; fn f(a: 5); f(); a"), Value::Number(5f64));
+        }
+
+        #[test]
+        fn constants() {
+            assert_eq!(run("A:: 1; fn f(A + 1); f()"), Value::Number(2f64));
+        }
+
+        #[test]
+        fn conditions() {
+            assert_eq!(run("if(1 = 2, a: 1) else (a: 2); a"), Value::Number(2f64));
+            assert_eq!(
+                run("a: 5; if(a < 2, b: 1) else if (a < 10, b: 2) else (b: 3); b"),
+                Value::Number(2f64)
+            );
+            assert_eq!(run("if(true, 1, 2)"), Value::Number(1f64));
+        }
+
+        #[test]
+        fn loops() {
+            assert_eq!(run("i: 0; while(i < 10, i +: 1); i"), Value::Number(10f64));
+            assert_eq!(run("i: 0; until(i = 3, i +: 1); i"), Value::Number(3f64));
+            assert_eq!(
+                run("i: 0; while(true, i +: 1; if(i = 4, stop)); i"),
+                Value::Number(4f64)
+            );
+            assert_eq!(
+                run("last: (); while(true, n, last: n; if(n = 2, stop)); last"),
+                Value::Number(2f64)
+            );
+            assert_eq!(run("n: 0; until(n = 3, i, n +: 1); i"), Value::Number(2f64));
+        }
+
+        #[test]
+        fn each_loops() {
+            assert_eq!(
+                run("sum: 0; each({1, 2, 3}, item, index, sum +: item * index); sum"),
+                Value::Number(8f64)
+            );
+            assert_eq!(
+                run(
+                    r#"keys: ""; sum: 0; each({a: 1, b: 2}, value, key, keys +: key; sum +: value); "&(keys)&(sum)""#
+                ),
+                Value::String("ab3".into())
+            );
+            assert_eq!(
+                run(r#"s: ""; each("abc", c, s: c + s); s"#),
+                Value::String("cba".into())
+            );
+            assert_eq!(
+                run("sum: 0; each(5, i, sum +: i); sum"),
+                Value::Number(10f64)
+            );
+            assert_eq!(
+                run(
+                    "n: 0; each({1, 2, 3, 4}, item, if(item = 2, pass @<); if(item = 4, stop); n +: item); n"
+                ),
+                Value::Number(4f64)
+            );
+            // The iterated array is copied
+            assert_eq!(
+                run("a: {1, 2}; each(a, item, a.(std.len(a)): item); std.len(a)"),
+                Value::Number(4f64)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn each_loop_on_boolean() {
+            run("each(true, item, ())");
+        }
+
+        #[test]
+        fn scope_targets() {
+            // Numbered targets skip the inner scopes
+            assert_eq!(
+                run("fn outer(fn killer(return @-1 true); killer(); return false); outer()"),
+                Value::Boolean(true)
+            );
+            assert_eq!(
+                run("fn outer(fn killer(return @< 1); killer(); return 2); outer()"),
+                Value::Number(1f64)
+            );
+            // Named targets
+            assert_eq!(
+                run(
+                    "i: 0; n: 0; while @outer(i < 3, i +: 1; j: 0; while(j < 3, j +: 1; if(j = 2, pass @outer); n +: 1)); n"
+                ),
+                Value::Number(3f64)
+            );
+            assert_eq!(
+                run("i: 0; while @outer(true, i +: 1; while(true, stop @outer)); i"),
+                Value::Number(1f64)
+            );
+            assert_eq!(
+                run("fn f @top(fn(return @top 5)(); return 0); f()"),
+                Value::Number(5f64)
+            );
+            // `pass` ends the targeted condition
+            assert_eq!(
+                run("a: 0; if @cond(true, if(true, pass @cond; a: 1); a: 2); a"),
+                Value::Number(0f64)
+            );
+        }
+
+
+        #[test]
+        fn breakers_across_functions() {
+            // The iterators of the loops left by a breaker are released
+            assert_eq!(
+                run(
+                    "n: 0; each @outer(3, i, each({1, 2, 3}, j, fn skip(pass @outer); if(j = 2, skip()); n +: 1)); n"
+                ),
+                Value::Number(3f64)
+            );
+            assert_eq!(
+                run("n: 0; each @outer(5, i, fn leave(stop @outer); if(i = 3, leave()); n +: 1); n"),
+                Value::Number(3f64)
+            );
+            assert_eq!(
+                run(
+                    "fn f @top(fn inner(x, if(x > 2, return @top x * 10); return inner(x + 1)); inner(0); return 0); f()"
+                ),
+                Value::Number(30f64)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn breaker_outside_its_scope() {
+            run("fns: {}; each @outer(1, i, fns.0: fn(stop @outer)); fns.0()");
+        }
+
+        #[test]
+        fn modifiers() {
+            // Variables
+            assert_eq!(
+                run("fn debug(def, args, return def); #(debug) my_var: true; my_var"),
+                Value::String("my_var".into())
+            );
+            assert_eq!(
+                run("fn double(name, args, return 2 * args.1); #(double, double) a: 3; a"),
+                Value::Number(12f64)
+            );
+            assert_eq!(
+                run("fn constant(name, args, return args.0); #(constant) A:: 1; A"),
+                Value::Boolean(true)
+            );
+            // Functions
+            assert_eq!(
+                run(
+                    "fn debug(def, args, return def); #(debug) fn my_func(); r: my_func(); r = my_func"
+                ),
+                Value::Boolean(true)
+            );
+            assert_eq!(
+                run(
+                    "fn twice(def, args, return 2 * def(args.0)); fn plus_one(def, args, return 1 + def(args.0)); #(twice, plus_one) fn f(n, return n); f(3)"
+                ),
+                Value::Number(7f64)
+            );
+            // Modifiers returned by a call
+            assert_eq!(
+                run(
+                    "fn times(n, return fn(def, args, return n * def(args.0))); #(times(10)) fn f(a, return a + 1); f(1)"
+                ),
+                Value::Number(20f64)
+            );
+            assert_eq!(
+                run(
+                    "calls: 0; last: (); result: (); fn memo(def, args, if(last = args.0, return result); last: args.0; result: def(args.0); return result); #(memo) fn square(n, calls +: 1; return n * n); square(3); square(3); calls"
+                ),
+                Value::Number(1f64)
+            );
+            // Classes
+            assert_eq!(
+                run(
+                    "fn debug(def, args, return def); #(debug) cs MyClass(); r: new MyClass(); r = MyClass"
+                ),
+                Value::Boolean(true)
+            );
+            assert_eq!(
+                run(r#"fn named(def, args, return new def()); #(named) cs A(); std.type(new A())"#),
+                Value::String("instance".into())
+            );
+        }
+
+        #[test]
+        fn classes() {
+            let point = "cs Point(fn(x, y, self.x: x; self.y: y), name: \"point\"; fn norm(x2: self.x * self.x; return x2 + self.y * self.y)); p: new Point(3, 4);";
+            assert_eq!(run(&format!("{} p.norm()", point)), Value::Number(25f64));
+            assert_eq!(
+                run(&format!("{} p.name", point)),
+                Value::String("point".into())
+            );
+            // Methods are bound to their instance
+            assert_eq!(
+                run(&format!("{} norm: p.norm; p.x: 0; norm()", point)),
+                Value::Number(16f64)
+            );
+            // Fields hide methods
+            assert_eq!(
+                run(&format!("{} p.norm: 1; p.norm", point)),
+                Value::Number(1f64)
+            );
+            // Named functions are methods, even without constructor
+            assert_eq!(
+                run("cs A(fn one(return 1)); a: new A(); a.one()"),
+                Value::Number(1f64)
+            );
+            // Attributes are initialised for each instance
+            assert_eq!(
+                run("cs A(items: {}); a: new A(); b: new A(); a.items.0: 1; std.len(b.items)"),
+                Value::Number(0f64)
+            );
+        }
+
+        #[test]
+        fn inheritance() {
+            let diamond = r#"
+                cs A(fn(self.log: "A"), fn who(return "A"));
+                cs B(A, fn(super(); self.log +: "B"), fn who(return "B" + super.who()));
+                cs C(A, fn(super(); self.log +: "C"), fn who(return "C" + super.who()));
+                cs D(B, C, fn(super(); self.log +: "D"), fn who(return "D" + super.who()));
+                d: new D();
+            "#;
+            // Methods are resolved using the C3 linearization
+            assert_eq!(
+                run(&format!("{} d.who()", diamond)),
+                Value::String("DBCA".into())
+            );
+            // Constructors are chained through `super`
+            assert_eq!(
+                run(&format!("{} d.log", diamond)),
+                Value::String("ACBD".into())
+            );
+            // Constructors and attributes are inherited
+            assert_eq!(
+                run(&format!(
+                    "{} cs E(D, value: 1); e: new E(); e.log + e.value",
+                    diamond
+                )),
+                Value::String("ACBD1".into())
+            );
+            assert_eq!(
+                run("cs A(value: 1; other: 1); cs B(A, value: 2); b: new B(); b.value + b.other"),
+                Value::Number(3f64)
+            );
+        }
+
+        #[test]
+        fn visibility() {
+            let account = r#"
+                cs Account(
+                    fn(amount, self.balance: amount),
+                    #(private) balance: 0;
+                    #(protected) fn audit(return self.balance);
+                    fn get(return self.balance);
+                    fn same(other, return other.balance = self.balance);
+                );
+                cs Savings(Account, fn check(return self.audit()));
+                a: new Account(10);
+                s: new Savings(10);
+            "#;
+            // Private members can be used by the methods of their class
+            assert_eq!(run(&format!("{} a.get()", account)), Value::Number(10f64));
+            assert_eq!(run(&format!("{} a.same(s)", account)), Value::Boolean(true));
+            // Protected members can be used by the methods of the child classes
+            assert_eq!(run(&format!("{} s.check()", account)), Value::Number(10f64));
+        }
+
+        #[test]
+        fn statics() {
+            let counter = r#"
+                cs Counter(
+                    fn(self.id: Counter.next()),
+                    #(static) count: 0;
+                    #(static) fn next(Counter.count +: 1; return Counter.count);
+                );
+                cs Sub(Counter);
+                a: new Counter();
+                b: new Sub();
+            "#;
+            // Static members live on the class, and are shared with the child classes
+            assert_eq!(
+                run(&format!("{} Counter.count", counter)),
+                Value::Number(2f64)
+            );
+            assert_eq!(run(&format!("{} b.id", counter)), Value::Number(2f64));
+            assert_eq!(run(&format!("{} Sub.next()", counter)), Value::Number(3f64));
+            assert_eq!(
+                run(&format!("{} Sub.count: 10; Counter.count", counter)),
+                Value::Number(10f64)
+            );
+            // Static members are not part of the instances
+            assert_eq!(
+                run("cs A(#(static) n: 1); a: new A(); a.n: 2; A.n"),
+                Value::Number(1f64)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn private_member_access() {
+            run("cs A(#(private) secret: 1); a: new A(); a.secret");
+        }
+
+        #[test]
+        #[should_panic]
+        fn protected_member_access() {
+            run("cs A(#(protected) fn f(return 1)); cs B(A); b: new B(); b.f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn private_member_from_child() {
+            run("cs A(#(private) secret: 1); cs B(A, fn f(return self.secret)); b: new B(); b.f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn static_member_on_instance() {
+            run("cs A(#(static) fn f(return 1)); a: new A(); a.f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn multiple_visibilities() {
+            run("cs A(#(private, public) value: 1)");
+        }
+
+        #[test]
+        #[should_panic]
+        fn inconsistent_inheritance() {
+            run("cs A(); cs B(A); cs C(A, B)");
+        }
+
+        #[test]
+        #[should_panic]
+        fn constructor_arguments() {
+            run("cs A(); new A(1)");
+        }
+
+        #[test]
+        fn objects() {
+            assert_eq!(run("{1, 2}.1"), Value::Number(2f64));
+            assert_eq!(run("arr: {1, 2}; arr.2: 3; arr.(-1)"), Value::Number(3f64));
+            assert_eq!(
+                run(r#"s: {a: 1, "b": 2}; s.c: s.a + s.b; s.("c")"#),
+                Value::Number(3f64)
+            );
+            assert_eq!(run("a: {1}; b: a; b.0: 2; a.0"), Value::Number(2f64));
+        }
+
+        #[test]
+        fn equality() {
+            assert_eq!(run("{1, {a: 2}} = {1, {a: 2}}"), Value::Boolean(true));
+            assert_eq!(run("{a: 1, b: 2} = {b: 2, a: 1}"), Value::Boolean(true));
+            assert_eq!(run("{1, 2} = {2, 1}"), Value::Boolean(false));
+            assert_eq!(run("fn f(); f = f"), Value::Boolean(true));
+            assert_eq!(
+                run("cs A(); a: new A(); b: new A(); a = b"),
+                Value::Boolean(false)
+            );
+            assert_eq!(run(r#"1 = "1""#), Value::Boolean(false));
+        }
+
+        #[test]
+        fn truthiness() {
+            for falsy in ["()", "false", "0", r#""""#, "{}", "{:}"] {
+                assert_eq!(run(&format!("if({}, 1, 2)", falsy)), Value::Number(2f64));
+            }
+            for truthy in ["true", "-1", r#""0""#, "{0}", "{a: ()}", "fn()"] {
+                assert_eq!(run(&format!("if({}, 1, 2)", truthy)), Value::Number(1f64));
+            }
+        }
+
+        #[test]
+        fn display() {
+            assert_eq!(
+                run(r#""&({1, "a", {k: 2.5, "a b": ()}})""#).to_string(),
+                r#"{1, "a", {k: 2.5, "a b": ()}}"#
+            );
+            assert_eq!(run(r#""&({:})""#).to_string(), "{:}");
+            assert_eq!(
+                run(r#"cs A(); "&(A) &(new A())""#).to_string(),
+                "<cs A> <A instance>"
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn undefined_variable() {
+            run("undefined_variable");
+        }
+
+        #[test]
+        #[should_panic]
+        fn local_variable_outside_function() {
+            run("fn f(b: 5); f(); b");
+        }
+
+        #[test]
+        #[should_panic]
+        fn constant_reassignment() {
+            run("A:: 1; fn f(A: 2); f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn builtin_reassignment() {
+            run("std: 1");
+        }
+
+        #[test]
+        #[should_panic]
+        fn unknown_scope_target() {
+            run("fn f(return @unknown 1); f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn numbered_scope_target_too_deep() {
+            run("fn f(return @<< 1); f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn invalid_scope_target() {
+            run("fn f @scope(while(true, stop @scope)); f()");
+        }
+
+        #[test]
+        #[should_panic]
+        fn breaker_outside_loop() {
+            run("stop");
+        }
+    };
+}
+pub(crate) use engine_tests;
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-interpreter");
+
+    fn run(script: &str) -> Value {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        Interpreter::new()
+            .run(parser.parse())
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    engine_tests!();
+}
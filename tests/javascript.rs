@@ -0,0 +1,141 @@
+use flylang::flylang::compilers;
+
+use crate::compilers::tests::{compile, execute, run};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const COMPILER: &str = "js";
+
+    #[test]
+    fn registered() {
+        assert!(compilers::find("js").is_some());
+        assert!(compilers::find("unknown").is_none());
+    }
+
+    #[test]
+    fn declarations() {
+        let code = compile(COMPILER, "a: 1; a: a + 1; fn f(b, c: b; return c); let: 2");
+        assert_eq!(code.matches("let a").count(), 1);
+        // Variables live in their function
+        assert!(code.contains("let c"));
+        assert!(code.contains("function f(b)"));
+        // Reserved words of JavaScript are renamed
+        assert!(code.contains("$let"));
+        assert!(code.contains("export { a, f, $let };"));
+    }
+
+    #[test]
+    fn values() {
+        let Some(output) = run(
+            COMPILER,
+            "values",
+            r#"a: {x: 1, "b c": {1, "two"}}; std.out(a, a.x + 1, "n = &(a.x)", {}, {:}, ());
+            std.out(7 // 2, -7 // 2, 2 ** 3, 1 / 4, {1, 2} = {1, 2}, std.len("abc"), std.type(a))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(
+            output,
+            "{x: 1, \"b c\": {1, \"two\"}} 2 n = 1 {} {:} ()\n3 -4 8 0.25 true 3 structure\n"
+        );
+    }
+
+    #[test]
+    fn loops_and_breakers() {
+        let Some(output) = run(
+            COMPILER,
+            "loops_and_breakers",
+            r#"s: 0; each @outer({1, 2, 3}, i, each({10, 20}, j, if(i = 3, stop @outer); s +: i * j));
+            n: 0; while(n < 5, n +: 1; if(n = 2, pass @<); std.out(n));
+            fn find @f(items, target, fn check(v, if(v = target, return @f "found")); each(items, item, check(item)); return "none");
+            std.out(s, find({1, 2}, 2), find({1}, 3))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "1\n3\n4\n5\n90 found none\n");
+    }
+
+    #[test]
+    fn classes() {
+        let Some(output) = run(
+            COMPILER,
+            "classes",
+            r#"cs A(fn who(return "A"));
+            cs B(A, fn who(return "B" + super.who()));
+            cs C(A, fn who(return "C" + super.who()));
+            cs D(B, C, #(static) count: 0; fn who(return "D" + super.who()));
+            d: new D(); D.count +: 1; method: d.who;
+            std.out(method(), D.count, d, D, std.type(d))"#,
+        ) else {
+            return;
+        };
+        // `super` follows the method resolution order of the instance
+        assert_eq!(output, "DBCA 1 <D instance> <cs D> instance\n");
+    }
+
+    #[test]
+    fn modifiers() {
+        let Some(output) = run(
+            COMPILER,
+            "modifiers",
+            r#"fn double(name, args, return 2 * args.1); #(double, double) a: 3;
+            fn twice(def, args, return 2 * def(args.0)); fn plus_one(def, args, return 1 + def(args.0));
+            #(twice, plus_one) fn f(n, return n);
+            std.out(a, f(3))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "12 7\n");
+    }
+
+    #[test]
+    fn errors_stop_the_program() {
+        let Some(output) = execute(COMPILER, "errors", "a: 0; std.out(1 / a)") else {
+            return;
+        };
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Division by zero."));
+    }
+
+    #[test]
+    fn loop_variables() {
+        let Some(output) = run(
+            COMPILER,
+            "loop_variables",
+            r#"n: 0; while(n < 3, i, n +: 1); each({"a", "b"}, item, index, ()); std.out(i, item, index)"#,
+        ) else {
+            return;
+        };
+        // The variables keep the values of the last execution of the body
+        assert_eq!(output, "2 b 1\n");
+    }
+
+    #[test]
+    fn runtime_errors() {
+        let scripts = [
+            (r#"fn f(a, return a * 2); f("a")"#, "E0019"),
+            (r#"fn f(a, b, return a < b); f(1, "a")"#, "E0019"),
+            ("fn f(a, return a - 1); f({})", "E0019"),
+            ("fn f(a, return -a); f({})", "E0019"),
+            ("fn f(a, ()); f(1, 2)", "E0021"),
+            ("cs C(); C()", "E0018"),
+            ("s: {x: 1}; s.y", "E0025"),
+            ("a: {1, 2}; a.2", "E0027"),
+            ("A:: 1; A: 2", "E0029"),
+            ("A:: 1; fn f(A: 2); f()", "E0029"),
+            ("std.out(w)", "E0017"),
+        ];
+
+        for (test, (script, code)) in scripts.iter().enumerate() {
+            let Some(output) = execute(COMPILER, &format!("runtime_errors_{}", test), script)
+            else {
+                return;
+            };
+            assert!(!output.status.success(), "{}", script);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains(code), "{} : {}", script, stderr);
+        }
+    }
+}
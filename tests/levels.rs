@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use flylang::{
+    behavior::LangBehavior,
+    flylang::{
+        cache::errors::CacheNotWritten,
+        errors::{
+            Diagnostics, ErrorType, RaisableErr,
+            codes::ErrorId,
+            levels::{self, Level, Levels},
+        },
+    },
+};
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// Read the levels from a behavior file with the given content
+    fn configured(test: &str, content: &str) -> Levels {
+        let folder = std::env::temp_dir()
+            .join(format!("flylang-tests-levels-{}", std::process::id()))
+            .join(test);
+        fs::create_dir_all(&folder).unwrap();
+        let file = folder.join("flylang.toml");
+        fs::write(&file, content).unwrap();
+
+        Levels::new().configured(&LangBehavior::new_parsed(&file), &file)
+    }
+
+    #[test]
+    fn parse_codes() {
+        assert_eq!("E0038".parse(), Ok(ErrorId::WeirdExtension));
+        assert_eq!("E0001".parse(), Ok(ErrorId::UnknownCharacter));
+        assert_eq!("E9999".parse::<ErrorId>(), Err(()));
+        assert_eq!("0038".parse::<ErrorId>(), Err(()));
+        assert_eq!(ErrorId::ALL.len(), 54);
+
+        assert!(ErrorId::WeirdExtension.is_warning());
+        assert!(!ErrorId::UnclosedScope.is_warning());
+    }
+
+    #[test]
+    fn default_levels() {
+        let levels = Levels::new();
+        assert_eq!(
+            levels.apply(ErrorId::EmptyScope, ErrorType::Warn),
+            Some(ErrorType::Warn)
+        );
+        assert_eq!(
+            levels.apply(ErrorId::UnknownCharacter, ErrorType::Stop),
+            Some(ErrorType::Stop)
+        );
+    }
+
+    #[test]
+    fn set_levels() {
+        let levels = Levels::new()
+            .with(ErrorId::EmptyScope, Level::Allow)
+            .with(ErrorId::WeirdExtension, Level::Deny)
+            .with(ErrorId::CacheNotWritten, Level::Warn)
+            .with(ErrorId::UnclosedScope, Level::Allow);
+
+        assert_eq!(levels.apply(ErrorId::EmptyScope, ErrorType::Warn), None);
+        assert_eq!(
+            levels.apply(ErrorId::WeirdExtension, ErrorType::Warn),
+            Some(ErrorType::Stop)
+        );
+        assert_eq!(
+            levels.apply(ErrorId::CacheNotWritten, ErrorType::Hint),
+            Some(ErrorType::Warn)
+        );
+        // The errors cannot be allowed
+        assert_eq!(
+            levels.apply(ErrorId::UnclosedScope, ErrorType::Stop),
+            Some(ErrorType::Stop)
+        );
+    }
+
+    #[test]
+    fn deny_warnings() {
+        let levels = Levels::new()
+            .with(ErrorId::EmptyScope, Level::Allow)
+            .with(ErrorId::CacheNotWritten, Level::Warn)
+            .deny_warnings(true);
+
+        assert_eq!(levels.apply(ErrorId::EmptyScope, ErrorType::Warn), None);
+        assert_eq!(
+            levels.apply(ErrorId::CacheNotWritten, ErrorType::Warn),
+            Some(ErrorType::Stop)
+        );
+        assert_eq!(
+            levels.apply(ErrorId::PathNotFound, ErrorType::Warn),
+            Some(ErrorType::Stop)
+        );
+        assert_eq!(
+            levels.apply(ErrorId::PathNotFound, ErrorType::Hint),
+            Some(ErrorType::Hint)
+        );
+    }
+
+    #[test]
+    fn behavior() {
+        assert_eq!(
+            configured(
+                "behavior",
+                "[language.diagnostics]\nE0008 = \"allow\"\nE0038 = \"deny\"\nE0039 = \"warn\"\n"
+            ),
+            Levels::new()
+                .with(ErrorId::EmptyScope, Level::Allow)
+                .with(ErrorId::WeirdExtension, Level::Deny)
+                .with(ErrorId::CacheNotWritten, Level::Warn)
+        );
+
+        // The invalid entries are ignored, like the ones setting the level of an error
+        assert_eq!(
+            configured(
+                "invalid",
+                "[language.diagnostics]\nE9999 = \"allow\"\nE0038 = \"forbid\"\nE0039 = 1\nE0008 = \"allow\"\nE0004 = \"allow\"\n"
+            ),
+            Levels::new().with(ErrorId::EmptyScope, Level::Allow)
+        );
+        assert_eq!(
+            configured("not-a-table", "[language]\ndiagnostics = \"allow\"\n"),
+            Levels::new()
+        );
+    }
+
+    #[test]
+    fn reported() {
+        // Only this test changes the current levels, and only for this code
+        levels::set_levels(Levels::new().with(ErrorId::CacheNotWritten, Level::Deny));
+
+        let error = CacheNotWritten {
+            path: PathBuf::from("main.flyc"),
+            reason: String::from("read-only"),
+        };
+        assert_eq!(error.reported_kind(), Some(ErrorType::Stop));
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Box::new(error));
+        assert!(diagnostics.has_errors());
+
+        levels::set_levels(Levels::new());
+    }
+}
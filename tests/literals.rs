@@ -116,6 +116,18 @@ pub mod tests {
         assert_eq!(num, 0.158f64);
     }
 
+    #[test]
+    fn number_float_with_integer() {
+        for (script, expected) in [("2.5", 2.5f64), ("10.05", 10.05f64), ("1_0.0_1", 10.01f64)] {
+            let parsed = FlyLang::anonymous_parser(script, SCRIPTS_LABEL)
+                .parse()
+                .to_vec();
+
+            let num: f64 = NumberRepresentation::from(parsed[0].location()).into();
+            assert_eq!(num, expected);
+        }
+    }
+
     #[test]
     fn number_neg_float() {
         let parsed = FlyLang::anonymous_parser(r#"-.9874"#, SCRIPTS_LABEL)
@@ -0,0 +1,209 @@
+use flylang::flylang::{
+    FlyLang,
+    compilers::{self, Compiler, lua::Lua},
+};
+
+use crate::compilers::tests::{compile, execute, run};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-lua");
+    const COMPILER: &str = "lua";
+
+    #[test]
+    fn registered() {
+        assert!(compilers::find("lua").is_some());
+    }
+
+    #[test]
+    fn statements() {
+        let code = compile(
+            COMPILER,
+            "s: {a: 1, b: {1, 2}}; fn f(x, return x); g: fn(y, return y); class: if(s, 1, 2); end: 1",
+        );
+        // Variables are locals of the chunk, renamed when they are Lua keywords
+        assert!(code.contains("local s, f, g, class, end_"));
+        assert!(code.contains("s = fly.structure(\"a\", 1.0, \"b\", fly.array(1.0, 2.0))"));
+        assert!(code.contains("f = fly.fn(\"f\", function(x)"));
+        assert!(code.contains("class = fly.truthy(s) and 1.0 or 2.0"));
+        // The module returns its variables, with their flylang names
+        assert!(code.contains(
+            "return fly.structure(\"s\", s, \"f\", f, \"g\", g, \"class\", class, \"end\", end_)"
+        ));
+    }
+
+    #[test]
+    fn use_of_every_name_is_unsupported() {
+        let mut parser = FlyLang::anonymous_parser(r#"use "x""#, SCRIPTS_LABEL);
+        let branches = parser.parse().clone();
+        assert!(Lua.compile(parser.module(), &branches).is_err());
+    }
+
+    #[test]
+    fn values() {
+        let Some(output) = run(
+            COMPILER,
+            "values",
+            r#"a: {x: 1, "b c": {1, "two"}}; std.out(a, a.x + 1, "n = &(a.x)", {}, {:}, ());
+            std.out(7 // 2, -7 // 2, 7 % -3, 2 ** 3, 1 / 4, {1, 2} = {1, 2}, 1 = true, std.len("abc"), std.type(a));
+            st: {a: (), b: 2}; std.out(st, std.len(st), !{:}, 2 ** 70, 2 ** 60, 1 / 3)"#,
+        ) else {
+            return;
+        };
+        assert_eq!(
+            output,
+            "{x: 1, \"b c\": {1, \"two\"}} 2 n = 1 {} {:} ()\n3 -4 1 8 0.25 true false 3 structure\n\
+            {a: (), b: 2} 2 true 1180591620717411300000 1152921504606847000 0.3333333333333333\n"
+        );
+    }
+
+    #[test]
+    fn loops_and_breakers() {
+        let Some(output) = run(
+            COMPILER,
+            "loops_and_breakers",
+            r#"s: 0; each @outer({1, 2, 3}, i, each({10, 20}, j, if(i = 3, stop @outer); s +: i * j));
+            n: 0; while(n < 5, n +: 1; if(n = 2, pass @<); std.out(n));
+            fn find @f(items, target, fn check(v, if(v = target, return @f "found")); each(items, item, check(item)); return "none");
+            std.out(s, find({1, 2}, 2), find({1}, 3));
+            t: 0; until(t >= 2, iteration, std.out("it", iteration); t +: 1)"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "1\n3\n4\n5\n90 found none\nit 0\nit 1\n");
+    }
+
+    #[test]
+    fn breakers_across_functions() {
+        let Some(output) = run(
+            COMPILER,
+            "breakers_across_functions",
+            r#"fn each_until(items, limit, r: {}; each @l(items, item, fn check(v, if(v > limit, stop @l); if(v = 2, pass @l)); check(item); r.(std.len(r)): item; if(item = 5, return r)); return r);
+            std.out(each_until({1, 2, 3, 9, 4}, 5), each_until({1, 5, 7}, 10));
+            fn g(x, if @c(x > 0, fn k(pass @c); k(); std.out("never")); while @w(true, fn m(stop @w); each(3, i, if(i = 1, m()))); return "done");
+            fn r @rec(n, if(n = 0, return 0); fn sub(return @rec n + r(n - 1)); sub(); return 99);
+            std.out(g(1), r(4))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "{1, 3} {1, 5}\ndone 10\n");
+    }
+
+    #[test]
+    fn expressions_with_statements() {
+        let Some(output) = run(
+            COMPILER,
+            "expressions_with_statements",
+            r#"apply: fn(f, x, return f(x)); y: (z: 3) + 1;
+            fn sign(v, return if(v > 0, "+", if(v < 0, "-", "0")));
+            std.out(apply(fn(v, return v * 2), 21), y, z, sign(2), sign(-2), sign(0), if(true, (), 3))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "42 4 3 + - 0 ()\n");
+    }
+
+    #[test]
+    fn classes() {
+        let Some(output) = run(
+            COMPILER,
+            "classes",
+            r#"cs A(fn who(return "A"));
+            cs B(A, fn who(return "B" + super.who()));
+            cs C(A, fn who(return "C" + super.who()));
+            cs D(B, C, #(static) count: 0; fn who(return "D" + super.who()));
+            d: new D(); D.count +: 1; method: d.who;
+            std.out(method(), D.count, d, D, std.type(d));
+            cs P(fn(a, self.v: a), v: 5; fn get(return self.v));
+            cs Q(P, fn(a, super(a * 2)));
+            q: new Q(4); std.out(q.get(), q.v, q.get = q.get)"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "DBCA 1 <D instance> <cs D> instance\n8 8 true\n");
+    }
+
+    #[test]
+    fn modifiers() {
+        let Some(output) = run(
+            COMPILER,
+            "modifiers",
+            r#"fn double(name, args, return 2 * args.1); #(double, double) a: 3;
+            fn twice(def, args, return 2 * def(args.0)); fn plus_one(def, args, return 1 + def(args.0));
+            #(twice, plus_one) fn f(n, return n);
+            fn named(def, args, return new def()); #(named) cs K();
+            std.out(a, f(3), std.type(new K()), f)"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "12 7 instance <fn f>\n");
+    }
+
+    #[test]
+    fn loop_variables() {
+        let Some(output) = run(
+            COMPILER,
+            "loop_variables",
+            r#"n: 0; while(n < 3, i, n +: 1); each({"a", "b"}, item, index, ()); std.out(i, item, index)"#,
+        ) else {
+            return;
+        };
+        // The variables keep the values of the last execution of the body
+        assert_eq!(output, "2 b 1\n");
+    }
+
+    #[test]
+    fn constants() {
+        let scripts = [
+            "A:: 1; A: 2",
+            "A:: 1; fn f(A: 2); f()",
+            "A:: 1; fn A()",
+            "A:: 1; each({1}, A, ())",
+        ];
+
+        for (test, script) in scripts.iter().enumerate() {
+            let Some(output) = execute(COMPILER, &format!("constants_{}", test), script) else {
+                return;
+            };
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(!output.status.success(), "{}", script);
+            assert!(
+                stderr.contains("\"A\" is a constant and cannot be reassigned."),
+                "{} : {}",
+                script,
+                stderr
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_operations() {
+        let scripts = [
+            "fn f(a, return a < 2); f({1})",
+            r#"fn f(a, b, return a >= b); f(1, "a")"#,
+            "fn f(a, return a - 1); f({})",
+            r#"fn f(a, return a * 2); f("a")"#,
+            "fn f(a, return a ** 2); f(())",
+            "fn f(a, return -a); f({})",
+        ];
+
+        for (test, script) in scripts.iter().enumerate() {
+            let Some(output) = execute(COMPILER, &format!("invalid_operations_{}", test), script)
+            else {
+                return;
+            };
+            // The errors of the interpreter are raised, instead of the errors of Lua
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(!output.status.success(), "{}", script);
+            assert!(
+                stderr.contains("cannot be applied to"),
+                "{} : {}",
+                script,
+                stderr
+            );
+            assert!(!stderr.contains("attempt to"), "{} : {}", script, stderr);
+        }
+    }
+}
@@ -1,6 +1,24 @@
 use flylang::flylang::FlyLang;
 
+mod addons;
+mod analyser;
+mod c;
+mod cache;
+mod checker;
+mod compilers;
+mod cst;
+mod diagnostic;
+mod formatter;
+mod interpreter;
+mod javascript;
+mod levels;
 mod literals;
+mod lua;
+mod module;
+mod optimizer;
+mod python;
+mod recovery;
+mod vm;
 
 #[cfg(test)]
 pub mod tests {
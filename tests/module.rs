@@ -0,0 +1,107 @@
+use std::rc::Rc;
+
+use flylang::flylang::{
+    FlyLang,
+    module::{LangModule, char::LangModuleChar, slice::LangModuleSlice},
+};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-module");
+
+    fn module(script: &str) -> Rc<LangModule> {
+        FlyLang::anonymous_module(script, SCRIPTS_LABEL).into_rc()
+    }
+
+    fn char_at(module: &Rc<LangModule>, index: usize) -> LangModuleChar {
+        let mut modchar = LangModuleChar::new(module);
+        modchar.set(index);
+        modchar
+    }
+
+    #[test]
+    fn offsets() {
+        let module = module("aé€😀b");
+
+        assert_eq!(module.char_count(), 5);
+        assert_eq!(
+            (0..=5)
+                .map(|index| module.offset(index))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 3, 6, 10, 11]
+        );
+        // A byte inside a character gives the index of this character
+        assert_eq!(module.index(4), 2);
+        assert_eq!(module.index(11), 5);
+
+        assert_eq!(
+            module.chars().map(|c| c.code()).collect::<String>(),
+            "aé€😀b"
+        );
+        assert_eq!(char_at(&module, 3).offset(), 6);
+    }
+
+    #[test]
+    fn lines() {
+        let module = module("a: 1;\r\nb: \"é\";\n\nc");
+
+        assert_eq!(module.line_starts(), &[0, 7, 16, 17]);
+        assert_eq!(module.line(6), 0);
+        assert_eq!(module.line(7), 1);
+        assert_eq!(module.line(16), 2);
+        assert_eq!(module.line(17), 3);
+
+        // The first character of a line
+        let b = char_at(&module, 7);
+        assert_eq!((b.code(), b.line(), b.line_index()), ('b', 1, 0));
+        // The columns are counted in characters
+        let semicolon = char_at(&module, 13);
+        assert_eq!(
+            (semicolon.code(), semicolon.line(), semicolon.line_index()),
+            (';', 1, 6)
+        );
+        let c = char_at(&module, 16);
+        assert_eq!((c.code(), c.line(), c.line_index()), ('c', 3, 0));
+    }
+
+    #[test]
+    fn slices() {
+        let module = module("x: \"é€\";\ny");
+
+        let chars: Vec<LangModuleChar> = module.chars().skip(4).take(2).collect();
+        let slice = LangModuleSlice::from(&chars);
+        assert_eq!((slice.range(), slice.code()), (4..9, "é€"));
+        assert_eq!(LangModuleSlice::from(&chars[1]).code(), "€");
+
+        assert_eq!(slice.start().unwrap().code(), 'é');
+        assert_eq!(slice.end().unwrap().code(), '€');
+        assert_eq!(slice.to_string(), "<tests-module>:1:5");
+
+        let y = LangModuleSlice::new_with(&module, 12..13);
+        assert_eq!(y.to_string(), "<tests-module>:2:1");
+    }
+
+    #[test]
+    fn tokens() {
+        let mut lexer = FlyLang::anonymous_lexer("a: \"😀\" + b;", SCRIPTS_LABEL);
+        let ranges: Vec<_> = lexer
+            .tokenize()
+            .iter()
+            .map(|token| token.location().range())
+            .collect();
+
+        assert_eq!(ranges, vec![0..1, 1..2, 3..9, 10..11, 12..13, 13..14]);
+
+        // The expressions of a string following other characters
+        let mut lexer = FlyLang::anonymous_lexer("é: \"€ &(x) b\"; c", SCRIPTS_LABEL);
+        let ranges: Vec<_> = lexer
+            .tokenize()
+            .iter()
+            .map(|token| token.location().range())
+            .collect();
+        assert_eq!(ranges, vec![0..2, 2..3, 4..16, 16..17, 18..19]);
+        assert!(lexer.diagnostics().is_empty());
+    }
+}
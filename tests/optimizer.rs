@@ -0,0 +1,141 @@
+use flylang::flylang::{FlyLang, optimizer::Optimizer};
+
+#[cfg(test)]
+pub mod tests {
+    use flylang::flylang::{
+        interpreter::Interpreter,
+        parser::ast::{
+            Branches,
+            definables::Definables,
+            expressions::{Expressions, literals::ParsedLiterals},
+            instructions::Instructions,
+        },
+    };
+
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-optimizer");
+
+    fn optimize(script: &str) -> Branches {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        Optimizer::new().optimize(parser.parse())
+    }
+
+    fn literal(branches: &Branches) -> &ParsedLiterals {
+        assert_eq!(branches.len(), 1);
+        match branches[0].kind() {
+            Instructions::ValueOf(Expressions::Literal(literal)) => literal,
+            other => panic!("Expected a literal, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn constant_folding() {
+        for (script, expected) in [
+            ("1 + 2", 3f64),
+            ("1 + (2 * 3)", 7f64),
+            ("2 ** (1 + 2)", 8f64),
+        ] {
+            assert!(matches!(
+                literal(&optimize(script)),
+                ParsedLiterals::Computed(n) if *n == expected
+            ));
+        }
+        assert!(matches!(literal(&optimize("1 < 2")), ParsedLiterals::True));
+        assert!(matches!(
+            literal(&optimize("!(1 = 1)")),
+            ParsedLiterals::False
+        ));
+        assert!(matches!(
+            literal(&optimize("false & f()")),
+            ParsedLiterals::False
+        ));
+
+        let optimized = optimize(r#""a" + 1"#);
+        let ParsedLiterals::String(items) = literal(&optimized) else {
+            panic!("Expected a string");
+        };
+        assert_eq!(items.len(), 1);
+    }
+
+    #[test]
+    fn not_constant() {
+        // Errors are kept to be raised at runtime
+        for script in ["a + 1", "1 / 0", "true & f()", r#""&(a)""#] {
+            let optimized = optimize(script);
+            assert!(!matches!(
+                optimized[0].kind(),
+                Instructions::ValueOf(Expressions::Literal(ParsedLiterals::Computed(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn conditions() {
+        assert!(matches!(
+            literal(&optimize("if(true, a)")),
+            ParsedLiterals::Word
+        ));
+        assert!(matches!(
+            literal(&optimize("if(false, a)")),
+            ParsedLiterals::Empty
+        ));
+        assert!(matches!(
+            literal(&optimize("if(1 > 2, 1, 2)")),
+            ParsedLiterals::Computed(n) if *n == 2f64
+        ));
+        let optimized = optimize("if(1 > 2, a, b)");
+        let Instructions::ValueOf(Expressions::Prioritized(kept)) = optimized[0].kind() else {
+            panic!("Expected the kept expression");
+        };
+        assert_eq!(kept.location().code(), "b");
+
+        // The scope of the condition is kept if a breaker may use it
+        let optimized = optimize("while(true, if(true, stop) else (a))");
+        let Instructions::Loop(repetition) = optimized[0].kind() else {
+            panic!("Expected a loop");
+        };
+        let Instructions::If(condition) = repetition.process[0].kind() else {
+            panic!("Expected a condition");
+        };
+        assert!(condition.fallback.is_none());
+    }
+
+    #[test]
+    fn dead_code() {
+        let optimized = optimize("fn f(a(); return 1; a(); 3)");
+        let Instructions::ValueOf(Expressions::Defined(Definables::Function(function))) =
+            optimized[0].kind()
+        else {
+            panic!("Expected a function");
+        };
+        assert_eq!(function.execution.len(), 2);
+
+        // Only the value of the last instruction is used
+        assert!(matches!(
+            literal(&optimize("1; 2; a")),
+            ParsedLiterals::Word
+        ));
+    }
+
+    #[test]
+    fn same_behavior() {
+        let scripts = [
+            r#""1 + 1 = &(1 + 1)""#,
+            "a: 2 ** 3; a + 1",
+            "n: 0; while(n < 5, if(true, n +: 1)); n",
+            "fn f(x, if(false, return 0) else if(x > 1, return 1) else (return 2)); a: f(5); b: f(0); a + b",
+            "i: 0; while(true, i +: 1; if(i > 2, stop)); i",
+            "if(true, 1; 2)",
+            "fn f(if(true, 5)); f()",
+            "if(true, 1, 2)",
+        ];
+
+        for script in scripts {
+            let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+            let original = Interpreter::new().run(parser.parse()).ok();
+            let optimized = Interpreter::new().run(&optimize(script)).ok();
+            assert_eq!(original, optimized, "{}", script);
+        }
+    }
+}
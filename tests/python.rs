@@ -0,0 +1,158 @@
+use flylang::flylang::compilers;
+
+use crate::compilers::tests::{compile, execute, run};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const COMPILER: &str = "py";
+
+    #[test]
+    fn registered() {
+        assert!(compilers::find("py").is_some());
+    }
+
+    #[test]
+    fn statements() {
+        let code = compile(
+            COMPILER,
+            "s: {a: 1, b: {1, 2}}; fn f(x, return x); g: fn(y, return y); class: if(s, 1, 2)",
+        );
+        // Structures are dicts and arrays are lists
+        assert!(code.contains("s = {\"a\": 1, \"b\": [1, 2]}"));
+        assert!(code.contains("def f(x=None):"));
+        // Functions defined in an expression are defined before its statement
+        assert!(code.contains("def _fly_fn"));
+        assert!(code.contains("class_ = 1 if _fly.truthy(s) else 2"));
+        assert!(code.contains("__all__ = [\"s\", \"f\", \"g\", \"class_\"]"));
+    }
+
+    #[test]
+    fn enclosing_variables() {
+        let code = compile(
+            COMPILER,
+            "count: 0; fn inc(count +: 1); fn counter(n: 0; fn step(n +: 1); step(); return n)",
+        );
+        assert!(code.contains("global count"));
+        assert!(code.contains("nonlocal n"));
+    }
+
+    #[test]
+    fn variables_declared_after_a_function() {
+        let Some(output) = run(
+            COMPILER,
+            "variables_declared_after_a_function",
+            r#"fn f(x: 2); x: 1; f();
+            fn g(fn h(y: 2); y: 1; h(); return y);
+            n: 0; while(n < 3, i, n +: 1);
+            std.out(x, g(), i)"#,
+        ) else {
+            return;
+        };
+        // The functions assign the variables of the module and of `g` existing when they are called
+        assert_eq!(output, "2 2 2\n");
+    }
+
+    #[test]
+    fn values() {
+        let Some(output) = run(
+            COMPILER,
+            "values",
+            r#"a: {x: 1, "b c": {1, "two"}}; std.out(a, a.x + 1, "n = &(a.x)", {}, {:}, ());
+            std.out(7 // 2, -7 // 2, 7 % -3, 2 ** 3, 1 / 4, {1, 2} = {1, 2}, 1 = true, std.len("abc"), std.type(a))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(
+            output,
+            "{x: 1, \"b c\": {1, \"two\"}} 2 n = 1 {} {:} ()\n3 -4 1 8 0.25 true false 3 structure\n"
+        );
+    }
+
+    #[test]
+    fn loops_and_breakers() {
+        let Some(output) = run(
+            COMPILER,
+            "loops_and_breakers",
+            r#"s: 0; each @outer({1, 2, 3}, i, each({10, 20}, j, if(i = 3, stop @outer); s +: i * j));
+            n: 0; while(n < 5, n +: 1; if(n = 2, pass @<); std.out(n));
+            fn find @f(items, target, fn check(v, if(v = target, return @f "found")); each(items, item, check(item)); return "none");
+            std.out(s, find({1, 2}, 2), find({1}, 3))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "1\n3\n4\n5\n90 found none\n");
+    }
+
+    #[test]
+    fn expressions_with_statements() {
+        let Some(output) = run(
+            COMPILER,
+            "expressions_with_statements",
+            r#"apply: fn(f, x, return f(x)); y: (z: 3) + 1;
+            fn sign(v, return if(v > 0, "+", if(v < 0, "-", "0")));
+            std.out(apply(fn(v, return v * 2), 21), y, z, sign(2), sign(-2), sign(0))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "42 4 3 + - 0\n");
+    }
+
+    #[test]
+    fn classes() {
+        let Some(output) = run(
+            COMPILER,
+            "classes",
+            r#"cs A(fn who(return "A"));
+            cs B(A, fn who(return "B" + super.who()));
+            cs C(A, fn who(return "C" + super.who()));
+            cs D(B, C, #(static) count: 0; fn who(return "D" + super.who()));
+            d: new D(); D.count +: 1; method: d.who;
+            std.out(method(), D.count, d, D, std.type(d))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "DBCA 1 <D instance> <cs D> instance\n");
+    }
+
+    #[test]
+    fn modifiers() {
+        let Some(output) = run(
+            COMPILER,
+            "modifiers",
+            r#"fn double(name, args, return 2 * args.1); #(double, double) a: 3;
+            fn twice(def, args, return 2 * def(args.0)); fn plus_one(def, args, return 1 + def(args.0));
+            #(twice, plus_one) fn f(n, return n);
+            fn named(def, args, return new def()); #(named) cs K();
+            std.out(a, f(3), std.type(new K()))"#,
+        ) else {
+            return;
+        };
+        assert_eq!(output, "12 7 instance\n");
+    }
+
+    #[test]
+    fn runtime_errors() {
+        let scripts = [
+            (r#"fn f(a, return a * 2); f("a")"#, "E0019"),
+            (r#"fn f(a, b, return a < b); f(1, "a")"#, "E0019"),
+            ("fn f(a, return -a); f({})", "E0019"),
+            ("s: {x: 1}; s.y", "E0025"),
+            ("a: {1, 2}; a.2", "E0027"),
+            ("A:: 1; A: 2", "E0029"),
+            ("A:: 1; fn f(A: 2); f()", "E0029"),
+        ];
+
+        for (test, (script, code)) in scripts.iter().enumerate() {
+            let Some(output) = execute(COMPILER, &format!("runtime_errors_{}", test), script)
+            else {
+                return;
+            };
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(!output.status.success(), "{}", script);
+            assert!(stderr.contains(code), "{} : {}", script, stderr);
+            assert!(!stderr.contains("TypeError"), "{} : {}", script, stderr);
+        }
+    }
+}
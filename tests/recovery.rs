@@ -0,0 +1,122 @@
+use flylang::flylang::{FlyLang, errors::Diagnostics};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-recovery");
+
+    /// Parse the script and return its errors
+    fn errors(script: &str) -> Diagnostics {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        match parser.try_parse() {
+            Ok(_) => Diagnostics::new(),
+            Err(diagnostics) => diagnostics,
+        }
+    }
+
+    /// The code and the code of the location of each error
+    fn located(diagnostics: &Diagnostics) -> Vec<(i32, String)> {
+        diagnostics
+            .errors()
+            .iter()
+            .map(|error| {
+                (
+                    error._code(),
+                    error
+                        ._location()
+                        .map(|location| location.code().to_string())
+                        .unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_code() {
+        let mut parser = FlyLang::anonymous_parser("a: 1; f(a, 2)", SCRIPTS_LABEL);
+        assert_eq!(parser.try_parse().unwrap().len(), 2);
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn lexer_errors() {
+        let mut lexer = FlyLang::anonymous_lexer("a: $; b: (1}; c: {2)); d: `", SCRIPTS_LABEL);
+        lexer.tokenize();
+
+        assert_eq!(
+            located(lexer.diagnostics()),
+            vec![
+                (2, "$".into()),
+                (3, "}".into()),
+                (3, ")".into()),
+                (3, ")".into()),
+                (2, "`".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scopes_stay_balanced() {
+        let mut lexer = FlyLang::anonymous_lexer("a: (1 + {x: 2); b: (1}", SCRIPTS_LABEL);
+        let tokens = lexer.tokenize();
+
+        let count = |kind: &str| {
+            tokens
+                .iter()
+                .filter(|token| format!("{:?}", token.kind()) == kind)
+                .count()
+        };
+        assert_eq!(count("Block(Openning)"), 2);
+        assert_eq!(count("Block(Closing)"), 2);
+        assert_eq!(count("Object(Openning)"), 1);
+        assert_eq!(count("Object(Closing)"), 1);
+        assert_eq!(lexer.diagnostics().errors().len(), 2);
+    }
+
+    #[test]
+    fn unclosed_scopes() {
+        assert_eq!(
+            located(&errors("a: (1, {2, \"&(3")),
+            vec![
+                (3, "&(".into()),
+                (3, "\"".into()),
+                (3, "{".into()),
+                (3, "(".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn resync() {
+        // On the end of the instruction
+        assert_eq!(
+            located(&errors("a: 1 + ; b: 2 ** ; c: 3")),
+            vec![(2, ";".into()), (2, ";".into())]
+        );
+        // On the end of an argument
+        assert_eq!(
+            located(&errors("f(1 +, 2); g(3, * 4, 5)")),
+            vec![(2, ",".into()), (2, "*".into())]
+        );
+        // On the end of a scope
+        assert_eq!(
+            located(&errors("fn f(a, b: ); f(1 -); x")),
+            vec![(2, ")".into()), (2, ")".into())]
+        );
+    }
+
+    #[test]
+    fn caused_errors_are_ignored() {
+        // The instruction containing an invalid character is not valid
+        assert_eq!(located(&errors("a: $;")), vec![(2, "$".into())]);
+        // The call containing an invalid argument is not valid
+        assert_eq!(located(&errors("f(1 +, 2)")), vec![(2, ",".into())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_raises() {
+        FlyLang::anonymous_parser("a: 1 + ;", SCRIPTS_LABEL).parse();
+    }
+}
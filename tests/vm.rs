@@ -0,0 +1,119 @@
+use flylang::flylang::{
+    FlyLang, cache,
+    errors::codes::ErrorId,
+    interpreter::Interpreter,
+    module::LangModule,
+    vm::{VirtualMachine, compiler::Compiler, disassembler::disassemble, value::Value},
+};
+
+use crate::{cache::tests::project, interpreter::engine_tests};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SCRIPTS_LABEL: Option<&str> = Some("tests-vm");
+
+    fn run(script: &str) -> Value {
+        let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+        let program = Compiler::compile(parser.parse()).unwrap_or_else(|e| panic!("{:?}", e));
+        VirtualMachine::new()
+            .run(&program)
+            .unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    engine_tests!();
+
+    #[test]
+    fn disassembler() {
+        let mut parser =
+            FlyLang::anonymous_parser("x: 1; fn add(a, return a + x); add(2)", SCRIPTS_LABEL);
+        let program = Compiler::compile(parser.parse()).unwrap_or_else(|e| panic!("{:?}", e));
+        let code = disassemble(&program);
+
+        assert!(code.contains("fn #0 <module>"));
+        assert!(code.contains("fn #1 add (1 arguments"));
+        // Operands are described by a comment
+        assert!(code.contains("Function(1)"));
+        assert!(code.contains("; <fn add>"));
+        assert!(code.contains("; a"));
+    }
+
+    #[test]
+    fn unassigned_variables() {
+        // The variables assigned by code that never runs are not defined, like in the interpreter
+        for script in [
+            "while(false, j, ()); j",
+            "each({}, item, index, ()); index",
+            "if(false, x: 1); x",
+            "fn f(if(false, x: 1); return x); f()",
+        ] {
+            let mut parser = FlyLang::anonymous_parser(script, SCRIPTS_LABEL);
+            let branches = parser.parse();
+            let interpreted = Interpreter::new().run(branches).map(|_| ());
+            let executed = Compiler::compile(branches)
+                .and_then(|program| VirtualMachine::new().run(&program))
+                .map(|_| ());
+
+            let undefined = Err(ErrorId::RuntimeUndefinedVariable);
+            assert_eq!(interpreted.map_err(|e| e._id()), undefined, "{}", script);
+            assert_eq!(executed.map_err(|e| e._id()), undefined, "{}", script);
+        }
+
+        // The missing arguments are empty
+        assert_eq!(run("fn f(a, b, return b); f(1)"), Value::Empty);
+    }
+
+    #[test]
+    fn packages() {
+        let (folder, behavior) = project(
+            "vm-packages",
+            &[
+                (
+                    "main.fly",
+                    r#"
+                    use "./lib.fly" in lib;
+                    use {double} from "./lib.fly";
+                    use "./constants.fly";
+                    lib.state.count: 1;
+                    use "./lib.fly" in again;
+                    {lib.name, double(21), answer, again.state.count}
+                    "#,
+                ),
+                (
+                    "lib.fly",
+                    r#"name: "lib"; factor: 2; state: {count: 0}; fn double(x, return x * factor)"#,
+                ),
+                ("constants.fly", "answer: 42; if(false, never: 1)"),
+                ("missing.fly", r#"use "./nowhere.fly" in nowhere"#),
+                ("name.fly", r#"use {never} from "./constants.fly""#),
+                ("first.fly", r#"use "./second.fly""#),
+                ("second.fly", r#"use "./first.fly""#),
+            ],
+        );
+
+        // The modules are executed once, and both engines give the same results
+        for (file, expected) in [
+            ("main.fly", Ok(String::from(r#"{"lib", 42, 42, 1}"#))),
+            ("missing.fly", Err(ErrorId::PackageNotFound)),
+            ("name.fly", Err(ErrorId::UndefinedProperty)),
+            ("first.fly", Err(ErrorId::CircularUse)),
+        ] {
+            let module = LangModule::new(folder.join(file)).unwrap().into_rc();
+            let branches = cache::parse(&module, &behavior);
+            let interpreted = Interpreter::with_behavior(&behavior)
+                .run(&branches)
+                .map(|value| value.to_string());
+            let executed = Compiler::compile_with_behavior(&branches, &behavior)
+                .and_then(|program| VirtualMachine::new().run(&program))
+                .map(|value| value.to_string());
+
+            assert_eq!(interpreted.map_err(|e| e._id()), expected, "{}", file);
+            assert_eq!(executed.map_err(|e| e._id()), expected, "{}", file);
+        }
+
+        // The used modules are loaded through the cache
+        assert!(folder.join("lib.flyc").exists());
+        assert!(folder.join("constants.flyc").exists());
+    }
+}